@@ -1,6 +1,25 @@
 //! Audit command implementation.
-
-use crate::cli::{AuditCommands, AuditLabelArgs, AuditLogArgs, AuditRecordArgs, AuditSummaryArgs};
+//!
+//! Interaction entries in `.beads/interactions.jsonl` form a hash chain: each
+//! entry stores the SHA-256 of its predecessor (`prev_hash`) and of its own
+//! content (`hash`). Entries written before chaining existed are anchored by
+//! hashing the raw bytes that precede the first chained entry. The newest hash
+//! recorded from this clone is kept in database metadata, so truncating the
+//! log is detected as well as editing it.
+//!
+//! The log is committed, so two branches may both append to it. Git's union
+//! merge (registered by `br hooks install`) keeps both sides one after the
+//! other, which forks the chain: the other branch's first entry points back
+//! to an entry before ours rather than to the line above it. A fork looks the
+//! same as a forged entry chained to an earlier one, so verification only
+//! accepts it with `--allow-merged`; otherwise every entry must chain to the
+//! line above it. The next append chains to the last line, joining the
+//! branches again.
+
+use crate::cli::{
+    AuditCommands, AuditExportArgs, AuditLabelArgs, AuditLogArgs, AuditRecordArgs,
+    AuditSummaryArgs, AuditVerifyArgs,
+};
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::model::EventType;
@@ -9,7 +28,7 @@ use chrono::{DateTime, Utc};
 use rich_rust::prelude::*;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self, Read, Write};
 use std::path::{Path, PathBuf};
@@ -17,6 +36,21 @@ use std::sync::atomic::{AtomicU64, Ordering};
 
 static ID_COUNTER: AtomicU64 = AtomicU64::new(0);
 
+/// The audit log, in the beads directory.
+pub const INTERACTIONS_JSONL_FILE: &str = "interactions.jsonl";
+
+/// Metadata key holding the hash of the newest entry recorded from this clone.
+const METADATA_AUDIT_CHAIN_HEAD: &str = "audit_chain_head";
+
+/// Environment variable holding the key used to sign export manifests.
+const SIGNING_KEY_ENV: &str = "BEADS_AUDIT_SIGNING_KEY";
+
+/// How long an append waits for another writer's lock on the log.
+const LOCK_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+const LOCK_RETRY_INTERVAL: std::time::Duration = std::time::Duration::from_millis(10);
+/// Age after which a leftover lock file is treated as abandoned.
+const LOCK_STALE_AFTER: std::time::Duration = std::time::Duration::from_secs(60);
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case")]
 struct AuditEntry {
@@ -52,6 +86,11 @@ struct AuditEntry {
 
     #[serde(skip_serializing_if = "Option::is_none")]
     extra: Option<serde_json::Map<String, serde_json::Value>>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prev_hash: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    hash: Option<String>,
 }
 
 #[derive(Debug, Serialize)]
@@ -67,6 +106,52 @@ struct AuditLabelOutput {
     label: String,
 }
 
+#[derive(Debug, Default, Serialize)]
+struct AuditVerifyReport {
+    entries: usize,
+    chained: usize,
+    legacy: usize,
+    /// Entries chained to something other than the entry before them, i.e.
+    /// where a merged branch starts. Only accepted with `--allow-merged`.
+    forks: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    head: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    recorded_head: Option<String>,
+    valid: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_break: Option<ChainBreak>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+struct ChainBreak {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    id: Option<String>,
+    reason: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct AuditManifest {
+    file: String,
+    sha256: String,
+    bytes: usize,
+    entries: usize,
+    chained: usize,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    chain_head: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    first_id: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    last_id: Option<String>,
+    exported_at: DateTime<Utc>,
+    exported_by: String,
+    algorithm: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    signature: Option<String>,
+}
+
 // New structs for Log/Summary JSON output
 #[derive(Debug, Serialize)]
 struct AuditLogOutput {
@@ -130,11 +215,417 @@ pub fn execute(
     let actor = config::resolve_actor(&layer);
 
     match command {
-        AuditCommands::Record(args) => record_entry(args, &beads_dir, &actor, ctx),
-        AuditCommands::Label(args) => label_entry(args, &beads_dir, &actor, ctx),
+        AuditCommands::Record(args) => record_entry(args, &beads_dir, &actor, cli, ctx),
+        AuditCommands::Label(args) => label_entry(args, &beads_dir, &actor, cli, ctx),
         AuditCommands::Log(args) => execute_log(args, &beads_dir, cli, json, ctx),
         AuditCommands::Summary(args) => execute_summary(args, &beads_dir, cli, json, ctx),
+        AuditCommands::Verify(args) => execute_verify(args, &beads_dir, cli, ctx),
+        AuditCommands::Export(args) => execute_export(args, &beads_dir, &actor, cli, ctx),
+    }
+}
+
+fn execute_verify(
+    args: &AuditVerifyArgs,
+    beads_dir: &Path,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let contents = read_interactions(beads_dir)?;
+    let recorded_head = load_chain_head(beads_dir, cli);
+    let report = verify_chain(&contents, recorded_head.as_deref(), args.allow_merged);
+
+    if ctx.is_json() {
+        ctx.json_pretty(&report);
+    } else {
+        render_verify_report(&report, ctx);
+    }
+
+    if !report.valid {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn render_verify_report(report: &AuditVerifyReport, ctx: &OutputContext) {
+    ctx.print(&format!(
+        "Entries: {} ({} chained, {} before chaining)",
+        report.entries, report.chained, report.legacy
+    ));
+    if let Some(head) = &report.head {
+        ctx.print(&format!("Head:    {head}"));
+    }
+    if report.forks > 0 {
+        ctx.print(&format!("Merged branches: {}", report.forks));
+    }
+    match &report.first_break {
+        None => ctx.success("Audit chain intact"),
+        Some(broken) => {
+            let location = match (broken.line, &broken.id) {
+                (Some(line), Some(id)) => format!("line {line} ({id}): "),
+                (Some(line), None) => format!("line {line}: "),
+                _ => String::new(),
+            };
+            ctx.error(&format!(
+                "Audit chain broken at {location}{}",
+                broken.reason
+            ));
+        }
+    }
+}
+
+fn execute_export(
+    args: &AuditExportArgs,
+    beads_dir: &Path,
+    actor: &str,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let contents = read_interactions(beads_dir)?;
+    let Some(output) = &args.output else {
+        io::stdout().write_all(&contents)?;
+        return Ok(());
+    };
+
+    let report = verify_chain(
+        &contents,
+        load_chain_head(beads_dir, cli).as_deref(),
+        args.allow_merged,
+    );
+    if let Some(broken) = report.first_break.as_ref().filter(|_| args.signed_manifest) {
+        return Err(BeadsError::validation(
+            "signed-manifest",
+            format!(
+                "refusing to sign a broken audit chain: {} (run `br audit verify`)",
+                broken.reason
+            ),
+        ));
+    }
+
+    let signing_key = if args.signed_manifest {
+        let key = std::env::var(SIGNING_KEY_ENV)
+            .ok()
+            .filter(|key| !key.is_empty())
+            .ok_or_else(|| {
+                BeadsError::validation(
+                    "signed-manifest",
+                    format!("set {SIGNING_KEY_ENV} to the signing key"),
+                )
+            })?;
+        Some(key)
+    } else {
+        None
+    };
+
+    fs::write(output, &contents)?;
+    let manifest_path = signing_key
+        .map(|key| -> Result<PathBuf> {
+            let manifest = build_manifest(output, &contents, &report, actor, key.as_bytes())?;
+            let path = manifest_path_for(output);
+            fs::write(&path, serde_json::to_string_pretty(&manifest)? + "\n")?;
+            Ok(path)
+        })
+        .transpose()?;
+
+    if ctx.is_json() {
+        ctx.json_pretty(&serde_json::json!({
+            "output": output.display().to_string(),
+            "entries": report.entries,
+            "manifest": manifest_path.as_ref().map(|p| p.display().to_string()),
+        }));
+    } else {
+        ctx.success(&format!(
+            "Exported {} entries to {}",
+            report.entries,
+            output.display()
+        ));
+        if let Some(path) = manifest_path {
+            ctx.info(&format!("Signed manifest: {}", path.display()));
+        }
+    }
+    Ok(())
+}
+
+fn manifest_path_for(output: &Path) -> PathBuf {
+    let mut name = output.as_os_str().to_os_string();
+    name.push(".manifest.json");
+    PathBuf::from(name)
+}
+
+fn build_manifest(
+    output: &Path,
+    contents: &[u8],
+    report: &AuditVerifyReport,
+    actor: &str,
+    key: &[u8],
+) -> Result<AuditManifest> {
+    let ids: Vec<String> = String::from_utf8_lossy(contents)
+        .lines()
+        .filter_map(|line| serde_json::from_str::<serde_json::Value>(line).ok())
+        .filter_map(|value| value.get("id")?.as_str().map(str::to_string))
+        .collect();
+    let mut manifest = AuditManifest {
+        file: output
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().to_string()),
+        sha256: format!("{:x}", Sha256::digest(contents)),
+        bytes: contents.len(),
+        entries: report.entries,
+        chained: report.chained,
+        chain_head: report.head.clone(),
+        first_id: ids.first().cloned(),
+        last_id: ids.last().cloned(),
+        exported_at: Utc::now(),
+        exported_by: actor.to_string(),
+        algorithm: "HMAC-SHA256".to_string(),
+        signature: None,
+    };
+    manifest.signature = Some(sign_manifest(&manifest, key)?);
+    Ok(manifest)
+}
+
+/// HMAC-SHA256 over the manifest's canonical JSON (sorted keys, no signature).
+fn sign_manifest(manifest: &AuditManifest, key: &[u8]) -> Result<String> {
+    let mut value = serde_json::to_value(manifest)?;
+    if let Some(object) = value.as_object_mut() {
+        object.remove("signature");
     }
+    Ok(hmac_sha256(key, canonical_json(&value).as_bytes()))
+}
+
+/// Compact JSON with object keys sorted recursively.
+///
+/// `Value::to_string` follows the map's iteration order, which depends on
+/// whether serde_json's `preserve_order` feature is enabled anywhere in the
+/// build, so it cannot be hashed directly.
+fn canonical_json(value: &serde_json::Value) -> String {
+    match value {
+        serde_json::Value::Object(object) => {
+            let mut keys: Vec<&String> = object.keys().collect();
+            keys.sort();
+            let fields: Vec<String> = keys
+                .into_iter()
+                .map(|key| {
+                    format!(
+                        "{}:{}",
+                        serde_json::Value::String(key.clone()),
+                        canonical_json(&object[key])
+                    )
+                })
+                .collect();
+            format!("{{{}}}", fields.join(","))
+        }
+        serde_json::Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(canonical_json).collect();
+            format!("[{}]", items.join(","))
+        }
+        other => other.to_string(),
+    }
+}
+
+fn hmac_sha256(key: &[u8], message: &[u8]) -> String {
+    const BLOCK_SIZE: usize = 64;
+    let mut block = [0u8; BLOCK_SIZE];
+    if key.len() > BLOCK_SIZE {
+        block[..32].copy_from_slice(&Sha256::digest(key));
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut inner = Sha256::new();
+    inner.update(block.map(|b| b ^ 0x36));
+    inner.update(message);
+    let mut outer = Sha256::new();
+    outer.update(block.map(|b| b ^ 0x5c));
+    outer.update(inner.finalize());
+    format!("{:x}", outer.finalize())
+}
+
+/// Walk the log and report the first entry that breaks the chain.
+///
+/// With `allow_merged`, an entry may chain to any earlier entry rather than
+/// the one above it, as the first entry of a union-merged branch does.
+fn verify_chain(
+    contents: &[u8],
+    recorded_head: Option<&str>,
+    allow_merged: bool,
+) -> AuditVerifyReport {
+    let mut report = AuditVerifyReport {
+        recorded_head: recorded_head.map(str::to_string),
+        ..AuditVerifyReport::default()
+    };
+    // The anchor the chain started from, then every chained entry so far.
+    let mut known = HashSet::new();
+    let mut offset = 0;
+
+    for (index, raw) in contents.split_inclusive(|&b| b == b'\n').enumerate() {
+        let start = offset;
+        offset += raw.len();
+        let line = String::from_utf8_lossy(raw);
+        if line.trim().is_empty() {
+            continue;
+        }
+        report.entries += 1;
+
+        let expected = report
+            .head
+            .clone()
+            .unwrap_or_else(|| chain_anchor(&contents[..start]));
+        if known.is_empty() {
+            known.insert(expected.clone());
+        }
+        let merge_parents = allow_merged.then_some(&known);
+        match check_entry(&line, &expected, report.head.is_some(), merge_parents) {
+            Ok(Some((hash, forked))) => {
+                report.chained += 1;
+                if forked {
+                    report.forks += 1;
+                }
+                known.insert(hash.clone());
+                report.head = Some(hash);
+            }
+            Ok(None) => {
+                report.legacy += 1;
+                known.clear();
+            }
+            Err(reason) => {
+                let id = serde_json::from_str::<serde_json::Value>(&line)
+                    .ok()
+                    .and_then(|value| value.get("id")?.as_str().map(str::to_string));
+                report.first_break = Some(ChainBreak {
+                    line: Some(index + 1),
+                    id,
+                    reason,
+                });
+                return report;
+            }
+        }
+    }
+
+    if recorded_head.is_some_and(|recorded| !known.contains(recorded)) {
+        report.first_break = Some(ChainBreak {
+            line: None,
+            id: None,
+            reason: "recorded chain head is missing; the log was truncated or rewritten"
+                .to_string(),
+        });
+        return report;
+    }
+
+    report.valid = true;
+    report
+}
+
+/// Check one entry, returning its hash and whether it forks off an earlier
+/// entry than `expected`, if it is chained.
+///
+/// `expected` is the hash of the last chained entry or, before the chain
+/// starts, the anchor of all preceding content, so unchained lines cannot be
+/// slipped in ahead of the first chained entry. `merge_parents`, given only
+/// with `--allow-merged`, holds every hash a merged branch may fork from.
+fn check_entry(
+    line: &str,
+    expected: &str,
+    chain_started: bool,
+    merge_parents: Option<&HashSet<String>>,
+) -> std::result::Result<Option<(String, bool)>, String> {
+    let value: serde_json::Value =
+        serde_json::from_str(line).map_err(|e| format!("invalid JSON: {e}"))?;
+    let Some(hash) = value.get("hash").and_then(serde_json::Value::as_str) else {
+        return if chain_started {
+            Err("entry without a hash after the chain started".to_string())
+        } else {
+            Ok(None)
+        };
+    };
+
+    let prev = value
+        .get("prev_hash")
+        .and_then(serde_json::Value::as_str)
+        .ok_or_else(|| "entry has a hash but no prev_hash".to_string())?;
+    let forked = prev != expected;
+    if forked && !merge_parents.is_some_and(|parents| parents.contains(prev)) {
+        return Err(if merge_parents.is_none() && chain_started {
+            "prev_hash does not match the preceding entry (if this log was merged from two \
+             branches, rerun with --allow-merged)"
+                .to_string()
+        } else {
+            "prev_hash does not match the preceding entry".to_string()
+        });
+    }
+    if entry_hash(prev, &value) != hash {
+        return Err("content does not match its hash (entry was modified)".to_string());
+    }
+    Ok(Some((hash.to_string(), forked)))
+}
+
+/// Hash of an entry: SHA-256 over the predecessor's hash and the entry's
+/// canonical JSON (sorted keys, `hash` field removed).
+fn entry_hash(prev_hash: &str, value: &serde_json::Value) -> String {
+    let mut value = value.clone();
+    if let Some(object) = value.as_object_mut() {
+        object.remove("hash");
+    }
+    let mut hasher = Sha256::new();
+    hasher.update(prev_hash.as_bytes());
+    hasher.update(b"\n");
+    hasher.update(canonical_json(&value).as_bytes());
+    format!("{:x}", hasher.finalize())
+}
+
+/// Predecessor hash for an entry that follows unchained content.
+fn chain_anchor(preceding: &[u8]) -> String {
+    format!("{:x}", Sha256::digest(preceding))
+}
+
+/// Hash to chain the next entry to, given the log's current content.
+fn next_prev_hash(contents: &[u8]) -> String {
+    String::from_utf8_lossy(contents)
+        .lines()
+        .rev()
+        .find(|line| !line.trim().is_empty())
+        .and_then(|line| serde_json::from_str::<AuditEntry>(line).ok())
+        .and_then(|entry| entry.hash)
+        .unwrap_or_else(|| chain_anchor(contents))
+}
+
+fn read_interactions(beads_dir: &Path) -> Result<Vec<u8>> {
+    let path = beads_dir.join(INTERACTIONS_JSONL_FILE);
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    Ok(fs::read(path)?)
+}
+
+fn load_chain_head(beads_dir: &Path, cli: &config::CliOverrides) -> Option<String> {
+    let storage_ctx = config::open_storage_with_cli(beads_dir, cli).ok()?;
+    storage_ctx
+        .storage
+        .get_metadata(METADATA_AUDIT_CHAIN_HEAD)
+        .ok()
+        .flatten()
+}
+
+/// Append an entry and record it as the chain head.
+///
+/// The entry is already on disk if storing the head fails, so that is only
+/// logged.
+fn append_chained(
+    beads_dir: &Path,
+    cli: &config::CliOverrides,
+    entry: &mut AuditEntry,
+) -> Result<String> {
+    let id = append_entry(beads_dir, entry)?;
+    if let Some(hash) = &entry.hash {
+        let stored = config::open_storage_with_cli(beads_dir, cli).and_then(|mut storage_ctx| {
+            storage_ctx
+                .storage
+                .set_metadata(METADATA_AUDIT_CHAIN_HEAD, hash)
+        });
+        if let Err(e) = stored {
+            tracing::warn!(error = %e, "Failed to record audit chain head");
+        }
+    }
+    Ok(id)
 }
 
 fn execute_log(
@@ -257,6 +748,7 @@ fn record_entry(
     args: &AuditRecordArgs,
     beads_dir: &Path,
     actor: &str,
+    cli: &config::CliOverrides,
     ctx: &OutputContext,
 ) -> Result<()> {
     let use_stdin = args.stdin;
@@ -301,10 +793,12 @@ fn record_entry(
             label: None,
             reason: None,
             extra: None,
+            prev_hash: None,
+            hash: None,
         }
     };
 
-    let id = append_entry(beads_dir, &mut entry)?;
+    let id = append_chained(beads_dir, cli, &mut entry)?;
     let output = AuditRecordOutput {
         id: id.clone(),
         kind: entry.kind.clone(),
@@ -323,6 +817,7 @@ fn label_entry(
     args: &AuditLabelArgs,
     beads_dir: &Path,
     actor: &str,
+    cli: &config::CliOverrides,
     ctx: &OutputContext,
 ) -> Result<()> {
     let label = args
@@ -349,9 +844,11 @@ fn label_entry(
         label: Some(label.clone()),
         reason: clean_opt(args.reason.as_deref()),
         extra: None,
+        prev_hash: None,
+        hash: None,
     };
 
-    let id = append_entry(beads_dir, &mut entry)?;
+    let id = append_chained(beads_dir, cli, &mut entry)?;
     let output = AuditLabelOutput {
         id: id.clone(),
         parent_id: args.entry_id.clone(),
@@ -416,7 +913,24 @@ fn append_entry(beads_dir: &Path, entry: &mut AuditEntry) -> Result<String> {
         entry.created_at = Some(Utc::now());
     }
 
-    let mut line = serde_json::to_vec(&entry)?;
+    // Hold the lock from reading the head until the line is written, so
+    // concurrent writers cannot chain to the same predecessor.
+    let _lock = InteractionsLock::acquire(beads_dir)?;
+
+    // Chain to the current last line; a missing trailing newline is repaired
+    // first so the anchor covers exactly the bytes before this entry.
+    let mut existing = fs::read(&path)?;
+    let mut line = Vec::new();
+    if !existing.is_empty() && !existing.ends_with(b"\n") {
+        existing.push(b'\n');
+        line.push(b'\n');
+    }
+    let prev_hash = next_prev_hash(&existing);
+    entry.hash = None;
+    entry.prev_hash = Some(prev_hash.clone());
+    entry.hash = Some(entry_hash(&prev_hash, &serde_json::to_value(&*entry)?));
+
+    line.extend(serde_json::to_vec(&entry)?);
     line.push(b'\n');
 
     let mut file = fs::OpenOptions::new()
@@ -429,13 +943,67 @@ fn append_entry(beads_dir: &Path, entry: &mut AuditEntry) -> Result<String> {
     Ok(entry.id.as_ref().expect("id set before append").clone())
 }
 
+/// Exclusive lock on the interactions log, held as `.beads/interactions.lock`.
+///
+/// The lock file is created with `create_new`, so only one process holds it;
+/// it is removed on drop. A lock older than [`LOCK_STALE_AFTER`] is assumed
+/// to belong to a crashed writer and is broken.
+struct InteractionsLock {
+    path: PathBuf,
+}
+
+impl InteractionsLock {
+    fn acquire(beads_dir: &Path) -> Result<Self> {
+        let path = beads_dir.join("interactions.lock");
+        let deadline = std::time::Instant::now() + LOCK_TIMEOUT;
+        loop {
+            match fs::OpenOptions::new()
+                .write(true)
+                .create_new(true)
+                .open(&path)
+            {
+                Ok(mut file) => {
+                    let _ = writeln!(file, "{}", std::process::id());
+                    return Ok(Self { path });
+                }
+                Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+                    let stale = fs::metadata(&path)
+                        .and_then(|meta| meta.modified())
+                        .ok()
+                        .and_then(|modified| modified.elapsed().ok())
+                        .is_some_and(|age| age > LOCK_STALE_AFTER);
+                    if stale {
+                        tracing::warn!(path = %path.display(), "Breaking stale interactions lock");
+                        let _ = fs::remove_file(&path);
+                        continue;
+                    }
+                    if std::time::Instant::now() >= deadline {
+                        return Err(BeadsError::Config(format!(
+                            "timed out waiting for {}; remove it if no other br process is running",
+                            path.display()
+                        )));
+                    }
+                    std::thread::sleep(LOCK_RETRY_INTERVAL);
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+}
+
+impl Drop for InteractionsLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
 fn ensure_interactions_file(beads_dir: &Path) -> Result<PathBuf> {
     if !beads_dir.exists() {
         return Err(BeadsError::NotInitialized);
     }
 
     fs::create_dir_all(beads_dir)?;
-    let path = beads_dir.join("interactions.jsonl");
+    let path = beads_dir.join(INTERACTIONS_JSONL_FILE);
     if !path.exists() {
         fs::write(&path, b"")?;
     }
@@ -660,6 +1228,8 @@ mod tests {
             label: None,
             reason: None,
             extra: None,
+            prev_hash: None,
+            hash: None,
         }
     }
 
@@ -686,6 +1256,253 @@ mod tests {
         assert_eq!(second["id"], id_b);
     }
 
+    #[test]
+    fn test_append_chains_entries() {
+        let dir = temp_beads_dir();
+        let beads_dir = dir.path().join(".beads");
+
+        let mut entry_a = base_entry("llm_call");
+        append_entry(&beads_dir, &mut entry_a).expect("append A");
+        let mut entry_b = base_entry("tool_call");
+        append_entry(&beads_dir, &mut entry_b).expect("append B");
+
+        assert_eq!(entry_a.prev_hash, Some(chain_anchor(b"")));
+        assert_eq!(entry_b.prev_hash, entry_a.hash);
+
+        let contents = read_interactions(&beads_dir).unwrap();
+        let report = verify_chain(&contents, entry_b.hash.as_deref(), false);
+        assert!(report.valid);
+        assert_eq!(report.chained, 2);
+        assert_eq!(report.head, entry_b.hash);
+    }
+
+    #[test]
+    fn test_verify_detects_edited_entry() {
+        let dir = temp_beads_dir();
+        let beads_dir = dir.path().join(".beads");
+
+        let mut entry_a = base_entry("llm_call");
+        entry_a.prompt = Some("original".to_string());
+        append_entry(&beads_dir, &mut entry_a).expect("append A");
+        let mut entry_b = base_entry("tool_call");
+        append_entry(&beads_dir, &mut entry_b).expect("append B");
+
+        let path = beads_dir.join("interactions.jsonl");
+        let edited = fs::read_to_string(&path)
+            .unwrap()
+            .replace("original", "rewritten");
+        fs::write(&path, edited).unwrap();
+
+        let report = verify_chain(&read_interactions(&beads_dir).unwrap(), None, false);
+        assert!(!report.valid);
+        let broken = report.first_break.expect("break reported");
+        assert_eq!(broken.line, Some(1));
+        assert_eq!(broken.id, entry_a.id);
+        assert!(broken.reason.contains("modified"));
+    }
+
+    #[test]
+    fn test_verify_detects_truncation() {
+        let dir = temp_beads_dir();
+        let beads_dir = dir.path().join(".beads");
+
+        let mut entry_a = base_entry("llm_call");
+        append_entry(&beads_dir, &mut entry_a).expect("append A");
+        let first_line = fs::read(beads_dir.join("interactions.jsonl")).unwrap();
+        let mut entry_b = base_entry("tool_call");
+        append_entry(&beads_dir, &mut entry_b).expect("append B");
+
+        let report = verify_chain(&first_line, entry_b.hash.as_deref(), false);
+        assert!(!report.valid);
+        assert_eq!(report.first_break.unwrap().line, None);
+
+        // A head from earlier in the chain is fine: the log has only grown
+        let full = read_interactions(&beads_dir).unwrap();
+        assert!(verify_chain(&full, entry_a.hash.as_deref(), false).valid);
+    }
+
+    #[test]
+    fn test_verify_anchors_legacy_entries() {
+        let dir = temp_beads_dir();
+        let beads_dir = dir.path().join(".beads");
+        let path = beads_dir.join("interactions.jsonl");
+        fs::write(&path, "{\"id\":\"int-legacy\",\"kind\":\"llm_call\"}").unwrap();
+
+        let mut entry = base_entry("tool_call");
+        append_entry(&beads_dir, &mut entry).expect("append");
+
+        let report = verify_chain(&read_interactions(&beads_dir).unwrap(), None, false);
+        assert!(report.valid);
+        assert_eq!(report.legacy, 1);
+        assert_eq!(report.chained, 1);
+
+        let edited = fs::read_to_string(&path)
+            .unwrap()
+            .replace("int-legacy", "int-changed");
+        fs::write(&path, edited).unwrap();
+        let report = verify_chain(&read_interactions(&beads_dir).unwrap(), None, false);
+        let broken = report.first_break.expect("break reported");
+        assert_eq!(broken.line, Some(2));
+        assert!(broken.reason.contains("prev_hash"));
+    }
+
+    #[test]
+    fn test_verify_accepts_union_merge_of_two_branches() {
+        let dir = temp_beads_dir();
+        let beads_dir = dir.path().join(".beads");
+        let path = beads_dir.join(INTERACTIONS_JSONL_FILE);
+        append_entry(&beads_dir, &mut base_entry("llm_call")).expect("append base");
+        let base = fs::read(&path).unwrap();
+
+        // Each branch appends two entries to the same base
+        let mut tails = Vec::new();
+        for kind in ["ours", "theirs"] {
+            fs::write(&path, &base).unwrap();
+            append_entry(&beads_dir, &mut base_entry(kind)).expect("append");
+            append_entry(&beads_dir, &mut base_entry(kind)).expect("append");
+            tails.push(fs::read(&path).unwrap()[base.len()..].to_vec());
+        }
+        let theirs_head = String::from_utf8_lossy(&tails[1])
+            .lines()
+            .last()
+            .and_then(|line| serde_json::from_str::<AuditEntry>(line).ok())
+            .and_then(|entry| entry.hash);
+
+        // What git's union merge writes: the base, then ours, then theirs
+        let merged = [base.clone(), tails[0].clone(), tails[1].clone()].concat();
+        fs::write(&path, &merged).unwrap();
+        let report = verify_chain(&merged, theirs_head.as_deref(), false);
+        let broken = report
+            .first_break
+            .expect("fork rejected without --allow-merged");
+        assert_eq!(broken.line, Some(4));
+        assert!(broken.reason.contains("--allow-merged"));
+
+        let report = verify_chain(&merged, theirs_head.as_deref(), true);
+        assert!(report.valid, "{:?}", report.first_break);
+        assert_eq!(report.chained, 5);
+        assert_eq!(report.forks, 1);
+
+        // The next append joins the branches by chaining to the last line
+        let mut next = base_entry("after_merge");
+        append_entry(&beads_dir, &mut next).expect("append after merge");
+        assert_eq!(next.prev_hash, theirs_head);
+        let report = verify_chain(&read_interactions(&beads_dir).unwrap(), None, true);
+        assert!(report.valid);
+        assert_eq!(report.forks, 1);
+
+        // Dropping an entry from one branch is still detected
+        let merged = String::from_utf8_lossy(&merged);
+        let without_first_theirs: Vec<&str> = merged
+            .lines()
+            .enumerate()
+            .filter(|(index, _)| *index != 3)
+            .map(|(_, line)| line)
+            .collect();
+        let tampered = format!("{}\n", without_first_theirs.join("\n"));
+        let report = verify_chain(tampered.as_bytes(), None, true);
+        assert_eq!(report.first_break.unwrap().line, Some(4));
+    }
+
+    #[test]
+    fn test_verify_rejects_forged_entry_mid_file() {
+        let dir = temp_beads_dir();
+        let beads_dir = dir.path().join(".beads");
+        let mut entries = Vec::new();
+        for kind in ["llm_call", "tool_call", "llm_call"] {
+            let mut entry = base_entry(kind);
+            append_entry(&beads_dir, &mut entry).expect("append");
+            entries.push(entry);
+        }
+
+        // A correctly hashed entry chained to the first one, inserted after
+        // the second
+        let mut forged = serde_json::to_value(base_entry("forged")).unwrap();
+        let prev = entries[0].hash.clone().unwrap();
+        forged["prev_hash"] = serde_json::Value::String(prev.clone());
+        forged["hash"] = serde_json::Value::String(entry_hash(&prev, &forged));
+
+        let contents = fs::read_to_string(beads_dir.join(INTERACTIONS_JSONL_FILE)).unwrap();
+        let mut lines: Vec<String> = contents.lines().map(str::to_string).collect();
+        lines.insert(2, forged.to_string());
+        let tampered = format!("{}\n", lines.join("\n"));
+
+        let report = verify_chain(tampered.as_bytes(), entries[2].hash.as_deref(), false);
+        assert!(!report.valid);
+        assert_eq!(report.first_break.unwrap().line, Some(3));
+    }
+
+    #[test]
+    fn test_verify_rejects_lines_slipped_before_the_chain() {
+        let dir = temp_beads_dir();
+        let beads_dir = dir.path().join(".beads");
+        let path = beads_dir.join(INTERACTIONS_JSONL_FILE);
+        fs::write(&path, "{\"id\":\"int-legacy\",\"kind\":\"llm_call\"}\n").unwrap();
+        append_entry(&beads_dir, &mut base_entry("tool_call")).expect("append");
+
+        let contents = fs::read_to_string(&path).unwrap();
+        let (legacy, chained) = contents.split_at(contents.find('\n').unwrap() + 1);
+        let slipped = format!("{legacy}{{\"id\":\"int-forged\",\"kind\":\"x\"}}\n{chained}");
+        let report = verify_chain(slipped.as_bytes(), None, false);
+        assert_eq!(report.first_break.unwrap().line, Some(3));
+    }
+
+    #[test]
+    fn test_hmac_sha256_matches_rfc4231() {
+        assert_eq!(
+            hmac_sha256(b"Jefe", b"what do ya want for nothing?"),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+    }
+
+    #[test]
+    fn test_concurrent_appends_keep_chain_valid() {
+        let dir = temp_beads_dir();
+        let beads_dir = dir.path().join(".beads");
+
+        let handles: Vec<_> = (0..8)
+            .map(|_| {
+                let beads_dir = beads_dir.clone();
+                std::thread::spawn(move || {
+                    for _ in 0..5 {
+                        let mut entry = base_entry("tool_call");
+                        append_entry(&beads_dir, &mut entry).expect("append");
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("writer thread");
+        }
+
+        let contents = read_interactions(&beads_dir).unwrap();
+        let report = verify_chain(&contents, None, false);
+        assert!(report.valid);
+        assert_eq!(report.chained, 40);
+        assert!(!beads_dir.join("interactions.lock").exists());
+    }
+
+    #[test]
+    fn test_canonical_json_sorts_keys_recursively() {
+        let a = serde_json::json!({"b": 1, "a": {"z": [{"y": 1, "x": 2}], "c": null}});
+        let b = serde_json::json!({"a": {"c": null, "z": [{"x": 2, "y": 1}]}, "b": 1});
+        assert_eq!(canonical_json(&a), canonical_json(&b));
+        assert_eq!(
+            canonical_json(&a),
+            r#"{"a":{"c":null,"z":[{"x":2,"y":1}]},"b":1}"#
+        );
+    }
+
+    #[test]
+    fn test_manifest_signature_covers_contents() {
+        let report = verify_chain(b"", None, false);
+        let manifest =
+            build_manifest(Path::new("audit.jsonl"), b"", &report, "alice", b"key").unwrap();
+        let signature = manifest.signature.clone().unwrap();
+        assert_eq!(sign_manifest(&manifest, b"key").unwrap(), signature);
+        assert_ne!(sign_manifest(&manifest, b"other").unwrap(), signature);
+    }
+
     #[test]
     fn test_record_output_shape() {
         let output = AuditRecordOutput {
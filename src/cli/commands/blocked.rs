@@ -8,6 +8,7 @@ use crate::config::{
     open_storage_with_cli, should_use_color,
 };
 use crate::error::Result;
+use crate::format::projection;
 use crate::format::{BlockedIssue, BlockedIssueOutput};
 use crate::model::{IssueType, Priority};
 use crate::output::{OutputContext, OutputMode};
@@ -124,55 +125,21 @@ pub fn execute(
     }
 
     // Output
+    let fields = projection::parse_fields(args.fields.as_deref());
+    if args.ndjson {
+        return projection::write_ndjson(&blocked_output(&blocked_issues), fields);
+    }
     if matches!(ctx.mode(), OutputMode::Quiet) {
         return Ok(());
     }
 
     match output_format {
         OutputFormat::Json => {
-            let output: Vec<BlockedIssueOutput> = blocked_issues
-                .iter()
-                .map(|bi| BlockedIssueOutput {
-                    blocked_by: bi
-                        .blocked_by
-                        .iter()
-                        .map(|blocker_ref| blocker_id_from_ref(blocker_ref).to_string())
-                        .collect(),
-                    blocked_by_count: bi.blocked_by_count,
-                    created_at: bi.issue.created_at,
-                    created_by: bi.issue.created_by.clone(),
-                    description: bi.issue.description.clone(),
-                    id: bi.issue.id.clone(),
-                    issue_type: bi.issue.issue_type.clone(),
-                    priority: bi.issue.priority,
-                    status: bi.issue.status.clone(),
-                    title: bi.issue.title.clone(),
-                    updated_at: bi.issue.updated_at,
-                })
-                .collect();
+            let output = projection::project(&blocked_output(&blocked_issues), fields.as_deref())?;
             ctx.json_pretty(&output);
         }
         OutputFormat::Toon => {
-            let output: Vec<BlockedIssueOutput> = blocked_issues
-                .iter()
-                .map(|bi| BlockedIssueOutput {
-                    blocked_by: bi
-                        .blocked_by
-                        .iter()
-                        .map(|blocker_ref| blocker_id_from_ref(blocker_ref).to_string())
-                        .collect(),
-                    blocked_by_count: bi.blocked_by_count,
-                    created_at: bi.issue.created_at,
-                    created_by: bi.issue.created_by.clone(),
-                    description: bi.issue.description.clone(),
-                    id: bi.issue.id.clone(),
-                    issue_type: bi.issue.issue_type.clone(),
-                    priority: bi.issue.priority,
-                    status: bi.issue.status.clone(),
-                    title: bi.issue.title.clone(),
-                    updated_at: bi.issue.updated_at,
-                })
-                .collect();
+            let output = projection::project(&blocked_output(&blocked_issues), fields.as_deref())?;
             ctx.toon_with_stats(&output, args.stats);
         }
         OutputFormat::Text | OutputFormat::Csv => {
@@ -188,6 +155,30 @@ pub fn execute(
     Ok(())
 }
 
+/// Convert blocked issues to the bd-compatible JSON rows.
+fn blocked_output(blocked_issues: &[BlockedIssue]) -> Vec<BlockedIssueOutput> {
+    blocked_issues
+        .iter()
+        .map(|bi| BlockedIssueOutput {
+            blocked_by: bi
+                .blocked_by
+                .iter()
+                .map(|blocker_ref| blocker_id_from_ref(blocker_ref).to_string())
+                .collect(),
+            blocked_by_count: bi.blocked_by_count,
+            created_at: bi.issue.created_at,
+            created_by: bi.issue.created_by.clone(),
+            description: bi.issue.description.clone(),
+            id: bi.issue.id.clone(),
+            issue_type: bi.issue.issue_type.clone(),
+            priority: bi.issue.priority,
+            status: bi.issue.status.clone(),
+            title: bi.issue.title.clone(),
+            updated_at: bi.issue.updated_at,
+        })
+        .collect()
+}
+
 /// Sort blocked issues by priority (ascending), then by blocker count (descending).
 fn sort_blocked_issues(issues: &mut [BlockedIssue]) {
     issues.sort_by(|a, b| {
@@ -0,0 +1,593 @@
+//! Board command implementation.
+//!
+//! `br board` lays issues out as kanban columns. By status the columns are
+//! open, in progress, blocked, deferred and recently closed. Unlike `br list`,
+//! the blocked column comes from the blocked cache rather than the stored
+//! status: an issue with open blockers shows as blocked whatever its status
+//! says, and one whose blockers have all closed moves back to open.
+
+use crate::cli::{BoardArgs, BoardBy};
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::format::{format_status_icon, truncate_title, wrap_text};
+use crate::model::{Issue, IssueType, Priority, Status};
+use crate::output::{OutputContext, OutputMode, Theme};
+use crate::storage::{ListFilters, SqliteStorage, WhereClause};
+use crate::util::time::parse_duration;
+use chrono::{DateTime, Utc};
+use rich_rust::prelude::*;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use unicode_width::UnicodeWidthStr;
+
+/// Status columns, in board order.
+const STATUS_COLUMNS: [&str; 5] = [
+    "open",
+    "in_progress",
+    "blocked",
+    "deferred",
+    "closed_recently",
+];
+
+/// Column for issues without an assignee or a matching label.
+const NONE_COLUMN: &str = "(none)";
+
+/// Narrower than this, columns are stacked instead of side by side.
+const MIN_CARD_WIDTH: usize = 18;
+
+/// Cards this wide also show labels.
+const LABEL_CARD_WIDTH: usize = 28;
+
+/// One issue on the board.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardCard {
+    pub id: String,
+    pub title: String,
+    pub status: Status,
+    pub priority: Priority,
+    pub issue_type: IssueType,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
+    /// Open blockers, from the blocked cache.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub blocked_by: Vec<String>,
+}
+
+/// A board column. `count` is the number of matching issues; `issues` may be
+/// cut short by `--limit`.
+#[derive(Debug, Clone, Serialize)]
+pub struct BoardColumn {
+    pub name: String,
+    pub count: usize,
+    pub issues: Vec<BoardCard>,
+}
+
+impl BoardColumn {
+    fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            count: 0,
+            issues: Vec::new(),
+        }
+    }
+
+    fn push(&mut self, card: BoardCard) {
+        self.count += 1;
+        self.issues.push(card);
+    }
+
+    fn heading(&self) -> String {
+        format!("{} ({})", self.name, self.count)
+    }
+}
+
+#[derive(Serialize)]
+struct BoardOutput<'a> {
+    by: &'static str,
+    columns: &'a [BoardColumn],
+}
+
+/// Execute the board command.
+///
+/// # Errors
+///
+/// Returns an error if an argument is invalid or the database query fails.
+pub fn execute(args: &BoardArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let closed_since = parse_duration(&args.recent)
+        .and_then(|recent| Utc::now().checked_sub_signed(recent))
+        .ok_or_else(|| {
+            BeadsError::validation("recent", "expected a duration such as 7d, 2w or 36h")
+        })?;
+    let prefix = args.prefix.as_deref().filter(|prefix| !prefix.is_empty());
+    if args.by == BoardBy::LabelPrefix && prefix.is_none() {
+        return Err(BeadsError::validation(
+            "prefix",
+            "--by label-prefix needs --prefix, e.g. --prefix stage:",
+        ));
+    }
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let filters = build_filters(args, closed_since)?;
+    let mut columns = build_board(
+        &storage_ctx.storage,
+        &filters,
+        args.by,
+        prefix,
+        closed_since,
+    )?;
+    if args.limit > 0 {
+        for column in &mut columns {
+            column.issues.truncate(args.limit);
+        }
+    }
+
+    if ctx.is_json() {
+        let by = match args.by {
+            BoardBy::Status => "status",
+            BoardBy::Assignee => "assignee",
+            BoardBy::LabelPrefix => "label-prefix",
+        };
+        ctx.json_pretty(&BoardOutput {
+            by,
+            columns: &columns,
+        });
+        return Ok(());
+    }
+    match ctx.mode() {
+        OutputMode::Quiet => {}
+        OutputMode::Rich => render_board_rich(&columns, ctx),
+        _ => render_board_plain(&columns, ctx.width()),
+    }
+    Ok(())
+}
+
+/// Filters for the board's issues. Closed issues are only loaded when
+/// grouping by status, and only those closed after `closed_since`.
+fn build_filters(args: &BoardArgs, closed_since: DateTime<Utc>) -> Result<ListFilters> {
+    let types = args
+        .types
+        .iter()
+        .map(|value| value.parse())
+        .collect::<Result<Vec<IssueType>>>()?;
+    let priorities = args
+        .priority
+        .iter()
+        .map(|value| value.parse())
+        .collect::<Result<Vec<Priority>>>()?;
+    Ok(ListFilters {
+        types: (!types.is_empty()).then_some(types),
+        priorities: (!priorities.is_empty()).then_some(priorities),
+        assignee: args.assignee.clone(),
+        labels: (!args.label.is_empty()).then(|| args.label.clone()),
+        include_closed: args.by == BoardBy::Status,
+        include_deferred: true,
+        closed_after: Some(closed_since),
+        where_clause: args.where_.as_deref().map(WhereClause::parse).transpose()?,
+        ..ListFilters::default()
+    })
+}
+
+/// Group the issues matching `filters` into columns.
+///
+/// Closed issues only appear (in `closed_recently`, when grouping by status)
+/// if they closed after `closed_since`. Within a column, cards are ordered by
+/// priority, then age.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn build_board(
+    storage: &SqliteStorage,
+    filters: &ListFilters,
+    by: BoardBy,
+    prefix: Option<&str>,
+    closed_since: DateTime<Utc>,
+) -> Result<Vec<BoardColumn>> {
+    let mut issues = storage.list_issues(filters)?;
+    issues.sort_by(|a, b| {
+        a.priority
+            .cmp(&b.priority)
+            .then(a.created_at.cmp(&b.created_at))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    let ids: Vec<String> = issues.iter().map(|issue| issue.id.clone()).collect();
+    let mut blockers = storage.get_blockers_for_issues(&ids)?;
+    let mut labels = storage.get_labels_for_issues(&ids)?;
+
+    let mut columns: Vec<BoardColumn> = match by {
+        BoardBy::Status => STATUS_COLUMNS.into_iter().map(BoardColumn::new).collect(),
+        BoardBy::Assignee | BoardBy::LabelPrefix => Vec::new(),
+    };
+    let mut grouped: BTreeMap<String, BoardColumn> = BTreeMap::new();
+    for issue in issues {
+        let blocked_by = blockers.remove(&issue.id);
+        let is_blocked = blocked_by.is_some();
+        let issue_labels = labels.remove(&issue.id).unwrap_or_default();
+        let column = match by {
+            BoardBy::Status => status_column(&issue, is_blocked, closed_since).map(str::to_string),
+            _ if issue.status.is_terminal() => None,
+            BoardBy::Assignee => Some(
+                issue
+                    .assignee
+                    .clone()
+                    .unwrap_or_else(|| NONE_COLUMN.to_string()),
+            ),
+            BoardBy::LabelPrefix => Some(label_column(&issue_labels, prefix.unwrap_or_default())),
+        };
+        let Some(column) = column else {
+            continue;
+        };
+        let card = BoardCard {
+            blocked_by: blocked_by.unwrap_or_default(),
+            id: issue.id,
+            title: issue.title,
+            status: issue.status,
+            priority: issue.priority,
+            issue_type: issue.issue_type,
+            assignee: issue.assignee,
+            labels: issue_labels,
+        };
+        match columns.iter_mut().find(|existing| existing.name == column) {
+            Some(existing) => existing.push(card),
+            None => grouped
+                .entry(column.clone())
+                .or_insert_with(|| BoardColumn::new(&column))
+                .push(card),
+        }
+    }
+
+    // Named columns sort alphabetically, with the catch-all last.
+    let none = grouped.remove(NONE_COLUMN);
+    columns.extend(grouped.into_values());
+    columns.extend(none);
+    Ok(columns)
+}
+
+/// Status column for an issue, or `None` if it is off the board.
+fn status_column(
+    issue: &Issue,
+    is_blocked: bool,
+    closed_since: DateTime<Utc>,
+) -> Option<&'static str> {
+    match issue.status {
+        Status::Closed => issue
+            .closed_at
+            .is_some_and(|closed_at| closed_at >= closed_since)
+            .then_some("closed_recently"),
+        Status::Tombstone => None,
+        Status::Deferred => Some("deferred"),
+        _ if is_blocked => Some("blocked"),
+        Status::InProgress => Some("in_progress"),
+        // Open, pinned, custom, and a stored `blocked` with no open blockers.
+        _ => Some("open"),
+    }
+}
+
+/// Column for `--by label-prefix`: the rest of the first label with `prefix`.
+fn label_column(labels: &[String], prefix: &str) -> String {
+    let mut matching: Vec<&str> = labels
+        .iter()
+        .filter_map(|label| label.strip_prefix(prefix))
+        .filter(|rest| !rest.is_empty())
+        .collect();
+    matching.sort_unstable();
+    matching
+        .first()
+        .map_or_else(|| NONE_COLUMN.to_string(), |rest| (*rest).to_string())
+}
+
+/// A card title in at most two lines of `width` columns.
+fn title_lines(title: &str, width: usize) -> Vec<String> {
+    let mut lines = wrap_text(title, width);
+    if lines.len() > 2 {
+        let rest = lines[1..].join(" ");
+        lines.truncate(1);
+        lines.push(truncate_title(&rest, width));
+    }
+    lines
+}
+
+/// Card text for plain output, sized to `width`.
+fn card_lines(card: &BoardCard, width: usize) -> Vec<String> {
+    let mut lines = vec![truncate_title(
+        &format!(
+            "{} {} P{}",
+            format_status_icon(&card.status),
+            card.id,
+            card.priority.0
+        ),
+        width,
+    )];
+    lines.extend(title_lines(&card.title, width));
+    if let Some(assignee) = &card.assignee {
+        lines.push(truncate_title(&format!("@{assignee}"), width));
+    }
+    if width >= LABEL_CARD_WIDTH && !card.labels.is_empty() {
+        lines.push(truncate_title(&card.labels.join(", "), width));
+    }
+    if !card.blocked_by.is_empty() {
+        lines.push(truncate_title(
+            &format!("blocked by {}", card.blocked_by.join(", ")),
+            width,
+        ));
+    }
+    lines
+}
+
+fn push_card(text: &mut Text, card: &BoardCard, width: usize, theme: &Theme) {
+    text.append_styled(&card.id, theme.issue_id.clone());
+    text.append(" ");
+    text.append_styled(
+        &format!("P{}", card.priority.0),
+        theme.priority_style(card.priority),
+    );
+    for line in title_lines(&card.title, width) {
+        text.append("\n");
+        text.append_styled(&line, theme.issue_title.clone());
+    }
+    if let Some(assignee) = &card.assignee {
+        text.append("\n");
+        text.append_styled(
+            &truncate_title(&format!("@{assignee}"), width),
+            theme.username.clone(),
+        );
+    }
+    if width >= LABEL_CARD_WIDTH && !card.labels.is_empty() {
+        text.append("\n");
+        text.append_styled(
+            &truncate_title(&card.labels.join(", "), width),
+            theme.label.clone(),
+        );
+    }
+    if !card.blocked_by.is_empty() {
+        text.append("\n");
+        text.append_styled(
+            &truncate_title(&format!("blocked by {}", card.blocked_by.join(", ")), width),
+            theme.status_blocked.clone(),
+        );
+    }
+    text.append("\n");
+}
+
+fn push_more(text: &mut Text, column: &BoardColumn, theme: &Theme) {
+    let hidden = column.count - column.issues.len();
+    if hidden > 0 {
+        text.append_styled(&format!("+{hidden} more\n"), theme.dimmed.clone());
+    }
+}
+
+/// Columns side by side in a table when they fit, otherwise one panel each.
+fn render_board_rich(columns: &[BoardColumn], ctx: &OutputContext) {
+    let theme = ctx.theme();
+    let width = ctx.width();
+    if columns.is_empty() {
+        ctx.render(&Panel::from_text("No issues on the board."));
+        return;
+    }
+
+    // Each column costs a border and two cells of padding.
+    let card_width = width.saturating_sub(1) / columns.len();
+    let card_width = card_width.saturating_sub(3);
+    if card_width < MIN_CARD_WIDTH {
+        for column in columns {
+            let mut content = Text::new("");
+            for card in &column.issues {
+                push_card(&mut content, card, width.saturating_sub(4), theme);
+            }
+            push_more(&mut content, column, theme);
+            let panel = Panel::from_rich_text(&content, width)
+                .title(Text::styled(&column.heading(), theme.panel_title.clone()))
+                .box_style(theme.box_style);
+            ctx.render(&panel);
+        }
+        return;
+    }
+
+    let mut table = Table::new()
+        .box_style(theme.box_style)
+        .border_style(theme.table_border.clone())
+        .header_style(theme.table_header.clone());
+    for column in columns {
+        table = table.with_column(Column::new(&column.heading()).width(card_width));
+    }
+    let rows = columns
+        .iter()
+        .map(|column| column.issues.len() + usize::from(column.count > column.issues.len()))
+        .max()
+        .unwrap_or(0);
+    for row in 0..rows {
+        let cells: Vec<Cell> = columns
+            .iter()
+            .map(|column| {
+                let mut text = Text::new("");
+                match column.issues.get(row) {
+                    Some(card) => push_card(&mut text, card, card_width, theme),
+                    None if row == column.issues.len() => push_more(&mut text, column, theme),
+                    None => {}
+                }
+                Cell::new(text)
+            })
+            .collect();
+        table.add_row(Row::new(cells));
+    }
+    ctx.render(&table);
+}
+
+fn pad(text: &str, width: usize) -> String {
+    let used = UnicodeWidthStr::width(text);
+    format!("{text}{}", " ".repeat(width.saturating_sub(used)))
+}
+
+fn plain_column(column: &BoardColumn, width: usize) -> Vec<String> {
+    let heading = truncate_title(&column.heading(), width);
+    let mut lines = vec![heading, "─".repeat(width)];
+    for card in &column.issues {
+        lines.extend(card_lines(card, width));
+        lines.push(String::new());
+    }
+    let hidden = column.count - column.issues.len();
+    if hidden > 0 {
+        lines.push(format!("+{hidden} more"));
+    }
+    lines
+}
+
+fn render_board_plain(columns: &[BoardColumn], width: usize) {
+    if columns.is_empty() {
+        println!("No issues on the board.");
+        return;
+    }
+    let gap = 2;
+    let card_width = width.saturating_sub(gap * (columns.len() - 1)) / columns.len();
+    if card_width < MIN_CARD_WIDTH {
+        for column in columns {
+            for line in plain_column(column, width) {
+                println!("{line}");
+            }
+            println!();
+        }
+        return;
+    }
+
+    let rendered: Vec<Vec<String>> = columns
+        .iter()
+        .map(|column| plain_column(column, card_width))
+        .collect();
+    let rows = rendered.iter().map(Vec::len).max().unwrap_or(0);
+    for row in 0..rows {
+        let line: Vec<String> = rendered
+            .iter()
+            .map(|lines| pad(lines.get(row).map_or("", String::as_str), card_width))
+            .collect();
+        println!("{}", line.join(&" ".repeat(gap)).trim_end());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_issue(id: &str, status: Status) -> Issue {
+        let now = Utc::now();
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            status,
+            priority: Priority::MEDIUM,
+            created_at: now,
+            updated_at: now,
+            ..Issue::default()
+        }
+    }
+
+    fn ids(column: &BoardColumn) -> Vec<&str> {
+        column.issues.iter().map(|card| card.id.as_str()).collect()
+    }
+
+    #[test]
+    fn test_build_board_uses_blocked_cache() {
+        init_logging();
+        info!("test_build_board_uses_blocked_cache: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let now = Utc::now();
+        let mut recent = make_issue("bd-done", Status::Closed);
+        recent.closed_at = Some(now - Duration::days(1));
+        let mut old = make_issue("bd-old", Status::Closed);
+        old.closed_at = Some(now - Duration::days(30));
+        for issue in [
+            make_issue("bd-open", Status::Open),
+            make_issue("bd-work", Status::InProgress),
+            make_issue("bd-stuck", Status::InProgress),
+            make_issue("bd-stale-flag", Status::Blocked),
+            make_issue("bd-later", Status::Deferred),
+            recent,
+            old,
+        ] {
+            storage.create_issue(&issue, "tester").unwrap();
+        }
+        storage
+            .add_dependency("bd-stuck", "bd-open", "blocks", "tester")
+            .unwrap();
+        storage
+            .add_label("bd-work", "stage:review", "tester")
+            .unwrap();
+
+        let filters = ListFilters {
+            include_closed: true,
+            include_deferred: true,
+            closed_after: Some(now - Duration::days(7)),
+            ..ListFilters::default()
+        };
+        let loaded = storage.list_issues(&filters).unwrap();
+        assert!(loaded.iter().all(|issue| issue.id != "bd-old"));
+        assert_eq!(loaded.len(), 6);
+        let columns = build_board(
+            &storage,
+            &filters,
+            BoardBy::Status,
+            None,
+            now - Duration::days(7),
+        )
+        .unwrap();
+        let names: Vec<&str> = columns.iter().map(|column| column.name.as_str()).collect();
+        assert_eq!(names, STATUS_COLUMNS);
+        let mut open = ids(&columns[0]);
+        open.sort_unstable();
+        assert_eq!(open, vec!["bd-open", "bd-stale-flag"]);
+        assert_eq!(ids(&columns[1]), vec!["bd-work"]);
+        assert_eq!(ids(&columns[2]), vec!["bd-stuck"]);
+        assert_eq!(columns[2].issues[0].blocked_by, vec!["bd-open"]);
+        assert_eq!(ids(&columns[3]), vec!["bd-later"]);
+        assert_eq!(ids(&columns[4]), vec!["bd-done"]);
+
+        let by_stage = build_board(
+            &storage,
+            &filters,
+            BoardBy::LabelPrefix,
+            Some("stage:"),
+            now,
+        )
+        .unwrap();
+        assert_eq!(by_stage.len(), 2);
+        assert_eq!(by_stage[0].name, "review");
+        assert_eq!(by_stage[1].name, NONE_COLUMN);
+        assert_eq!(by_stage[1].count, 4);
+        info!("test_build_board_uses_blocked_cache: assertions passed");
+    }
+
+    #[test]
+    fn test_card_lines_fit_width() {
+        init_logging();
+        info!("test_card_lines_fit_width: starting");
+        let card = BoardCard {
+            id: "bd-1".to_string(),
+            title: "A title that is far too long to fit on two short lines".to_string(),
+            status: Status::Open,
+            priority: Priority(1),
+            issue_type: IssueType::Task,
+            assignee: Some("alice".to_string()),
+            labels: vec!["ui".to_string()],
+            blocked_by: Vec::new(),
+        };
+        let narrow = card_lines(&card, 20);
+        assert_eq!(narrow.len(), 4);
+        assert!(
+            narrow
+                .iter()
+                .all(|line| UnicodeWidthStr::width(line.as_str()) <= 20)
+        );
+        assert!(narrow[2].ends_with("..."));
+        assert_eq!(narrow[3], "@alice");
+        assert_eq!(card_lines(&card, 40).last().unwrap(), "ui");
+        info!("test_card_lines_fit_width: assertions passed");
+    }
+}
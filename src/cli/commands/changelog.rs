@@ -1,19 +1,26 @@
 //! Changelog command implementation.
 //!
 //! Generates release notes from closed issues since a given date or git reference.
-//! Groups issues by type and sorts by priority within each group.
+//! Groups issues by type (or label, or parent epic) and sorts by priority within
+//! each group. With `--format` the notes are rendered as a markdown document that
+//! can be inserted into an existing `CHANGELOG.md`, and every run suggests a
+//! semver bump from the configured type and label rules.
 
-use crate::cli::ChangelogArgs;
-use crate::config;
+use crate::cli::{ChangelogArgs, ChangelogFormat, ChangelogGroupBy};
+use crate::config::{self, BumpRules};
 use crate::error::{BeadsError, Result};
 use crate::model::{Issue, Status};
 use crate::output::{OutputContext, OutputMode};
-use crate::storage::ListFilters;
+use crate::storage::{ListFilters, SqliteStorage};
 use crate::util::time::{parse_flexible_timestamp, parse_relative_time};
 use chrono::{DateTime, Utc};
 use rich_rust::prelude::*;
 use serde::Serialize;
-use std::collections::BTreeMap;
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::io::ErrorKind;
+use std::path::Path;
 use std::process::Command;
 use tracing::debug;
 
@@ -22,20 +29,25 @@ use tracing::debug;
 pub struct ChangelogOutput {
     /// Start date for the changelog period.
     pub since: String,
-    /// End date for the changelog period (now).
+    /// End date for the changelog period (now unless `--until` is given).
     pub until: String,
     /// Total number of closed issues in the period.
     pub total_closed: usize,
-    /// Issues grouped by type.
+    /// Version bump suggested by the closed issues.
+    pub suggested_bump: SemverBump,
+    /// Issues grouped by type, label or epic.
     pub groups: Vec<ChangelogGroup>,
 }
 
-/// A group of issues by type.
+/// A group of issues by type, label or epic.
 #[derive(Serialize, Debug)]
 pub struct ChangelogGroup {
-    /// Issue type (feature, bug, task, etc.).
+    /// Issue type (feature, bug, task, etc.); empty unless grouped by type.
+    #[serde(skip_serializing_if = "String::is_empty")]
     pub issue_type: String,
-    /// Human-readable label for the type.
+    /// Group key: the type, label or epic ID (empty for the catch-all group).
+    pub key: String,
+    /// Human-readable label for the group.
     pub label: String,
     /// Issues in this group, sorted by priority.
     pub issues: Vec<ChangelogEntry>,
@@ -47,15 +59,51 @@ pub struct ChangelogEntry {
     pub id: String,
     pub title: String,
     pub priority: String,
+    pub issue_type: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub labels: Vec<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub closed_at: Option<String>,
 }
 
+/// Semver component a release should bump.
+#[derive(Serialize, Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(rename_all = "lowercase")]
+pub enum SemverBump {
+    None,
+    Patch,
+    Minor,
+    Major,
+}
+
+impl SemverBump {
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::None => "none",
+            Self::Patch => "patch",
+            Self::Minor => "minor",
+            Self::Major => "major",
+        }
+    }
+}
+
+/// Keep a Changelog section names, in the order the spec lists them.
+const KEEP_A_CHANGELOG_SECTIONS: [&str; 6] = [
+    "Added",
+    "Changed",
+    "Deprecated",
+    "Removed",
+    "Fixed",
+    "Security",
+];
+
 /// Execute changelog generation.
 ///
 /// # Errors
 ///
-/// Returns an error if config loading, git lookup, or storage access fails.
+/// Returns an error if config loading, git lookup, storage access, or writing
+/// the `--prepend` file fails.
 ///
 /// # Panics
 ///
@@ -68,70 +116,66 @@ pub fn execute(
 ) -> Result<()> {
     let beads_dir = config::discover_beads_dir_with_cli(cli)?;
     let config::OpenStorageResult { storage, .. } = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage), cli)?;
+    let rules = config::bump_rules_from_layer(&layer);
 
     let (since_dt, since_label) = resolve_since(args)?;
-    let until = Utc::now();
+    let (until_dt, until_label) = resolve_until(args)?;
 
-    debug!(since = %since_label, "Filtering closed issues for changelog");
+    debug!(since = %since_label, until = %until_label, "Filtering closed issues for changelog");
 
     let filters = ListFilters {
         statuses: Some(vec![Status::Closed]),
         include_closed: true,
         ..Default::default()
     };
-    let issues = storage.list_issues(&filters)?;
-
-    let mut grouped: BTreeMap<String, Vec<Issue>> = BTreeMap::new();
-    for issue in issues {
-        if let Some(since_dt) = since_dt {
-            let Some(closed_at) = issue.closed_at else {
-                continue;
-            };
-            if closed_at < since_dt {
-                continue;
-            }
-        }
-        grouped
-            .entry(issue.issue_type.as_str().to_string())
-            .or_default()
-            .push(issue);
-    }
-
-    let mut groups = Vec::new();
-    for (issue_type, mut items) in grouped {
-        items.sort_by_key(|issue| issue.priority);
-        let label = type_to_header(&issue_type);
-        let issues = items
-            .into_iter()
-            .map(|issue| ChangelogEntry {
-                id: issue.id,
-                title: issue.title,
-                priority: issue.priority.to_string(),
-                closed_at: issue.closed_at.map(|dt| dt.to_rfc3339()),
-            })
-            .collect();
-
-        groups.push(ChangelogGroup {
-            issue_type: issue_type.clone(),
-            label,
-            issues,
-        });
-    }
+    let issues: Vec<Issue> = storage
+        .list_issues(&filters)?
+        .into_iter()
+        .filter(|issue| in_period(issue.closed_at, since_dt, until_dt))
+        .collect();
 
+    let groups = group_issues(&storage, issues, args.group_by)?;
     let total_closed = groups.iter().map(|g| g.issues.len()).sum();
     let output = ChangelogOutput {
         since: since_label,
-        until: until.to_rfc3339(),
+        until: until_label,
         total_closed,
+        suggested_bump: suggest_bump(&groups, &rules),
         groups,
     };
 
     debug!(
         total_closed = output.total_closed,
         groups = output.groups.len(),
+        bump = output.suggested_bump.as_str(),
         "Generated changelog"
     );
 
+    if let (Some(format), Some(path)) = (args.format, &args.prepend) {
+        let section = render_document(&output, format, args, &rules);
+        prepend_to_file(path, &section)?;
+        if json {
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "path": path.display().to_string(),
+                    "total_closed": output.total_closed,
+                    "suggested_bump": output.suggested_bump,
+                }))
+                .unwrap()
+            );
+        } else {
+            ctx.success(&format!(
+                "Updated {} with {} entries (suggested bump: {})",
+                path.display(),
+                output.total_closed,
+                output.suggested_bump.as_str()
+            ));
+        }
+        return Ok(());
+    }
+
     if json {
         // Print JSON directly - don't rely on ctx.json_pretty() since the
         // OutputContext may not be in JSON mode when --robot flag is used
@@ -139,7 +183,9 @@ pub fn execute(
         return Ok(());
     }
 
-    if matches!(ctx.mode(), OutputMode::Rich) {
+    if let Some(format) = args.format {
+        print!("{}", render_document(&output, format, args, &rules));
+    } else if matches!(ctx.mode(), OutputMode::Rich) {
         render_changelog_rich(&output, ctx);
     } else {
         print_text_output(&output);
@@ -148,6 +194,320 @@ pub fn execute(
     Ok(())
 }
 
+/// Whether an issue closed at `closed_at` falls inside the changelog period.
+fn in_period(
+    closed_at: Option<DateTime<Utc>>,
+    since: Option<DateTime<Utc>>,
+    until: Option<DateTime<Utc>>,
+) -> bool {
+    if since.is_none() && until.is_none() {
+        return true;
+    }
+    closed_at.is_some_and(|closed| {
+        since.is_none_or(|since| closed >= since) && until.is_none_or(|until| closed <= until)
+    })
+}
+
+/// Group closed issues and sort each group by priority.
+///
+/// Catch-all groups (no label, no parent epic) sort last.
+fn group_issues(
+    storage: &SqliteStorage,
+    issues: Vec<Issue>,
+    group_by: ChangelogGroupBy,
+) -> Result<Vec<ChangelogGroup>> {
+    let ids: Vec<String> = issues.iter().map(|issue| issue.id.clone()).collect();
+    let mut labels_by_id = storage.get_labels_for_issues(&ids)?;
+    let mut epic_titles: HashMap<String, String> = HashMap::new();
+
+    let mut grouped: BTreeMap<(bool, String), (String, Vec<Issue>)> = BTreeMap::new();
+    for mut issue in issues {
+        issue.labels = labels_by_id.remove(&issue.id).unwrap_or_default();
+        issue.labels.sort();
+        let (key, label) = match group_by {
+            ChangelogGroupBy::Type => {
+                let issue_type = issue.issue_type.as_str().to_string();
+                let label = type_to_header(&issue_type);
+                (issue_type, label)
+            }
+            ChangelogGroupBy::Label => issue.labels.first().map_or_else(
+                || (String::new(), "Unlabeled".to_string()),
+                |label| (label.clone(), label.clone()),
+            ),
+            ChangelogGroupBy::Epic => match storage.get_parent_id(&issue.id)? {
+                Some(epic_id) => {
+                    if let Entry::Vacant(slot) = epic_titles.entry(epic_id.clone()) {
+                        let title = storage
+                            .get_issue(&epic_id)?
+                            .map_or_else(|| epic_id.clone(), |epic| epic.title);
+                        slot.insert(title);
+                    }
+                    let label = format!("{} ({epic_id})", epic_titles[&epic_id]);
+                    (epic_id, label)
+                }
+                None => (String::new(), "Other".to_string()),
+            },
+        };
+        grouped
+            .entry((key.is_empty(), key))
+            .or_insert_with(|| (label, Vec::new()))
+            .1
+            .push(issue);
+    }
+
+    let groups = grouped
+        .into_iter()
+        .map(|((_, key), (label, mut items))| {
+            items.sort_by_key(|issue| issue.priority);
+            ChangelogGroup {
+                issue_type: if group_by == ChangelogGroupBy::Type {
+                    key.clone()
+                } else {
+                    String::new()
+                },
+                key,
+                label,
+                issues: items.into_iter().map(entry_from_issue).collect(),
+            }
+        })
+        .collect();
+    Ok(groups)
+}
+
+fn entry_from_issue(issue: Issue) -> ChangelogEntry {
+    ChangelogEntry {
+        priority: issue.priority.to_string(),
+        issue_type: issue.issue_type.as_str().to_string(),
+        closed_at: issue.closed_at.map(|dt| dt.to_rfc3339()),
+        id: issue.id,
+        title: issue.title,
+        labels: issue.labels,
+    }
+}
+
+/// Bump a single entry calls for under `rules`.
+fn entry_bump(entry: &ChangelogEntry, rules: &BumpRules) -> SemverBump {
+    let listed = |types: &[String], labels: &[String]| {
+        types
+            .iter()
+            .any(|t| t.eq_ignore_ascii_case(&entry.issue_type))
+            || entry
+                .labels
+                .iter()
+                .any(|label| labels.iter().any(|l| l.eq_ignore_ascii_case(label)))
+    };
+    if listed(rules.major_types.as_slice(), rules.major_labels.as_slice()) {
+        SemverBump::Major
+    } else if listed(rules.minor_types.as_slice(), rules.minor_labels.as_slice()) {
+        SemverBump::Minor
+    } else {
+        SemverBump::Patch
+    }
+}
+
+/// Largest bump any closed issue calls for (`none` for an empty period).
+fn suggest_bump(groups: &[ChangelogGroup], rules: &BumpRules) -> SemverBump {
+    groups
+        .iter()
+        .flat_map(|group| &group.issues)
+        .map(|entry| entry_bump(entry, rules))
+        .max()
+        .unwrap_or(SemverBump::None)
+}
+
+/// Render a changelog section as markdown, starting with a `## ` heading.
+fn render_document(
+    output: &ChangelogOutput,
+    format: ChangelogFormat,
+    args: &ChangelogArgs,
+    rules: &BumpRules,
+) -> String {
+    let release = args.release.as_deref().unwrap_or("Unreleased");
+    let date = format_date_brief(&output.until);
+    let mut doc = match format {
+        ChangelogFormat::KeepAChangelog if release.eq_ignore_ascii_case("unreleased") => {
+            format!("## [{release}]\n")
+        }
+        ChangelogFormat::KeepAChangelog => format!("## [{release}] - {date}\n"),
+        ChangelogFormat::Markdown => format!(
+            "## {release}\n\n_{} closed issues, {}_\n",
+            output.total_closed,
+            format_date_range(&output.since, &output.until)
+        ),
+        ChangelogFormat::Conventional => format!("## {release} ({date})\n"),
+    };
+
+    for (name, entries) in document_sections(output, format, args.group_by) {
+        doc.push_str(&format!("\n### {name}\n\n"));
+        for entry in entries {
+            let line = match format {
+                ChangelogFormat::KeepAChangelog => format!("- {} ({})", entry.title, entry.id),
+                ChangelogFormat::Markdown => {
+                    format!("- [{}] {} ({})", entry.priority, entry.title, entry.id)
+                }
+                ChangelogFormat::Conventional => {
+                    let breaking = entry_bump(entry, rules) == SemverBump::Major;
+                    format!(
+                        "- {}{}: {} ({})",
+                        conventional_type(&entry.issue_type),
+                        if breaking { "!" } else { "" },
+                        entry.title,
+                        entry.id
+                    )
+                }
+            };
+            doc.push_str(&line);
+            doc.push('\n');
+        }
+    }
+    doc
+}
+
+/// Sections of a rendered document with their entries.
+///
+/// Keep a Changelog grouped by type uses the spec's own sections instead.
+fn document_sections(
+    output: &ChangelogOutput,
+    format: ChangelogFormat,
+    group_by: ChangelogGroupBy,
+) -> Vec<(String, Vec<&ChangelogEntry>)> {
+    if format != ChangelogFormat::KeepAChangelog || group_by != ChangelogGroupBy::Type {
+        return output
+            .groups
+            .iter()
+            .map(|group| (group.label.clone(), group.issues.iter().collect()))
+            .collect();
+    }
+
+    let mut sections: Vec<Vec<&ChangelogEntry>> = vec![Vec::new(); KEEP_A_CHANGELOG_SECTIONS.len()];
+    for entry in output.groups.iter().flat_map(|group| &group.issues) {
+        sections[keep_a_changelog_section(entry)].push(entry);
+    }
+    KEEP_A_CHANGELOG_SECTIONS
+        .iter()
+        .zip(sections)
+        .filter(|(_, entries)| !entries.is_empty())
+        .map(|(name, entries)| ((*name).to_string(), entries))
+        .collect()
+}
+
+/// Index into [`KEEP_A_CHANGELOG_SECTIONS`] for an entry.
+///
+/// `security`, `deprecated` and `removed` labels win over the issue type.
+fn keep_a_changelog_section(entry: &ChangelogEntry) -> usize {
+    let has_label = |name: &str| entry.labels.iter().any(|l| l.eq_ignore_ascii_case(name));
+    if has_label("security") {
+        5
+    } else if has_label("removed") {
+        3
+    } else if has_label("deprecated") {
+        2
+    } else {
+        match entry.issue_type.as_str() {
+            "feature" => 0,
+            "bug" => 4,
+            _ => 1,
+        }
+    }
+}
+
+/// Conventional-commit prefix for an issue type.
+fn conventional_type(issue_type: &str) -> &str {
+    match issue_type {
+        "feature" => "feat",
+        "bug" => "fix",
+        "docs" => "docs",
+        "task" | "chore" | "epic" | "question" => "chore",
+        other => other,
+    }
+}
+
+/// Insert `section` into the changelog at `path`, creating the file if needed.
+fn prepend_to_file(path: &Path, section: &str) -> Result<()> {
+    let existing = match fs::read_to_string(path) {
+        Ok(contents) => Some(contents),
+        Err(err) if err.kind() == ErrorKind::NotFound => None,
+        Err(err) => return Err(err.into()),
+    };
+    fs::write(path, prepend_section(existing.as_deref(), section))?;
+    Ok(())
+}
+
+/// Insert `section` above the first `## ` release in a changelog.
+///
+/// A section for the same release (e.g. `[Unreleased]`) is replaced in place
+/// instead. Link reference definitions at the end of the file are kept.
+fn prepend_section(existing: Option<&str>, section: &str) -> String {
+    let section = section.trim_end();
+    let Some(existing) = existing else {
+        return format!("# Changelog\n\n{section}\n");
+    };
+
+    let lines: Vec<&str> = existing.lines().collect();
+    let release = section.lines().next().and_then(heading_release);
+    let replace_at = release.and_then(|release| {
+        lines.iter().position(|line| {
+            is_release_heading(line) && heading_release(line).as_deref() == Some(release.as_str())
+        })
+    });
+    let (start, end) = match replace_at {
+        Some(start) => {
+            let end = lines[start + 1..]
+                .iter()
+                .position(|line| is_release_heading(line) || is_link_definition(line))
+                .map_or(lines.len(), |offset| start + 1 + offset);
+            (start, end)
+        }
+        None => {
+            let at = lines
+                .iter()
+                .position(|line| is_release_heading(line) || is_link_definition(line))
+                .unwrap_or(lines.len());
+            (at, at)
+        }
+    };
+
+    let mut out = String::new();
+    for line in &lines[..start] {
+        out.push_str(line);
+        out.push('\n');
+    }
+    if !out.is_empty() && !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+    out.push_str(section);
+    out.push_str("\n\n");
+    for line in &lines[end..] {
+        out.push_str(line);
+        out.push('\n');
+    }
+    format!("{}\n", out.trim_end())
+}
+
+/// Release name of a `## ` heading, lowercased: `## [1.2.0] - 2024-01-01` gives `1.2.0`.
+fn heading_release(line: &str) -> Option<String> {
+    let rest = line.strip_prefix("## ")?.trim();
+    let name = rest.strip_prefix('[').map_or_else(
+        || {
+            rest.split(" (")
+                .next()
+                .and_then(|name| name.split(" - ").next())
+                .unwrap_or(rest)
+        },
+        |bracketed| bracketed.split(']').next().unwrap_or(bracketed),
+    );
+    Some(name.trim().to_lowercase()).filter(|name| !name.is_empty())
+}
+
+fn is_release_heading(line: &str) -> bool {
+    line.starts_with("## ")
+}
+
+/// Markdown link reference definition, as used for release compare links.
+fn is_link_definition(line: &str) -> bool {
+    line.starts_with('[') && line.contains("]: ")
+}
+
 /// Convert issue type to human-readable changelog header.
 fn type_to_header(issue_type: &str) -> String {
     match issue_type {
@@ -181,6 +541,10 @@ fn print_text_output(output: &ChangelogOutput) {
             println!("- [{}] {} {}", entry.priority, entry.id, entry.title);
         }
     }
+    if output.total_closed > 0 {
+        println!();
+        println!("Suggested version bump: {}", output.suggested_bump.as_str());
+    }
 }
 
 /// Render changelog with rich formatting.
@@ -224,6 +588,12 @@ fn render_changelog_rich(output: &ChangelogOutput, ctx: &OutputContext) {
         ),
         theme.success.clone(),
     );
+    if output.total_closed > 0 {
+        content.append_styled(
+            &format!(" · suggested bump: {}", output.suggested_bump.as_str()),
+            theme.dimmed.clone(),
+        );
+    }
 
     // Wrap in panel
     let panel = Panel::from_rich_text(&content, width)
@@ -286,6 +656,21 @@ fn resolve_since(args: &ChangelogArgs) -> Result<(Option<DateTime<Utc>>, String)
     Ok((None, "all".to_string()))
 }
 
+/// Resolve the end of the period: a date, a relative time, or a git reference.
+fn resolve_until(args: &ChangelogArgs) -> Result<(Option<DateTime<Utc>>, String)> {
+    let Some(until) = args.until.as_deref() else {
+        return Ok((None, Utc::now().to_rfc3339()));
+    };
+    if let Some(dt) = parse_relative_time(until) {
+        return Ok((Some(dt), dt.to_rfc3339()));
+    }
+    if let Ok(dt) = parse_flexible_timestamp(until, "until") {
+        return Ok((Some(dt), dt.to_rfc3339()));
+    }
+    let dt = git_ref_date(until)?;
+    Ok((Some(dt), dt.to_rfc3339()))
+}
+
 fn git_ref_date(reference: &str) -> Result<DateTime<Utc>> {
     let output = Command::new("git")
         .args(["show", "-s", "--format=%cI", reference])
@@ -347,6 +732,23 @@ mod tests {
         assert_eq!(label, "all");
     }
 
+    #[test]
+    fn test_resolve_until() {
+        let args = ChangelogArgs {
+            until: Some("2023-06-01T00:00:00Z".to_string()),
+            ..Default::default()
+        };
+        let (dt, label) = resolve_until(&args).unwrap();
+        assert_eq!(
+            dt.unwrap(),
+            Utc.with_ymd_and_hms(2023, 6, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(label, "2023-06-01T00:00:00+00:00");
+
+        let (dt, _) = resolve_until(&ChangelogArgs::default()).unwrap();
+        assert!(dt.is_none());
+    }
+
     #[test]
     fn test_type_to_header() {
         assert_eq!(type_to_header("bug"), "Bug Fixes");
@@ -398,31 +800,40 @@ mod tests {
             since: "2024-01-01T00:00:00Z".to_string(),
             until: "2024-01-22T00:00:00Z".to_string(),
             total_closed: 3,
+            suggested_bump: SemverBump::Minor,
             groups: vec![
                 ChangelogGroup {
                     issue_type: "bug".to_string(),
+                    key: "bug".to_string(),
                     label: "Bug Fixes".to_string(),
                     issues: vec![ChangelogEntry {
                         id: "bd-abc1".to_string(),
                         title: "Fix auth timeout".to_string(),
                         priority: "P1".to_string(),
+                        issue_type: "bug".to_string(),
+                        labels: vec![],
                         closed_at: Some("2024-01-15T00:00:00Z".to_string()),
                     }],
                 },
                 ChangelogGroup {
                     issue_type: "feature".to_string(),
+                    key: "feature".to_string(),
                     label: "Features".to_string(),
                     issues: vec![
                         ChangelogEntry {
                             id: "bd-def2".to_string(),
                             title: "Add dark mode".to_string(),
                             priority: "P2".to_string(),
+                            issue_type: "feature".to_string(),
+                            labels: vec![],
                             closed_at: Some("2024-01-16T00:00:00Z".to_string()),
                         },
                         ChangelogEntry {
                             id: "bd-ghi3".to_string(),
                             title: "User preferences".to_string(),
                             priority: "P2".to_string(),
+                            issue_type: "feature".to_string(),
+                            labels: vec![],
                             closed_at: Some("2024-01-17T00:00:00Z".to_string()),
                         },
                     ],
@@ -442,13 +853,17 @@ mod tests {
             since: "all".to_string(),
             until: "2024-01-22T00:00:00Z".to_string(),
             total_closed: 1,
+            suggested_bump: SemverBump::Patch,
             groups: vec![ChangelogGroup {
                 issue_type: "bug".to_string(),
+                key: "bug".to_string(),
                 label: "Bug Fixes".to_string(),
                 issues: vec![ChangelogEntry {
                     id: "bd-test".to_string(),
                     title: "Test issue".to_string(),
                     priority: "P1".to_string(),
+                    issue_type: "bug".to_string(),
+                    labels: vec![],
                     closed_at: None,
                 }],
             }],
@@ -459,6 +874,7 @@ mod tests {
         assert!(json_str.contains("\"total_closed\": 1"));
         assert!(json_str.contains("Bug Fixes"));
         assert!(json_str.contains("bd-test"));
+        assert!(json_str.contains("\"suggested_bump\": \"patch\""));
         // closed_at should be omitted when None
         assert!(!json_str.contains("closed_at"));
     }
@@ -469,10 +885,143 @@ mod tests {
             since: "all".to_string(),
             until: "2024-01-22T00:00:00Z".to_string(),
             total_closed: 0,
+            suggested_bump: SemverBump::None,
             groups: vec![],
         };
 
         assert!(output.groups.is_empty());
         assert_eq!(output.total_closed, 0);
     }
+
+    fn make_entry(id: &str, issue_type: &str, labels: &[&str]) -> ChangelogEntry {
+        ChangelogEntry {
+            id: id.to_string(),
+            title: format!("Title {id}"),
+            priority: "P2".to_string(),
+            issue_type: issue_type.to_string(),
+            labels: labels.iter().map(ToString::to_string).collect(),
+            closed_at: None,
+        }
+    }
+
+    #[test]
+    fn test_in_period() {
+        let jan = |day| Some(Utc.with_ymd_and_hms(2024, 1, day, 0, 0, 0).unwrap());
+        assert!(in_period(None, None, None));
+        assert!(!in_period(None, jan(1), None));
+        assert!(in_period(jan(5), jan(1), jan(10)));
+        assert!(!in_period(jan(12), jan(1), jan(10)));
+        assert!(!in_period(jan(1), jan(2), None));
+    }
+
+    #[test]
+    fn test_suggest_bump_rules() {
+        let rules = BumpRules::default();
+        let group = |issues| ChangelogGroup {
+            issue_type: String::new(),
+            key: String::new(),
+            label: "All".to_string(),
+            issues,
+        };
+        assert_eq!(suggest_bump(&[], &rules), SemverBump::None);
+        let patch = [group(vec![make_entry("bd-1", "bug", &[])])];
+        assert_eq!(suggest_bump(&patch, &rules), SemverBump::Patch);
+        let minor = [group(vec![
+            make_entry("bd-1", "bug", &[]),
+            make_entry("bd-2", "feature", &[]),
+        ])];
+        assert_eq!(suggest_bump(&minor, &rules), SemverBump::Minor);
+        let major = [group(vec![make_entry("bd-3", "task", &["Breaking"])])];
+        assert_eq!(suggest_bump(&major, &rules), SemverBump::Major);
+    }
+
+    #[test]
+    fn test_render_documents() {
+        let rules = BumpRules::default();
+        let output = ChangelogOutput {
+            since: "all".to_string(),
+            until: "2024-01-22T00:00:00Z".to_string(),
+            total_closed: 3,
+            suggested_bump: SemverBump::Major,
+            groups: vec![ChangelogGroup {
+                issue_type: String::new(),
+                key: String::new(),
+                label: "All".to_string(),
+                issues: vec![
+                    make_entry("bd-1", "feature", &[]),
+                    make_entry("bd-2", "bug", &["security"]),
+                    make_entry("bd-3", "task", &["breaking"]),
+                ],
+            }],
+        };
+        let args = ChangelogArgs {
+            release: Some("1.2.0".to_string()),
+            ..Default::default()
+        };
+
+        let kac = render_document(&output, ChangelogFormat::KeepAChangelog, &args, &rules);
+        assert_eq!(
+            kac,
+            "## [1.2.0] - 2024-01-22\n\n### Added\n\n- Title bd-1 (bd-1)\n\n\
+             ### Changed\n\n- Title bd-3 (bd-3)\n\n### Security\n\n- Title bd-2 (bd-2)\n"
+        );
+
+        let conventional = render_document(&output, ChangelogFormat::Conventional, &args, &rules);
+        assert!(conventional.starts_with("## 1.2.0 (2024-01-22)\n\n### All\n"));
+        assert!(conventional.contains("- feat: Title bd-1 (bd-1)\n"));
+        assert!(conventional.contains("- fix: Title bd-2 (bd-2)\n"));
+        assert!(conventional.contains("- chore!: Title bd-3 (bd-3)\n"));
+
+        let unreleased = render_document(
+            &output,
+            ChangelogFormat::KeepAChangelog,
+            &ChangelogArgs::default(),
+            &rules,
+        );
+        assert!(unreleased.starts_with("## [Unreleased]\n"));
+    }
+
+    #[test]
+    fn test_prepend_section() {
+        let section = "## [Unreleased]\n\n### Fixed\n\n- New fix (bd-9)\n";
+        assert_eq!(
+            prepend_section(None, section),
+            "# Changelog\n\n## [Unreleased]\n\n### Fixed\n\n- New fix (bd-9)\n"
+        );
+
+        let existing = "# Changelog\n\nIntro.\n\n## [Unreleased]\n\n- Old (bd-1)\n\n\
+                        ## [1.0.0] - 2024-01-01\n\n- First (bd-0)\n\n\
+                        [unreleased]: https://example.com/compare\n";
+        let replaced = prepend_section(Some(existing), section);
+        assert_eq!(
+            replaced,
+            "# Changelog\n\nIntro.\n\n## [Unreleased]\n\n### Fixed\n\n- New fix (bd-9)\n\n\
+             ## [1.0.0] - 2024-01-01\n\n- First (bd-0)\n\n\
+             [unreleased]: https://example.com/compare\n"
+        );
+
+        let release = "## [1.1.0] - 2024-02-01\n\n- Feature (bd-5)\n";
+        let inserted = prepend_section(Some(existing), release);
+        assert!(inserted.starts_with(
+            "# Changelog\n\nIntro.\n\n## [1.1.0] - 2024-02-01\n\n- Feature (bd-5)\n\n## [Unreleased]\n"
+        ));
+        assert!(inserted.ends_with("[unreleased]: https://example.com/compare\n"));
+    }
+
+    #[test]
+    fn test_heading_release() {
+        assert_eq!(
+            heading_release("## [Unreleased]").as_deref(),
+            Some("unreleased")
+        );
+        assert_eq!(
+            heading_release("## [1.2.0] - 2024-01-01").as_deref(),
+            Some("1.2.0")
+        );
+        assert_eq!(
+            heading_release("## 1.2.0 (2024-01-01)").as_deref(),
+            Some("1.2.0")
+        );
+        assert_eq!(heading_release("### Added"), None);
+    }
 }
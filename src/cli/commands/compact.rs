@@ -0,0 +1,743 @@
+//! Compact command implementation.
+//!
+//! Shrinks old closed issues so agents reading the JSONL export don't spend
+//! tokens on ancient history. Compaction is deterministic: text fields are
+//! cut to their first paragraph at a fixed length and comments collapse into
+//! a single digest, always computed from the original content so running a
+//! higher level later gives the same result as running it directly. The full
+//! originals are appended to `compaction-archive.jsonl` before anything is
+//! changed, and `br compact --restore <id>` puts them back.
+
+use crate::cli::CompactArgs;
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::model::{Comment, EventType, Issue, Status};
+use crate::output::OutputContext;
+use crate::storage::SqliteStorage;
+use crate::util::id::{IdResolver, ResolverConfig, find_matching_ids};
+use crate::util::time::parse_duration;
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs::{File, OpenOptions};
+use std::io::{BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::{debug, info};
+
+/// Archive of pre-compaction originals, kept next to the JSONL export.
+pub const COMPACTION_ARCHIVE_FILE: &str = "compaction-archive.jsonl";
+
+/// Prefix marking a comment digest written by compaction.
+const DIGEST_PREFIX: &str = "[compacted]";
+
+/// Per-level limits, in characters.
+struct LevelLimits {
+    description: usize,
+    /// `None` drops the field entirely.
+    design_and_notes: Option<usize>,
+    /// `None` omits the last-comment excerpt from the digest.
+    comment_excerpt: Option<usize>,
+}
+
+const fn limits(level: u8) -> LevelLimits {
+    if level >= 2 {
+        LevelLimits {
+            description: 160,
+            design_and_notes: None,
+            comment_excerpt: None,
+        }
+    } else {
+        LevelLimits {
+            description: 500,
+            design_and_notes: Some(200),
+            comment_excerpt: Some(200),
+        }
+    }
+}
+
+/// One line of the compaction archive.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ArchiveEntry {
+    pub id: String,
+    pub archived_at: DateTime<Utc>,
+    /// The issue as it was before its first compaction, with comments,
+    /// labels and dependencies.
+    pub original: Issue,
+}
+
+/// Per-issue result.
+#[derive(Debug, Serialize)]
+pub struct CompactedIssue {
+    pub id: String,
+    pub title: String,
+    pub level: u8,
+    pub original_size: usize,
+    pub compacted_size: usize,
+}
+
+/// Result of a compaction run.
+#[derive(Debug, Serialize)]
+pub struct CompactReport {
+    pub dry_run: bool,
+    pub level: u8,
+    pub cutoff: DateTime<Utc>,
+    pub issues: Vec<CompactedIssue>,
+    pub original_bytes: usize,
+    pub compacted_bytes: usize,
+    pub archive_path: String,
+}
+
+/// Result of restoring issues from the archive.
+#[derive(Debug, Serialize)]
+pub struct RestoreReport {
+    pub restored: Vec<String>,
+    pub not_archived: Vec<String>,
+}
+
+/// Execute the compact command.
+///
+/// # Errors
+///
+/// Returns an error if `--older-than` is invalid, the archive cannot be
+/// written, or a database operation fails.
+pub fn execute(args: &CompactArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&config_layer);
+    let archive_path = storage_ctx.paths.beads_dir.join(COMPACTION_ARCHIVE_FILE);
+
+    if !args.restore.is_empty() {
+        let id_config = config::id_config_from_layer(&config_layer);
+        let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+        let all_ids = storage_ctx.storage.get_all_ids()?;
+        let resolved = resolver.resolve_all(
+            &args.restore,
+            |id| all_ids.iter().any(|existing| existing == id),
+            |hash| find_matching_ids(&all_ids, hash),
+        )?;
+        let ids: Vec<String> = resolved.into_iter().map(|r| r.id).collect();
+        let report = restore(&mut storage_ctx.storage, &archive_path, &ids, &actor)?;
+        storage_ctx.flush_no_db_if_dirty()?;
+        render_restore(&report, ctx);
+        return Ok(());
+    }
+
+    let invalid_age = || {
+        BeadsError::validation(
+            "older-than",
+            format!("invalid age '{}' (expected e.g. 90d, 12w)", args.older_than),
+        )
+    };
+    let age = parse_duration(&args.older_than).ok_or_else(invalid_age)?;
+    let now = Utc::now();
+    let cutoff = now.checked_sub_signed(age).ok_or_else(invalid_age)?;
+    let project_root = storage_ctx
+        .paths
+        .beads_dir
+        .parent()
+        .map_or_else(|| PathBuf::from("."), Path::to_path_buf);
+    let commit = if args.dry_run {
+        None
+    } else {
+        head_commit(&project_root)
+    };
+
+    let report = compact(
+        &mut storage_ctx.storage,
+        &archive_path,
+        &CompactOptions {
+            level: args.level,
+            cutoff,
+            limit: args.limit,
+            dry_run: args.dry_run,
+            now,
+            commit,
+        },
+        &actor,
+    )?;
+    storage_ctx.flush_no_db_if_dirty()?;
+    render_report(&report, ctx);
+    Ok(())
+}
+
+/// Options for a compaction run.
+#[derive(Debug, Clone)]
+pub struct CompactOptions {
+    pub level: u8,
+    /// Issues closed before this instant are eligible.
+    pub cutoff: DateTime<Utc>,
+    pub limit: Option<usize>,
+    pub dry_run: bool,
+    /// Recorded as `compacted_at`.
+    pub now: DateTime<Utc>,
+    /// Recorded as `compacted_at_commit`.
+    pub commit: Option<String>,
+}
+
+/// Compact eligible issues.
+///
+/// An issue is eligible when it is closed, was closed before the cutoff, and
+/// is below the requested level. Originals are archived before the database
+/// is touched.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read or written, or a database
+/// operation fails.
+pub fn compact(
+    storage: &mut SqliteStorage,
+    archive_path: &Path,
+    options: &CompactOptions,
+    actor: &str,
+) -> Result<CompactReport> {
+    let archive = load_archive(archive_path)?;
+    let level = i32::from(options.level);
+
+    let mut candidates: Vec<Issue> = storage
+        .get_all_issues_for_export()?
+        .into_iter()
+        .filter(|issue| {
+            issue.status == Status::Closed
+                && issue.closed_at.unwrap_or(issue.updated_at) < options.cutoff
+                && issue.compaction_level.unwrap_or(0) < level
+        })
+        .collect();
+    if let Some(limit) = options.limit {
+        candidates.truncate(limit);
+    }
+
+    let mut planned = Vec::with_capacity(candidates.len());
+    let mut new_entries = Vec::new();
+    for candidate in candidates {
+        let Some(current) = storage.get_issue_for_export(&candidate.id)? else {
+            continue;
+        };
+        let original = if current.compaction_level.unwrap_or(0) > 0 {
+            archive
+                .get(&current.id)
+                .map_or_else(|| current.clone(), |entry| entry.original.clone())
+        } else {
+            new_entries.push(ArchiveEntry {
+                id: current.id.clone(),
+                archived_at: options.now,
+                original: current.clone(),
+            });
+            current.clone()
+        };
+
+        let mut compacted =
+            with_compactable_fields(&current, &compact_issue(&original, options.level));
+        compacted.compacted_at = Some(options.now);
+        compacted.compacted_at_commit.clone_from(&options.commit);
+        planned.push((current, compacted));
+    }
+
+    if !options.dry_run {
+        append_archive(archive_path, &new_entries)?;
+    }
+
+    let mut report = CompactReport {
+        dry_run: options.dry_run,
+        level: options.level,
+        cutoff: options.cutoff,
+        issues: Vec::with_capacity(planned.len()),
+        original_bytes: 0,
+        compacted_bytes: 0,
+        archive_path: archive_path.display().to_string(),
+    };
+
+    for (current, compacted) in planned {
+        let before = record_size(&current);
+        let after = record_size(&compacted);
+        if !options.dry_run {
+            storage.apply_compaction(
+                &compacted,
+                EventType::Compacted,
+                &format!(
+                    "Compacted to level {}: {before} -> {after} bytes",
+                    options.level
+                ),
+                actor,
+            )?;
+        }
+        debug!(id = %compacted.id, before, after, "Compacted issue");
+        report.original_bytes += before;
+        report.compacted_bytes += after;
+        report.issues.push(CompactedIssue {
+            id: compacted.id,
+            title: compacted.title,
+            level: options.level,
+            original_size: before,
+            compacted_size: after,
+        });
+    }
+
+    info!(
+        count = report.issues.len(),
+        saved = report.original_bytes.saturating_sub(report.compacted_bytes),
+        dry_run = options.dry_run,
+        "Compaction finished"
+    );
+    Ok(report)
+}
+
+/// Restore compacted issues from the archive.
+///
+/// # Errors
+///
+/// Returns an error if the archive cannot be read or a database operation fails.
+pub fn restore(
+    storage: &mut SqliteStorage,
+    archive_path: &Path,
+    ids: &[String],
+    actor: &str,
+) -> Result<RestoreReport> {
+    let archive = load_archive(archive_path)?;
+    let mut report = RestoreReport {
+        restored: Vec::new(),
+        not_archived: Vec::new(),
+    };
+
+    for id in ids {
+        let (Some(entry), Some(current)) = (archive.get(id), storage.get_issue(id)?) else {
+            report.not_archived.push(id.clone());
+            continue;
+        };
+        storage.apply_compaction(
+            &with_compactable_fields(&current, &entry.original),
+            EventType::Updated,
+            "Restored from compaction archive",
+            actor,
+        )?;
+        report.restored.push(id.clone());
+    }
+
+    Ok(report)
+}
+
+/// Compute the compacted form of an issue.
+///
+/// Only description, design, notes, comments and the compaction metadata
+/// change; the result depends on nothing but `original` and `level`.
+#[must_use]
+pub fn compact_issue(original: &Issue, level: u8) -> Issue {
+    let limits = limits(level);
+    let mut compacted = original.clone();
+
+    compacted.description = original
+        .description
+        .as_deref()
+        .and_then(|text| summarize(text, limits.description));
+    compacted.design = limits
+        .design_and_notes
+        .and_then(|limit| summarize(original.design.as_deref()?, limit));
+    compacted.notes = limits
+        .design_and_notes
+        .and_then(|limit| summarize(original.notes.as_deref()?, limit));
+    compacted.comments = comment_digest(&original.comments, limits.comment_excerpt)
+        .into_iter()
+        .collect();
+
+    compacted.compaction_level = Some(i32::from(level));
+    compacted.original_size = Some(
+        original
+            .original_size
+            .filter(|size| *size > 0)
+            .unwrap_or_else(|| i32::try_from(record_size(original)).unwrap_or(i32::MAX)),
+    );
+    compacted.content_hash = Some(compacted.compute_content_hash());
+    compacted
+}
+
+/// Copy the fields compaction touches from `source` onto `target`, leaving
+/// everything edited since (title, status, labels, ...) as it is now.
+fn with_compactable_fields(target: &Issue, source: &Issue) -> Issue {
+    let mut issue = target.clone();
+    issue.description.clone_from(&source.description);
+    issue.design.clone_from(&source.design);
+    issue.notes.clone_from(&source.notes);
+    issue.comments.clone_from(&source.comments);
+    issue.compaction_level = source.compaction_level;
+    issue.compacted_at = source.compacted_at;
+    issue
+        .compacted_at_commit
+        .clone_from(&source.compacted_at_commit);
+    issue.original_size = source.original_size;
+    issue.content_hash = Some(issue.compute_content_hash());
+    issue
+}
+
+/// Reduce text to its first paragraph, cut at a word boundary to `limit`
+/// characters. Text already within the limit is kept verbatim.
+fn summarize(text: &str, limit: usize) -> Option<String> {
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    if trimmed.chars().count() <= limit {
+        return Some(trimmed.to_string());
+    }
+
+    let paragraph = trimmed.split("\n\n").next().unwrap_or(trimmed);
+    let words: Vec<&str> = paragraph.split_whitespace().collect();
+    let mut summary = String::new();
+    let mut length = 0;
+    let mut used = 0;
+    for word in &words {
+        let extra = usize::from(length > 0) + word.chars().count();
+        if length + extra > limit {
+            break;
+        }
+        if length > 0 {
+            summary.push(' ');
+        }
+        summary.push_str(word);
+        length += extra;
+        used += 1;
+    }
+    if used == 0 {
+        // A single word longer than the limit: cut it.
+        summary = paragraph.chars().take(limit).collect();
+    }
+
+    summary.push_str(" …");
+    Some(summary)
+}
+
+/// Collapse comments into a single digest comment, or `None` if there are none.
+///
+/// The digest keeps the last comment's author and timestamp so it sorts where
+/// the thread ended.
+fn comment_digest(comments: &[Comment], excerpt: Option<usize>) -> Option<Comment> {
+    let first = comments.first()?;
+    let last = comments.last()?;
+    if comments.len() == 1 && first.body.starts_with(DIGEST_PREFIX) {
+        return Some(first.clone());
+    }
+
+    let mut authors: Vec<&str> = Vec::new();
+    for comment in comments {
+        if !authors.contains(&comment.author.as_str()) {
+            authors.push(&comment.author);
+        }
+    }
+
+    let mut body = format!(
+        "{DIGEST_PREFIX} {} comment{} by {} from {} to {}.",
+        comments.len(),
+        if comments.len() == 1 { "" } else { "s" },
+        authors.join(", "),
+        first.created_at.format("%Y-%m-%d"),
+        last.created_at.format("%Y-%m-%d"),
+    );
+    if let Some(summary) = excerpt.and_then(|limit| summarize(&last.body, limit)) {
+        body.push_str(" Last: ");
+        body.push_str(&summary);
+    }
+
+    Some(Comment {
+        id: 0,
+        issue_id: last.issue_id.clone(),
+        author: last.author.clone(),
+        body,
+        created_at: last.created_at,
+    })
+}
+
+/// Size of an issue's JSONL record in bytes.
+fn record_size(issue: &Issue) -> usize {
+    serde_json::to_string(issue).map_or(0, |json| json.len())
+}
+
+/// Load the archive, keyed by issue ID. Later entries win.
+fn load_archive(path: &Path) -> Result<HashMap<String, ArchiveEntry>> {
+    let mut entries = HashMap::new();
+    if !path.exists() {
+        return Ok(entries);
+    }
+
+    let reader = BufReader::new(File::open(path)?);
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let entry: ArchiveEntry = serde_json::from_str(&line).map_err(|e| {
+            BeadsError::Config(format!(
+                "Invalid JSON in {} at line {}: {e}",
+                path.display(),
+                line_num + 1
+            ))
+        })?;
+        entries.insert(entry.id.clone(), entry);
+    }
+    Ok(entries)
+}
+
+fn append_archive(path: &Path, entries: &[ArchiveEntry]) -> Result<()> {
+    if entries.is_empty() {
+        return Ok(());
+    }
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    for entry in entries {
+        let json = serde_json::to_string(entry)?;
+        writeln!(file, "{json}")?;
+    }
+    file.sync_all()?;
+    Ok(())
+}
+
+/// Current git HEAD of the project, if it is a git checkout.
+fn head_commit(project_root: &Path) -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "HEAD"])
+        .current_dir(project_root)
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    let commit = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!commit.is_empty()).then_some(commit)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_size(bytes: usize) -> String {
+    const KB: usize = 1024;
+    const MB: usize = 1024 * 1024;
+
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+fn render_report(report: &CompactReport, ctx: &OutputContext) {
+    if ctx.is_json() {
+        ctx.json_pretty(report);
+        return;
+    }
+
+    if report.issues.is_empty() {
+        ctx.info(&format!(
+            "No closed issues to compact to level {} (closed before {})",
+            report.level,
+            report.cutoff.format("%Y-%m-%d")
+        ));
+        return;
+    }
+
+    let verb = if report.dry_run {
+        "Would compact"
+    } else {
+        "Compacted"
+    };
+    for issue in &report.issues {
+        ctx.print(&format!(
+            "  {} {} ({} -> {})",
+            issue.id,
+            issue.title,
+            format_size(issue.original_size),
+            format_size(issue.compacted_size)
+        ));
+    }
+    let message = format!(
+        "{verb} {} issue(s) to level {}: {} -> {} (saves {})",
+        report.issues.len(),
+        report.level,
+        format_size(report.original_bytes),
+        format_size(report.compacted_bytes),
+        format_size(report.original_bytes.saturating_sub(report.compacted_bytes))
+    );
+    if report.dry_run {
+        ctx.info(&message);
+    } else {
+        ctx.success(&message);
+        ctx.info(&format!("Originals archived in {}", report.archive_path));
+    }
+}
+
+fn render_restore(report: &RestoreReport, ctx: &OutputContext) {
+    if ctx.is_json() {
+        ctx.json_pretty(report);
+        return;
+    }
+    for id in &report.restored {
+        ctx.success(&format!("Restored {id} from compaction archive"));
+    }
+    for id in &report.not_archived {
+        ctx.warning(&format!("{id} has no entry in the compaction archive"));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tempfile::TempDir;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn closed_issue(id: &str, closed_days_ago: i64) -> Issue {
+        let closed_at = Utc::now() - Duration::days(closed_days_ago);
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            description: Some(format!(
+                "{}\n\nSecond paragraph with more detail.",
+                "word ".repeat(200).trim_end()
+            )),
+            design: Some("design ".repeat(100)),
+            notes: Some("short note".to_string()),
+            status: Status::Closed,
+            closed_at: Some(closed_at),
+            updated_at: closed_at,
+            ..Issue::default()
+        }
+    }
+
+    fn options(level: u8, dry_run: bool) -> CompactOptions {
+        CompactOptions {
+            level,
+            cutoff: Utc::now() - Duration::days(90),
+            limit: None,
+            dry_run,
+            now: Utc::now(),
+            commit: None,
+        }
+    }
+
+    #[test]
+    fn test_summarize_keeps_short_text_and_cuts_long_text() {
+        init_logging();
+        info!("test_summarize_keeps_short_text_and_cuts_long_text: starting");
+        assert_eq!(summarize("  short  ", 20), Some("short".to_string()));
+        assert_eq!(summarize("   ", 20), None);
+        assert_eq!(
+            summarize("one two three four\n\nsecond", 9),
+            Some("one two …".to_string())
+        );
+        assert_eq!(
+            summarize("abcdefghijklmnop", 5),
+            Some("abcde …".to_string())
+        );
+        info!("test_summarize_keeps_short_text_and_cuts_long_text: assertions passed");
+    }
+
+    #[test]
+    fn test_compact_issue_is_deterministic() {
+        init_logging();
+        info!("test_compact_issue_is_deterministic: starting");
+        let mut issue = closed_issue("bd-1", 200);
+        issue.comments = vec![
+            Comment {
+                id: 1,
+                issue_id: "bd-1".to_string(),
+                author: "alice".to_string(),
+                body: "first".to_string(),
+                created_at: Utc::now() - Duration::days(300),
+            },
+            Comment {
+                id: 2,
+                issue_id: "bd-1".to_string(),
+                author: "bob".to_string(),
+                body: "final answer".to_string(),
+                created_at: Utc::now() - Duration::days(250),
+            },
+        ];
+
+        let once = compact_issue(&issue, 1);
+        let twice = compact_issue(&issue, 1);
+        assert_eq!(once, twice);
+        assert_eq!(once.compaction_level, Some(1));
+        assert!(once.description.as_ref().unwrap().chars().count() <= 502);
+        assert_eq!(once.notes.as_deref(), Some("short note"));
+        assert_eq!(once.comments.len(), 1);
+        assert!(once.comments[0].body.starts_with(DIGEST_PREFIX));
+        assert!(once.comments[0].body.contains("alice, bob"));
+        assert!(once.comments[0].body.contains("Last: final answer"));
+        assert!(once.original_size.unwrap() > 0);
+
+        let level2 = compact_issue(&issue, 2);
+        assert!(level2.design.is_none());
+        assert!(level2.notes.is_none());
+        assert!(!level2.comments[0].body.contains("Last:"));
+        info!("test_compact_issue_is_deterministic: assertions passed");
+    }
+
+    #[test]
+    fn test_compact_archives_and_restores() {
+        init_logging();
+        info!("test_compact_archives_and_restores: starting");
+        let temp = TempDir::new().unwrap();
+        let archive_path = temp.path().join(COMPACTION_ARCHIVE_FILE);
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let old = closed_issue("bd-old", 200);
+        let recent = closed_issue("bd-new", 10);
+        storage.create_issue(&old, "tester").unwrap();
+        storage.create_issue(&recent, "tester").unwrap();
+        storage.add_comment("bd-old", "alice", "a comment").unwrap();
+
+        let dry = compact(&mut storage, &archive_path, &options(1, true), "tester").unwrap();
+        assert_eq!(dry.issues.len(), 1);
+        assert!(!archive_path.exists());
+
+        let report = compact(&mut storage, &archive_path, &options(1, false), "tester").unwrap();
+        assert_eq!(report.issues.len(), 1);
+        assert_eq!(report.issues[0].id, "bd-old");
+        assert!(report.compacted_bytes < report.original_bytes);
+
+        let compacted = storage.get_issue_for_export("bd-old").unwrap().unwrap();
+        assert_eq!(compacted.compaction_level, Some(1));
+        assert_eq!(compacted.comments.len(), 1);
+        assert!(compacted.comments[0].body.starts_with(DIGEST_PREFIX));
+
+        // Already at level 1: nothing more to do at the same level
+        let again = compact(&mut storage, &archive_path, &options(1, false), "tester").unwrap();
+        assert!(again.issues.is_empty());
+
+        let restored = restore(
+            &mut storage,
+            &archive_path,
+            &["bd-old".to_string(), "bd-new".to_string()],
+            "tester",
+        )
+        .unwrap();
+        assert_eq!(restored.restored, vec!["bd-old".to_string()]);
+        assert_eq!(restored.not_archived, vec!["bd-new".to_string()]);
+
+        let back = storage.get_issue_for_export("bd-old").unwrap().unwrap();
+        assert_eq!(back.description, old.description);
+        assert_eq!(back.compaction_level, Some(0));
+        assert_eq!(back.comments[0].body, "a comment");
+        info!("test_compact_archives_and_restores: assertions passed");
+    }
+
+    #[test]
+    fn test_level_two_starts_from_archived_original() {
+        init_logging();
+        info!("test_level_two_starts_from_archived_original: starting");
+        let temp = TempDir::new().unwrap();
+        let archive_path = temp.path().join(COMPACTION_ARCHIVE_FILE);
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let old = closed_issue("bd-old", 200);
+        storage.create_issue(&old, "tester").unwrap();
+
+        compact(&mut storage, &archive_path, &options(1, false), "tester").unwrap();
+        compact(&mut storage, &archive_path, &options(2, false), "tester").unwrap();
+
+        let stored = storage.get_issue("bd-old").unwrap().unwrap();
+        let direct = compact_issue(&old, 2);
+        assert_eq!(stored.compaction_level, Some(2));
+        assert_eq!(stored.description, direct.description);
+        assert_eq!(load_archive(&archive_path).unwrap().len(), 1);
+        info!("test_level_two_starts_from_archived_original: assertions passed");
+    }
+}
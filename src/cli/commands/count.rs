@@ -3,7 +3,7 @@ use crate::config;
 use crate::error::Result;
 use crate::model::{IssueType, Priority, Status};
 use crate::output::{OutputContext, OutputMode};
-use crate::storage::{ListFilters, SqliteStorage};
+use crate::storage::{ListFilters, SqliteStorage, WhereClause};
 use rich_rust::prelude::*;
 use serde::Serialize;
 use std::collections::BTreeMap;
@@ -63,6 +63,11 @@ pub fn execute(
     filters.include_closed = filters.include_closed || args.include_closed;
     filters.include_templates = args.include_templates;
     filters.title_contains.clone_from(&args.title_contains);
+    if let Some(expr) = &args.where_ {
+        let clause = WhereClause::parse(expr)?;
+        filters.include_closed = filters.include_closed || clause.constrains_status();
+        filters.where_clause = Some(clause);
+    }
 
     let issues = storage.list_issues(&filters)?;
     let total = issues.len();
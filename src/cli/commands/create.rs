@@ -3,12 +3,14 @@ use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::model::{Dependency, DependencyType, Issue, IssueType, Priority, Status};
 use crate::output::OutputContext;
-use crate::storage::SqliteStorage;
+use crate::storage::{IssueUpdate, SqliteStorage};
 use crate::util::id::{IdGenerator, child_id};
-use crate::util::markdown_import::{parse_dependency, parse_markdown_file};
+use crate::util::markdown_import::{ParsedIssue, parse_dependency, parse_markdown_file};
 use crate::util::time::parse_flexible_timestamp;
+use crate::util::wip::{WipCandidate, check_transition};
 use crate::validation::{IssueValidator, LabelValidator};
 use chrono::{DateTime, Utc};
+use serde::Serialize;
 use std::path::Path;
 use std::str::FromStr;
 
@@ -18,6 +20,8 @@ pub struct CreateConfig {
     pub default_priority: Priority,
     pub default_issue_type: IssueType,
     pub actor: String,
+    /// Checked against the initial status unless `--force` is given.
+    pub wip_limits: Vec<config::WipLimit>,
 }
 
 /// Execute the create command.
@@ -55,6 +59,7 @@ pub fn execute(args: &CreateArgs, cli: &config::CliOverrides, ctx: &OutputContex
         default_priority: config::default_priority_from_layer(&layer)?,
         default_issue_type: config::default_issue_type_from_layer(&layer)?,
         actor: config::resolve_actor(&layer),
+        wip_limits: config::wip_limits_from_layer(&layer),
     };
 
     let issue = create_issue_impl(&mut storage_ctx.storage, args, &config)?;
@@ -262,13 +267,29 @@ pub fn create_issue_impl(
     // 6. Populate Relations (labels & dependencies)
     populate_relations(&mut issue, args, &config.actor, now);
 
+    // 6b. WIP limits for the initial status, counted in the insert's transaction
+    let wip_limits = if args.force {
+        &[][..]
+    } else {
+        &config.wip_limits[..]
+    };
+    let candidate = WipCandidate {
+        id: &issue.id,
+        status: issue.status.as_str(),
+        assignee: issue.assignee.as_deref(),
+        labels: &issue.labels,
+    };
+
     // 7. Dry Run check - return early
     if args.dry_run {
+        check_transition(&storage.reader(), wip_limits, &candidate)?;
         return Ok(issue);
     }
 
     // 8. Create (atomic)
-    storage.create_issue(&issue, &config.actor)?;
+    storage.create_issue_guarded(&issue, &config.actor, |reader| {
+        check_transition(reader, wip_limits, &candidate)
+    })?;
 
     Ok(issue)
 }
@@ -384,6 +405,15 @@ fn populate_relations(issue: &mut Issue, args: &CreateArgs, actor: &str, now: Da
     }
 }
 
+/// One issue in `create --file` JSON output, tagged with what happened to it.
+#[derive(Debug, Serialize)]
+struct ImportedIssue {
+    /// `created` or `updated` (for blocks with an ID marker).
+    action: &'static str,
+    #[serde(flatten)]
+    issue: Issue,
+}
+
 #[allow(clippy::too_many_lines)]
 fn execute_import(
     path: &Path,
@@ -407,6 +437,11 @@ fn execute_import(
     let default_priority = config::default_priority_from_layer(&layer)?;
     let default_issue_type = config::default_issue_type_from_layer(&layer)?;
     let actor = config::resolve_actor(&layer);
+    let wip_limits = if args.force {
+        Vec::new()
+    } else {
+        config::wip_limits_from_layer(&layer)
+    };
     let now = Utc::now();
     let _json_mode = cli.json.unwrap_or(false);
     let due_at = parse_optional_date(args.due.as_deref())?;
@@ -429,9 +464,10 @@ fn execute_import(
     let storage = &mut storage_ctx.storage;
     let id_gen = IdGenerator::new(id_config);
 
-    // Track created IDs for output
+    // Track created and updated IDs for output
     let mut created_ids = Vec::new();
-    let mut created_issues = Vec::new();
+    let mut updated_ids = Vec::new();
+    let mut imported_issues = Vec::new();
 
     for parsed in parsed_issues {
         let title = parsed.title.trim().to_string();
@@ -440,6 +476,47 @@ fn execute_import(
             continue;
         }
 
+        // Blocks exported with an ID marker update the issue they came from
+        if let Some(marker_id) = parsed.id.clone() {
+            match storage.get_issue(&marker_id)? {
+                Some(existing) if existing.status != Status::Tombstone => {
+                    match update_from_markdown(storage, &existing, &parsed, &actor, &wip_limits) {
+                        Ok(true) => {
+                            if ctx.is_json() {
+                                if let Some(full_issue) =
+                                    storage.get_issue_for_export(&marker_id)?
+                                {
+                                    imported_issues.push(ImportedIssue {
+                                        action: "updated",
+                                        issue: full_issue,
+                                    });
+                                }
+                            }
+                            updated_ids.push((marker_id, title));
+                        }
+                        Ok(false) => {}
+                        Err(err) => eprintln!("✗ Failed to update {marker_id}: {err}"),
+                    }
+                    continue;
+                }
+                _ => eprintln!(
+                    "warning: issue {marker_id} not found, creating '{title}' as a new issue"
+                ),
+            }
+        }
+
+        let (status, closed_at) = match parsed.status.as_deref().map(Status::from_str) {
+            Some(Ok(status)) => {
+                let closed_at = matches!(status, Status::Closed).then_some(now);
+                (status, closed_at)
+            }
+            Some(Err(err)) => {
+                eprintln!("✗ Failed to create {title}: {err}");
+                continue;
+            }
+            None => (import_status.clone(), import_closed_at),
+        };
+
         let count = storage.count_issues()?;
         let id = id_gen.generate(
             &title,
@@ -478,7 +555,7 @@ fn execute_import(
             id: id.clone(),
             title: title.clone(),
             description: parsed.description,
-            status: import_status.clone(),
+            status,
             priority,
             issue_type,
             created_at: now,
@@ -493,9 +570,9 @@ fn execute_import(
             design: parsed.design,
             acceptance_criteria: parsed.acceptance_criteria,
             content_hash: None,
-            notes: None,
+            notes: parsed.notes,
             created_by: None,
-            closed_at: import_closed_at,
+            closed_at,
             close_reason: None,
             closed_by_session: None,
             source_system: None,
@@ -574,14 +651,26 @@ fn execute_import(
             });
         }
 
-        if let Err(err) = storage.create_issue(&issue, &actor) {
+        let candidate = WipCandidate {
+            id: &id,
+            status: issue.status.as_str(),
+            assignee: issue.assignee.as_deref(),
+            labels: &issue.labels,
+        };
+        let created = storage.create_issue_guarded(&issue, &actor, |reader| {
+            check_transition(reader, &wip_limits, &candidate)
+        });
+        if let Err(err) = created {
             eprintln!("✗ Failed to create {title}: {err}");
             continue;
         }
 
         if ctx.is_json() {
             if let Some(full_issue) = storage.get_issue_for_export(&id)? {
-                created_issues.push(full_issue);
+                imported_issues.push(ImportedIssue {
+                    action: "created",
+                    issue: full_issue,
+                });
             } else {
                 eprintln!("warning: could not load created issue {id} for JSON output");
             }
@@ -591,15 +680,27 @@ fn execute_import(
     }
 
     if ctx.is_json() {
-        ctx.json_pretty(&created_issues);
-    } else if !created_ids.is_empty() {
-        ctx.success(&format!(
-            "Created {} issues from {}:",
-            created_ids.len(),
-            path.display()
-        ));
-        for (id, title) in created_ids {
-            ctx.print(&format!("  {id}: {title}"));
+        ctx.json_pretty(&imported_issues);
+    } else {
+        if !created_ids.is_empty() {
+            ctx.success(&format!(
+                "Created {} issues from {}:",
+                created_ids.len(),
+                path.display()
+            ));
+            for (id, title) in created_ids {
+                ctx.print(&format!("  {id}: {title}"));
+            }
+        }
+        if !updated_ids.is_empty() {
+            ctx.success(&format!(
+                "Updated {} issues from {}:",
+                updated_ids.len(),
+                path.display()
+            ));
+            for (id, title) in updated_ids {
+                ctx.print(&format!("  {id}: {title}"));
+            }
         }
     }
 
@@ -607,6 +708,179 @@ fn execute_import(
     Ok(())
 }
 
+/// Apply a markdown block carrying an ID marker to the existing issue.
+///
+/// Export writes a section for every non-empty field, so a missing or empty
+/// text, assignee, label or dependency section clears that field. Status,
+/// priority and type are always exported and are left alone when missing.
+/// Only fields that differ from the stored values are written, and a new
+/// status or assignee is checked against `wip_limits` first. Returns whether
+/// anything changed.
+fn update_from_markdown(
+    storage: &mut SqliteStorage,
+    existing: &Issue,
+    parsed: &ParsedIssue,
+    actor: &str,
+    wip_limits: &[config::WipLimit],
+) -> Result<bool> {
+    let id = existing.id.as_str();
+    let changed_text = |new: Option<&String>, old: &Option<String>| {
+        let new = new.filter(|value| !value.trim().is_empty());
+        let old = old.as_ref().filter(|value| !value.trim().is_empty());
+        (new != old).then(|| new.cloned())
+    };
+
+    let mut update = IssueUpdate {
+        description: changed_text(parsed.description.as_ref(), &existing.description),
+        design: changed_text(parsed.design.as_ref(), &existing.design),
+        acceptance_criteria: changed_text(
+            parsed.acceptance_criteria.as_ref(),
+            &existing.acceptance_criteria,
+        ),
+        notes: changed_text(parsed.notes.as_ref(), &existing.notes),
+        assignee: changed_text(parsed.assignee.as_ref(), &existing.assignee),
+        ..IssueUpdate::default()
+    };
+    let title = parsed.title.trim();
+    if title != existing.title {
+        update.title = Some(title.to_string());
+    }
+    if let Some(status) = parsed.status.as_deref() {
+        let status = Status::from_str(status)?;
+        if status != existing.status {
+            update.status = Some(status);
+        }
+    }
+    if let Some(priority) = parsed.priority.as_deref() {
+        let priority = Priority::from_str(priority)?;
+        if priority != existing.priority {
+            update.priority = Some(priority);
+        }
+    }
+    if let Some(issue_type) = parsed.issue_type.as_deref() {
+        let issue_type = IssueType::from_str(issue_type)?;
+        if issue_type != existing.issue_type {
+            update.issue_type = Some(issue_type);
+        }
+    }
+
+    let labels: Vec<String> = parsed
+        .labels
+        .iter()
+        .map(|label| label.trim().to_string())
+        .filter(|label| !label.is_empty())
+        .collect();
+    let candidate = WipCandidate {
+        id,
+        status: update.status.as_ref().unwrap_or(&existing.status).as_str(),
+        assignee: update
+            .assignee
+            .as_ref()
+            .map_or(existing.assignee.as_deref(), Option::as_deref),
+        labels: &labels,
+    };
+
+    let mut changed = false;
+    if !update.is_empty() {
+        storage.update_issue_guarded(id, &update, actor, |reader| {
+            check_transition(reader, wip_limits, &candidate)
+        })?;
+        changed = true;
+    }
+
+    // A missing Labels or Dependencies section empties the stored set
+    changed |= sync_labels(storage, id, &parsed.labels, actor)?;
+    changed |= sync_dependencies(storage, id, &parsed.dependencies, actor)?;
+
+    Ok(changed)
+}
+
+/// Replace an issue's labels with `labels`, skipping invalid ones.
+///
+/// Returns whether the stored set changed.
+pub(crate) fn sync_labels(
+    storage: &mut SqliteStorage,
+    id: &str,
+    labels: &[String],
+    actor: &str,
+) -> Result<bool> {
+    let mut wanted: Vec<String> = Vec::new();
+    for label in labels {
+        let label = label.trim();
+        if label.is_empty() || wanted.iter().any(|l| l == label) {
+            continue;
+        }
+        if let Err(err) = LabelValidator::validate(label) {
+            eprintln!(
+                "warning: skipping invalid label '{label}' for issue {id}: {}",
+                err.message
+            );
+            continue;
+        }
+        wanted.push(label.to_string());
+    }
+    wanted.sort();
+    if wanted == storage.get_labels(id)? {
+        return Ok(false);
+    }
+    storage.set_labels(id, &wanted, actor)?;
+    Ok(true)
+}
+
+/// Replace an issue's outgoing dependencies with `deps` (`type:id` or bare IDs).
+///
+/// Returns whether anything was added or removed.
+pub(crate) fn sync_dependencies(
+    storage: &mut SqliteStorage,
+    id: &str,
+    deps: &[String],
+    actor: &str,
+) -> Result<bool> {
+    let current = storage.get_dependencies_full(id)?;
+    let exists = |dep_id: &str, type_str: &str| {
+        current
+            .iter()
+            .any(|dep| dep.depends_on_id == dep_id && dep.dep_type.as_str() == type_str)
+    };
+
+    let mut wanted: Vec<(String, String)> = Vec::new();
+    for dep_str in deps {
+        let (mut type_str, dep_id, valid) = parse_dependency(dep_str.trim());
+        // Custom types can't be created from a plan, but an exported plan
+        // lists the ones the issue already has, so those are kept as-is.
+        if !valid && !exists(&dep_id, &type_str) {
+            eprintln!("warning: skipping invalid dependency type '{type_str}' for issue {id}");
+            continue;
+        }
+        if type_str.eq_ignore_ascii_case("blocked-by") {
+            type_str = "blocks".to_string();
+        }
+        if dep_id == id {
+            eprintln!("warning: skipping self-dependency for issue {id}");
+            continue;
+        }
+        wanted.push((dep_id, type_str));
+    }
+
+    let mut changed = false;
+    for dep in &current {
+        let keep = wanted.iter().any(|(dep_id, type_str)| {
+            *dep_id == dep.depends_on_id && type_str == dep.dep_type.as_str()
+        });
+        if !keep {
+            storage.remove_dependency(id, &dep.depends_on_id, actor)?;
+            changed = true;
+        }
+    }
+    for (dep_id, type_str) in &wanted {
+        if !exists(dep_id, type_str) {
+            storage.add_dependency(id, dep_id, type_str, actor)?;
+            changed = true;
+        }
+    }
+    Ok(changed)
+}
+
 fn parse_optional_date(s: Option<&str>) -> Result<Option<DateTime<Utc>>> {
     match s {
         Some(s) if !s.trim().is_empty() => parse_flexible_timestamp(s, "date").map(Some),
@@ -643,6 +917,7 @@ mod tests {
             ephemeral: false,
             dry_run: false,
             silent: false,
+            force: false,
             file: None,
         }
     }
@@ -658,6 +933,7 @@ mod tests {
             default_priority: Priority::MEDIUM,
             default_issue_type: IssueType::Task,
             actor: "test_user".to_string(),
+            wip_limits: Vec::new(),
         }
     }
 
@@ -1046,4 +1322,181 @@ mod tests {
         assert_eq!(labels, vec!["trimmed"]);
         info!("test_create_issue_trims_labels: assertions passed");
     }
+
+    #[test]
+    fn test_update_from_markdown_applies_only_changes() {
+        init_test_logging();
+        info!("test_update_from_markdown_applies_only_changes: starting");
+        let mut storage = setup_memory_storage();
+        let config = default_config();
+        let mut args = default_args();
+        args.labels = vec!["old".to_string()];
+        args.description = Some("Keep me".to_string());
+        let issue = create_issue_impl(&mut storage, &args, &config).expect("create failed");
+        let mut blocker_args = default_args();
+        blocker_args.title = Some("Blocker".to_string());
+        let blocker =
+            create_issue_impl(&mut storage, &blocker_args, &config).expect("create failed");
+
+        let content = format!(
+            "## Renamed\n<!-- id: {} -->\n\n### Status\nin_progress\n\n### Labels\nnew, old\n\n### Dependencies\nblocks:{}\n\n### Description\nKeep me\n",
+            issue.id, blocker.id
+        );
+        let parsed = crate::util::markdown_import::parse_markdown_content(&content).unwrap();
+        let changed = update_from_markdown(&mut storage, &issue, &parsed[0], "test_user", &[])
+            .expect("update failed");
+        assert!(changed);
+
+        let loaded = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(loaded.title, "Renamed");
+        assert_eq!(loaded.status, Status::InProgress);
+        assert_eq!(loaded.description.as_deref(), Some("Keep me"));
+        assert_eq!(storage.get_labels(&issue.id).unwrap(), vec!["new", "old"]);
+        assert_eq!(
+            storage.get_dependencies(&issue.id).unwrap(),
+            vec![blocker.id.clone()]
+        );
+
+        // Applying the same block again is a no-op
+        let changed = update_from_markdown(&mut storage, &loaded, &parsed[0], "test_user", &[])
+            .expect("update failed");
+        assert!(!changed);
+        info!("test_update_from_markdown_applies_only_changes: assertions passed");
+    }
+
+    #[test]
+    fn test_update_from_markdown_round_trip_clears_removed_sections() {
+        init_test_logging();
+        info!("test_update_from_markdown_round_trip_clears_removed_sections: starting");
+        let mut storage = setup_memory_storage();
+        let config = default_config();
+        let mut blocker_args = default_args();
+        blocker_args.title = Some("Blocker".to_string());
+        let blocker =
+            create_issue_impl(&mut storage, &blocker_args, &config).expect("create failed");
+        let mut args = default_args();
+        args.description = Some("Describe me".to_string());
+        args.assignee = Some("alice".to_string());
+        args.labels = vec!["keep".to_string()];
+        args.deps = vec![format!("blocks:{}", blocker.id)];
+        let issue = create_issue_impl(&mut storage, &args, &config).expect("create failed");
+        let notes = IssueUpdate {
+            notes: Some(Some("Some notes".to_string())),
+            ..IssueUpdate::default()
+        };
+        storage
+            .update_issue(&issue.id, &notes, "test_user")
+            .unwrap();
+
+        // Export, then drop every optional section from the block
+        let exported = storage.get_issue_for_export(&issue.id).unwrap().unwrap();
+        let markdown = crate::util::markdown_import::format_markdown_issue(&exported);
+        assert!(markdown.contains("### Notes"));
+        let edited: String = markdown
+            .split("\n### ")
+            .filter(|section| {
+                !["Description", "Notes", "Assignee", "Labels", "Dependencies"]
+                    .iter()
+                    .any(|name| section.starts_with(name))
+            })
+            .collect::<Vec<_>>()
+            .join("\n### ");
+        let parsed = crate::util::markdown_import::parse_markdown_content(&edited).unwrap();
+        assert_eq!(parsed[0].id.as_deref(), Some(issue.id.as_str()));
+
+        let existing = storage.get_issue(&issue.id).unwrap().unwrap();
+        let changed = update_from_markdown(&mut storage, &existing, &parsed[0], "test_user", &[])
+            .expect("update failed");
+        assert!(changed);
+
+        let loaded = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(loaded.description, None);
+        assert_eq!(loaded.notes, None);
+        assert_eq!(loaded.assignee, None);
+        assert_eq!(loaded.status, existing.status);
+        assert!(storage.get_labels(&issue.id).unwrap().is_empty());
+        assert!(storage.get_dependencies(&issue.id).unwrap().is_empty());
+
+        // Applying the edited block again is a no-op
+        let changed = update_from_markdown(&mut storage, &loaded, &parsed[0], "test_user", &[])
+            .expect("update failed");
+        assert!(!changed);
+        info!("test_update_from_markdown_round_trip_clears_removed_sections: assertions passed");
+    }
+
+    #[test]
+    fn test_create_and_markdown_update_check_wip_limits() {
+        init_test_logging();
+        info!("test_create_and_markdown_update_check_wip_limits: starting");
+        let mut storage = setup_memory_storage();
+        let mut config = default_config();
+        config.wip_limits = vec![config::WipLimit {
+            status: "in_progress".to_string(),
+            scope: config::WipScope::Total,
+            limit: 1,
+        }];
+        let mut args = default_args();
+        args.status = Some("in_progress".to_string());
+        create_issue_impl(&mut storage, &args, &config).expect("first fits the limit");
+
+        let err = create_issue_impl(&mut storage, &args, &config).unwrap_err();
+        assert!(matches!(err, BeadsError::WipLimitExceeded { .. }));
+        args.force = true;
+        create_issue_impl(&mut storage, &args, &config).expect("force skips the check");
+
+        let open = create_issue_impl(&mut storage, &default_args(), &config).unwrap();
+        let content = format!(
+            "## Test Issue\n<!-- id: {} -->\n\n### Status\nin_progress\n",
+            open.id
+        );
+        let parsed = crate::util::markdown_import::parse_markdown_content(&content).unwrap();
+        let err = update_from_markdown(
+            &mut storage,
+            &open,
+            &parsed[0],
+            "test_user",
+            &config.wip_limits,
+        )
+        .unwrap_err();
+        assert!(matches!(err, BeadsError::WipLimitExceeded { .. }));
+        let loaded = storage.get_issue(&open.id).unwrap().unwrap();
+        assert_eq!(loaded.status, Status::Open);
+        info!("test_create_and_markdown_update_check_wip_limits: assertions passed");
+    }
+
+    #[test]
+    fn test_update_from_markdown_export_is_a_no_op() {
+        init_test_logging();
+        info!("test_update_from_markdown_export_is_a_no_op: starting");
+        let mut storage = setup_memory_storage();
+        let config = default_config();
+        let mut related_args = default_args();
+        related_args.title = Some("Related".to_string());
+        let related =
+            create_issue_impl(&mut storage, &related_args, &config).expect("create failed");
+        let mut args = default_args();
+        let description = "Crashes on save.\n\n### Steps to reproduce\n1. Open\n\n## Not an issue";
+        args.description = Some(description.to_string());
+        let issue = create_issue_impl(&mut storage, &args, &config).expect("create failed");
+        storage
+            .add_dependency(&issue.id, &related.id, "duplicates-ish", "test_user")
+            .unwrap();
+
+        let exported = storage.get_issue_for_export(&issue.id).unwrap().unwrap();
+        let markdown = crate::util::markdown_import::format_markdown_plan(&[exported]);
+        let parsed = crate::util::markdown_import::parse_markdown_content(&markdown).unwrap();
+        assert_eq!(parsed.len(), 1);
+
+        let existing = storage.get_issue(&issue.id).unwrap().unwrap();
+        let changed = update_from_markdown(&mut storage, &existing, &parsed[0], "test_user", &[])
+            .expect("update failed");
+        assert!(!changed);
+
+        let loaded = storage.get_issue(&issue.id).unwrap().unwrap();
+        assert_eq!(loaded.description.as_deref(), Some(description));
+        let deps = storage.get_dependencies_full(&issue.id).unwrap();
+        assert_eq!(deps.len(), 1);
+        assert_eq!(deps[0].dep_type.as_str(), "duplicates-ish");
+        info!("test_update_from_markdown_export_is_a_no_op: assertions passed");
+    }
 }
@@ -9,6 +9,7 @@ use crate::output::{OutputContext, OutputMode};
 use crate::storage::IssueUpdate;
 use crate::util::id::{IdResolver, ResolverConfig, find_matching_ids};
 use crate::util::time::parse_flexible_timestamp;
+use crate::util::wip::check_status_change;
 use rich_rust::prelude::*;
 use serde::Serialize;
 
@@ -54,6 +55,11 @@ pub fn execute_defer(
 
     let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
     let actor = config::resolve_actor(&config_layer);
+    let wip_limits = if args.force {
+        Vec::new()
+    } else {
+        config::wip_limits_from_layer(&config_layer)
+    };
     let id_config = config::id_config_from_layer(&config_layer);
     let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
     let all_ids = storage_ctx.storage.get_all_ids()?;
@@ -118,7 +124,9 @@ pub fn execute_defer(
         };
 
         // Apply update
-        storage.update_issue(id, &update, &actor)?;
+        storage.update_issue_guarded(id, &update, &actor, |reader| {
+            check_status_change(reader, &wip_limits, id, &Status::Deferred)
+        })?;
         tracing::info!(id = %id, defer_until = ?defer_until, "Issue deferred");
 
         // Update last touched
@@ -192,6 +200,11 @@ pub fn execute_undefer(
 
     let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
     let actor = config::resolve_actor(&config_layer);
+    let wip_limits = if args.force {
+        Vec::new()
+    } else {
+        config::wip_limits_from_layer(&config_layer)
+    };
     let id_config = config::id_config_from_layer(&config_layer);
     let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
     let all_ids = storage_ctx.storage.get_all_ids()?;
@@ -239,7 +252,9 @@ pub fn execute_undefer(
         };
 
         // Apply update
-        storage.update_issue(id, &update, &actor)?;
+        storage.update_issue_guarded(id, &update, &actor, |reader| {
+            check_status_change(reader, &wip_limits, id, &Status::Open)
+        })?;
         tracing::info!(id = %id, "Issue undeferred");
 
         // Update last touched
@@ -596,6 +611,7 @@ mod tests {
         let args = DeferArgs {
             ids: vec!["bd-defer-1".to_string()],
             until: Some("+1d".to_string()),
+            force: false,
             robot: true,
         };
         execute_defer(&args, true, &CliOverrides::default(), &ctx).expect("defer");
@@ -621,6 +637,7 @@ mod tests {
         let args = DeferArgs {
             ids: vec!["bd-defer-2".to_string()],
             until: None,
+            force: false,
             robot: true,
         };
         execute_defer(&args, true, &CliOverrides::default(), &ctx).expect("defer");
@@ -646,12 +663,14 @@ mod tests {
         let defer_args = DeferArgs {
             ids: vec!["bd-defer-3".to_string()],
             until: Some("+1d".to_string()),
+            force: false,
             robot: true,
         };
         execute_defer(&defer_args, true, &CliOverrides::default(), &ctx).expect("defer");
 
         let undefer_args = UndeferArgs {
             ids: vec!["bd-defer-3".to_string()],
+            force: false,
             robot: true,
         };
         execute_undefer(&undefer_args, true, &CliOverrides::default(), &ctx).expect("undefer");
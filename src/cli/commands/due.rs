@@ -0,0 +1,235 @@
+//! Due-date listings: `br overdue` and `br due`.
+//!
+//! Both list live issues with a `due_at`, soonest deadline first. `br overdue`
+//! shows those already past due; `br due --within 7d` shows those coming due
+//! in the window. Escalation rules that act on due dates live in
+//! [`escalate`](super::escalate).
+
+use crate::cli::{DueArgs, OverdueArgs};
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::model::{Issue, Priority, Status};
+use crate::output::{OutputContext, OutputMode};
+use crate::storage::{ListFilters, SqliteStorage};
+use crate::util::time::parse_duration;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// JSON output row for `br overdue` and `br due`.
+#[derive(Debug, Clone, Serialize)]
+pub struct DueIssue {
+    pub id: String,
+    pub title: String,
+    pub status: Status,
+    pub priority: Priority,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    pub due_at: DateTime<Utc>,
+    /// Hours until the deadline; negative once overdue.
+    pub due_in_hours: i64,
+}
+
+impl DueIssue {
+    fn new(issue: &Issue, due_at: DateTime<Utc>, now: DateTime<Utc>) -> Self {
+        Self {
+            id: issue.id.clone(),
+            title: issue.title.clone(),
+            status: issue.status.clone(),
+            priority: issue.priority,
+            assignee: issue.assignee.clone(),
+            due_at,
+            due_in_hours: (due_at - now).num_hours(),
+        }
+    }
+}
+
+/// Execute the overdue command.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn execute_overdue(
+    args: &OverdueArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let now = Utc::now();
+
+    let issues = issues_due_between(
+        &storage_ctx.storage,
+        args.assignee.as_deref(),
+        None,
+        now,
+        now,
+    )?;
+    print_due(&issues, &format!("Overdue issues ({})", issues.len()), ctx);
+    Ok(())
+}
+
+/// Execute the due command.
+///
+/// # Errors
+///
+/// Returns an error if `--within` is not a duration or the query fails.
+pub fn execute_due(args: &DueArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let within = parse_duration(&args.within).ok_or_else(|| {
+        BeadsError::validation("within", "expected a duration such as 3d, 2w or 36h")
+    })?;
+    let now = Utc::now();
+    let until = now.checked_add_signed(within).ok_or_else(|| {
+        BeadsError::validation("within", format!("'{}' reaches too far ahead", args.within))
+    })?;
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+
+    let issues = issues_due_between(
+        &storage_ctx.storage,
+        args.assignee.as_deref(),
+        Some(now),
+        until,
+        now,
+    )?;
+    print_due(
+        &issues,
+        &format!("Issues due within {} ({})", args.within, issues.len()),
+        ctx,
+    );
+    Ok(())
+}
+
+/// Live issues with a due date after `after` (exclusive, if given) and up to
+/// `until` (inclusive), soonest first, then by priority.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn issues_due_between(
+    storage: &SqliteStorage,
+    assignee: Option<&str>,
+    after: Option<DateTime<Utc>>,
+    until: DateTime<Utc>,
+    now: DateTime<Utc>,
+) -> Result<Vec<DueIssue>> {
+    let filters = ListFilters {
+        assignee: assignee.map(str::to_string),
+        include_deferred: true,
+        ..ListFilters::default()
+    };
+    let mut due: Vec<DueIssue> = storage
+        .list_issues(&filters)?
+        .iter()
+        .filter(|issue| !issue.status.is_terminal())
+        .filter_map(|issue| {
+            let due_at = issue.due_at?;
+            let in_window = due_at <= until && after.is_none_or(|after| due_at > after);
+            in_window.then(|| DueIssue::new(issue, due_at, now))
+        })
+        .collect();
+    due.sort_by(|a, b| {
+        a.due_at
+            .cmp(&b.due_at)
+            .then(a.priority.cmp(&b.priority))
+            .then_with(|| a.id.cmp(&b.id))
+    });
+    Ok(due)
+}
+
+/// Render an offset from now as `3d` or `5h`.
+fn format_offset(hours: i64) -> String {
+    let hours = hours.abs();
+    if hours >= 24 {
+        format!("{}d", hours / 24)
+    } else {
+        format!("{hours}h")
+    }
+}
+
+fn print_due(issues: &[DueIssue], heading: &str, ctx: &OutputContext) {
+    if ctx.is_json() {
+        ctx.json(&issues);
+        return;
+    }
+    if matches!(ctx.mode(), OutputMode::Quiet) {
+        return;
+    }
+
+    println!("{heading}:");
+    for (idx, issue) in issues.iter().enumerate() {
+        let when = if issue.due_in_hours < 0 {
+            format!("{} overdue", format_offset(issue.due_in_hours))
+        } else {
+            format!("due in {}", format_offset(issue.due_in_hours))
+        };
+        let assignee = issue
+            .assignee
+            .as_deref()
+            .map_or_else(String::new, |name| format!(" ({name})"));
+        println!(
+            "{}. [P{}] {when} ({}) {} {}{assignee}",
+            idx + 1,
+            issue.priority.0,
+            issue.due_at.format("%Y-%m-%d"),
+            issue.id,
+            issue.title
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_issue(id: &str, due_at: Option<DateTime<Utc>>) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            priority: Priority::MEDIUM,
+            due_at,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn test_issues_due_between() {
+        init_logging();
+        info!("test_issues_due_between: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let now = Utc::now();
+        for issue in [
+            make_issue("bd-late", Some(now - Duration::days(3))),
+            make_issue("bd-soon", Some(now + Duration::days(2))),
+            make_issue("bd-later", Some(now + Duration::days(20))),
+            make_issue("bd-none", None),
+        ] {
+            storage.create_issue(&issue, "tester").unwrap();
+        }
+        let mut closed = make_issue("bd-done", Some(now - Duration::days(1)));
+        closed.status = Status::Closed;
+        closed.closed_at = Some(now);
+        storage.create_issue(&closed, "tester").unwrap();
+
+        let overdue = issues_due_between(&storage, None, None, now, now).unwrap();
+        assert_eq!(overdue.len(), 1);
+        assert_eq!(overdue[0].id, "bd-late");
+        assert!(overdue[0].due_in_hours < 0);
+
+        let upcoming =
+            issues_due_between(&storage, None, Some(now), now + Duration::days(7), now).unwrap();
+        assert_eq!(upcoming.len(), 1);
+        assert_eq!(upcoming[0].id, "bd-soon");
+
+        assert_eq!(format_offset(-72), "3d");
+        assert_eq!(format_offset(5), "5h");
+        info!("test_issues_due_between: assertions passed");
+    }
+}
@@ -0,0 +1,678 @@
+//! Edit command implementation.
+//!
+//! Renders an issue as markdown with YAML front matter for the short fields
+//! and `## Section` blocks for the long text fields, opens it in `$EDITOR`,
+//! and writes back only the fields that changed. If the issue was modified by
+//! something else while the editor was open, or a new status or assignee is
+//! over a WIP limit, nothing is written and the edited file is kept so the
+//! changes can be reapplied.
+
+use crate::cli::EditArgs;
+use crate::cli::commands::create::{sync_dependencies, sync_labels};
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::model::{Issue, IssueType, Priority, Status};
+use crate::output::OutputContext;
+use crate::storage::{IssueUpdate, SqliteStorage};
+use crate::util::id::{IdResolver, ResolverConfig};
+use crate::util::markdown_import::{escape_headings, unescape_heading};
+use crate::util::time::parse_flexible_timestamp;
+use crate::util::wip::{WipCandidate, check_transition};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::collections::HashMap;
+use std::env;
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::str::FromStr;
+
+const FRONT_MATTER_KEYS: [&str; 9] = [
+    "title", "status", "priority", "type", "assignee", "labels", "deps", "due", "defer",
+];
+
+const SECTIONS: [&str; 4] = ["Description", "Design", "Acceptance Criteria", "Notes"];
+
+/// Front matter as rendered into the edit buffer.
+#[derive(Serialize)]
+struct FrontMatter<'a> {
+    title: &'a str,
+    status: &'a str,
+    priority: i32,
+    #[serde(rename = "type")]
+    issue_type: &'a str,
+    assignee: Option<&'a str>,
+    labels: Vec<String>,
+    deps: Vec<String>,
+    due: Option<String>,
+    defer: Option<String>,
+}
+
+/// An issue as read back from the edit buffer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct EditedIssue {
+    title: String,
+    status: Status,
+    priority: Priority,
+    issue_type: IssueType,
+    assignee: Option<String>,
+    labels: Vec<String>,
+    deps: Vec<String>,
+    due_at: Option<DateTime<Utc>>,
+    defer_until: Option<DateTime<Utc>>,
+    description: Option<String>,
+    design: Option<String>,
+    acceptance_criteria: Option<String>,
+    notes: Option<String>,
+}
+
+/// Execute the edit command.
+///
+/// # Errors
+///
+/// Returns an error if the issue cannot be found, the editor fails, the edited
+/// file does not parse, or the issue changed while it was being edited.
+pub fn execute(args: &EditArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let actor = config::resolve_actor(&config_layer);
+
+    let input = match &args.id {
+        Some(id) => id.clone(),
+        None => {
+            let last_touched = crate::util::get_last_touched_id(&beads_dir);
+            if last_touched.is_empty() {
+                return Err(BeadsError::validation(
+                    "id",
+                    "no issue ID provided and no last-touched issue",
+                ));
+            }
+            last_touched
+        }
+    };
+    let id_config = config::id_config_from_layer(&config_layer);
+    let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+    let storage = &mut storage_ctx.storage;
+    let id = resolver
+        .resolve(
+            &input,
+            |id| storage.id_exists(id).unwrap_or(false),
+            |hash| storage.find_ids_by_hash(hash).unwrap_or_default(),
+        )?
+        .id;
+
+    let before = storage
+        .get_issue_for_export(&id)?
+        .filter(|issue| issue.status != Status::Tombstone)
+        .ok_or_else(|| BeadsError::IssueNotFound { id: id.clone() })?;
+    let original = render_edit_document(&before)?;
+
+    let path = create_edit_file(&id, &original)?;
+    run_editor(&path)?;
+    let edited_text = fs::read_to_string(&path)?;
+
+    if edited_text.trim().is_empty() || edited_text == original {
+        fs::remove_file(&path)?;
+        report(ctx, &id, &[]);
+        return Ok(());
+    }
+
+    let edited = parse_edit_document(&edited_text).map_err(|err| keep_file(&err, &path))?;
+    if changed_since(storage, &before, &original)? {
+        return Err(BeadsError::validation(
+            "edit",
+            format!(
+                "{id} changed while it was being edited; nothing was written. Your edits are in {}",
+                path.display()
+            ),
+        ));
+    }
+
+    let wip_limits = if args.force {
+        Vec::new()
+    } else {
+        config::wip_limits_from_layer(&config_layer)
+    };
+    let changed = apply_edit(storage, &before, &edited, &actor, &wip_limits)
+        .map_err(|err| keep_file(&err, &path))?;
+    fs::remove_file(&path)?;
+    crate::util::set_last_touched_id(&beads_dir, &id);
+    report(ctx, &id, &changed);
+
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+fn report(ctx: &OutputContext, id: &str, changed: &[&str]) {
+    if ctx.is_json() {
+        ctx.json_pretty(&serde_json::json!({ "id": id, "changed": changed }));
+    } else if changed.is_empty() {
+        ctx.info(&format!("No changes to {id}"));
+    } else {
+        ctx.success(&format!("Updated {id}: {}", changed.join(", ")));
+    }
+}
+
+fn keep_file(err: &BeadsError, path: &Path) -> BeadsError {
+    BeadsError::validation(
+        "edit",
+        format!("{err}. Your edits are in {}", path.display()),
+    )
+}
+
+/// Write the edit buffer to a new file in the temp directory.
+///
+/// The file is created exclusively (`create_new`), so a symlink or file
+/// planted at the predictable name is never followed or overwritten; the
+/// next free name is used instead.
+fn create_edit_file(id: &str, contents: &str) -> Result<PathBuf> {
+    let pid = std::process::id();
+    for attempt in 0u32..100 {
+        let name = if attempt == 0 {
+            format!("br-edit-{id}-{pid}.md")
+        } else {
+            format!("br-edit-{id}-{pid}-{attempt}.md")
+        };
+        let path = env::temp_dir().join(name);
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        match options.open(&path) {
+            Ok(mut file) => {
+                file.write_all(contents.as_bytes())?;
+                return Ok(path);
+            }
+            Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {}
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Err(BeadsError::validation(
+        "edit",
+        format!(
+            "could not create an edit file for {id} in {}",
+            env::temp_dir().display()
+        ),
+    ))
+}
+
+/// Open `path` in `$EDITOR` (then `$VISUAL`, then `vi`) and wait for it to exit.
+fn run_editor(path: &Path) -> Result<()> {
+    let editor = env::var("EDITOR")
+        .or_else(|_| env::var("VISUAL"))
+        .unwrap_or_else(|_| "vi".to_string());
+    // Allow editors with arguments, e.g. `code --wait`
+    let mut parts = editor.split_whitespace();
+    let program = parts.next().unwrap_or("vi");
+    let status = Command::new(program).args(parts).arg(path).status()?;
+    if !status.success() {
+        return Err(BeadsError::validation(
+            "editor",
+            format!("{editor} exited with {status}; nothing was written"),
+        ));
+    }
+    Ok(())
+}
+
+/// Render an issue into the edit buffer format.
+///
+/// Lines in the text fields that would read as a section heading are
+/// escaped, so they stay in their field when the buffer is parsed back.
+fn render_edit_document(issue: &Issue) -> Result<String> {
+    let mut labels = issue.labels.clone();
+    labels.sort();
+    let mut deps: Vec<String> = issue
+        .dependencies
+        .iter()
+        .map(|dep| format!("{}:{}", dep.dep_type.as_str(), dep.depends_on_id))
+        .collect();
+    deps.sort();
+
+    let front = FrontMatter {
+        title: &issue.title,
+        status: issue.status.as_str(),
+        priority: issue.priority.0,
+        issue_type: issue.issue_type.as_str(),
+        assignee: issue.assignee.as_deref(),
+        labels,
+        deps,
+        due: issue.due_at.map(|date| date.to_rfc3339()),
+        defer: issue.defer_until.map(|date| date.to_rfc3339()),
+    };
+    let yaml = serde_yaml::to_string(&front)?;
+
+    let mut out = format!(
+        "---\n# Editing {}. Save and quit to apply; empty the file to cancel.\n{yaml}---\n",
+        issue.id
+    );
+    let texts = [
+        &issue.description,
+        &issue.design,
+        &issue.acceptance_criteria,
+        &issue.notes,
+    ];
+    for (name, text) in SECTIONS.iter().zip(texts) {
+        out.push_str(&format!("\n## {name}\n\n"));
+        if let Some(text) = text.as_deref().map(str::trim_end).filter(|t| !t.is_empty()) {
+            out.push_str(&escape_headings(text));
+            out.push('\n');
+        }
+    }
+    Ok(out)
+}
+
+/// Parse the edit buffer back into field values.
+fn parse_edit_document(text: &str) -> Result<EditedIssue> {
+    let rest = text
+        .strip_prefix("---\n")
+        .or_else(|| text.strip_prefix("---\r\n"))
+        .ok_or_else(|| BeadsError::validation("front matter", "file must start with ---"))?;
+    let (front, body) = rest
+        .split_once("\n---\n")
+        .or_else(|| rest.split_once("\n---\r\n"))
+        .ok_or_else(|| BeadsError::validation("front matter", "missing closing ---"))?;
+
+    let fields = front_matter_fields(front)?;
+    let required = |key: &str| -> Result<String> {
+        scalar(&fields, key)?
+            .ok_or_else(|| BeadsError::validation(key, "is required in the front matter"))
+    };
+    let date = |key: &str| -> Result<Option<DateTime<Utc>>> {
+        scalar(&fields, key)?
+            .map(|value| parse_flexible_timestamp(&value, key))
+            .transpose()
+    };
+
+    let status = Status::from_str(&required("status")?)?;
+    if status == Status::Tombstone {
+        return Err(BeadsError::validation(
+            "status",
+            "use `br delete` to delete an issue",
+        ));
+    }
+    let [description, design, acceptance_criteria, notes] = parse_sections(body)?;
+
+    Ok(EditedIssue {
+        title: required("title")?,
+        status,
+        priority: Priority::from_str(&required("priority")?)?,
+        issue_type: IssueType::from_str(&required("type")?)?,
+        assignee: scalar(&fields, "assignee")?,
+        labels: list(&fields, "labels")?,
+        deps: list(&fields, "deps")?,
+        due_at: date("due")?,
+        defer_until: date("defer")?,
+        description,
+        design,
+        acceptance_criteria,
+        notes,
+    })
+}
+
+fn front_matter_fields(front: &str) -> Result<HashMap<String, serde_yaml::Value>> {
+    let value: serde_yaml::Value = serde_yaml::from_str(front)?;
+    let serde_yaml::Value::Mapping(map) = value else {
+        return Err(BeadsError::validation(
+            "front matter",
+            "expected `key: value` lines",
+        ));
+    };
+
+    let mut fields = HashMap::new();
+    for (key, value) in map {
+        let key = key.as_str().unwrap_or_default().to_string();
+        if !FRONT_MATTER_KEYS.contains(&key.as_str()) {
+            return Err(BeadsError::validation(
+                "front matter",
+                format!(
+                    "unknown key '{key}' (expected {})",
+                    FRONT_MATTER_KEYS.join(", ")
+                ),
+            ));
+        }
+        fields.insert(key, value);
+    }
+    Ok(fields)
+}
+
+/// Read a scalar front matter value; null, missing and blank values are `None`.
+fn scalar(fields: &HashMap<String, serde_yaml::Value>, key: &str) -> Result<Option<String>> {
+    let value = match fields.get(key) {
+        None | Some(serde_yaml::Value::Null) => None,
+        Some(serde_yaml::Value::String(s)) => Some(s.trim().to_string()),
+        Some(serde_yaml::Value::Number(n)) => Some(n.to_string()),
+        Some(serde_yaml::Value::Bool(b)) => Some(b.to_string()),
+        Some(_) => {
+            return Err(BeadsError::validation(key, "expected a single value"));
+        }
+    };
+    Ok(value.filter(|s| !s.is_empty()))
+}
+
+/// Read a list front matter value, given as a YAML sequence or a comma-separated string.
+fn list(fields: &HashMap<String, serde_yaml::Value>, key: &str) -> Result<Vec<String>> {
+    let items: Vec<String> = match fields.get(key) {
+        None | Some(serde_yaml::Value::Null) => Vec::new(),
+        Some(serde_yaml::Value::String(s)) => s.split(',').map(str::to_string).collect(),
+        Some(serde_yaml::Value::Sequence(values)) => values
+            .iter()
+            .map(|value| match value {
+                serde_yaml::Value::String(s) => Ok(s.clone()),
+                serde_yaml::Value::Number(n) => Ok(n.to_string()),
+                _ => Err(BeadsError::validation(key, "expected a list of values")),
+            })
+            .collect::<Result<_>>()?,
+        Some(_) => return Err(BeadsError::validation(key, "expected a list of values")),
+    };
+    Ok(items
+        .into_iter()
+        .map(|item| item.trim().to_string())
+        .filter(|item| !item.is_empty())
+        .collect())
+}
+
+/// Split the body into the text sections, in `SECTIONS` order.
+///
+/// Headings inside fenced code blocks and `##` headings that are not section
+/// names are content; a removed or empty section clears the field. Heading
+/// lines that the text itself contains were escaped with a backslash when
+/// rendered, and are unescaped here.
+fn parse_sections(body: &str) -> Result<[Option<String>; 4]> {
+    let mut sections: [Vec<String>; 4] = Default::default();
+    let mut current: Option<usize> = None;
+    let mut in_fence = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_fence = !in_fence;
+        } else if !in_fence {
+            let heading = line.strip_prefix("## ").and_then(|name| {
+                SECTIONS
+                    .iter()
+                    .position(|section| section.eq_ignore_ascii_case(name.trim()))
+            });
+            if heading.is_some() {
+                current = heading;
+                continue;
+            }
+        }
+
+        match current {
+            Some(index) if in_fence => sections[index].push(line.to_string()),
+            Some(index) => sections[index].push(unescape_heading(line)),
+            None if line.trim().is_empty() => {}
+            None => {
+                return Err(BeadsError::validation(
+                    "body",
+                    format!(
+                        "text before the first section heading (expected ## {})",
+                        SECTIONS.join(", ## ")
+                    ),
+                ));
+            }
+        }
+    }
+
+    Ok(sections.map(|lines| {
+        let text = lines.join("\n").trim().to_string();
+        (!text.is_empty()).then_some(text)
+    }))
+}
+
+/// Whether the issue was modified since `original` was rendered from `before`.
+fn changed_since(storage: &SqliteStorage, before: &Issue, original: &str) -> Result<bool> {
+    let Some(current) = storage.get_issue_for_export(&before.id)? else {
+        return Ok(true);
+    };
+    Ok(current.updated_at != before.updated_at || render_edit_document(&current)? != original)
+}
+
+/// Write the fields that differ from `before`; returns the names of the changed fields.
+fn apply_edit(
+    storage: &mut SqliteStorage,
+    before: &Issue,
+    edited: &EditedIssue,
+    actor: &str,
+    wip_limits: &[config::WipLimit],
+) -> Result<Vec<&'static str>> {
+    let mut changed = Vec::new();
+    let mut update = IssueUpdate::default();
+
+    if edited.title != before.title {
+        update.title = Some(edited.title.clone());
+        changed.push("title");
+    }
+    if edited.status != before.status {
+        update.status = Some(edited.status.clone());
+        changed.push("status");
+    }
+    if edited.priority != before.priority {
+        update.priority = Some(edited.priority);
+        changed.push("priority");
+    }
+    if edited.issue_type != before.issue_type {
+        update.issue_type = Some(edited.issue_type.clone());
+        changed.push("type");
+    }
+    if edited.assignee != before.assignee {
+        update.assignee = Some(edited.assignee.clone());
+        changed.push("assignee");
+    }
+    if edited.due_at != before.due_at {
+        update.due_at = Some(edited.due_at);
+        changed.push("due");
+    }
+    if edited.defer_until != before.defer_until {
+        update.defer_until = Some(edited.defer_until);
+        changed.push("defer");
+    }
+
+    update.description = diff_text(edited.description.as_deref(), before.description.as_deref());
+    update.design = diff_text(edited.design.as_deref(), before.design.as_deref());
+    update.acceptance_criteria = diff_text(
+        edited.acceptance_criteria.as_deref(),
+        before.acceptance_criteria.as_deref(),
+    );
+    update.notes = diff_text(edited.notes.as_deref(), before.notes.as_deref());
+    for (name, text) in [
+        ("description", &update.description),
+        ("design", &update.design),
+        ("acceptance_criteria", &update.acceptance_criteria),
+        ("notes", &update.notes),
+    ] {
+        if text.is_some() {
+            changed.push(name);
+        }
+    }
+
+    if !update.is_empty() {
+        storage.update_issue_guarded(&before.id, &update, actor, |reader| {
+            check_transition(
+                reader,
+                wip_limits,
+                &WipCandidate {
+                    id: &before.id,
+                    status: edited.status.as_str(),
+                    assignee: edited.assignee.as_deref(),
+                    labels: &edited.labels,
+                },
+            )
+        })?;
+    }
+    if sync_labels(storage, &before.id, &edited.labels, actor)? {
+        changed.push("labels");
+    }
+    if sync_dependencies(storage, &before.id, &edited.deps, actor)? {
+        changed.push("deps");
+    }
+    Ok(changed)
+}
+
+fn diff_text(new: Option<&str>, old: Option<&str>) -> Option<Option<String>> {
+    (new.map(str::trim_end) != old.map(str::trim_end)).then(|| new.map(str::to_string))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_issue(id: &str, title: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: title.to_string(),
+            priority: Priority::MEDIUM,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            ..Issue::default()
+        }
+    }
+
+    fn setup() -> (SqliteStorage, Issue) {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let mut issue = make_issue("bd-edit", "Edit me");
+        issue.design = Some("First paragraph.\n\nSecond paragraph.".to_string());
+        storage.create_issue(&issue, "tester").unwrap();
+        storage
+            .create_issue(&make_issue("bd-blocker", "Blocker"), "tester")
+            .unwrap();
+        storage.add_label("bd-edit", "backend", "tester").unwrap();
+        let issue = storage.get_issue_for_export("bd-edit").unwrap().unwrap();
+        (storage, issue)
+    }
+
+    #[test]
+    fn test_render_parse_round_trip() {
+        init_logging();
+        info!("test_render_parse_round_trip: starting");
+        let (_storage, issue) = setup();
+        let doc = render_edit_document(&issue).unwrap();
+        assert!(doc.starts_with("---\n# Editing bd-edit."));
+
+        let edited = parse_edit_document(&doc).unwrap();
+        assert_eq!(edited.title, "Edit me");
+        assert_eq!(edited.status, issue.status);
+        assert_eq!(edited.priority, issue.priority);
+        assert_eq!(edited.labels, vec!["backend"]);
+        assert!(edited.deps.is_empty());
+        assert_eq!(edited.design, issue.design);
+        assert_eq!(edited.description, None);
+        info!("test_render_parse_round_trip: assertions passed");
+    }
+
+    #[test]
+    fn test_heading_lines_in_text_round_trip() {
+        init_logging();
+        info!("test_heading_lines_in_text_round_trip: starting");
+        let (mut storage, _issue) = setup();
+        let description = "Intro\n## Notes\nstill description\n\\## Design";
+        storage
+            .update_issue(
+                "bd-edit",
+                &IssueUpdate {
+                    description: Some(Some(description.to_string())),
+                    ..IssueUpdate::default()
+                },
+                "tester",
+            )
+            .unwrap();
+        let issue = storage.get_issue_for_export("bd-edit").unwrap().unwrap();
+
+        let doc = render_edit_document(&issue).unwrap();
+        assert!(doc.contains("\n\\## Notes\n"));
+        let edited = parse_edit_document(&doc).unwrap();
+        assert_eq!(edited.description.as_deref(), Some(description));
+        assert_eq!(edited.notes, None);
+        assert_eq!(edited.design, issue.design);
+
+        let changed = apply_edit(&mut storage, &issue, &edited, "tester", &[]).unwrap();
+        assert!(changed.is_empty());
+        info!("test_heading_lines_in_text_round_trip: assertions passed");
+    }
+
+    #[test]
+    fn test_apply_edit_writes_only_changes() {
+        init_logging();
+        info!("test_apply_edit_writes_only_changes: starting");
+        let (mut storage, issue) = setup();
+        let doc = render_edit_document(&issue)
+            .unwrap()
+            .replace("priority: 2", "priority: P1")
+            .replace("labels:\n- backend", "labels: [backend, ui]")
+            .replace("deps: []", "deps:\n- blocks:bd-blocker")
+            .replace("## Notes\n", "## Notes\n\nRemember:\n```\n## Design\n```\n");
+        let edited = parse_edit_document(&doc).unwrap();
+
+        let changed = apply_edit(&mut storage, &issue, &edited, "tester", &[]).unwrap();
+        assert_eq!(changed, vec!["priority", "notes", "labels", "deps"]);
+
+        let after = storage.get_issue_for_export("bd-edit").unwrap().unwrap();
+        assert_eq!(after.priority, Priority::HIGH);
+        assert_eq!(after.design, issue.design);
+        assert_eq!(
+            after.notes.as_deref(),
+            Some("Remember:\n```\n## Design\n```")
+        );
+        assert_eq!(after.labels, vec!["backend", "ui"]);
+        assert_eq!(after.dependencies[0].depends_on_id, "bd-blocker");
+
+        let again = apply_edit(&mut storage, &after, &edited, "tester", &[]).unwrap();
+        assert!(again.is_empty());
+        info!("test_apply_edit_writes_only_changes: assertions passed");
+    }
+
+    #[test]
+    fn test_changed_since_detects_concurrent_update() {
+        init_logging();
+        info!("test_changed_since_detects_concurrent_update: starting");
+        let (mut storage, issue) = setup();
+        let doc = render_edit_document(&issue).unwrap();
+        assert!(!changed_since(&storage, &issue, &doc).unwrap());
+
+        storage.add_label("bd-edit", "urgent", "other").unwrap();
+        assert!(changed_since(&storage, &issue, &doc).unwrap());
+        info!("test_changed_since_detects_concurrent_update: assertions passed");
+    }
+
+    #[test]
+    fn test_parse_rejects_bad_documents() {
+        init_logging();
+        info!("test_parse_rejects_bad_documents: starting");
+        let (_storage, issue) = setup();
+        let doc = render_edit_document(&issue).unwrap();
+
+        let unknown_key = doc.replace("title:", "owner: bob\ntitle:");
+        assert!(parse_edit_document(&unknown_key).is_err());
+
+        let stray_text = doc.replace("\n## Description", "stray\n## Description");
+        assert!(parse_edit_document(&stray_text).is_err());
+
+        let no_front_matter = doc.replacen("---\n", "", 1);
+        assert!(parse_edit_document(&no_front_matter).is_err());
+        info!("test_parse_rejects_bad_documents: assertions passed");
+    }
+
+    #[test]
+    fn test_create_edit_file_never_reuses_existing_path() {
+        init_logging();
+        info!("test_create_edit_file_never_reuses_existing_path: starting");
+        let first = create_edit_file("bd-edittmp", "first").unwrap();
+        let second = create_edit_file("bd-edittmp", "second").unwrap();
+        assert_ne!(first, second);
+        assert_eq!(fs::read_to_string(&first).unwrap(), "first");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "second");
+        fs::remove_file(first).unwrap();
+        fs::remove_file(second).unwrap();
+        info!("test_create_edit_file_never_reuses_existing_path: assertions passed");
+    }
+}
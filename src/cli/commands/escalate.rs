@@ -0,0 +1,411 @@
+//! Escalate command implementation.
+//!
+//! Applies the due-date escalation rules from config (see
+//! [`config::escalation_rules_from_layer`]): once an issue is within a rule's
+//! window of its due date, raise its priority and/or add a label. Each rule
+//! fires once per issue and due date, and records a comment and an
+//! `escalated` event, so a human lowering the priority afterwards is not
+//! overridden. The comment is exported with the issue, so other clones see
+//! the firing too. With `escalation.auto: true` the rules also run before
+//! every command.
+
+use crate::cli::EscalateArgs;
+use crate::config::{self, EscalationRule};
+use crate::error::Result;
+use crate::model::{Issue, Priority};
+use crate::output::{OutputContext, OutputMode};
+use crate::storage::{ListFilters, SqliteStorage};
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+
+/// One rule firing for one issue.
+#[derive(Debug, Clone, Serialize)]
+pub struct Escalation {
+    pub id: String,
+    pub title: String,
+    pub rule: String,
+    pub due_at: DateTime<Utc>,
+    /// New priority, if the rule raised it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub priority: Option<Priority>,
+    /// Priority before the rule raised it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub previous_priority: Option<Priority>,
+    /// Label the rule added.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub label: Option<String>,
+}
+
+impl Escalation {
+    /// Whether the rule changes anything (it may fire on an issue that already
+    /// has the priority and label).
+    #[must_use]
+    pub const fn changes_issue(&self) -> bool {
+        self.priority.is_some() || self.label.is_some()
+    }
+
+    /// What the rule changed, e.g. `priority P3 -> P1, label +overdue`.
+    fn changes(&self) -> String {
+        let mut changes = Vec::new();
+        if let (Some(from), Some(to)) = (self.previous_priority, self.priority) {
+            changes.push(format!("priority P{} -> P{}", from.0, to.0));
+        }
+        if let Some(label) = &self.label {
+            changes.push(format!("label +{label}"));
+        }
+        changes.join(", ")
+    }
+
+    fn details(&self) -> String {
+        format!(
+            "Escalation rule '{}' (due {}): {}",
+            self.rule,
+            self.due_at.format("%Y-%m-%d"),
+            self.changes()
+        )
+    }
+}
+
+/// Execute the escalate command.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be read or updated.
+pub fn execute(args: &EscalateArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let rules = config::escalation_rules_from_layer(&layer);
+    let actor = config::resolve_actor(&layer);
+    let now = Utc::now();
+
+    let escalations = if args.dry_run {
+        plan_escalations(&storage_ctx.storage, &rules, now)?
+    } else {
+        apply_escalations(&mut storage_ctx.storage, &rules, &actor, now)?
+    };
+    let changed: Vec<&Escalation> = escalations
+        .iter()
+        .filter(|escalation| escalation.changes_issue())
+        .collect();
+
+    if ctx.is_json() {
+        ctx.json_pretty(&changed);
+    } else if !matches!(ctx.mode(), OutputMode::Quiet) {
+        if rules.is_empty() {
+            println!("No escalation rules configured (see `escalation.<name>.*` in config).");
+        } else if changed.is_empty() {
+            println!("Nothing to escalate.");
+        }
+        let verb = if args.dry_run {
+            "Would escalate"
+        } else {
+            "Escalated"
+        };
+        for escalation in &changed {
+            println!(
+                "{verb} {}: {} [{}]",
+                escalation.id,
+                escalation.details(),
+                escalation.title
+            );
+        }
+    }
+
+    if !args.dry_run {
+        storage_ctx.flush_no_db_if_dirty()?;
+    }
+    Ok(())
+}
+
+/// Run the escalation rules if `escalation.auto` is enabled. Returns how many
+/// issues were changed.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be read or updated.
+pub fn auto_escalate(storage: &mut SqliteStorage, layer: &config::ConfigLayer) -> Result<usize> {
+    if !config::escalation_auto_from_layer(layer) {
+        return Ok(0);
+    }
+    let rules = config::escalation_rules_from_layer(layer);
+    if rules.is_empty() {
+        return Ok(0);
+    }
+    let actor = config::resolve_actor(layer);
+    let applied = apply_escalations(storage, &rules, &actor, Utc::now())?;
+    Ok(applied
+        .iter()
+        .filter(|escalation| escalation.changes_issue())
+        .count())
+}
+
+/// Work out which rules fire at `now` without changing anything.
+///
+/// Rules are evaluated in name order per issue, so a later rule sees the
+/// priority and labels an earlier one would set.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be read.
+pub fn plan_escalations(
+    storage: &SqliteStorage,
+    rules: &[EscalationRule],
+    now: DateTime<Utc>,
+) -> Result<Vec<Escalation>> {
+    if rules.is_empty() {
+        return Ok(Vec::new());
+    }
+    let filters = ListFilters {
+        include_deferred: true,
+        ..ListFilters::default()
+    };
+    let issues: Vec<Issue> = storage
+        .list_issues(&filters)?
+        .into_iter()
+        .filter(|issue| issue.due_at.is_some() && !issue.status.is_terminal())
+        .collect();
+    let ids: Vec<String> = issues.iter().map(|issue| issue.id.clone()).collect();
+    let mut labels = storage.get_labels_for_issues(&ids)?;
+
+    let mut escalations = Vec::new();
+    for issue in &issues {
+        let Some(due_at) = issue.due_at else {
+            continue;
+        };
+        let due_key = due_at.to_rfc3339();
+        let mut priority = issue.priority;
+        let mut issue_labels = labels.remove(&issue.id).unwrap_or_default();
+
+        for rule in rules {
+            let Some(fires_at) = due_at.checked_add_signed(rule.offset) else {
+                tracing::warn!(
+                    issue = %issue.id,
+                    rule = %rule.name,
+                    "Escalation offset is out of range for this due date, skipping rule"
+                );
+                continue;
+            };
+            if now < fires_at || storage.has_escalation(&issue.id, &rule.name, &due_key)? {
+                continue;
+            }
+            let raised = rule.priority.filter(|target| target.0 < priority.0);
+            let added = rule
+                .label
+                .clone()
+                .filter(|label| !issue_labels.contains(label));
+            escalations.push(Escalation {
+                id: issue.id.clone(),
+                title: issue.title.clone(),
+                rule: rule.name.clone(),
+                due_at,
+                priority: raised,
+                previous_priority: raised.map(|_| priority),
+                label: added.clone(),
+            });
+            if let Some(target) = raised {
+                priority = target;
+            }
+            issue_labels.extend(added);
+        }
+    }
+    Ok(escalations)
+}
+
+/// Apply the rules that fire at `now` and record each firing.
+///
+/// Each firing is written in one transaction. Rules that would change
+/// nothing are returned but not recorded.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be read or updated.
+pub fn apply_escalations(
+    storage: &mut SqliteStorage,
+    rules: &[EscalationRule],
+    actor: &str,
+    now: DateTime<Utc>,
+) -> Result<Vec<Escalation>> {
+    let escalations = plan_escalations(storage, rules, now)?;
+    for escalation in &escalations {
+        // A rule that would change nothing is not recorded as having fired
+        if !escalation.changes_issue() {
+            continue;
+        }
+        storage.apply_escalation(
+            &escalation.id,
+            &escalation.rule,
+            &escalation.due_at.to_rfc3339(),
+            escalation.priority,
+            escalation.label.as_deref(),
+            &escalation.changes(),
+            actor,
+        )?;
+    }
+    Ok(escalations)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn rule(
+        name: &str,
+        offset: Duration,
+        priority: Option<i32>,
+        label: Option<&str>,
+    ) -> EscalationRule {
+        EscalationRule {
+            name: name.to_string(),
+            offset,
+            priority: priority.map(Priority),
+            label: label.map(str::to_string),
+        }
+    }
+
+    #[test]
+    fn test_apply_escalations_fires_once_per_due_date() {
+        init_logging();
+        info!("test_apply_escalations_fires_once_per_due_date: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let now = Utc::now();
+        let issue = Issue {
+            id: "bd-1".to_string(),
+            title: "Ship it".to_string(),
+            priority: Priority(3),
+            due_at: Some(now + Duration::days(1)),
+            created_at: now,
+            updated_at: now,
+            ..Issue::default()
+        };
+        storage.create_issue(&issue, "tester").unwrap();
+        let rules = vec![
+            rule("a-due-soon", -Duration::days(2), Some(1), None),
+            rule("b-overdue", Duration::days(1), Some(0), Some("overdue")),
+        ];
+
+        let planned = plan_escalations(&storage, &rules, now).unwrap();
+        assert_eq!(planned.len(), 1);
+        assert_eq!(planned[0].previous_priority, Some(Priority(3)));
+
+        let applied = apply_escalations(&mut storage, &rules, "tester", now).unwrap();
+        assert_eq!(applied.len(), 1);
+        let updated = storage.get_issue("bd-1").unwrap().unwrap();
+        assert_eq!(updated.priority, Priority(1));
+        assert!(
+            apply_escalations(&mut storage, &rules, "tester", now)
+                .unwrap()
+                .is_empty()
+        );
+
+        // Two days past due, the overdue rule fires too.
+        let later = now + Duration::days(3);
+        let applied = apply_escalations(&mut storage, &rules, "tester", later).unwrap();
+        assert_eq!(applied.len(), 1);
+        assert_eq!(applied[0].rule, "b-overdue");
+        assert_eq!(storage.get_labels("bd-1").unwrap(), vec!["overdue"]);
+        assert_eq!(
+            storage.get_issue("bd-1").unwrap().unwrap().priority,
+            Priority(0)
+        );
+        info!("test_apply_escalations_fires_once_per_due_date: assertions passed");
+    }
+
+    #[test]
+    fn test_out_of_range_offsets_are_skipped() {
+        init_logging();
+        info!("test_out_of_range_offsets_are_skipped: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let now = Utc::now();
+        let issue = Issue {
+            id: "bd-1".to_string(),
+            title: "Ship it".to_string(),
+            priority: Priority(3),
+            due_at: Some(now),
+            created_at: now,
+            updated_at: now,
+            ..Issue::default()
+        };
+        storage.create_issue(&issue, "tester").unwrap();
+        let huge = Duration::try_days(1_000_000_000).unwrap();
+        let rules = vec![
+            rule("far-after", huge, Some(1), None),
+            rule("far-before", -huge, None, Some("soon")),
+        ];
+
+        assert!(plan_escalations(&storage, &rules, now).unwrap().is_empty());
+        info!("test_out_of_range_offsets_are_skipped: assertions passed");
+    }
+
+    #[test]
+    fn test_no_change_escalation_is_not_recorded() {
+        init_logging();
+        info!("test_no_change_escalation_is_not_recorded: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let now = Utc::now();
+        let issue = Issue {
+            id: "bd-1".to_string(),
+            title: "Ship it".to_string(),
+            priority: Priority(0),
+            due_at: Some(now - Duration::days(1)),
+            created_at: now,
+            updated_at: now,
+            ..Issue::default()
+        };
+        storage.create_issue(&issue, "tester").unwrap();
+        let rules = vec![rule("overdue", Duration::zero(), Some(1), None)];
+
+        let applied = apply_escalations(&mut storage, &rules, "tester", now).unwrap();
+        assert_eq!(applied.len(), 1);
+        assert!(!applied[0].changes_issue());
+        let after = storage.get_issue_for_export("bd-1").unwrap().unwrap();
+        assert!(after.comments.is_empty());
+        let due_key = issue.due_at.unwrap().to_rfc3339();
+        assert!(!storage.has_escalation("bd-1", "overdue", &due_key).unwrap());
+        info!("test_no_change_escalation_is_not_recorded: assertions passed");
+    }
+
+    #[test]
+    fn test_escalation_record_travels_with_the_issue() {
+        init_logging();
+        info!("test_escalation_record_travels_with_the_issue: starting");
+        let now = Utc::now();
+        let issue = Issue {
+            id: "bd-1".to_string(),
+            title: "Ship it".to_string(),
+            priority: Priority(3),
+            due_at: Some(now - Duration::days(1)),
+            created_at: now,
+            updated_at: now,
+            ..Issue::default()
+        };
+        let rules = vec![rule("overdue", Duration::zero(), Some(1), None)];
+
+        let mut origin = SqliteStorage::open_memory().unwrap();
+        origin.create_issue(&issue, "tester").unwrap();
+        apply_escalations(&mut origin, &rules, "tester", now).unwrap();
+        let exported = origin.get_issue_for_export("bd-1").unwrap().unwrap();
+        assert_eq!(exported.comments.len(), 1);
+
+        // A fresh clone has no local record, only the exported issue, on
+        // which a human has since lowered the priority again.
+        let mut clone = SqliteStorage::open_memory().unwrap();
+        let lowered = Issue {
+            priority: Priority(3),
+            comments: Vec::new(),
+            ..exported.clone()
+        };
+        clone.create_issue(&lowered, "tester").unwrap();
+        clone
+            .sync_comments_for_import("bd-1", &exported.comments)
+            .unwrap();
+        assert!(plan_escalations(&clone, &rules, now).unwrap().is_empty());
+        info!("test_escalation_record_travels_with_the_issue: assertions passed");
+    }
+}
@@ -0,0 +1,562 @@
+//! Flow metrics for `br stats --flow`.
+//!
+//! Lead time runs from creation to close and cycle time from the first move to
+//! `in_progress` to close, taken from issue timestamps and `status_changed`
+//! events. Weekly buckets give throughput, WIP and burndown/burnup for the
+//! whole project, one epic (with its descendants) or one label.
+
+use crate::cli::{OutputFormat, StatsArgs};
+use crate::error::{BeadsError, Result};
+use crate::format::csv::escape_field;
+use crate::model::{Event, EventType, Issue, Status};
+use crate::output::OutputContext;
+use crate::storage::{ListFilters, SqliteStorage};
+use crate::util::id::{IdResolver, ResolverConfig};
+use crate::util::time::{parse_duration, parse_flexible_timestamp, parse_relative_time};
+use chrono::{DateTime, Datelike, Duration, NaiveDate, NaiveTime, Utc};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet, VecDeque};
+use tracing::debug;
+
+/// Window used when `--since` is not given.
+const DEFAULT_WINDOW_DAYS: i64 = 90;
+
+/// Characters used for text sparklines, lowest to highest.
+const SPARK_CHARS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Flow metrics output.
+#[derive(Debug, Serialize)]
+pub struct FlowMetrics {
+    /// Start of the measured window.
+    pub since: String,
+    /// End of the measured window (now).
+    pub until: String,
+    /// `epic:<id>` or `label:<name>` when the metrics are scoped.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scope: Option<String>,
+    /// Creation to close, for issues closed in the window.
+    pub lead_time: DurationStats,
+    /// First `in_progress` to close, for issues closed in the window.
+    pub cycle_time: DurationStats,
+    /// Weekly series, oldest first (weeks start on Monday).
+    pub weeks: Vec<FlowWeek>,
+}
+
+/// Distribution of a duration, in hours.
+#[derive(Debug, Default, Serialize, PartialEq)]
+pub struct DurationStats {
+    pub count: usize,
+    pub mean_hours: Option<f64>,
+    pub p50_hours: Option<f64>,
+    pub p85_hours: Option<f64>,
+    pub p95_hours: Option<f64>,
+}
+
+/// One week of flow data.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct FlowWeek {
+    /// Monday the week starts on (YYYY-MM-DD).
+    pub week_start: String,
+    /// Issues created during the week.
+    pub created: usize,
+    /// Issues closed during the week (throughput).
+    pub closed: usize,
+    /// Issues in progress at the end of the week.
+    pub wip: usize,
+    /// Issues created but not closed at the end of the week (burndown).
+    pub remaining: usize,
+    /// Issues closed by the end of the week (burnup).
+    pub completed: usize,
+}
+
+/// Compute and print flow metrics.
+///
+/// # Errors
+///
+/// Returns an error if `--since` or `--epic` cannot be resolved or a query fails.
+pub fn execute(
+    args: &StatsArgs,
+    storage: &SqliteStorage,
+    prefix: &str,
+    format: OutputFormat,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let until = Utc::now();
+    let since = resolve_window_start(args.since.as_deref(), until)?;
+    let (issues, scope) = scoped_issues(storage, args, prefix)?;
+    let events = storage.get_all_events(0)?;
+    debug!(
+        issues = issues.len(),
+        events = events.len(),
+        scope = ?scope,
+        "Computing flow metrics"
+    );
+
+    let mut metrics = compute_flow(&issues, &events, since, until);
+    metrics.scope = scope;
+
+    match format {
+        OutputFormat::Json => ctx.json_pretty(&metrics),
+        OutputFormat::Toon => ctx.toon_with_stats(&metrics, args.stats),
+        OutputFormat::Csv => print!("{}", format_flow_csv(&metrics)),
+        OutputFormat::Text => print_flow_text(&metrics),
+    }
+    Ok(())
+}
+
+/// Resolve `--since` for time-series stats: `60d`/`12w`, `-60d`, or a date.
+///
+/// Defaults to 90 days before `now`.
+///
+/// # Errors
+///
+/// Returns an error if the value is neither a duration nor a date.
+pub fn resolve_window_start(since: Option<&str>, now: DateTime<Utc>) -> Result<DateTime<Utc>> {
+    let Some(since) = since else {
+        return Ok(now - Duration::days(DEFAULT_WINDOW_DAYS));
+    };
+    if let Some(duration) = parse_duration(since) {
+        return now.checked_sub_signed(duration).ok_or_else(|| {
+            BeadsError::validation("since", format!("'{since}' reaches too far back"))
+        });
+    }
+    if let Some(dt) = parse_relative_time(since) {
+        return Ok(dt);
+    }
+    parse_flexible_timestamp(since, "since")
+}
+
+/// Live issues in scope, and a label for the scope.
+fn scoped_issues(
+    storage: &SqliteStorage,
+    args: &StatsArgs,
+    prefix: &str,
+) -> Result<(Vec<Issue>, Option<String>)> {
+    let filters = ListFilters {
+        include_closed: true,
+        ..Default::default()
+    };
+    let mut issues: Vec<Issue> = storage
+        .list_issues(&filters)?
+        .into_iter()
+        .filter(|issue| issue.status != Status::Tombstone)
+        .collect();
+
+    if let Some(epic) = args.epic.as_deref() {
+        let resolver = IdResolver::new(ResolverConfig::with_prefix(prefix.to_string()));
+        let epic_id = resolver
+            .resolve(
+                epic,
+                |id| storage.id_exists(id).unwrap_or(false),
+                |hash| storage.find_ids_by_hash(hash).unwrap_or_default(),
+            )?
+            .id;
+        let descendants = descendant_ids(storage, &epic_id)?;
+        issues.retain(|issue| descendants.contains(&issue.id));
+        return Ok((issues, Some(format!("epic:{epic_id}"))));
+    }
+
+    if let Some(label) = args.label.as_deref() {
+        let ids: Vec<String> = issues.iter().map(|issue| issue.id.clone()).collect();
+        let labels = storage.get_labels_for_issues(&ids)?;
+        issues.retain(|issue| {
+            labels
+                .get(&issue.id)
+                .is_some_and(|labels| labels.iter().any(|l| l == label))
+        });
+        return Ok((issues, Some(format!("label:{label}"))));
+    }
+
+    Ok((issues, None))
+}
+
+/// IDs of every issue below `root` through parent-child links.
+fn descendant_ids(storage: &SqliteStorage, root: &str) -> Result<HashSet<String>> {
+    let mut seen = HashSet::new();
+    let mut queue = VecDeque::from([root.to_string()]);
+    while let Some(id) = queue.pop_front() {
+        for child in storage.get_dependents_with_metadata(&id)? {
+            if child.dep_type == "parent-child" && seen.insert(child.id.clone()) {
+                queue.push_back(child.id);
+            }
+        }
+    }
+    Ok(seen)
+}
+
+/// Compute flow metrics for `issues` over `[since, until]`.
+fn compute_flow(
+    issues: &[Issue],
+    events: &[Event],
+    since: DateTime<Utc>,
+    until: DateTime<Utc>,
+) -> FlowMetrics {
+    let (started, intervals) = in_progress_history(issues, events);
+
+    let mut lead = Vec::new();
+    let mut cycle = Vec::new();
+    for issue in issues {
+        let Some(closed_at) = issue.closed_at else {
+            continue;
+        };
+        if closed_at < since || closed_at > until {
+            continue;
+        }
+        lead.push(hours(closed_at - issue.created_at));
+        if let Some(start) = started.get(issue.id.as_str()).filter(|s| **s <= closed_at) {
+            cycle.push(hours(closed_at - *start));
+        }
+    }
+
+    let mut weeks = Vec::new();
+    let mut week = monday_of(since.date_naive());
+    while week <= until.date_naive() {
+        let begin = week.and_time(NaiveTime::MIN).and_utc();
+        let end = (begin + Duration::weeks(1)).min(until);
+        weeks.push(FlowWeek {
+            week_start: week.format("%Y-%m-%d").to_string(),
+            created: issues
+                .iter()
+                .filter(|i| i.created_at >= begin && i.created_at < end)
+                .count(),
+            closed: issues
+                .iter()
+                .filter(|i| i.closed_at.is_some_and(|c| c >= begin && c < end))
+                .count(),
+            wip: intervals
+                .iter()
+                .filter(|(start, stop)| *start <= end && stop.is_none_or(|stop| stop > end))
+                .count(),
+            remaining: issues
+                .iter()
+                .filter(|&i| i.created_at <= end && !closed_by(i, end))
+                .count(),
+            completed: issues.iter().filter(|&i| closed_by(i, end)).count(),
+        });
+        week += Duration::weeks(1);
+    }
+
+    FlowMetrics {
+        since: since.to_rfc3339(),
+        until: until.to_rfc3339(),
+        scope: None,
+        lead_time: duration_stats(lead),
+        cycle_time: duration_stats(cycle),
+        weeks,
+    }
+}
+
+type InProgressInterval = (DateTime<Utc>, Option<DateTime<Utc>>);
+
+/// First `in_progress` time per issue, and every `in_progress` interval.
+///
+/// Issues that are in progress without a matching event (e.g. imported ones)
+/// count as in progress since they were created.
+fn in_progress_history<'a>(
+    issues: &'a [Issue],
+    events: &[Event],
+) -> (HashMap<&'a str, DateTime<Utc>>, Vec<InProgressInterval>) {
+    let ids: HashMap<&str, &'a Issue> = issues.iter().map(|i| (i.id.as_str(), i)).collect();
+    let mut changes: Vec<&Event> = events
+        .iter()
+        .filter(|e| {
+            e.event_type == EventType::StatusChanged && ids.contains_key(e.issue_id.as_str())
+        })
+        .collect();
+    changes.sort_by_key(|e| (e.created_at, e.id));
+
+    let mut started: HashMap<&str, DateTime<Utc>> = HashMap::new();
+    let mut open: HashMap<&str, DateTime<Utc>> = HashMap::new();
+    let mut intervals = Vec::new();
+    for event in changes {
+        let Some((&id, _)) = ids.get_key_value(event.issue_id.as_str()) else {
+            continue;
+        };
+        if event.new_value.as_deref() == Some(Status::InProgress.as_str()) {
+            started.entry(id).or_insert(event.created_at);
+            open.entry(id).or_insert(event.created_at);
+        } else if let Some(start) = open.remove(id) {
+            intervals.push((start, Some(event.created_at)));
+        }
+    }
+
+    for issue in issues {
+        if issue.status == Status::InProgress && !open.contains_key(issue.id.as_str()) {
+            open.insert(issue.id.as_str(), issue.created_at);
+        }
+    }
+    intervals.extend(open.into_values().map(|start| (start, None)));
+    (started, intervals)
+}
+
+fn closed_by(issue: &Issue, end: DateTime<Utc>) -> bool {
+    issue.closed_at.is_some_and(|closed| closed <= end)
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn hours(duration: Duration) -> f64 {
+    duration.num_minutes() as f64 / 60.0
+}
+
+fn monday_of(date: NaiveDate) -> NaiveDate {
+    date - Duration::days(i64::from(date.weekday().num_days_from_monday()))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn duration_stats(mut values: Vec<f64>) -> DurationStats {
+    if values.is_empty() {
+        return DurationStats::default();
+    }
+    values.sort_by(f64::total_cmp);
+    let mean = values.iter().sum::<f64>() / values.len() as f64;
+    DurationStats {
+        count: values.len(),
+        mean_hours: Some(round1(mean)),
+        p50_hours: Some(round1(percentile(&values, 50))),
+        p85_hours: Some(round1(percentile(&values, 85))),
+        p95_hours: Some(round1(percentile(&values, 95))),
+    }
+}
+
+/// Nearest-rank percentile of sorted, non-empty `values`.
+fn percentile(values: &[f64], pct: usize) -> f64 {
+    let rank = (pct * values.len()).div_ceil(100).max(1);
+    values[rank.min(values.len()) - 1]
+}
+
+fn round1(value: f64) -> f64 {
+    (value * 10.0).round() / 10.0
+}
+
+/// Render `values` as a one-line sparkline scaled to the largest value.
+#[allow(
+    clippy::cast_precision_loss,
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss
+)]
+#[must_use]
+pub fn sparkline(values: &[usize]) -> String {
+    let max = values.iter().copied().max().unwrap_or(0);
+    values
+        .iter()
+        .map(|&value| {
+            if max == 0 {
+                SPARK_CHARS[0]
+            } else {
+                let step = (value as f64 / max as f64 * 7.0).round() as usize;
+                SPARK_CHARS[step.min(7)]
+            }
+        })
+        .collect()
+}
+
+/// Format hours as days, or hours below one day.
+fn format_hours(hours: Option<f64>) -> String {
+    match hours {
+        Some(h) if h < 24.0 => format!("{h:.1}h"),
+        Some(h) => format!("{:.1}d", h / 24.0),
+        None => "-".to_string(),
+    }
+}
+
+/// Date part of an RFC 3339 timestamp.
+fn day(stamp: &str) -> &str {
+    stamp.get(..10).unwrap_or(stamp)
+}
+
+fn weekly(metrics: &FlowMetrics, pick: fn(&FlowWeek) -> usize) -> Vec<usize> {
+    metrics.weeks.iter().map(pick).collect()
+}
+
+fn print_flow_text(metrics: &FlowMetrics) {
+    let scope = metrics
+        .scope
+        .as_deref()
+        .map(|scope| format!(" ({scope})"))
+        .unwrap_or_default();
+    println!(
+        "Flow metrics {} → {}{scope}\n",
+        day(&metrics.since),
+        day(&metrics.until)
+    );
+
+    for (name, stats) in [
+        ("Lead time", &metrics.lead_time),
+        ("Cycle time", &metrics.cycle_time),
+    ] {
+        println!(
+            "{name:<11} n={:<4} p50 {:>6}  p85 {:>6}  p95 {:>6}  mean {:>6}",
+            stats.count,
+            format_hours(stats.p50_hours),
+            format_hours(stats.p85_hours),
+            format_hours(stats.p95_hours),
+            format_hours(stats.mean_hours),
+        );
+    }
+
+    let closed = weekly(metrics, |w| w.closed);
+    let created = weekly(metrics, |w| w.created);
+    println!("\nWeekly ({} weeks, oldest first):", metrics.weeks.len());
+    println!(
+        "  Throughput  {}  {} closed",
+        sparkline(&closed),
+        closed.iter().sum::<usize>()
+    );
+    println!(
+        "  Created     {}  {} created",
+        sparkline(&created),
+        created.iter().sum::<usize>()
+    );
+    for (name, values) in [
+        ("WIP", weekly(metrics, |w| w.wip)),
+        ("Remaining", weekly(metrics, |w| w.remaining)),
+        ("Completed", weekly(metrics, |w| w.completed)),
+    ] {
+        let now = values.last().copied().unwrap_or(0);
+        println!("  {name:<10}  {}  now {now}", sparkline(&values));
+    }
+}
+
+/// Weekly series as CSV, one row per week.
+fn format_flow_csv(metrics: &FlowMetrics) -> String {
+    let mut out = String::from("week_start,created,closed,wip,remaining,completed\n");
+    for week in &metrics.weeks {
+        out.push_str(&format!(
+            "{},{},{},{},{},{}\n",
+            escape_field(&week.week_start),
+            week.created,
+            week.closed,
+            week.wip,
+            week.remaining,
+            week.completed
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn at(day: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(2024, 1, day, 12, 0, 0).unwrap()
+    }
+
+    fn make_issue(id: &str, created: u32, closed: Option<u32>, status: Status) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: id.to_string(),
+            status,
+            created_at: at(created),
+            updated_at: at(created),
+            closed_at: closed.map(at),
+            ..Issue::default()
+        }
+    }
+
+    fn status_event(id: i64, issue_id: &str, old: &str, new: &str, day: u32) -> Event {
+        Event {
+            id,
+            issue_id: issue_id.to_string(),
+            event_type: EventType::StatusChanged,
+            actor: "tester".to_string(),
+            old_value: Some(old.to_string()),
+            new_value: Some(new.to_string()),
+            comment: None,
+            created_at: at(day),
+        }
+    }
+
+    #[test]
+    fn test_percentile_and_stats() {
+        init_logging();
+        info!("test_percentile_and_stats: starting");
+        let values: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert!((percentile(&values, 50) - 10.0).abs() < f64::EPSILON);
+        assert!((percentile(&values, 95) - 19.0).abs() < f64::EPSILON);
+        assert!((percentile(&[4.0], 85) - 4.0).abs() < f64::EPSILON);
+
+        let stats = duration_stats(vec![48.0, 24.0, 72.0]);
+        assert_eq!(stats.count, 3);
+        assert_eq!(stats.mean_hours, Some(48.0));
+        assert_eq!(stats.p50_hours, Some(48.0));
+        assert_eq!(duration_stats(Vec::new()), DurationStats::default());
+        info!("test_percentile_and_stats: assertions passed");
+    }
+
+    #[test]
+    fn test_compute_flow_weeks_and_times() {
+        init_logging();
+        info!("test_compute_flow_weeks_and_times: starting");
+        // 2024-01-01 is a Monday.
+        let issues = vec![
+            make_issue("bd-1", 1, Some(5), Status::Closed),
+            make_issue("bd-2", 2, Some(10), Status::Closed),
+            make_issue("bd-3", 9, None, Status::InProgress),
+            make_issue("bd-4", 9, None, Status::Open),
+        ];
+        let events = vec![
+            status_event(1, "bd-1", "open", "in_progress", 3),
+            status_event(2, "bd-1", "in_progress", "closed", 5),
+            status_event(3, "bd-2", "open", "in_progress", 8),
+            status_event(4, "bd-2", "in_progress", "closed", 10),
+            status_event(5, "bd-3", "open", "in_progress", 11),
+        ];
+        let since = Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap();
+        let until = Utc.with_ymd_and_hms(2024, 1, 14, 23, 0, 0).unwrap();
+        let metrics = compute_flow(&issues, &events, since, until);
+
+        assert_eq!(metrics.lead_time.count, 2);
+        assert_eq!(metrics.lead_time.p50_hours, Some(96.0));
+        assert_eq!(metrics.cycle_time.count, 2);
+        assert_eq!(metrics.cycle_time.p95_hours, Some(48.0));
+
+        assert_eq!(
+            metrics.weeks,
+            vec![
+                FlowWeek {
+                    week_start: "2024-01-01".to_string(),
+                    created: 2,
+                    closed: 1,
+                    wip: 0,
+                    remaining: 1,
+                    completed: 1,
+                },
+                FlowWeek {
+                    week_start: "2024-01-08".to_string(),
+                    created: 2,
+                    closed: 1,
+                    wip: 1,
+                    remaining: 2,
+                    completed: 2,
+                },
+            ]
+        );
+        info!("test_compute_flow_weeks_and_times: assertions passed");
+    }
+
+    #[test]
+    fn test_sparkline_and_window() {
+        init_logging();
+        info!("test_sparkline_and_window: starting");
+        assert_eq!(sparkline(&[0, 1, 2, 4]), "▁▃▅█");
+        assert_eq!(sparkline(&[0, 0]), "▁▁");
+        let now = Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap();
+        assert_eq!(
+            resolve_window_start(Some("60d"), now).unwrap(),
+            now - Duration::days(60)
+        );
+        assert_eq!(
+            resolve_window_start(None, now).unwrap(),
+            now - Duration::days(90)
+        );
+        assert!(resolve_window_start(Some("soon"), now).is_err());
+        info!("test_sparkline_and_window: assertions passed");
+    }
+}
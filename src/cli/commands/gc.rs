@@ -0,0 +1,371 @@
+//! Garbage collection command implementation.
+//!
+//! Hard-deletes tombstones past their retention period, prunes old events and
+//! export bookkeeping, drops orphaned child counters, and finishes with
+//! `VACUUM`/`ANALYZE`. When tombstones are purged the JSONL export is rewritten
+//! in the same run, and import skips expired tombstones that are no longer in
+//! the database, so purged issues do not come back from an older JSONL.
+
+use crate::cli::GcArgs;
+use crate::config::{self, ConfigPaths};
+use crate::error::{BeadsError, Result};
+use crate::output::OutputContext;
+use crate::storage::{GcStats, SqliteStorage};
+use crate::sync::events::prune_events_jsonl;
+use crate::sync::{ExportConfig, export_to_jsonl_with_policy, finalize_export};
+use crate::util::time::parse_duration;
+use chrono::{DateTime, Utc};
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use tracing::{debug, info};
+
+/// Result of a gc run.
+#[derive(Debug, Serialize)]
+pub struct GcReport {
+    pub dry_run: bool,
+    pub cutoff: DateTime<Utc>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tombstone_retention_days: Option<u64>,
+    pub purged_ids: Vec<String>,
+    #[serde(flatten)]
+    pub stats: GcStats,
+    /// Lines removed from the shared `events.jsonl` (0 on dry runs).
+    pub shared_events_pruned: usize,
+    pub jsonl_bytes_before: u64,
+    pub jsonl_bytes_after: u64,
+    pub db_bytes_before: u64,
+    pub db_bytes_after: u64,
+    pub vacuumed: bool,
+}
+
+/// Execute the gc command.
+///
+/// # Errors
+///
+/// Returns an error if `--older-than` is invalid, a database operation fails,
+/// or the JSONL export cannot be rewritten.
+pub fn execute(args: &GcArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let invalid_age = || {
+        BeadsError::validation(
+            "older-than",
+            format!(
+                "invalid age '{}' (expected e.g. 365d, 12w)",
+                args.older_than
+            ),
+        )
+    };
+    let cutoff = parse_duration(&args.older_than)
+        .and_then(|age| Utc::now().checked_sub_signed(age))
+        .ok_or_else(invalid_age)?;
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let config::OpenStorageResult {
+        mut storage,
+        paths,
+        no_db,
+    } = config::open_storage_with_cli(&beads_dir, cli)?;
+
+    let retention_days = args
+        .tombstone_days
+        .or(paths.metadata.deletions_retention_days);
+    let report = collect(
+        &mut storage,
+        &paths,
+        cutoff,
+        retention_days,
+        args.dry_run,
+        !args.no_vacuum && !no_db,
+    )?;
+
+    render_report(&report, ctx);
+    Ok(())
+}
+
+/// Run garbage collection against an open storage.
+///
+/// # Errors
+///
+/// Returns an error if a database operation fails or the JSONL export cannot
+/// be rewritten.
+pub fn collect(
+    storage: &mut SqliteStorage,
+    paths: &ConfigPaths,
+    cutoff: DateTime<Utc>,
+    retention_days: Option<u64>,
+    dry_run: bool,
+    vacuum: bool,
+) -> Result<GcReport> {
+    let tombstones: Vec<String> = storage
+        .get_all_issues_for_export()?
+        .into_iter()
+        .filter(|issue| issue.is_expired_tombstone(retention_days))
+        .map(|issue| issue.id)
+        .collect();
+    debug!(count = tombstones.len(), "Expired tombstones found");
+
+    let jsonl_bytes_before = file_size(&paths.jsonl_path);
+    let db_bytes_before = db_size(&paths.db_path);
+
+    let stats = storage.garbage_collect(&tombstones, cutoff, dry_run)?;
+
+    let mut shared_events_pruned = 0;
+    let (jsonl_bytes_after, db_bytes_after) = if dry_run {
+        (
+            jsonl_bytes_before.saturating_sub(jsonl_bytes_of(&paths.jsonl_path, &tombstones)),
+            db_bytes_before.saturating_sub(storage.reclaimable_bytes()?),
+        )
+    } else {
+        if stats.tombstones_purged > 0 {
+            rewrite_jsonl(storage, paths, retention_days)?;
+        }
+        // Otherwise the next import would bring pruned events back
+        shared_events_pruned = prune_events_jsonl(storage, &paths.beads_dir, cutoff, &tombstones)?;
+        if vacuum {
+            storage.vacuum_and_analyze()?;
+        }
+        (file_size(&paths.jsonl_path), db_size(&paths.db_path))
+    };
+
+    info!(
+        tombstones = stats.tombstones_purged,
+        events = stats.events_pruned,
+        dry_run,
+        "Garbage collection finished"
+    );
+
+    Ok(GcReport {
+        dry_run,
+        cutoff,
+        tombstone_retention_days: retention_days,
+        purged_ids: tombstones,
+        stats,
+        shared_events_pruned,
+        jsonl_bytes_before,
+        jsonl_bytes_after,
+        db_bytes_before,
+        db_bytes_after,
+        vacuumed: vacuum && !dry_run,
+    })
+}
+
+/// Rewrite the JSONL export so purged tombstones are gone from it too.
+fn rewrite_jsonl(
+    storage: &mut SqliteStorage,
+    paths: &ConfigPaths,
+    retention_days: Option<u64>,
+) -> Result<()> {
+    let export_config = ExportConfig {
+        // The database may legitimately hold fewer issues than the JSONL now
+        force: true,
+        is_default_path: paths.jsonl_path == paths.beads_dir.join("issues.jsonl"),
+        retention_days,
+        beads_dir: Some(paths.beads_dir.clone()),
+        allow_external_jsonl: false,
+        show_progress: false,
+        ..Default::default()
+    };
+    let (result, _report) =
+        export_to_jsonl_with_policy(storage, &paths.jsonl_path, &export_config)?;
+    finalize_export(storage, &result, Some(&result.issue_hashes))?;
+    Ok(())
+}
+
+/// Bytes the given issues occupy in the JSONL file, newlines included.
+fn jsonl_bytes_of(path: &Path, ids: &[String]) -> u64 {
+    if ids.is_empty() {
+        return 0;
+    }
+    let Ok(contents) = fs::read_to_string(path) else {
+        return 0;
+    };
+    contents
+        .lines()
+        .filter(|line| {
+            serde_json::from_str::<serde_json::Value>(line)
+                .ok()
+                .and_then(|value| value.get("id")?.as_str().map(str::to_string))
+                .is_some_and(|id| ids.contains(&id))
+        })
+        .map(|line| line.len() as u64 + 1)
+        .sum()
+}
+
+fn file_size(path: &Path) -> u64 {
+    fs::metadata(path).map_or(0, |meta| meta.len())
+}
+
+/// Database size including its WAL file.
+fn db_size(path: &Path) -> u64 {
+    let mut wal = path.as_os_str().to_os_string();
+    wal.push("-wal");
+    file_size(path) + file_size(Path::new(&wal))
+}
+
+#[allow(clippy::cast_precision_loss)]
+fn format_size(bytes: u64) -> String {
+    const KB: u64 = 1024;
+    const MB: u64 = 1024 * 1024;
+
+    if bytes >= MB {
+        format!("{:.1} MB", bytes as f64 / MB as f64)
+    } else if bytes >= KB {
+        format!("{:.1} KB", bytes as f64 / KB as f64)
+    } else {
+        format!("{bytes} B")
+    }
+}
+
+fn render_report(report: &GcReport, ctx: &OutputContext) {
+    if ctx.is_json() {
+        ctx.json_pretty(report);
+        return;
+    }
+
+    let stats = &report.stats;
+    let title = if report.dry_run {
+        "Garbage collection (dry run)"
+    } else {
+        "Garbage collection"
+    };
+    ctx.section(title);
+
+    let retention = report.tombstone_retention_days.map_or_else(
+        || "no retention configured".to_string(),
+        |d| format!("retention {d}d"),
+    );
+    let lines = [
+        format!(
+            "  Tombstones purged:     {} ({retention})",
+            stats.tombstones_purged
+        ),
+        format!("  Dependencies removed:  {}", stats.dependencies_removed),
+        format!(
+            "  Events pruned:         {} (before {})",
+            stats.events_pruned,
+            report.cutoff.format("%Y-%m-%d")
+        ),
+        format!("  Dirty flags pruned:    {}", stats.dirty_pruned),
+        format!("  Export hashes pruned:  {}", stats.export_hashes_pruned),
+        format!("  Child counters pruned: {}", stats.child_counters_pruned),
+        format!("  Shared events pruned:  {}", report.shared_events_pruned),
+        format!(
+            "  JSONL:    {} -> {}",
+            format_size(report.jsonl_bytes_before),
+            format_size(report.jsonl_bytes_after)
+        ),
+        format!(
+            "  Database: {} -> {}{}",
+            format_size(report.db_bytes_before),
+            format_size(report.db_bytes_after),
+            if report.dry_run {
+                " (after VACUUM; pruned rows add to this)"
+            } else {
+                ""
+            }
+        ),
+    ];
+    for line in &lines {
+        ctx.print(line);
+    }
+
+    let saved = (report.jsonl_bytes_before + report.db_bytes_before)
+        .saturating_sub(report.jsonl_bytes_after + report.db_bytes_after);
+    if report.dry_run {
+        ctx.info(&format!("Would save at least {}", format_size(saved)));
+    } else {
+        ctx.success(&format!("Saved {}", format_size(saved)));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Issue, Status};
+    use chrono::Duration;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn tombstone(id: &str, deleted_days_ago: i64) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: format!("Deleted {id}"),
+            status: Status::Tombstone,
+            deleted_at: Some(Utc::now() - Duration::days(deleted_days_ago)),
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn test_garbage_collect_purges_expired_tombstones() {
+        init_logging();
+        info!("test_garbage_collect_purges_expired_tombstones: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let live = Issue {
+            id: "bd-live".to_string(),
+            title: "Live".to_string(),
+            ..Issue::default()
+        };
+        storage.create_issue(&live, "tester").unwrap();
+        storage
+            .create_issue(&tombstone("bd-old", 90), "tester")
+            .unwrap();
+        storage
+            .create_issue(&tombstone("bd-recent", 1), "tester")
+            .unwrap();
+        storage
+            .add_dependency("bd-live", "bd-old", "blocks", "tester")
+            .unwrap();
+
+        let expired: Vec<String> = storage
+            .get_all_issues_for_export()
+            .unwrap()
+            .into_iter()
+            .filter(|issue| issue.is_expired_tombstone(Some(30)))
+            .map(|issue| issue.id)
+            .collect();
+        assert_eq!(expired, vec!["bd-old".to_string()]);
+
+        let cutoff = Utc::now() - Duration::days(365);
+        let dry = storage.garbage_collect(&expired, cutoff, true).unwrap();
+        assert_eq!(dry.tombstones_purged, 1);
+        assert_eq!(dry.dependencies_removed, 1);
+        assert!(storage.get_issue("bd-old").unwrap().is_some());
+
+        let stats = storage.garbage_collect(&expired, cutoff, false).unwrap();
+        assert_eq!(stats, dry);
+        assert!(storage.get_issue("bd-old").unwrap().is_none());
+        assert!(storage.get_issue("bd-recent").unwrap().is_some());
+        assert!(storage.get_dependencies("bd-live").unwrap().is_empty());
+        info!("test_garbage_collect_purges_expired_tombstones: assertions passed");
+    }
+
+    #[test]
+    fn test_garbage_collect_prunes_old_events() {
+        init_logging();
+        info!("test_garbage_collect_prunes_old_events: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let issue = Issue {
+            id: "bd-1".to_string(),
+            title: "Issue".to_string(),
+            ..Issue::default()
+        };
+        storage.create_issue(&issue, "tester").unwrap();
+        assert!(!storage.get_events("bd-1", 10).unwrap().is_empty());
+
+        // Everything is newer than a cutoff in the past
+        let kept = storage
+            .garbage_collect(&[], Utc::now() - Duration::days(1), false)
+            .unwrap();
+        assert_eq!(kept.events_pruned, 0);
+
+        let pruned = storage
+            .garbage_collect(&[], Utc::now() + Duration::days(1), false)
+            .unwrap();
+        assert!(pruned.events_pruned > 0);
+        assert!(storage.get_events("bd-1", 10).unwrap().is_empty());
+        info!("test_garbage_collect_prunes_old_events: assertions passed");
+    }
+}
@@ -0,0 +1,524 @@
+//! Git command implementation.
+//!
+//! `br git index` walks `git log` and records which commits mention which
+//! issues, using the same ID matching as `orphans`. Only commits added since
+//! the last run are read unless history was rewritten or `--rebuild` is given.
+//! `br git apply-trailers` closes the issues named in closing trailers such as
+//! `Closes: bd-abc` or `Fixes bd-abc`, recording the commit SHA on the issue.
+
+use crate::cli::{GitApplyTrailersArgs, GitCommands, GitIndexArgs};
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::model::{CommitLink, Status};
+use crate::output::OutputContext;
+use crate::storage::{IssueUpdate, SqliteStorage};
+use crate::util::id::{issue_ref_regex, normalize_id};
+use chrono::{DateTime, Utc};
+use regex::Regex;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+use std::process::Command;
+use tracing::debug;
+
+/// Metadata key holding the HEAD commit of the last index run.
+const INDEX_HEAD_KEY: &str = "git_index_head";
+
+/// A commit read from `git log`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct GitCommit {
+    sha: String,
+    author: String,
+    authored_at: DateTime<Utc>,
+    message: String,
+}
+
+impl GitCommit {
+    fn subject(&self) -> &str {
+        self.message.lines().next().unwrap_or("").trim()
+    }
+}
+
+/// Result of an index run.
+#[derive(Debug, Default, Serialize)]
+struct IndexReport {
+    head: Option<String>,
+    full_scan: bool,
+    commits_scanned: usize,
+    links_added: usize,
+}
+
+/// An issue closed (or to be closed) by a commit trailer.
+#[derive(Debug, Serialize)]
+struct TrailerClose {
+    id: String,
+    sha: String,
+    subject: String,
+    trailer: String,
+}
+
+/// A trailer that was not applied.
+#[derive(Debug, Serialize)]
+struct TrailerSkip {
+    id: String,
+    sha: String,
+    reason: String,
+}
+
+/// Result of `apply-trailers`.
+#[derive(Debug, Serialize)]
+struct ApplyReport {
+    index: IndexReport,
+    dry_run: bool,
+    closed: Vec<TrailerClose>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    skipped: Vec<TrailerSkip>,
+}
+
+/// Execute the git command.
+///
+/// # Errors
+///
+/// Returns an error if the workspace is not inside a git repository, git
+/// commands fail, or database operations fail.
+pub fn execute(
+    command: &GitCommands,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let prefix = config::id_config_from_layer(&layer).prefix;
+    let repo = beads_dir.parent().unwrap_or(&beads_dir);
+
+    match command {
+        GitCommands::Index(args) => {
+            let report = run_index(&mut storage_ctx.storage, repo, &prefix, args)?;
+            render_index(&report, ctx);
+        }
+        GitCommands::ApplyTrailers(args) => {
+            let actor = config::resolve_actor(&layer);
+            let index = run_index(
+                &mut storage_ctx.storage,
+                repo,
+                &prefix,
+                &GitIndexArgs::default(),
+            )?;
+            let report = apply_trailers(&mut storage_ctx.storage, index, args, &actor)?;
+            render_apply(&report, ctx);
+            if !args.dry_run {
+                for close in &report.closed {
+                    crate::util::set_last_touched_id(&beads_dir, &close.id);
+                }
+            }
+        }
+    }
+
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+/// Index the commits added since the last run (or all of them).
+fn run_index(
+    storage: &mut SqliteStorage,
+    repo: &Path,
+    prefix: &str,
+    args: &GitIndexArgs,
+) -> Result<IndexReport> {
+    let Some(head) = git_output(repo, &["rev-parse", "--verify", "--quiet", "HEAD"])? else {
+        // Unborn branch: nothing to index yet.
+        return Ok(IndexReport {
+            full_scan: true,
+            ..IndexReport::default()
+        });
+    };
+
+    if args.rebuild {
+        storage.clear_commit_links()?;
+    }
+    let last = if args.rebuild {
+        None
+    } else {
+        storage
+            .get_metadata(INDEX_HEAD_KEY)?
+            .filter(|last| is_ancestor(repo, last, &head))
+    };
+    if last.as_deref() == Some(head.as_str()) {
+        return Ok(IndexReport {
+            head: Some(head),
+            ..IndexReport::default()
+        });
+    }
+
+    let range = last
+        .as_ref()
+        .map_or_else(|| head.clone(), |last| format!("{last}..{head}"));
+    debug!(range = %range, "Indexing commits");
+    let log = git_output(
+        repo,
+        &["log", "--format=%H%x1f%an%x1f%aI%x1f%B%x1e", &range],
+    )?
+    .unwrap_or_default();
+    let commits = parse_git_log(&log);
+
+    let refs_re = issue_ref_regex(prefix)?;
+    let trailer_re = closing_trailer_regex(prefix)?;
+    let links: Vec<CommitLink> = commits
+        .iter()
+        .flat_map(|commit| commit_links(commit, &refs_re, &trailer_re))
+        .collect();
+    let links_added = storage.add_commit_links(&links)?;
+    storage.set_metadata(INDEX_HEAD_KEY, &head)?;
+
+    Ok(IndexReport {
+        head: Some(head),
+        full_scan: last.is_none(),
+        commits_scanned: commits.len(),
+        links_added,
+    })
+}
+
+/// Close the issues named by closing trailers that have not been applied yet.
+fn apply_trailers(
+    storage: &mut SqliteStorage,
+    index: IndexReport,
+    args: &GitApplyTrailersArgs,
+    actor: &str,
+) -> Result<ApplyReport> {
+    let mut report = ApplyReport {
+        index,
+        dry_run: args.dry_run,
+        closed: Vec::new(),
+        skipped: Vec::new(),
+    };
+
+    for link in storage.get_pending_commit_closes()? {
+        let short = short_sha(&link.sha);
+        let Some(issue) = storage.get_issue(&link.issue_id)? else {
+            continue;
+        };
+        let already_planned = report.closed.iter().any(|c| c.id == link.issue_id);
+        if issue.status.is_terminal() || already_planned {
+            // Retire the trailer so reopening the issue later sticks.
+            if !args.dry_run {
+                storage.mark_commit_close_applied(&link.issue_id, &link.sha)?;
+            }
+            continue;
+        }
+        if !args.force && storage.is_blocked(&link.issue_id)? {
+            report.skipped.push(TrailerSkip {
+                id: link.issue_id.clone(),
+                sha: short.to_string(),
+                reason: "blocked by dependencies".to_string(),
+            });
+            continue;
+        }
+
+        if !args.dry_run {
+            let update = IssueUpdate {
+                status: Some(Status::Closed),
+                closed_at: Some(Some(Utc::now())),
+                close_reason: Some(Some(format!("Closed by commit {short}: {}", link.subject))),
+                closed_by_session: Some(Some(link.sha.clone())),
+                ..IssueUpdate::default()
+            };
+            storage.update_issue(&link.issue_id, &update, actor)?;
+            storage.mark_commit_close_applied(&link.issue_id, &link.sha)?;
+        }
+        report.closed.push(TrailerClose {
+            id: link.issue_id.clone(),
+            sha: short.to_string(),
+            subject: link.subject.clone(),
+            trailer: link.closes.clone().unwrap_or_default(),
+        });
+    }
+
+    Ok(report)
+}
+
+/// Run git in `repo` and return its trimmed stdout, or `None` if it failed.
+fn git_output(repo: &Path, args: &[&str]) -> Result<Option<String>> {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(args)
+        .output()
+        .map_err(|e| BeadsError::Config(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        if stderr.contains("not a git repository") {
+            return Err(BeadsError::Config(format!(
+                "{} is not inside a git repository",
+                repo.display()
+            )));
+        }
+        return Ok(None);
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    Ok(Some(stdout).filter(|s| !s.is_empty()))
+}
+
+/// Whether `ancestor` is still reachable from `head` (history was not rewritten).
+fn is_ancestor(repo: &Path, ancestor: &str, head: &str) -> bool {
+    Command::new("git")
+        .arg("-C")
+        .arg(repo)
+        .args(["merge-base", "--is-ancestor", ancestor, head])
+        .output()
+        .is_ok_and(|o| o.status.success())
+}
+
+/// Parse `git log --format=%H%x1f%an%x1f%aI%x1f%B%x1e` output.
+fn parse_git_log(log: &str) -> Vec<GitCommit> {
+    log.split('\x1e')
+        .filter_map(|record| {
+            let mut fields = record.trim_start_matches('\n').splitn(4, '\x1f');
+            let sha = fields.next()?.trim();
+            let author = fields.next()?;
+            let date = fields.next()?;
+            let message = fields.next().unwrap_or("");
+            if sha.is_empty() {
+                return None;
+            }
+            let authored_at = DateTime::parse_from_rfc3339(date.trim())
+                .ok()?
+                .with_timezone(&Utc);
+            Some(GitCommit {
+                sha: sha.to_string(),
+                author: author.to_string(),
+                authored_at,
+                message: message.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Build a regex matching closing trailer lines such as `Closes: bd-abc, bd-def`.
+///
+/// The keyword must start the line and the rest of the line must be issue IDs,
+/// so prose like "fix the bd-abc parser" does not close anything.
+fn closing_trailer_regex(prefix: &str) -> Result<Regex> {
+    let id = format!(r"{}-[a-z0-9]+(?:\.[0-9]+)*", regex::escape(prefix));
+    let pattern = format!(
+        r"(?im)^[ \t]*(close[sd]?|fix(?:e[sd])?|resolve[sd]?)[ \t]*:?[ \t]+({id}(?:[ \t]*,?[ \t]*{id})*)[ \t]*$"
+    );
+    Regex::new(&pattern).map_err(|e| BeadsError::Config(format!("Invalid regex pattern: {e}")))
+}
+
+/// Link a commit to every issue its message mentions.
+///
+/// Issues named in a closing trailer carry the trailer keyword in `closes`.
+fn commit_links(commit: &GitCommit, refs_re: &Regex, trailer_re: &Regex) -> Vec<CommitLink> {
+    let mut mentioned: BTreeMap<String, Option<String>> = BTreeMap::new();
+    for cap in refs_re.captures_iter(&commit.message) {
+        mentioned.entry(normalize_id(&cap[1])).or_insert(None);
+    }
+    for cap in trailer_re.captures_iter(&commit.message) {
+        let keyword = cap[1].to_lowercase();
+        for id in refs_re.captures_iter(&cap[2]) {
+            mentioned.insert(normalize_id(&id[1]), Some(keyword.clone()));
+        }
+    }
+
+    mentioned
+        .into_iter()
+        .map(|(issue_id, closes)| CommitLink {
+            issue_id,
+            sha: commit.sha.clone(),
+            author: commit.author.clone(),
+            authored_at: commit.authored_at,
+            subject: commit.subject().to_string(),
+            closes,
+        })
+        .collect()
+}
+
+fn short_sha(sha: &str) -> &str {
+    sha.get(..7).unwrap_or(sha)
+}
+
+fn render_index(report: &IndexReport, ctx: &OutputContext) {
+    if ctx.is_json() {
+        ctx.json_pretty(report);
+        return;
+    }
+    let Some(head) = &report.head else {
+        ctx.info("No commits to index yet.");
+        return;
+    };
+    if report.commits_scanned == 0 {
+        ctx.info(&format!(
+            "Commit index is up to date at {}.",
+            short_sha(head)
+        ));
+        return;
+    }
+    ctx.success(&format!(
+        "Indexed {} commits{}: {} new issue links (HEAD {})",
+        report.commits_scanned,
+        if report.full_scan { " (full scan)" } else { "" },
+        report.links_added,
+        short_sha(head)
+    ));
+}
+
+fn render_apply(report: &ApplyReport, ctx: &OutputContext) {
+    if ctx.is_json() {
+        ctx.json_pretty(report);
+        return;
+    }
+    if report.index.links_added > 0 {
+        ctx.info(&format!(
+            "Indexed {} commits, {} new issue links",
+            report.index.commits_scanned, report.index.links_added
+        ));
+    }
+    if report.closed.is_empty() {
+        ctx.info("No closing trailers to apply.");
+    } else {
+        let verb = if report.dry_run {
+            "Would close"
+        } else {
+            "Closed"
+        };
+        for close in &report.closed {
+            ctx.success(&format!(
+                "{verb} {} ({} {}: {})",
+                close.id, close.trailer, close.sha, close.subject
+            ));
+        }
+    }
+    for skip in &report.skipped {
+        ctx.warning(&format!(
+            "Skipped {} ({}): {}",
+            skip.id, skip.sha, skip.reason
+        ));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Issue, Priority};
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_commit(sha: &str, message: &str) -> GitCommit {
+        GitCommit {
+            sha: sha.to_string(),
+            author: "Dev".to_string(),
+            authored_at: Utc::now(),
+            message: message.to_string(),
+        }
+    }
+
+    fn make_issue(id: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            priority: Priority::MEDIUM,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn test_parse_git_log_records() {
+        init_logging();
+        info!("test_parse_git_log_records: starting");
+        let log = "aaaaaaa1\x1fAlice\x1f2025-01-02T10:00:00+01:00\x1fAdd parser\n\nRefs bd-1\n\x1e\n\
+                   bbbbbbb2\x1fBob\x1f2025-01-01T09:00:00Z\x1fInitial commit\n\x1e\n";
+        let commits = parse_git_log(log);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].sha, "aaaaaaa1");
+        assert_eq!(commits[0].author, "Alice");
+        assert_eq!(commits[0].subject(), "Add parser");
+        assert_eq!(commits[0].message, "Add parser\n\nRefs bd-1");
+        assert_eq!(
+            commits[0].authored_at.to_rfc3339(),
+            "2025-01-02T09:00:00+00:00"
+        );
+        assert_eq!(commits[1].subject(), "Initial commit");
+        info!("test_parse_git_log_records: assertions passed");
+    }
+
+    #[test]
+    fn test_commit_links_detects_closing_trailers() {
+        init_logging();
+        info!("test_commit_links_detects_closing_trailers: starting");
+        let refs_re = issue_ref_regex("bd").unwrap();
+        let trailer_re = closing_trailer_regex("bd").unwrap();
+        let commit = make_commit(
+            "abc1234def",
+            "Fix the bd-9 parser edge case\n\nSee also BD-2.\n\nCloses: bd-1, bd-3\nFixes bd-4\n",
+        );
+        let links = commit_links(&commit, &refs_re, &trailer_re);
+        let summary: Vec<(&str, Option<&str>)> = links
+            .iter()
+            .map(|l| (l.issue_id.as_str(), l.closes.as_deref()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("bd-1", Some("closes")),
+                ("bd-2", None),
+                ("bd-3", Some("closes")),
+                ("bd-4", Some("fixes")),
+                ("bd-9", None),
+            ]
+        );
+        assert!(
+            links
+                .iter()
+                .all(|l| l.subject == "Fix the bd-9 parser edge case")
+        );
+        info!("test_commit_links_detects_closing_trailers: assertions passed");
+    }
+
+    #[test]
+    fn test_apply_trailers_closes_once() {
+        init_logging();
+        info!("test_apply_trailers_closes_once: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        for id in ["bd-1", "bd-2"] {
+            storage.create_issue(&make_issue(id), "tester").unwrap();
+        }
+        let refs_re = issue_ref_regex("bd").unwrap();
+        let trailer_re = closing_trailer_regex("bd").unwrap();
+        let commit = make_commit("0123456789abcdef", "Ship it\n\nCloses: bd-1\nRefs bd-2");
+        let links = commit_links(&commit, &refs_re, &trailer_re);
+        assert_eq!(storage.add_commit_links(&links).unwrap(), 2);
+        assert_eq!(storage.add_commit_links(&links).unwrap(), 0);
+        assert_eq!(storage.get_commit_links("bd-2").unwrap().len(), 1);
+
+        let args = GitApplyTrailersArgs {
+            dry_run: true,
+            force: false,
+        };
+        let report = apply_trailers(&mut storage, IndexReport::default(), &args, "tester").unwrap();
+        assert_eq!(report.closed.len(), 1);
+        let issue = storage.get_issue("bd-1").unwrap().unwrap();
+        assert_eq!(issue.status, Status::Open);
+
+        let args = GitApplyTrailersArgs::default();
+        let report = apply_trailers(&mut storage, IndexReport::default(), &args, "tester").unwrap();
+        assert_eq!(report.closed[0].id, "bd-1");
+        let issue = storage.get_issue("bd-1").unwrap().unwrap();
+        assert_eq!(issue.status, Status::Closed);
+        assert_eq!(
+            issue.close_reason.as_deref(),
+            Some("Closed by commit 0123456: Ship it")
+        );
+        assert_eq!(issue.closed_by_session.as_deref(), Some("0123456789abcdef"));
+        assert!(storage.get_pending_commit_closes().unwrap().is_empty());
+
+        let report = apply_trailers(&mut storage, IndexReport::default(), &args, "tester").unwrap();
+        assert!(report.closed.is_empty());
+        info!("test_apply_trailers_closes_once: assertions passed");
+    }
+}
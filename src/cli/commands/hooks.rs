@@ -0,0 +1,789 @@
+//! Git hooks command implementation.
+//!
+//! Installs pre-commit, post-merge and post-checkout hooks that keep the JSONL
+//! export in step with the database, and registers `br merge-driver` for the
+//! JSONL file. All git interaction goes through the git CLI: hook scripts are
+//! written to the directory reported by `git rev-parse --git-path hooks`, and
+//! the merge driver is registered with `git config --local` plus a
+//! `.gitattributes` entry. Sync I/O is not involved, so the `sync::path`
+//! allowlist is unaffected.
+
+use crate::cli::commands::audit::INTERACTIONS_JSONL_FILE;
+use crate::cli::{HooksCommands, HooksInstallArgs, HooksTrustArgs};
+use crate::config;
+use crate::error::{BeadsError, Result};
+use crate::hooks::HookTrust;
+use crate::output::OutputContext;
+use crate::sync::events::EVENTS_JSONL_FILE;
+use serde::Serialize;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use tracing::{debug, info};
+
+/// Marker line identifying hooks written by `br hooks install`.
+const HOOK_MARKER: &str = "# br-managed-hook";
+
+/// Suffix for backups of foreign hooks replaced with `--force`.
+const BACKUP_SUFFIX: &str = ".br-backup";
+
+/// Merge driver name used in git config and `.gitattributes`.
+const MERGE_DRIVER_NAME: &str = "beads";
+
+/// Command git runs for the merge driver (`%O` base, `%A` ours/result,
+/// `%B` theirs, `%P` path in the repository).
+const MERGE_DRIVER_COMMAND: &str = "br merge-driver %O %A %B %P";
+
+/// Git hooks managed by `br hooks`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum GitHook {
+    PreCommit,
+    PostMerge,
+    PostCheckout,
+}
+
+impl GitHook {
+    const ALL: [Self; 3] = [Self::PreCommit, Self::PostMerge, Self::PostCheckout];
+
+    const fn file_name(self) -> &'static str {
+        match self {
+            Self::PreCommit => "pre-commit",
+            Self::PostMerge => "post-merge",
+            Self::PostCheckout => "post-checkout",
+        }
+    }
+
+    /// Full script for this hook. `jsonl_rel` is the JSONL path relative to the
+    /// repository root, used to stage the flushed export on commit.
+    fn script(self, jsonl_rel: Option<&str>) -> String {
+        let body = match self {
+            Self::PreCommit => {
+                let mut body = String::from(
+                    "# Flush issue changes to JSONL and validate before committing.\n\
+                     command -v br >/dev/null 2>&1 || { echo \"beads: br not found on PATH; skipping pre-commit hook\" >&2; exit 0; }\n\
+                     br sync --flush-only >/dev/null || { echo \"beads: 'br sync --flush-only' failed\" >&2; exit 1; }\n\
+                     br doctor >/dev/null || { echo \"beads: 'br doctor' found problems; fix them or commit with --no-verify\" >&2; exit 1; }\n",
+                );
+                if let Some(rel) = jsonl_rel {
+                    let quoted = shell_quote(rel);
+                    body.push_str(&format!("[ -f {quoted} ] && git add -- {quoted}\n"));
+                }
+                body.push_str("exit 0\n");
+                body
+            }
+            Self::PostMerge => "# Import issue changes brought in by the merge (no-op when JSONL is unchanged).\n\
+                 command -v br >/dev/null 2>&1 || exit 0\n\
+                 br sync --import-only >/dev/null || echo \"beads: import failed; run 'br sync --import-only'\" >&2\n\
+                 exit 0\n"
+                .to_string(),
+            Self::PostCheckout => "# Import issue changes after switching branches (no-op when JSONL is unchanged).\n\
+                 [ \"$3\" = \"1\" ] || exit 0\n\
+                 command -v br >/dev/null 2>&1 || exit 0\n\
+                 br sync --import-only >/dev/null || echo \"beads: import failed; run 'br sync --import-only'\" >&2\n\
+                 exit 0\n"
+                .to_string(),
+        };
+        format!("#!/bin/sh\n{HOOK_MARKER}: {}\n{body}", self.file_name())
+    }
+}
+
+/// Install state of a single hook file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum HookState {
+    /// No hook file.
+    Missing,
+    /// Current br-managed hook.
+    Installed,
+    /// br-managed hook from an older version.
+    Outdated,
+    /// Hook not written by br (left alone unless `--force`).
+    Foreign,
+}
+
+#[derive(Debug, Serialize)]
+struct HookReport {
+    name: &'static str,
+    path: String,
+    state: HookState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    action: Option<&'static str>,
+}
+
+#[derive(Debug, Serialize)]
+struct MergeDriverReport {
+    configured: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    driver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    gitattributes: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct HooksReport {
+    hooks_dir: String,
+    hooks: Vec<HookReport>,
+    merge_driver: MergeDriverReport,
+}
+
+/// Execute the hooks command.
+///
+/// # Errors
+///
+/// Returns an error if the workspace is not inside a git repository, git
+/// commands fail, or hook files cannot be written.
+pub fn execute(
+    command: &HooksCommands,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    if let HooksCommands::Trust(args) = command {
+        return trust(args, &beads_dir, cli, ctx);
+    }
+    let paths = config::ConfigPaths::resolve(&beads_dir, cli.db.as_ref())?;
+    let start = beads_dir.parent().unwrap_or(&beads_dir);
+    let repo = GitRepo::discover(start)?;
+    let jsonl_rel = repo.relative_path(&paths.jsonl_path);
+    let union_rels = union_merge_paths(&repo, &beads_dir);
+    debug!(
+        root = %repo.root.display(),
+        hooks_dir = %repo.hooks_dir.display(),
+        jsonl = ?jsonl_rel,
+        "Resolved git repository"
+    );
+
+    let report = match command {
+        HooksCommands::Install(args) => install(args, &repo, jsonl_rel.as_deref(), &union_rels)?,
+        HooksCommands::Uninstall => uninstall(&repo, jsonl_rel.as_deref(), &union_rels)?,
+        HooksCommands::Status => status(&repo, jsonl_rel.as_deref())?,
+        HooksCommands::Trust(_) => unreachable!("handled above"),
+    };
+
+    if ctx.is_json() {
+        ctx.json_pretty(&report);
+    } else {
+        render_report(&report, ctx);
+    }
+    Ok(())
+}
+
+#[derive(Debug, Serialize)]
+struct TrustReport {
+    trusted: bool,
+    hooks: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    fingerprint: Option<String>,
+    trust_file: String,
+}
+
+/// Record (or revoke) trust in the workspace's event hooks as they are now.
+fn trust(
+    args: &HooksTrustArgs,
+    beads_dir: &Path,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let trust_path = HookTrust::default_path()
+        .ok_or_else(|| BeadsError::Config("HOME is not set; cannot record hook trust".into()))?;
+    let storage_ctx = config::open_storage_with_cli(beads_dir, cli)?;
+    let layer = config::load_config(beads_dir, Some(&storage_ctx.storage), cli)?;
+    let runner = config::discover_hooks_from_layer(&layer, beads_dir);
+    let hooks: Vec<String> = runner
+        .as_ref()
+        .map(|runner| runner.installed().into_iter().map(str::to_string).collect())
+        .unwrap_or_default();
+
+    let mut store = HookTrust::load(&trust_path)?;
+    let fingerprint = if args.revoke {
+        store.revoke(beads_dir);
+        None
+    } else if let Some(runner) = &runner {
+        let fingerprint = runner.fingerprint();
+        store.trust(beads_dir, &fingerprint);
+        Some(fingerprint)
+    } else {
+        None
+    };
+    store.save(&trust_path)?;
+    info!(
+        revoke = args.revoke,
+        hooks = hooks.len(),
+        "Updated hook trust"
+    );
+
+    let report = TrustReport {
+        trusted: fingerprint.is_some(),
+        hooks,
+        fingerprint,
+        trust_file: trust_path.display().to_string(),
+    };
+    if ctx.is_json() {
+        ctx.json_pretty(&report);
+    } else if args.revoke {
+        ctx.success("Event hooks are no longer trusted on this machine");
+    } else if report.trusted {
+        ctx.success(&format!(
+            "Trusted event hooks: {} (changes to them need trusting again)",
+            report.hooks.join(", ")
+        ));
+    } else {
+        ctx.info("No event hooks found in this workspace");
+    }
+    Ok(())
+}
+
+fn install(
+    args: &HooksInstallArgs,
+    repo: &GitRepo,
+    jsonl_rel: Option<&str>,
+    union_rels: &[String],
+) -> Result<HooksReport> {
+    fs::create_dir_all(&repo.hooks_dir)?;
+
+    let mut hooks = Vec::new();
+    for hook in GitHook::ALL {
+        let path = repo.hooks_dir.join(hook.file_name());
+        let script = hook.script(jsonl_rel);
+        let action = match hook_state(&path, &script) {
+            HookState::Installed => "unchanged",
+            HookState::Missing | HookState::Outdated => {
+                write_hook(&path, &script)?;
+                "installed"
+            }
+            HookState::Foreign if args.force => {
+                fs::rename(&path, backup_path(&path))?;
+                write_hook(&path, &script)?;
+                "replaced"
+            }
+            HookState::Foreign => "skipped",
+        };
+        info!(hook = hook.file_name(), action, "Git hook");
+        hooks.push(HookReport {
+            name: hook.file_name(),
+            path: path.display().to_string(),
+            state: hook_state(&path, &script),
+            action: Some(action),
+        });
+    }
+
+    if !args.no_merge_driver {
+        repo.config_set(
+            &format!("merge.{MERGE_DRIVER_NAME}.name"),
+            "beads JSONL merge driver",
+        )?;
+        repo.config_set(
+            &format!("merge.{MERGE_DRIVER_NAME}.driver"),
+            MERGE_DRIVER_COMMAND,
+        )?;
+        if let Some(rel) = jsonl_rel {
+            add_attribute(&repo.root.join(".gitattributes"), &attribute_line(rel))?;
+        }
+        for rel in union_rels {
+            add_attribute(
+                &repo.root.join(".gitattributes"),
+                &union_attribute_line(rel),
+            )?;
+        }
+    }
+
+    Ok(HooksReport {
+        hooks_dir: repo.hooks_dir.display().to_string(),
+        hooks,
+        merge_driver: merge_driver_report(repo, jsonl_rel)?,
+    })
+}
+
+fn uninstall(
+    repo: &GitRepo,
+    jsonl_rel: Option<&str>,
+    union_rels: &[String],
+) -> Result<HooksReport> {
+    let mut hooks = Vec::new();
+    for hook in GitHook::ALL {
+        let path = repo.hooks_dir.join(hook.file_name());
+        let script = hook.script(jsonl_rel);
+        let action = match hook_state(&path, &script) {
+            HookState::Installed | HookState::Outdated => {
+                fs::remove_file(&path)?;
+                let backup = backup_path(&path);
+                if backup.is_file() {
+                    fs::rename(&backup, &path)?;
+                    "restored"
+                } else {
+                    "removed"
+                }
+            }
+            HookState::Missing => "unchanged",
+            HookState::Foreign => "skipped",
+        };
+        hooks.push(HookReport {
+            name: hook.file_name(),
+            path: path.display().to_string(),
+            state: hook_state(&path, &script),
+            action: Some(action),
+        });
+    }
+
+    if repo
+        .config_get(&format!("merge.{MERGE_DRIVER_NAME}.driver"))?
+        .is_some()
+    {
+        repo.git(&[
+            "config",
+            "--local",
+            "--remove-section",
+            &format!("merge.{MERGE_DRIVER_NAME}"),
+        ])?;
+    }
+    if let Some(rel) = jsonl_rel {
+        remove_attribute(&repo.root.join(".gitattributes"), &attribute_line(rel))?;
+    }
+    for rel in union_rels {
+        remove_attribute(
+            &repo.root.join(".gitattributes"),
+            &union_attribute_line(rel),
+        )?;
+    }
+
+    Ok(HooksReport {
+        hooks_dir: repo.hooks_dir.display().to_string(),
+        hooks,
+        merge_driver: merge_driver_report(repo, jsonl_rel)?,
+    })
+}
+
+fn status(repo: &GitRepo, jsonl_rel: Option<&str>) -> Result<HooksReport> {
+    let hooks = GitHook::ALL
+        .iter()
+        .map(|hook| {
+            let path = repo.hooks_dir.join(hook.file_name());
+            HookReport {
+                name: hook.file_name(),
+                state: hook_state(&path, &hook.script(jsonl_rel)),
+                path: path.display().to_string(),
+                action: None,
+            }
+        })
+        .collect();
+
+    Ok(HooksReport {
+        hooks_dir: repo.hooks_dir.display().to_string(),
+        hooks,
+        merge_driver: merge_driver_report(repo, jsonl_rel)?,
+    })
+}
+
+fn merge_driver_report(repo: &GitRepo, jsonl_rel: Option<&str>) -> Result<MergeDriverReport> {
+    let driver = repo.config_get(&format!("merge.{MERGE_DRIVER_NAME}.driver"))?;
+    let gitattributes = jsonl_rel.map(attribute_line).filter(|line| {
+        fs::read_to_string(repo.root.join(".gitattributes"))
+            .is_ok_and(|content| has_attribute(&content, line))
+    });
+    // A driver registered with another command line (e.g. before `%P` was
+    // passed) still works but is reported as outdated until reinstalled.
+    Ok(MergeDriverReport {
+        configured: driver.as_deref() == Some(MERGE_DRIVER_COMMAND) && gitattributes.is_some(),
+        driver,
+        gitattributes,
+    })
+}
+
+fn render_report(report: &HooksReport, ctx: &OutputContext) {
+    ctx.info(&format!("Git hooks ({}):", report.hooks_dir));
+    let mut skipped = false;
+    for hook in &report.hooks {
+        let state = match hook.state {
+            HookState::Missing => "not installed",
+            HookState::Installed => "installed",
+            HookState::Outdated => "outdated (run 'br hooks install')",
+            HookState::Foreign => "not managed by br",
+        };
+        let line = hook.action.map_or_else(
+            || format!("  {:<14} {state}", hook.name),
+            |action| format!("  {:<14} {state} ({action})", hook.name),
+        );
+        ctx.info(&line);
+        skipped |= hook.action == Some("skipped") && hook.state == HookState::Foreign;
+    }
+
+    let merge = &report.merge_driver;
+    if merge.configured {
+        ctx.info(&format!(
+            "Merge driver: configured ({})",
+            merge.gitattributes.as_deref().unwrap_or_default()
+        ));
+    } else if merge
+        .driver
+        .as_deref()
+        .is_some_and(|driver| driver != MERGE_DRIVER_COMMAND)
+    {
+        ctx.info("Merge driver: outdated (run 'br hooks install')");
+    } else {
+        ctx.info("Merge driver: not configured");
+    }
+
+    if skipped {
+        ctx.warning(
+            "Existing hooks were left in place; use --force to replace them (a backup is kept)",
+        );
+    }
+}
+
+/// A git repository located through the git CLI.
+#[derive(Debug)]
+struct GitRepo {
+    root: PathBuf,
+    hooks_dir: PathBuf,
+}
+
+impl GitRepo {
+    fn discover(start: &Path) -> Result<Self> {
+        let root = git_output(start, &["rev-parse", "--show-toplevel"]).map_err(|_| {
+            BeadsError::Config(format!(
+                "Not inside a git repository: {} (br hooks requires git)",
+                start.display()
+            ))
+        })?;
+        let root = PathBuf::from(root);
+        let root = root.canonicalize().unwrap_or(root);
+        let hooks = PathBuf::from(git_output(&root, &["rev-parse", "--git-path", "hooks"])?);
+        let hooks_dir = if hooks.is_absolute() {
+            hooks
+        } else {
+            root.join(hooks)
+        };
+        Ok(Self { root, hooks_dir })
+    }
+
+    /// Path relative to the repository root with `/` separators, if inside it.
+    fn relative_path(&self, path: &Path) -> Option<String> {
+        let file_name = path.file_name()?;
+        let parent = path.parent()?.canonicalize().ok()?;
+        let relative = parent.join(file_name);
+        let relative = relative.strip_prefix(&self.root).ok()?;
+        let parts: Vec<String> = relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy().into_owned())
+            .collect();
+        Some(parts.join("/"))
+    }
+
+    fn git(&self, args: &[&str]) -> Result<String> {
+        git_output(&self.root, args)
+    }
+
+    fn config_get(&self, key: &str) -> Result<Option<String>> {
+        let output = Command::new("git")
+            .args(["config", "--local", "--get", key])
+            .current_dir(&self.root)
+            .output()
+            .map_err(|e| BeadsError::Config(format!("Failed to run git: {e}")))?;
+        // Exit code 1 means the key is not set
+        if output.status.code() == Some(1) {
+            return Ok(None);
+        }
+        if !output.status.success() {
+            return Err(BeadsError::Config(format!(
+                "git config --get {key} failed: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            )));
+        }
+        Ok(Some(
+            String::from_utf8_lossy(&output.stdout).trim().to_string(),
+        ))
+    }
+
+    fn config_set(&self, key: &str, value: &str) -> Result<()> {
+        self.git(&["config", "--local", key, value]).map(|_| ())
+    }
+}
+
+fn git_output(dir: &Path, args: &[&str]) -> Result<String> {
+    let output = Command::new("git")
+        .args(args)
+        .current_dir(dir)
+        .output()
+        .map_err(|e| BeadsError::Config(format!("Failed to run git: {e}")))?;
+    if !output.status.success() {
+        return Err(BeadsError::Config(format!(
+            "git {} failed: {}",
+            args.join(" "),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn hook_state(path: &Path, expected: &str) -> HookState {
+    if !path.exists() {
+        return HookState::Missing;
+    }
+    match fs::read_to_string(path) {
+        Ok(content) if content == expected => HookState::Installed,
+        Ok(content) if content.contains(HOOK_MARKER) => HookState::Outdated,
+        _ => HookState::Foreign,
+    }
+}
+
+fn write_hook(path: &Path, script: &str) -> Result<()> {
+    fs::write(path, script)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(BACKUP_SUFFIX);
+    PathBuf::from(name)
+}
+
+fn attribute_line(pattern: &str) -> String {
+    format!("{pattern} merge={MERGE_DRIVER_NAME}")
+}
+
+/// Append-only logs that git's union merge combines correctly: shared events,
+/// and the audit log, whose verifier accepts the branches union merge leaves
+/// side by side (see [`super::audit`]).
+fn union_merge_paths(repo: &GitRepo, beads_dir: &Path) -> Vec<String> {
+    [EVENTS_JSONL_FILE, INTERACTIONS_JSONL_FILE]
+        .iter()
+        .filter_map(|file| repo.relative_path(&beads_dir.join(file)))
+        .collect()
+}
+
+fn union_attribute_line(pattern: &str) -> String {
+    format!("{pattern} merge=union")
+}
+
+fn has_attribute(content: &str, line: &str) -> bool {
+    content.lines().any(|existing| existing.trim() == line)
+}
+
+/// Append `line` to `.gitattributes` unless already present.
+fn add_attribute(path: &Path, line: &str) -> Result<bool> {
+    let mut content = if path.exists() {
+        fs::read_to_string(path)?
+    } else {
+        String::new()
+    };
+    if has_attribute(&content, line) {
+        return Ok(false);
+    }
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(line);
+    content.push('\n');
+    fs::write(path, content)?;
+    Ok(true)
+}
+
+/// Remove `line` from `.gitattributes`, deleting the file if nothing else remains.
+fn remove_attribute(path: &Path, line: &str) -> Result<bool> {
+    if !path.exists() {
+        return Ok(false);
+    }
+    let content = fs::read_to_string(path)?;
+    if !has_attribute(&content, line) {
+        return Ok(false);
+    }
+    let kept: Vec<&str> = content
+        .lines()
+        .filter(|existing| existing.trim() != line)
+        .collect();
+    if kept.iter().all(|existing| existing.trim().is_empty()) {
+        fs::remove_file(path)?;
+    } else {
+        fs::write(path, format!("{}\n", kept.join("\n")))?;
+    }
+    Ok(true)
+}
+
+/// Single-quote a string for POSIX sh.
+fn shell_quote(value: &str) -> String {
+    format!("'{}'", value.replace('\'', "'\\''"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn git_available() -> bool {
+        Command::new("git")
+            .arg("--version")
+            .output()
+            .is_ok_and(|o| o.status.success())
+    }
+
+    #[test]
+    fn test_hook_scripts_are_marked() {
+        init_logging();
+        info!("test_hook_scripts_are_marked: starting");
+        for hook in GitHook::ALL {
+            let script = hook.script(Some(".beads/issues.jsonl"));
+            assert!(script.starts_with("#!/bin/sh\n"));
+            assert!(script.contains(HOOK_MARKER));
+        }
+        let pre_commit = GitHook::PreCommit.script(Some(".beads/issues.jsonl"));
+        assert!(pre_commit.contains("br sync --flush-only"));
+        assert!(pre_commit.contains("git add -- '.beads/issues.jsonl'"));
+        assert!(!GitHook::PreCommit.script(None).contains("git add"));
+        assert!(
+            GitHook::PostMerge
+                .script(None)
+                .contains("br sync --import-only")
+        );
+        info!("test_hook_scripts_are_marked: assertions passed");
+    }
+
+    #[test]
+    fn test_hook_state() {
+        init_logging();
+        info!("test_hook_state: starting");
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join("pre-commit");
+        let script = GitHook::PreCommit.script(None);
+        assert_eq!(hook_state(&path, &script), HookState::Missing);
+        fs::write(&path, &script).unwrap();
+        assert_eq!(hook_state(&path, &script), HookState::Installed);
+        fs::write(
+            &path,
+            format!("#!/bin/sh\n{HOOK_MARKER}: pre-commit\nold\n"),
+        )
+        .unwrap();
+        assert_eq!(hook_state(&path, &script), HookState::Outdated);
+        fs::write(&path, "#!/bin/sh\nnpm test\n").unwrap();
+        assert_eq!(hook_state(&path, &script), HookState::Foreign);
+        info!("test_hook_state: assertions passed");
+    }
+
+    #[test]
+    fn test_gitattributes_add_and_remove() {
+        init_logging();
+        info!("test_gitattributes_add_and_remove: starting");
+        let temp = TempDir::new().unwrap();
+        let path = temp.path().join(".gitattributes");
+        let line = attribute_line(".beads/issues.jsonl");
+
+        assert!(add_attribute(&path, &line).unwrap());
+        assert!(!add_attribute(&path, &line).unwrap());
+        assert!(remove_attribute(&path, &line).unwrap());
+        assert!(!path.exists());
+
+        fs::write(&path, "*.png binary").unwrap();
+        assert!(add_attribute(&path, &line).unwrap());
+        assert_eq!(
+            fs::read_to_string(&path).unwrap(),
+            format!("*.png binary\n{line}\n")
+        );
+        assert!(remove_attribute(&path, &line).unwrap());
+        assert_eq!(fs::read_to_string(&path).unwrap(), "*.png binary\n");
+        info!("test_gitattributes_add_and_remove: assertions passed");
+    }
+
+    #[test]
+    fn test_shell_quote() {
+        init_logging();
+        info!("test_shell_quote: starting");
+        assert_eq!(shell_quote("a b"), "'a b'");
+        assert_eq!(shell_quote("it's"), "'it'\\''s'");
+        info!("test_shell_quote: assertions passed");
+    }
+
+    #[test]
+    fn test_install_status_uninstall_roundtrip() {
+        init_logging();
+        info!("test_install_status_uninstall_roundtrip: starting");
+        if !git_available() {
+            info!("test_install_status_uninstall_roundtrip: git unavailable, skipping");
+            return;
+        }
+        let temp = TempDir::new().unwrap();
+        git_output(temp.path(), &["init", "-q"]).unwrap();
+        let beads_dir = temp.path().join(".beads");
+        fs::create_dir_all(&beads_dir).unwrap();
+
+        let repo = GitRepo::discover(temp.path()).unwrap();
+        let jsonl_rel = repo.relative_path(&beads_dir.join("issues.jsonl"));
+        assert_eq!(jsonl_rel.as_deref(), Some(".beads/issues.jsonl"));
+        let union_rels = union_merge_paths(&repo, &beads_dir);
+
+        // A foreign hook is left alone without --force
+        fs::create_dir_all(&repo.hooks_dir).unwrap();
+        let foreign = repo.hooks_dir.join("post-merge");
+        fs::write(&foreign, "#!/bin/sh\necho mine\n").unwrap();
+
+        let args = HooksInstallArgs::default();
+        let report = install(&args, &repo, jsonl_rel.as_deref(), &union_rels).unwrap();
+        assert_eq!(report.hooks[0].state, HookState::Installed);
+        assert_eq!(report.hooks[1].action, Some("skipped"));
+        assert!(report.merge_driver.configured);
+        let attributes = fs::read_to_string(temp.path().join(".gitattributes")).unwrap();
+        assert!(has_attribute(
+            &attributes,
+            ".beads/events.jsonl merge=union"
+        ));
+        assert!(has_attribute(
+            &attributes,
+            ".beads/interactions.jsonl merge=union"
+        ));
+
+        let args = HooksInstallArgs {
+            force: true,
+            ..Default::default()
+        };
+        let report = install(&args, &repo, jsonl_rel.as_deref(), &union_rels).unwrap();
+        assert_eq!(report.hooks[1].action, Some("replaced"));
+        assert!(backup_path(&foreign).is_file());
+
+        let report = status(&repo, jsonl_rel.as_deref()).unwrap();
+        assert!(
+            report
+                .hooks
+                .iter()
+                .all(|hook| hook.state == HookState::Installed)
+        );
+        assert!(report.merge_driver.configured);
+
+        // An older registration is outdated until install rewrites it
+        repo.config_set(
+            &format!("merge.{MERGE_DRIVER_NAME}.driver"),
+            "br merge-driver %O %A %B",
+        )
+        .unwrap();
+        assert!(
+            !status(&repo, jsonl_rel.as_deref())
+                .unwrap()
+                .merge_driver
+                .configured
+        );
+        let report = install(&args, &repo, jsonl_rel.as_deref(), &union_rels).unwrap();
+        assert_eq!(
+            report.merge_driver.driver.as_deref(),
+            Some(MERGE_DRIVER_COMMAND)
+        );
+        assert!(report.merge_driver.configured);
+
+        let report = uninstall(&repo, jsonl_rel.as_deref(), &union_rels).unwrap();
+        assert_eq!(report.hooks[1].action, Some("restored"));
+        assert_eq!(
+            fs::read_to_string(&foreign).unwrap(),
+            "#!/bin/sh\necho mine\n"
+        );
+        assert!(!report.merge_driver.configured);
+        assert!(report.merge_driver.driver.is_none());
+        assert!(!temp.path().join(".gitattributes").exists());
+        info!("test_install_status_uninstall_roundtrip: assertions passed");
+    }
+}
@@ -0,0 +1,1786 @@
+//! Import command implementation.
+//!
+//! Brings issues over from other trackers' offline exports. Each source is
+//! parsed into [`ImportRecord`]s keyed by `external_ref`; records that are not
+//! in the database yet are created, known ones are updated in place, so
+//! re-running an import only applies what changed upstream. References between
+//! records become dependencies once every record has an ID.
+
+use crate::cli::{CsvPreset, ImportCommands, ImportCsvArgs, ImportGithubArgs};
+use crate::config::{self, ConfigLayer};
+use crate::error::{BeadsError, Result};
+use crate::format::csv;
+use crate::model::{Comment, DependencyType, Issue, IssueType, Priority, Status};
+use crate::output::OutputContext;
+use crate::storage::{IssueUpdate, SqliteStorage};
+use crate::util::id::IdGenerator;
+use crate::validation::{IssueValidator, LabelValidator};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use regex::Regex;
+use serde::Serialize;
+use serde_json::Value;
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::LazyLock;
+use tracing::{debug, info};
+
+/// `source_system` recorded on issues created from GitHub exports.
+const GITHUB_SOURCE: &str = "github";
+
+/// Markdown task list item (`- [ ] ...` / `- [x] ...`).
+static TASK_ITEM: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"^\s*[-*+]\s+\[[ xX]\]\s").expect("valid task item regex"));
+
+/// `#123`, `owner/repo#123` or a github.com issue URL.
+static ISSUE_REF: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?:^|[^\w/#])(?:([\w.-]+/[\w.-]+))?#(\d+)\b|https?://github\.com/([\w.-]+/[\w.-]+)/issues/(\d+)",
+    )
+    .expect("valid issue reference regex")
+});
+
+/// Parsed contents of one export file.
+#[derive(Debug, Default)]
+struct ImportBatch {
+    /// Value stored in `source_system` on created issues.
+    source_system: String,
+    records: Vec<ImportRecord>,
+    /// Relationships between records, by `external_ref`.
+    links: Vec<ImportLink>,
+    /// Problems found while parsing, reported with the import.
+    warnings: Vec<String>,
+}
+
+/// One issue from a foreign tracker, before it is matched against the database.
+#[derive(Debug, Clone, Default)]
+struct ImportRecord {
+    external_ref: String,
+    title: String,
+    description: Option<String>,
+    status: Status,
+    close_reason: Option<String>,
+    /// `None` keeps the configured default (or the local value on update).
+    issue_type: Option<IssueType>,
+    priority: Option<Priority>,
+    assignee: Option<String>,
+    created_by: Option<String>,
+    labels: Vec<String>,
+    created_at: Option<DateTime<Utc>>,
+    updated_at: Option<DateTime<Utc>>,
+    closed_at: Option<DateTime<Utc>>,
+    due_at: Option<DateTime<Utc>>,
+    comments: Vec<ImportComment>,
+}
+
+#[derive(Debug, Clone)]
+struct ImportComment {
+    author: String,
+    body: String,
+    created_at: Option<DateTime<Utc>>,
+}
+
+/// A dependency between two records: `from` depends on `to`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct ImportLink {
+    from: String,
+    to: String,
+    dep_type: DependencyType,
+}
+
+struct ImportOptions {
+    id_gen: IdGenerator,
+    default_priority: Priority,
+    default_issue_type: IssueType,
+    actor: String,
+    dry_run: bool,
+}
+
+/// Result of an import run.
+#[derive(Debug, Default, Serialize)]
+struct ImportReport {
+    source_system: String,
+    dry_run: bool,
+    created: Vec<ImportedIssue>,
+    updated: Vec<ImportedIssue>,
+    unchanged: usize,
+    comments_added: usize,
+    dependencies_added: usize,
+    /// References to issues that are neither in the export nor the database.
+    unresolved_refs: Vec<String>,
+    warnings: Vec<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct ImportedIssue {
+    id: String,
+    external_ref: String,
+    title: String,
+}
+
+/// Execute the import command.
+///
+/// # Errors
+///
+/// Returns an error if the export cannot be read or parsed, or if the
+/// database cannot be opened or written.
+pub fn execute(
+    command: &ImportCommands,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    match command {
+        ImportCommands::Github(args) => execute_github(args, cli, ctx),
+        ImportCommands::Csv(args) => execute_csv(args, cli, ctx),
+    }
+}
+
+fn execute_github(
+    args: &ImportGithubArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let contents = fs::read_to_string(&args.file)?;
+    run_import(&args.file, args.dry_run, cli, ctx, |_| {
+        parse_github_export(&contents, args.repo.as_deref())
+    })
+}
+
+fn execute_csv(
+    args: &ImportCsvArgs,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let contents = fs::read_to_string(&args.file)?;
+    run_import(&args.file, args.dry_run, cli, ctx, |layer| {
+        let tables = ValueTables::from_layer(layer);
+        parse_csv_export(&contents, args.preset, &args.map, &tables)
+    })
+}
+
+/// Parse an export with `parse` (which sees the loaded config) and apply it.
+fn run_import<F>(
+    path: &Path,
+    dry_run: bool,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+    parse: F,
+) -> Result<()>
+where
+    F: FnOnce(&ConfigLayer) -> Result<ImportBatch>,
+{
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+
+    let batch = parse(&layer)?;
+    debug!(
+        source = %batch.source_system,
+        records = batch.records.len(),
+        links = batch.links.len(),
+        "Parsed export"
+    );
+
+    let options = ImportOptions {
+        id_gen: IdGenerator::new(config::id_config_from_layer(&layer)),
+        default_priority: config::default_priority_from_layer(&layer)?,
+        default_issue_type: config::default_issue_type_from_layer(&layer)?,
+        actor: config::resolve_actor(&layer),
+        dry_run,
+    };
+
+    let report = apply_batch(&mut storage_ctx.storage, &batch, &options)?;
+    info!(
+        source = %report.source_system,
+        created = report.created.len(),
+        updated = report.updated.len(),
+        unchanged = report.unchanged,
+        dry_run,
+        "Import finished"
+    );
+
+    if !dry_run {
+        storage_ctx.flush_no_db_if_dirty()?;
+    }
+    render_report(&report, path, ctx);
+    Ok(())
+}
+
+/// Create or update every record, then link them.
+fn apply_batch(
+    storage: &mut SqliteStorage,
+    batch: &ImportBatch,
+    options: &ImportOptions,
+) -> Result<ImportReport> {
+    let mut report = ImportReport {
+        source_system: batch.source_system.clone(),
+        dry_run: options.dry_run,
+        warnings: batch.warnings.clone(),
+        ..ImportReport::default()
+    };
+    // external_ref -> issue ID, for everything this batch touched
+    let mut ids: HashMap<String, String> = HashMap::new();
+
+    for record in &batch.records {
+        if ids.contains_key(&record.external_ref) {
+            report.warnings.push(format!(
+                "{}: listed more than once, later entries ignored",
+                record.external_ref
+            ));
+            continue;
+        }
+        let id = match storage.find_by_external_ref(&record.external_ref)? {
+            Some(existing) => update_record(storage, &existing, record, options, &mut report)?,
+            None => create_record(storage, record, batch, &ids, options, &mut report)?,
+        };
+        if let Some(id) = id {
+            ids.insert(record.external_ref.clone(), id);
+        }
+    }
+
+    link_records(storage, &batch.links, &ids, options, &mut report)?;
+    Ok(report)
+}
+
+fn create_record(
+    storage: &mut SqliteStorage,
+    record: &ImportRecord,
+    batch: &ImportBatch,
+    planned: &HashMap<String, String>,
+    options: &ImportOptions,
+    report: &mut ImportReport,
+) -> Result<Option<String>> {
+    let now = Utc::now();
+    let created_at = record.created_at.unwrap_or(now);
+    let id = options.id_gen.generate(
+        &record.title,
+        record.description.as_deref(),
+        record.created_by.as_deref(),
+        created_at,
+        storage.count_issues()?,
+        // Dry runs never insert, so also avoid IDs already handed out
+        |id| storage.id_exists(id).unwrap_or(false) || planned.values().any(|p| p == id),
+    );
+
+    let closed = record.status == Status::Closed;
+    let mut issue = Issue {
+        id: id.clone(),
+        title: record.title.clone(),
+        description: record.description.clone(),
+        status: record.status.clone(),
+        priority: record.priority.unwrap_or(options.default_priority),
+        issue_type: record
+            .issue_type
+            .clone()
+            .unwrap_or_else(|| options.default_issue_type.clone()),
+        assignee: record.assignee.clone(),
+        created_at,
+        created_by: record.created_by.clone(),
+        updated_at: record.updated_at.unwrap_or(created_at).max(created_at),
+        closed_at: closed.then(|| closed_at_of(record, now)),
+        close_reason: record.close_reason.clone().filter(|_| closed),
+        due_at: record.due_at,
+        external_ref: Some(record.external_ref.clone()),
+        source_system: Some(batch.source_system.clone()),
+        labels: valid_labels(record, &mut report.warnings),
+        comments: record
+            .comments
+            .iter()
+            .map(|comment| Comment {
+                id: 0,
+                issue_id: id.clone(),
+                author: comment.author.clone(),
+                body: comment.body.clone(),
+                created_at: comment.created_at.unwrap_or(now),
+            })
+            .collect(),
+        ..Issue::default()
+    };
+    issue.content_hash = Some(issue.compute_content_hash());
+
+    if let Err(err) = IssueValidator::validate(&issue).map_err(BeadsError::from_validation_errors) {
+        report
+            .warnings
+            .push(format!("{}: skipped, {err}", record.external_ref));
+        return Ok(None);
+    }
+    if !options.dry_run {
+        storage.create_issue(&issue, &options.actor)?;
+    }
+
+    report.comments_added += issue.comments.len();
+    report.created.push(ImportedIssue {
+        id: id.clone(),
+        external_ref: record.external_ref.clone(),
+        title: record.title.clone(),
+    });
+    Ok(Some(id))
+}
+
+/// Bring an existing issue in line with its record.
+///
+/// Labels and comments are only ever added, so local additions survive a
+/// re-import; comments already present (same author and text) are skipped.
+fn update_record(
+    storage: &mut SqliteStorage,
+    existing: &Issue,
+    record: &ImportRecord,
+    options: &ImportOptions,
+    report: &mut ImportReport,
+) -> Result<Option<String>> {
+    let id = existing.id.clone();
+    if existing.status == Status::Tombstone {
+        report.warnings.push(format!(
+            "{}: {id} was deleted locally, skipped",
+            record.external_ref
+        ));
+        return Ok(None);
+    }
+
+    let update = diff_update(existing, record, Utc::now());
+    let current_labels = storage.get_labels(&id)?;
+    let new_labels: Vec<String> = valid_labels(record, &mut report.warnings)
+        .into_iter()
+        .filter(|label| !current_labels.contains(label))
+        .collect();
+    let current_comments: HashSet<(String, String)> = storage
+        .get_comments(&id)?
+        .into_iter()
+        .map(|comment| (comment.author, comment.body))
+        .collect();
+    let new_comments: Vec<&ImportComment> = record
+        .comments
+        .iter()
+        .filter(|comment| {
+            !current_comments.contains(&(comment.author.clone(), comment.body.clone()))
+        })
+        .collect();
+
+    if update.is_empty() && new_labels.is_empty() && new_comments.is_empty() {
+        report.unchanged += 1;
+        return Ok(Some(id));
+    }
+
+    if !options.dry_run {
+        if !update.is_empty() {
+            storage.update_issue(&id, &update, &options.actor)?;
+        }
+        for label in &new_labels {
+            storage.add_label(&id, label, &options.actor)?;
+        }
+        for comment in &new_comments {
+            storage.add_comment(&id, &comment.author, &comment.body)?;
+        }
+    }
+
+    report.comments_added += new_comments.len();
+    report.updated.push(ImportedIssue {
+        id: id.clone(),
+        external_ref: record.external_ref.clone(),
+        title: record.title.clone(),
+    });
+    Ok(Some(id))
+}
+
+/// Fields of `existing` that differ from `record`.
+///
+/// A record that is merely open does not override a local in-progress,
+/// blocked or deferred status; type, priority and due date are only touched
+/// when the source provides them.
+fn diff_update(existing: &Issue, record: &ImportRecord, now: DateTime<Utc>) -> IssueUpdate {
+    let mut update = IssueUpdate::default();
+    if existing.title != record.title {
+        update.title = Some(record.title.clone());
+    }
+    if existing.description != record.description {
+        update.description = Some(record.description.clone());
+    }
+    if existing.assignee != record.assignee {
+        update.assignee = Some(record.assignee.clone());
+    }
+    if let Some(issue_type) = record
+        .issue_type
+        .as_ref()
+        .filter(|issue_type| **issue_type != existing.issue_type)
+    {
+        update.issue_type = Some(issue_type.clone());
+    }
+    if let Some(priority) = record
+        .priority
+        .filter(|priority| *priority != existing.priority)
+    {
+        update.priority = Some(priority);
+    }
+    if record.due_at.is_some() && record.due_at != existing.due_at {
+        update.due_at = Some(record.due_at);
+    }
+
+    let keeps_local = record.status == Status::Open && existing.status != Status::Closed;
+    if record.status != existing.status && !keeps_local {
+        let closed = record.status == Status::Closed;
+        update.status = Some(record.status.clone());
+        update.closed_at = Some(closed.then(|| closed_at_of(record, now)));
+        update.close_reason = Some(record.close_reason.clone().filter(|_| closed));
+    }
+    update
+}
+
+fn closed_at_of(record: &ImportRecord, now: DateTime<Utc>) -> DateTime<Utc> {
+    record.closed_at.or(record.updated_at).unwrap_or(now)
+}
+
+fn valid_labels(record: &ImportRecord, warnings: &mut Vec<String>) -> Vec<String> {
+    record
+        .labels
+        .iter()
+        .filter(|label| match LabelValidator::validate(label) {
+            Ok(()) => true,
+            Err(err) => {
+                warnings.push(format!(
+                    "{}: skipping label '{label}': {}",
+                    record.external_ref, err.message
+                ));
+                false
+            }
+        })
+        .cloned()
+        .collect()
+}
+
+/// Turn record links into dependencies.
+///
+/// Parent-child links go first so they win over a `related` link between the
+/// same pair. Pairs that are already linked in either direction are left
+/// alone, so a re-import adds nothing; links that would form a cycle become
+/// warnings.
+fn link_records(
+    storage: &mut SqliteStorage,
+    links: &[ImportLink],
+    ids: &HashMap<String, String>,
+    options: &ImportOptions,
+    report: &mut ImportReport,
+) -> Result<()> {
+    let mut ordered: Vec<&ImportLink> = links.iter().collect();
+    ordered.sort_by_key(|link| link.dep_type != DependencyType::ParentChild);
+    let mut linked: HashSet<(String, String)> = HashSet::new();
+
+    for link in ordered {
+        let Some(from) = resolve_ref(storage, ids, &link.from)? else {
+            continue;
+        };
+        let Some(to) = resolve_ref(storage, ids, &link.to)? else {
+            if !report.unresolved_refs.contains(&link.to) {
+                report.unresolved_refs.push(link.to.clone());
+            }
+            continue;
+        };
+
+        let pair = if from < to {
+            (from.clone(), to.clone())
+        } else {
+            (to.clone(), from.clone())
+        };
+        if from == to || !linked.insert(pair) {
+            continue;
+        }
+        if storage.dependency_exists_between(&from, &to)?
+            || storage.dependency_exists_between(&to, &from)?
+        {
+            continue;
+        }
+
+        let added = if options.dry_run {
+            Ok(true)
+        } else {
+            storage.add_dependency(&from, &to, link.dep_type.as_str(), &options.actor)
+        };
+        match added {
+            Ok(true) => report.dependencies_added += 1,
+            Ok(false) => {}
+            Err(err) => report
+                .warnings
+                .push(format!("{from} -> {to} ({}): {err}", link.dep_type)),
+        }
+    }
+    Ok(())
+}
+
+fn resolve_ref(
+    storage: &SqliteStorage,
+    ids: &HashMap<String, String>,
+    external_ref: &str,
+) -> Result<Option<String>> {
+    if let Some(id) = ids.get(external_ref) {
+        return Ok(Some(id.clone()));
+    }
+    Ok(storage
+        .find_by_external_ref(external_ref)?
+        .filter(|issue| issue.status != Status::Tombstone)
+        .map(|issue| issue.id))
+}
+
+fn render_report(report: &ImportReport, path: &Path, ctx: &OutputContext) {
+    if ctx.is_json() {
+        ctx.json_pretty(report);
+        return;
+    }
+
+    let total = report.created.len() + report.updated.len() + report.unchanged;
+    let verb = if report.dry_run {
+        "Would import"
+    } else {
+        "Imported"
+    };
+    ctx.success(&format!(
+        "{verb} {total} {} issue{} from {}: {} created, {} updated, {} unchanged",
+        report.source_system,
+        if total == 1 { "" } else { "s" },
+        path.display(),
+        report.created.len(),
+        report.updated.len(),
+        report.unchanged
+    ));
+    for issue in &report.created {
+        ctx.print(&format!(
+            "  + {}: {} ({})",
+            issue.id, issue.title, issue.external_ref
+        ));
+    }
+    for issue in &report.updated {
+        ctx.print(&format!(
+            "  ~ {}: {} ({})",
+            issue.id, issue.title, issue.external_ref
+        ));
+    }
+    if report.comments_added > 0 || report.dependencies_added > 0 {
+        ctx.info(&format!(
+            "{} comment(s) and {} dependency(ies) added",
+            report.comments_added, report.dependencies_added
+        ));
+    }
+    if !report.unresolved_refs.is_empty() {
+        ctx.warning(&format!(
+            "{} reference(s) point at issues outside this export: {}",
+            report.unresolved_refs.len(),
+            report.unresolved_refs.join(", ")
+        ));
+    }
+    for warning in &report.warnings {
+        ctx.warning(warning);
+    }
+}
+
+// ============================================================================
+// GitHub
+// ============================================================================
+
+/// Parse a GitHub issue export.
+///
+/// Accepts `gh issue list --json ...` output (camelCase fields) as well as
+/// REST API issue objects (snake_case), as a single array, several
+/// concatenated arrays (paginated dumps), one object per line, or a search
+/// result with `items`. Pull requests returned by the REST issues endpoint
+/// are skipped.
+fn parse_github_export(contents: &str, repo: Option<&str>) -> Result<ImportBatch> {
+    let mut batch = ImportBatch {
+        source_system: GITHUB_SOURCE.to_string(),
+        ..ImportBatch::default()
+    };
+
+    for value in serde_json::Deserializer::from_str(contents).into_iter::<Value>() {
+        let items = match value? {
+            Value::Array(items) => items,
+            Value::Object(mut map) => match map.remove("items") {
+                Some(Value::Array(items)) => items,
+                _ => vec![Value::Object(map)],
+            },
+            _ => {
+                return Err(BeadsError::validation(
+                    "file",
+                    "expected a JSON array or object of GitHub issues",
+                ));
+            }
+        };
+        for item in &items {
+            match github_record(item, repo) {
+                Ok(Some((record, links))) => {
+                    batch.records.push(record);
+                    batch.links.extend(links);
+                }
+                Ok(None) => {}
+                Err(reason) => batch.warnings.push(reason),
+            }
+        }
+    }
+    Ok(batch)
+}
+
+/// Map one GitHub issue onto a record plus the links found in its body.
+///
+/// Returns `Ok(None)` for pull requests and `Err` with a reason for entries
+/// that are not issues at all.
+fn github_record(
+    item: &Value,
+    repo_override: Option<&str>,
+) -> std::result::Result<Option<(ImportRecord, Vec<ImportLink>)>, String> {
+    let is_pull = item.get("pull_request").is_some()
+        || item.get("isPullRequest").and_then(Value::as_bool) == Some(true);
+    if is_pull {
+        return Ok(None);
+    }
+    let number = item
+        .get("number")
+        .and_then(Value::as_u64)
+        .ok_or_else(|| "entry without an issue number skipped".to_string())?;
+    let repo = repo_override
+        .map(str::to_string)
+        .or_else(|| github_repo_of(item));
+    let external_ref = github_ref(repo.as_deref(), number);
+
+    let closed = str_field(item, &["state"]).is_some_and(|s| s.eq_ignore_ascii_case("closed"));
+    let mut labels: Vec<String> = item
+        .get("labels")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|label| label.as_str().or_else(|| str_field(label, &["name"])))
+        .filter_map(label_slug)
+        .collect();
+    let issue_type = labels.iter().find_map(|label| github_issue_type(label));
+    if let Some(milestone) = item
+        .get("milestone")
+        .and_then(|milestone| str_field(milestone, &["title"]))
+        .and_then(|title| label_slug(&format!("milestone:{title}")))
+    {
+        labels.push(milestone);
+    }
+
+    // br has a single assignee; co-assignees are kept as labels
+    let mut assignees = item
+        .get("assignees")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(login);
+    let assignee = assignees
+        .next()
+        .or_else(|| item.get("assignee").and_then(login));
+    labels.extend(assignees.filter_map(|other| label_slug(&format!("assignee:{other}"))));
+    labels.sort();
+    labels.dedup();
+
+    let description = str_field(item, &["body"])
+        .map(normalize_text)
+        .filter(|body| !body.is_empty());
+    let links = github_links(
+        &external_ref,
+        repo.as_deref(),
+        description.as_deref().unwrap_or(""),
+    );
+
+    let record = ImportRecord {
+        title: str_field(item, &["title"]).unwrap_or("").trim().to_string(),
+        description,
+        status: if closed { Status::Closed } else { Status::Open },
+        close_reason: str_field(item, &["stateReason", "state_reason"])
+            .filter(|reason| closed && !reason.is_empty())
+            .map(str::to_lowercase),
+        issue_type,
+        priority: None,
+        assignee,
+        created_by: item
+            .get("author")
+            .or_else(|| item.get("user"))
+            .and_then(login),
+        labels,
+        created_at: date_field(item, &["createdAt", "created_at"]),
+        updated_at: date_field(item, &["updatedAt", "updated_at"]),
+        closed_at: date_field(item, &["closedAt", "closed_at"]),
+        due_at: None,
+        comments: github_comments(item),
+        external_ref,
+    };
+    Ok(Some((record, links)))
+}
+
+/// Comments embedded in the export (`gh --json comments`).
+///
+/// The REST API only reports a comment count here, which yields nothing.
+fn github_comments(item: &Value) -> Vec<ImportComment> {
+    item.get("comments")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|comment| {
+            let body = normalize_text(str_field(comment, &["body"])?);
+            if body.is_empty() {
+                return None;
+            }
+            let author = comment
+                .get("author")
+                .or_else(|| comment.get("user"))
+                .and_then(login)
+                .unwrap_or_else(|| "ghost".to_string());
+            Some(ImportComment {
+                author,
+                body,
+                created_at: date_field(comment, &["createdAt", "created_at"]),
+            })
+        })
+        .collect()
+}
+
+/// Dependencies implied by an issue body.
+///
+/// The first reference in a task list item (`- [ ] #12`) makes that issue a
+/// child of this one; any other reference becomes a `related` link. Fenced
+/// code blocks are ignored.
+fn github_links(this_ref: &str, repo: Option<&str>, body: &str) -> Vec<ImportLink> {
+    let mut links = Vec::new();
+    let mut in_code = false;
+
+    for line in body.lines() {
+        let trimmed = line.trim_start();
+        if trimmed.starts_with("```") || trimmed.starts_with("~~~") {
+            in_code = !in_code;
+            continue;
+        }
+        if in_code {
+            continue;
+        }
+
+        let mut task_item = TASK_ITEM.is_match(line);
+        for caps in ISSUE_REF.captures_iter(line) {
+            let target_repo = caps.get(1).or_else(|| caps.get(3)).map(|m| m.as_str());
+            let Some(number) = caps
+                .get(2)
+                .or_else(|| caps.get(4))
+                .and_then(|m| m.as_str().parse::<u64>().ok())
+            else {
+                continue;
+            };
+            let target = github_ref(target_repo.or(repo), number);
+            if target == this_ref {
+                continue;
+            }
+            let link = if task_item {
+                ImportLink {
+                    from: target,
+                    to: this_ref.to_string(),
+                    dep_type: DependencyType::ParentChild,
+                }
+            } else {
+                ImportLink {
+                    from: this_ref.to_string(),
+                    to: target,
+                    dep_type: DependencyType::Related,
+                }
+            };
+            task_item = false;
+            if !links.contains(&link) {
+                links.push(link);
+            }
+        }
+    }
+    links
+}
+
+/// `external_ref` for a GitHub issue: `gh-owner/repo#123`, or `gh-123` when
+/// the repository is unknown.
+fn github_ref(repo: Option<&str>, number: u64) -> String {
+    repo.map_or_else(
+        || format!("gh-{number}"),
+        |repo| format!("gh-{repo}#{number}"),
+    )
+}
+
+/// Repository (`owner/name`) an exported issue belongs to, from its URLs.
+fn github_repo_of(item: &Value) -> Option<String> {
+    ["repository_url", "html_url", "url"]
+        .iter()
+        .filter_map(|key| item.get(*key)?.as_str())
+        .find_map(repo_from_url)
+}
+
+fn repo_from_url(url: &str) -> Option<String> {
+    let (_, rest) = url
+        .split_once("/repos/")
+        .or_else(|| url.split_once("github.com/"))?;
+    let mut parts = rest.split('/').filter(|part| !part.is_empty());
+    let owner = parts.next()?;
+    let name = parts.next()?;
+    Some(format!("{owner}/{name}"))
+}
+
+fn github_issue_type(label: &str) -> Option<IssueType> {
+    match label {
+        "bug" => Some(IssueType::Bug),
+        "enhancement" | "feature" | "feature-request" => Some(IssueType::Feature),
+        "documentation" | "docs" => Some(IssueType::Docs),
+        "question" => Some(IssueType::Question),
+        "epic" => Some(IssueType::Epic),
+        "chore" => Some(IssueType::Chore),
+        _ => None,
+    }
+}
+
+// ============================================================================
+// CSV (Jira, Linear, generic)
+// ============================================================================
+
+/// The br field a CSV column is read into.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum CsvField {
+    ExternalRef,
+    /// Secondary identifier other rows may use to point at this one (Jira's
+    /// numeric `Issue id`).
+    SourceId,
+    Title,
+    Description,
+    Status,
+    Priority,
+    IssueType,
+    Assignee,
+    CreatedBy,
+    Labels,
+    CreatedAt,
+    UpdatedAt,
+    ClosedAt,
+    DueAt,
+    CloseReason,
+    Parent,
+    Comment,
+    Ignore,
+}
+
+impl CsvField {
+    /// Field names accepted by `--map`, also used as generic column names.
+    fn from_name(name: &str) -> Option<Self> {
+        let field = match name.trim().to_lowercase().replace('-', "_").as_str() {
+            "external_ref" | "id" | "key" => Self::ExternalRef,
+            "source_id" => Self::SourceId,
+            "title" | "summary" => Self::Title,
+            "description" | "body" => Self::Description,
+            "status" | "state" => Self::Status,
+            "priority" => Self::Priority,
+            "issue_type" | "type" => Self::IssueType,
+            "assignee" => Self::Assignee,
+            "created_by" | "reporter" | "creator" | "author" => Self::CreatedBy,
+            "labels" | "label" | "tags" => Self::Labels,
+            "created_at" | "created" => Self::CreatedAt,
+            "updated_at" | "updated" => Self::UpdatedAt,
+            "closed_at" | "closed" | "resolved" | "completed" => Self::ClosedAt,
+            "due_at" | "due" | "due_date" => Self::DueAt,
+            "close_reason" | "resolution" => Self::CloseReason,
+            "parent" | "epic" => Self::Parent,
+            "comment" | "comments" => Self::Comment,
+            "ignore" | "_" => Self::Ignore,
+            _ => return None,
+        };
+        Some(field)
+    }
+}
+
+/// Jira's CSV export columns (lowercased). Columns may repeat, e.g. one
+/// `Labels` and one `Comment` column per value.
+const JIRA_COLUMNS: &[(&str, CsvField)] = &[
+    ("issue key", CsvField::ExternalRef),
+    ("issue id", CsvField::SourceId),
+    ("summary", CsvField::Title),
+    ("description", CsvField::Description),
+    ("status", CsvField::Status),
+    ("priority", CsvField::Priority),
+    ("issue type", CsvField::IssueType),
+    ("assignee", CsvField::Assignee),
+    ("reporter", CsvField::CreatedBy),
+    ("creator", CsvField::Ignore),
+    ("labels", CsvField::Labels),
+    ("created", CsvField::CreatedAt),
+    ("updated", CsvField::UpdatedAt),
+    ("resolved", CsvField::ClosedAt),
+    ("due date", CsvField::DueAt),
+    ("resolution", CsvField::CloseReason),
+    ("comment", CsvField::Comment),
+    ("parent", CsvField::Parent),
+    ("parent id", CsvField::Parent),
+    ("custom field (epic link)", CsvField::Parent),
+    ("epic link", CsvField::Parent),
+];
+
+/// Linear's CSV export columns (lowercased).
+const LINEAR_COLUMNS: &[(&str, CsvField)] = &[
+    ("id", CsvField::ExternalRef),
+    ("title", CsvField::Title),
+    ("description", CsvField::Description),
+    ("status", CsvField::Status),
+    ("priority", CsvField::Priority),
+    ("assignee", CsvField::Assignee),
+    ("creator", CsvField::CreatedBy),
+    ("labels", CsvField::Labels),
+    ("created", CsvField::CreatedAt),
+    ("updated", CsvField::UpdatedAt),
+    ("completed", CsvField::ClosedAt),
+    ("canceled", CsvField::ClosedAt),
+    ("due date", CsvField::DueAt),
+    ("parent issue", CsvField::Parent),
+];
+
+/// Default status table; `import.status.<value>` in config overrides it.
+const STATUS_VALUES: &[(&str, &str)] = &[
+    ("to do", "open"),
+    ("todo", "open"),
+    ("new", "open"),
+    ("backlog", "open"),
+    ("triage", "open"),
+    ("reopened", "open"),
+    ("selected for development", "open"),
+    ("in progress", "in_progress"),
+    ("in development", "in_progress"),
+    ("in review", "in_progress"),
+    ("code review", "in_progress"),
+    ("on hold", "deferred"),
+    ("done", "closed"),
+    ("resolved", "closed"),
+    ("completed", "closed"),
+    ("canceled", "closed"),
+    ("cancelled", "closed"),
+    ("won't do", "closed"),
+    ("duplicate", "closed"),
+];
+
+/// Default priority table; `import.priority.<value>` in config overrides it.
+/// An empty target means "use the configured default priority".
+const PRIORITY_VALUES: &[(&str, &str)] = &[
+    ("highest", "0"),
+    ("blocker", "0"),
+    ("critical", "0"),
+    ("urgent", "0"),
+    ("high", "1"),
+    ("major", "1"),
+    ("medium", "2"),
+    ("normal", "2"),
+    ("low", "3"),
+    ("minor", "3"),
+    ("lowest", "4"),
+    ("trivial", "4"),
+    ("no priority", ""),
+];
+
+/// Default type table; `import.type.<value>` in config overrides it.
+const TYPE_VALUES: &[(&str, &str)] = &[
+    ("defect", "bug"),
+    ("story", "feature"),
+    ("new feature", "feature"),
+    ("improvement", "feature"),
+    ("enhancement", "feature"),
+    ("sub-task", "task"),
+    ("subtask", "task"),
+    ("documentation", "docs"),
+];
+
+/// Value mappings for status, priority and type, keyed by lowercased
+/// source value.
+struct ValueTables {
+    status: HashMap<String, String>,
+    priority: HashMap<String, String>,
+    issue_type: HashMap<String, String>,
+}
+
+impl ValueTables {
+    fn from_layer(layer: &ConfigLayer) -> Self {
+        Self {
+            status: Self::table(STATUS_VALUES, layer, "status"),
+            priority: Self::table(PRIORITY_VALUES, layer, "priority"),
+            issue_type: Self::table(TYPE_VALUES, layer, "type"),
+        }
+    }
+
+    fn table(
+        defaults: &[(&str, &str)],
+        layer: &ConfigLayer,
+        name: &str,
+    ) -> HashMap<String, String> {
+        let mut map: HashMap<String, String> = defaults
+            .iter()
+            .map(|(from, to)| ((*from).to_string(), (*to).to_string()))
+            .collect();
+        map.extend(config::import_mapping_from_layer(layer, name));
+        map
+    }
+
+    /// Look `raw` up in `table`, falling back to the raw value itself.
+    fn lookup<'a>(table: &'a HashMap<String, String>, raw: &'a str) -> &'a str {
+        table.get(&raw.to_lowercase()).map_or(raw, String::as_str)
+    }
+
+    fn status(&self, raw: &str) -> std::result::Result<Status, String> {
+        Status::from_str(Self::lookup(&self.status, raw)).map_err(|_| {
+            format!("unknown status '{raw}' imported as open (map it with import.status.<value>)")
+        })
+    }
+
+    fn priority(&self, raw: &str) -> std::result::Result<Option<Priority>, String> {
+        match Self::lookup(&self.priority, raw) {
+            "" => Ok(None),
+            value => Priority::from_str(value).map(Some).map_err(|_| {
+                format!("unknown priority '{raw}' ignored (map it with import.priority.<value>)")
+            }),
+        }
+    }
+
+    fn issue_type(&self, raw: &str) -> std::result::Result<IssueType, String> {
+        match IssueType::from_str(Self::lookup(&self.issue_type, raw)) {
+            Ok(issue_type) if issue_type.is_standard() => Ok(issue_type),
+            _ => Err(format!(
+                "unknown type '{raw}' ignored (map it with import.type.<value>)"
+            )),
+        }
+    }
+}
+
+/// Parse a CSV export.
+///
+/// Columns are matched by header name, case-insensitively: first against
+/// `--map` overrides, then the preset's table, then br's own field names.
+/// Columns that match nothing are ignored. Parent references (sub-task
+/// parents, epic links) become parent-child links; they may name a row by
+/// its key or by its source ID.
+fn parse_csv_export(
+    contents: &str,
+    preset: CsvPreset,
+    overrides: &[String],
+    tables: &ValueTables,
+) -> Result<ImportBatch> {
+    let (source_system, ref_prefix, preset_columns) = match preset {
+        CsvPreset::Jira => ("jira", "jira-", JIRA_COLUMNS),
+        CsvPreset::Linear => ("linear", "linear-", LINEAR_COLUMNS),
+        CsvPreset::Generic => ("csv", "", &[][..]),
+    };
+    let mut rows = csv::read_records(contents)?.into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| BeadsError::validation("file", "CSV file is empty"))?;
+    let columns = csv_columns(&header, preset_columns, overrides)?;
+    for (required, name) in [
+        (CsvField::ExternalRef, "external_ref"),
+        (CsvField::Title, "title"),
+    ] {
+        if !columns.contains(&required) {
+            return Err(BeadsError::validation(
+                "map",
+                format!("no column maps to {name}; add --map '<column>={name}'"),
+            ));
+        }
+    }
+
+    let mut batch = ImportBatch {
+        source_system: source_system.to_string(),
+        ..ImportBatch::default()
+    };
+    let mut aliases: HashMap<String, String> = HashMap::new();
+    let mut parents: Vec<(String, String)> = Vec::new();
+
+    // Header is line 1
+    for (line, row) in (2..).zip(rows) {
+        let cells = CsvRow {
+            columns: &columns,
+            row: &row,
+        };
+        let Some(key) = cells.first(CsvField::ExternalRef) else {
+            batch
+                .warnings
+                .push(format!("row {line}: no external reference, skipped"));
+            continue;
+        };
+        let external_ref = format!("{ref_prefix}{key}");
+        if let Some(source_id) = cells.first(CsvField::SourceId) {
+            aliases.insert(source_id.to_string(), external_ref.clone());
+        }
+        if let Some(parent) = cells.first(CsvField::Parent) {
+            parents.push((external_ref.clone(), parent.to_string()));
+        }
+        let record = csv_record(
+            &cells,
+            external_ref,
+            source_system,
+            tables,
+            &mut batch.warnings,
+        );
+        batch.records.push(record);
+    }
+
+    for (child, parent) in parents {
+        let to = aliases
+            .get(&parent)
+            .cloned()
+            .unwrap_or_else(|| format!("{ref_prefix}{parent}"));
+        batch.links.push(ImportLink {
+            from: child,
+            to,
+            dep_type: DependencyType::ParentChild,
+        });
+    }
+    Ok(batch)
+}
+
+/// Resolve each header to the field it feeds.
+fn csv_columns(
+    header: &[String],
+    preset_columns: &[(&str, CsvField)],
+    overrides: &[String],
+) -> Result<Vec<CsvField>> {
+    let mut mapped: HashMap<String, CsvField> = HashMap::new();
+    for spec in overrides {
+        let (column, field) = spec.rsplit_once('=').ok_or_else(|| {
+            BeadsError::validation("map", format!("'{spec}' is not COLUMN=FIELD"))
+        })?;
+        let column = column.trim().to_lowercase();
+        let field = CsvField::from_name(field)
+            .ok_or_else(|| BeadsError::validation("map", format!("unknown field '{field}'")))?;
+        if !header.iter().any(|h| h.trim().to_lowercase() == column) {
+            return Err(BeadsError::validation(
+                "map",
+                format!("no column named '{column}' in the CSV header"),
+            ));
+        }
+        mapped.insert(column, field);
+    }
+
+    Ok(header
+        .iter()
+        .map(|name| {
+            let name = name.trim().to_lowercase();
+            mapped
+                .get(&name)
+                .copied()
+                .or_else(|| {
+                    preset_columns
+                        .iter()
+                        .find(|(column, _)| *column == name)
+                        .map(|(_, field)| *field)
+                })
+                .or_else(|| CsvField::from_name(&name))
+                .unwrap_or(CsvField::Ignore)
+        })
+        .collect())
+}
+
+/// One CSV row viewed through the column mapping.
+struct CsvRow<'a> {
+    columns: &'a [CsvField],
+    row: &'a [String],
+}
+
+impl<'a> CsvRow<'a> {
+    /// Non-empty values of every column mapped to `field`.
+    fn all(&self, field: CsvField) -> impl Iterator<Item = &'a str> + '_ {
+        self.columns
+            .iter()
+            .zip(self.row.iter())
+            .filter(move |(column, _)| **column == field)
+            .map(|(_, value)| value.trim())
+            .filter(|value| !value.is_empty())
+    }
+
+    fn first(&self, field: CsvField) -> Option<&'a str> {
+        self.all(field).next()
+    }
+
+    fn date(
+        &self,
+        field: CsvField,
+        warnings: &mut Vec<String>,
+        external_ref: &str,
+    ) -> Option<DateTime<Utc>> {
+        let raw = self.first(field)?;
+        let parsed = csv_date(raw);
+        if parsed.is_none() {
+            warnings.push(format!("{external_ref}: unrecognized date '{raw}' ignored"));
+        }
+        parsed
+    }
+}
+
+fn csv_record(
+    cells: &CsvRow<'_>,
+    external_ref: String,
+    source_system: &str,
+    tables: &ValueTables,
+    warnings: &mut Vec<String>,
+) -> ImportRecord {
+    let mut note = |message: String| warnings.push(format!("{external_ref}: {message}"));
+
+    let raw_status = cells.first(CsvField::Status);
+    let status = match raw_status.map(|raw| tables.status(raw)) {
+        Some(Ok(status)) => status,
+        Some(Err(message)) => {
+            note(message);
+            Status::Open
+        }
+        None => Status::Open,
+    };
+    let priority = match cells
+        .first(CsvField::Priority)
+        .map(|raw| tables.priority(raw))
+    {
+        Some(Ok(priority)) => priority,
+        Some(Err(message)) => {
+            note(message);
+            None
+        }
+        None => None,
+    };
+    let issue_type = match cells
+        .first(CsvField::IssueType)
+        .map(|raw| tables.issue_type(raw))
+    {
+        Some(Ok(issue_type)) => Some(issue_type),
+        Some(Err(message)) => {
+            note(message);
+            None
+        }
+        None => None,
+    };
+
+    let closed = status == Status::Closed;
+    let close_reason = cells
+        .first(CsvField::CloseReason)
+        .or(raw_status)
+        .filter(|_| closed)
+        .map(str::to_lowercase);
+
+    let mut labels: Vec<String> = cells
+        .all(CsvField::Labels)
+        .flat_map(|cell| cell.split(','))
+        .filter_map(label_slug)
+        .collect();
+    labels.sort();
+    labels.dedup();
+
+    let comments = cells
+        .all(CsvField::Comment)
+        .map(|cell| csv_comment(cell, source_system))
+        .collect();
+
+    ImportRecord {
+        title: cells.first(CsvField::Title).unwrap_or("").to_string(),
+        description: cells.first(CsvField::Description).map(normalize_text),
+        status,
+        close_reason,
+        issue_type,
+        priority,
+        assignee: cells.first(CsvField::Assignee).map(str::to_string),
+        created_by: cells.first(CsvField::CreatedBy).map(str::to_string),
+        labels,
+        created_at: cells.date(CsvField::CreatedAt, warnings, &external_ref),
+        updated_at: cells.date(CsvField::UpdatedAt, warnings, &external_ref),
+        closed_at: cells.date(CsvField::ClosedAt, warnings, &external_ref),
+        due_at: cells.date(CsvField::DueAt, warnings, &external_ref),
+        comments,
+        external_ref,
+    }
+}
+
+/// A comment cell. Jira writes `<date>;<author>;<text>`; anything else is
+/// taken as plain text attributed to the source system.
+fn csv_comment(cell: &str, source_system: &str) -> ImportComment {
+    let mut parts = cell.splitn(3, ';');
+    let jira = match (parts.next(), parts.next(), parts.next()) {
+        (Some(date), Some(author), Some(body)) => {
+            csv_date(date).map(|created_at| (created_at, author, body))
+        }
+        _ => None,
+    };
+    jira.map_or_else(
+        || ImportComment {
+            author: source_system.to_string(),
+            body: normalize_text(cell),
+            created_at: None,
+        },
+        |(created_at, author, body)| ImportComment {
+            author: author.trim().to_string(),
+            body: normalize_text(body),
+            created_at: Some(created_at),
+        },
+    )
+}
+
+/// Parse the date formats trackers put in CSV exports. Times without a zone
+/// are taken as UTC.
+fn csv_date(raw: &str) -> Option<DateTime<Utc>> {
+    const DATE_TIMES: &[&str] = &[
+        "%Y-%m-%d %H:%M:%S",
+        "%Y-%m-%d %H:%M",
+        "%d/%b/%y %I:%M %p",
+        "%d/%b/%Y %I:%M %p",
+        "%m/%d/%Y %H:%M",
+    ];
+    const DATES: &[&str] = &["%Y-%m-%d", "%d/%b/%y", "%m/%d/%Y"];
+
+    let raw = raw.trim();
+    if let Ok(dt) = DateTime::parse_from_rfc3339(raw) {
+        return Some(dt.with_timezone(&Utc));
+    }
+    // JavaScript Date strings: `Tue Jan 02 2024 03:04:05 GMT+0000 (UTC)`
+    let js = raw.split(" (").next().unwrap_or(raw);
+    if let Ok(dt) = DateTime::parse_from_str(js, "%a %b %d %Y %H:%M:%S GMT%z") {
+        return Some(dt.with_timezone(&Utc));
+    }
+    DATE_TIMES
+        .iter()
+        .find_map(|format| NaiveDateTime::parse_from_str(raw, format).ok())
+        .or_else(|| {
+            DATES
+                .iter()
+                .find_map(|format| NaiveDate::parse_from_str(raw, format).ok())
+                .and_then(|date| date.and_hms_opt(0, 0, 0))
+        })
+        .map(|naive| naive.and_utc())
+}
+
+// ============================================================================
+// Helpers
+// ============================================================================
+
+fn str_field<'a>(value: &'a Value, keys: &[&str]) -> Option<&'a str> {
+    keys.iter().find_map(|key| value.get(*key)?.as_str())
+}
+
+fn date_field(value: &Value, keys: &[&str]) -> Option<DateTime<Utc>> {
+    str_field(value, keys)
+        .and_then(|raw| DateTime::parse_from_rfc3339(raw).ok())
+        .map(|dt| dt.with_timezone(&Utc))
+}
+
+fn login(user: &Value) -> Option<String> {
+    str_field(user, &["login"])
+        .filter(|login| !login.is_empty())
+        .map(str::to_string)
+}
+
+fn normalize_text(text: &str) -> String {
+    text.replace("\r\n", "\n").trim().to_string()
+}
+
+/// Squash a foreign label into br's label alphabet (`[a-z0-9_:-]`, at most
+/// 50 characters). Returns `None` when nothing usable is left.
+fn label_slug(name: &str) -> Option<String> {
+    let mut slug = String::new();
+    for c in name.trim().chars() {
+        if c.is_ascii_alphanumeric() || matches!(c, '_' | ':' | '-') {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with(['-', ':']) {
+            slug.push('-');
+        }
+    }
+    slug.truncate(50);
+    let slug = slug.trim_end_matches('-');
+    (!slug.is_empty()).then(|| slug.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::id::IdConfig;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn options(dry_run: bool) -> ImportOptions {
+        ImportOptions {
+            id_gen: IdGenerator::new(IdConfig::with_prefix("bd")),
+            default_priority: Priority::MEDIUM,
+            default_issue_type: IssueType::Task,
+            actor: "importer".to_string(),
+            dry_run,
+        }
+    }
+
+    const GH_EXPORT: &str = r#"[
+      {
+        "number": 1,
+        "title": "Crash on startup",
+        "body": "Seen since #2 landed.\r\n\r\n```\n#3 in a code block\n```",
+        "state": "CLOSED",
+        "stateReason": "COMPLETED",
+        "url": "https://github.com/acme/widgets/issues/1",
+        "author": {"login": "alice"},
+        "assignees": [{"login": "bob"}, {"login": "carol"}],
+        "labels": [{"name": "bug"}, {"name": "good first issue"}],
+        "milestone": {"title": "v1.0"},
+        "createdAt": "2024-01-02T03:04:05Z",
+        "closedAt": "2024-01-05T00:00:00Z",
+        "comments": [
+          {"author": {"login": "bob"}, "body": "Fixed in main", "createdAt": "2024-01-04T00:00:00Z"}
+        ]
+      },
+      {
+        "number": 2,
+        "title": "Tracking: release",
+        "body": "- [ ] #4\n- [x] acme/widgets#1",
+        "state": "OPEN",
+        "url": "https://github.com/acme/widgets/issues/2",
+        "labels": [],
+        "assignees": [],
+        "comments": []
+      }
+    ]"#;
+
+    #[test]
+    fn test_parse_gh_cli_export() {
+        init_logging();
+        info!("test_parse_gh_cli_export: starting");
+        let batch = parse_github_export(GH_EXPORT, None).unwrap();
+        assert_eq!(batch.records.len(), 2);
+
+        let first = &batch.records[0];
+        assert_eq!(first.external_ref, "gh-acme/widgets#1");
+        assert_eq!(first.status, Status::Closed);
+        assert_eq!(first.close_reason.as_deref(), Some("completed"));
+        assert_eq!(first.issue_type, Some(IssueType::Bug));
+        assert_eq!(first.assignee.as_deref(), Some("bob"));
+        assert_eq!(first.created_by.as_deref(), Some("alice"));
+        assert_eq!(
+            first.labels,
+            vec![
+                "assignee:carol",
+                "bug",
+                "good-first-issue",
+                "milestone:v1-0"
+            ]
+        );
+        assert_eq!(first.comments.len(), 1);
+        assert_eq!(first.comments[0].author, "bob");
+        assert!(!first.description.as_deref().unwrap().contains('\r'));
+
+        assert_eq!(
+            batch.links,
+            vec![
+                ImportLink {
+                    from: "gh-acme/widgets#1".to_string(),
+                    to: "gh-acme/widgets#2".to_string(),
+                    dep_type: DependencyType::Related,
+                },
+                ImportLink {
+                    from: "gh-acme/widgets#4".to_string(),
+                    to: "gh-acme/widgets#2".to_string(),
+                    dep_type: DependencyType::ParentChild,
+                },
+                ImportLink {
+                    from: "gh-acme/widgets#1".to_string(),
+                    to: "gh-acme/widgets#2".to_string(),
+                    dep_type: DependencyType::ParentChild,
+                },
+            ]
+        );
+        info!("test_parse_gh_cli_export: assertions passed");
+    }
+
+    #[test]
+    fn test_parse_rest_api_pages_skips_pull_requests() {
+        init_logging();
+        info!("test_parse_rest_api_pages_skips_pull_requests: starting");
+        let pages = r#"[{"number": 7, "title": "API issue", "state": "open",
+            "repository_url": "https://api.github.com/repos/acme/gears",
+            "user": {"login": "dave"}, "labels": ["help wanted"], "assignee": {"login": "erin"},
+            "created_at": "2024-03-01T00:00:00Z", "comments": 3}]
+            [{"number": 8, "title": "A PR", "state": "open", "pull_request": {}},
+             {"title": "no number"}]"#;
+        let batch = parse_github_export(pages, None).unwrap();
+        assert_eq!(batch.records.len(), 1);
+        assert_eq!(batch.warnings.len(), 1);
+
+        let record = &batch.records[0];
+        assert_eq!(record.external_ref, "gh-acme/gears#7");
+        assert_eq!(record.created_by.as_deref(), Some("dave"));
+        assert_eq!(record.assignee.as_deref(), Some("erin"));
+        assert_eq!(record.labels, vec!["help-wanted"]);
+        assert!(record.comments.is_empty());
+
+        let overridden = parse_github_export(pages, Some("acme/other")).unwrap();
+        assert_eq!(overridden.records[0].external_ref, "gh-acme/other#7");
+        info!("test_parse_rest_api_pages_skips_pull_requests: assertions passed");
+    }
+
+    #[test]
+    fn test_import_is_idempotent() {
+        init_logging();
+        info!("test_import_is_idempotent: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let batch = parse_github_export(GH_EXPORT, None).unwrap();
+
+        let first = apply_batch(&mut storage, &batch, &options(false)).unwrap();
+        assert_eq!(first.created.len(), 2);
+        assert_eq!(first.comments_added, 1);
+        assert_eq!(first.dependencies_added, 1);
+        assert_eq!(first.unresolved_refs, vec!["gh-acme/widgets#4"]);
+
+        let crash = storage
+            .find_by_external_ref("gh-acme/widgets#1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(crash.status, Status::Closed);
+        assert_eq!(crash.source_system.as_deref(), Some("github"));
+        assert_eq!(storage.get_comments(&crash.id).unwrap().len(), 1);
+
+        let second = apply_batch(&mut storage, &batch, &options(false)).unwrap();
+        assert!(second.created.is_empty());
+        assert!(second.updated.is_empty());
+        assert_eq!(second.unchanged, 2);
+        assert_eq!(second.comments_added, 0);
+        assert_eq!(second.dependencies_added, 0);
+        info!("test_import_is_idempotent: assertions passed");
+    }
+
+    #[test]
+    fn test_reimport_applies_upstream_changes() {
+        init_logging();
+        info!("test_reimport_applies_upstream_changes: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let batch = parse_github_export(GH_EXPORT, None).unwrap();
+        apply_batch(&mut storage, &batch, &options(false)).unwrap();
+
+        let tracking = storage
+            .find_by_external_ref("gh-acme/widgets#2")
+            .unwrap()
+            .unwrap();
+        let local = IssueUpdate {
+            status: Some(Status::InProgress),
+            ..IssueUpdate::default()
+        };
+        storage.update_issue(&tracking.id, &local, "me").unwrap();
+
+        let reopened = GH_EXPORT
+            .replace("\"CLOSED\"", "\"OPEN\"")
+            .replace("Crash on startup", "Crash on launch");
+        let batch = parse_github_export(&reopened, None).unwrap();
+        let report = apply_batch(&mut storage, &batch, &options(false)).unwrap();
+        assert_eq!(report.updated.len(), 1);
+        assert_eq!(report.unchanged, 1);
+
+        let crash = storage
+            .find_by_external_ref("gh-acme/widgets#1")
+            .unwrap()
+            .unwrap();
+        assert_eq!(crash.title, "Crash on launch");
+        assert_eq!(crash.status, Status::Open);
+        assert!(crash.closed_at.is_none());
+
+        // An open upstream issue keeps its local in-progress status
+        let tracking = storage.get_issue(&tracking.id).unwrap().unwrap();
+        assert_eq!(tracking.status, Status::InProgress);
+        info!("test_reimport_applies_upstream_changes: assertions passed");
+    }
+
+    #[test]
+    fn test_dry_run_writes_nothing() {
+        init_logging();
+        info!("test_dry_run_writes_nothing: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let batch = parse_github_export(GH_EXPORT, None).unwrap();
+        let report = apply_batch(&mut storage, &batch, &options(true)).unwrap();
+        assert_eq!(report.created.len(), 2);
+        assert_eq!(report.dependencies_added, 1);
+        assert_ne!(report.created[0].id, report.created[1].id);
+        assert_eq!(storage.count_issues().unwrap(), 0);
+        info!("test_dry_run_writes_nothing: assertions passed");
+    }
+
+    #[test]
+    fn test_label_slug() {
+        init_logging();
+        info!("test_label_slug: starting");
+        assert_eq!(
+            label_slug("Good First Issue").as_deref(),
+            Some("good-first-issue")
+        );
+        assert_eq!(label_slug("area: CLI").as_deref(), Some("area:cli"));
+        assert_eq!(label_slug("🐛 bug").as_deref(), Some("bug"));
+        assert_eq!(label_slug("!!!"), None);
+        assert_eq!(label_slug(&"x".repeat(80)).map(|s| s.len()), Some(50));
+        info!("test_label_slug: assertions passed");
+    }
+
+    const JIRA_CSV: &str = "\
+Summary,Issue key,Issue id,Issue Type,Status,Priority,Assignee,Reporter,Created,Resolved,Labels,Labels,Comment,Parent,Custom field (Epic Link)
+Checkout epic,SHOP-1,10001,Epic,In Progress,High,ann,bob,02/Jan/24 3:04 PM,,payments,,,,
+Card form,SHOP-2,10002,Story,Done,Highest,ann,bob,03/Jan/24 9:00 AM,05/Jan/24 10:00 AM,payments,ui,\"04/Jan/24 11:00 AM;bob;Looks good; ship it\",,SHOP-1
+Validate CVC,SHOP-3,10003,Sub-task,QA,Minor,,bob,2024-01-04 08:00,,,,,10002,
+";
+
+    fn default_tables() -> ValueTables {
+        ValueTables::from_layer(&ConfigLayer::default())
+    }
+
+    #[test]
+    fn test_parse_jira_csv() {
+        init_logging();
+        info!("test_parse_jira_csv: starting");
+        let batch = parse_csv_export(JIRA_CSV, CsvPreset::Jira, &[], &default_tables()).unwrap();
+        assert_eq!(batch.source_system, "jira");
+        assert_eq!(batch.records.len(), 3);
+
+        let epic = &batch.records[0];
+        assert_eq!(epic.external_ref, "jira-SHOP-1");
+        assert_eq!(epic.issue_type, Some(IssueType::Epic));
+        assert_eq!(epic.status, Status::InProgress);
+        assert_eq!(epic.priority, Some(Priority(1)));
+        assert_eq!(epic.created_by.as_deref(), Some("bob"));
+        assert_eq!(
+            epic.created_at.unwrap().to_rfc3339(),
+            "2024-01-02T15:04:00+00:00"
+        );
+
+        let story = &batch.records[1];
+        assert_eq!(story.issue_type, Some(IssueType::Feature));
+        assert_eq!(story.status, Status::Closed);
+        assert_eq!(story.close_reason.as_deref(), Some("done"));
+        assert!(story.closed_at.is_some());
+        assert_eq!(story.labels, vec!["payments", "ui"]);
+        assert_eq!(story.comments.len(), 1);
+        assert_eq!(story.comments[0].author, "bob");
+        assert_eq!(story.comments[0].body, "Looks good; ship it");
+
+        let subtask = &batch.records[2];
+        assert_eq!(subtask.issue_type, Some(IssueType::Task));
+        assert_eq!(subtask.status, Status::Open);
+        assert_eq!(subtask.priority, Some(Priority(3)));
+        assert_eq!(batch.warnings.len(), 1);
+        assert!(batch.warnings[0].contains("unknown status 'QA'"));
+
+        // Epic link by key, sub-task parent by numeric issue id
+        assert_eq!(
+            batch.links,
+            vec![
+                ImportLink {
+                    from: "jira-SHOP-2".to_string(),
+                    to: "jira-SHOP-1".to_string(),
+                    dep_type: DependencyType::ParentChild,
+                },
+                ImportLink {
+                    from: "jira-SHOP-3".to_string(),
+                    to: "jira-SHOP-2".to_string(),
+                    dep_type: DependencyType::ParentChild,
+                },
+            ]
+        );
+        info!("test_parse_jira_csv: assertions passed");
+    }
+
+    #[test]
+    fn test_parse_linear_csv_with_configured_status() {
+        init_logging();
+        info!("test_parse_linear_csv_with_configured_status: starting");
+        let csv = "\
+ID,Title,Description,Status,Priority,Labels,Created,Completed,Parent issue,Estimate
+ENG-1,Ship search,,Todo,No priority,\"Backend, Search\",Tue Jan 02 2024 03:04:05 GMT+0000 (Coordinated Universal Time),,,3
+ENG-2,Index docs,Build the index,QA,Urgent,,2024-01-03T00:00:00.000Z,,ENG-1,
+";
+        let mut layer = ConfigLayer::default();
+        layer
+            .runtime
+            .insert("import.status.QA".to_string(), "in_progress".to_string());
+        let tables = ValueTables::from_layer(&layer);
+        let batch = parse_csv_export(csv, CsvPreset::Linear, &[], &tables).unwrap();
+        assert!(batch.warnings.is_empty(), "{:?}", batch.warnings);
+
+        let search = &batch.records[0];
+        assert_eq!(search.external_ref, "linear-ENG-1");
+        assert_eq!(search.status, Status::Open);
+        assert_eq!(search.priority, None);
+        assert_eq!(search.description, None);
+        assert_eq!(search.labels, vec!["backend", "search"]);
+        assert_eq!(
+            search.created_at.unwrap().to_rfc3339(),
+            "2024-01-02T03:04:05+00:00"
+        );
+
+        let index = &batch.records[1];
+        assert_eq!(index.status, Status::InProgress);
+        assert_eq!(index.priority, Some(Priority(0)));
+        assert_eq!(batch.links[0].from, "linear-ENG-2");
+        assert_eq!(batch.links[0].to, "linear-ENG-1");
+        info!("test_parse_linear_csv_with_configured_status: assertions passed");
+    }
+
+    #[test]
+    fn test_csv_column_overrides() {
+        init_logging();
+        info!("test_csv_column_overrides: starting");
+        let csv = "Key,Name,Story Points\nOPS-7,Rotate keys,5\n";
+        let tables = default_tables();
+
+        let err = parse_csv_export(csv, CsvPreset::Generic, &[], &tables).unwrap_err();
+        assert!(err.to_string().contains("no column maps to title"));
+
+        let maps = vec!["name=title".to_string()];
+        let batch = parse_csv_export(csv, CsvPreset::Generic, &maps, &tables).unwrap();
+        assert_eq!(batch.source_system, "csv");
+        assert_eq!(batch.records[0].external_ref, "OPS-7");
+        assert_eq!(batch.records[0].title, "Rotate keys");
+
+        let unknown = vec!["Name=points".to_string()];
+        assert!(parse_csv_export(csv, CsvPreset::Generic, &unknown, &tables).is_err());
+        let missing = vec!["Owner=assignee".to_string()];
+        assert!(parse_csv_export(csv, CsvPreset::Generic, &missing, &tables).is_err());
+        info!("test_csv_column_overrides: assertions passed");
+    }
+
+    #[test]
+    fn test_csv_import_is_idempotent() {
+        init_logging();
+        info!("test_csv_import_is_idempotent: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let batch = parse_csv_export(JIRA_CSV, CsvPreset::Jira, &[], &default_tables()).unwrap();
+
+        let first = apply_batch(&mut storage, &batch, &options(false)).unwrap();
+        assert_eq!(first.created.len(), 3);
+        assert_eq!(first.dependencies_added, 2);
+        assert_eq!(first.comments_added, 1);
+
+        let story = storage
+            .find_by_external_ref("jira-SHOP-2")
+            .unwrap()
+            .unwrap();
+        let epic = storage
+            .find_by_external_ref("jira-SHOP-1")
+            .unwrap()
+            .unwrap();
+        assert!(
+            storage
+                .dependency_exists_between(&story.id, &epic.id)
+                .unwrap()
+        );
+
+        let second = apply_batch(&mut storage, &batch, &options(false)).unwrap();
+        assert!(second.created.is_empty());
+        assert!(second.updated.is_empty());
+        assert_eq!(second.unchanged, 3);
+        assert_eq!(second.dependencies_added, 0);
+        info!("test_csv_import_is_idempotent: assertions passed");
+    }
+
+    #[test]
+    fn test_csv_date_formats() {
+        init_logging();
+        info!("test_csv_date_formats: starting");
+        for raw in [
+            "2024-01-02T03:04:00Z",
+            "2024-01-02 03:04",
+            "02/Jan/24 3:04 AM",
+            "Tue Jan 02 2024 03:04:00 GMT+0000 (Coordinated Universal Time)",
+        ] {
+            assert_eq!(
+                csv_date(raw).map(|dt| dt.to_rfc3339()).as_deref(),
+                Some("2024-01-02T03:04:00+00:00"),
+                "{raw}"
+            );
+        }
+        assert!(csv_date("01/02/2024").is_some());
+        assert!(csv_date("next tuesday").is_none());
+        info!("test_csv_date_formats: assertions passed");
+    }
+}
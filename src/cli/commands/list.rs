@@ -7,13 +7,16 @@ use crate::cli::{ListArgs, OutputFormat, resolve_output_format};
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::format::csv;
+use crate::format::projection::{self, NdjsonWriter};
+use crate::format::template::Template;
 use crate::format::{IssueWithCounts, TextFormatOptions, format_issue_line_with, terminal_width};
-use crate::model::{IssueType, Priority, Status};
+use crate::model::{Issue, IssueType, Priority, Status};
 use crate::output::{IssueTable, IssueTableColumns, OutputContext, OutputMode};
-use crate::storage::{ListFilters, SqliteStorage};
+use crate::storage::{ListFilters, SqliteStorage, WhereClause};
 use chrono::Utc;
 use std::collections::HashSet;
 use std::io::IsTerminal;
+use std::ops::ControlFlow;
 
 /// Execute the list command.
 ///
@@ -44,74 +47,34 @@ pub fn execute(
         wrap: args.wrap,
     };
 
-    // Build filter from args
-    let mut filters = build_filters(args)?;
-    let client_filters = needs_client_filters(args);
-    let limit = if client_filters {
-        filters.limit.take()
-    } else {
-        None
-    };
-
-    // Validate sort key before query
-    validate_sort_key(args.sort.as_deref())?;
-
-    // Query issues
-    let issues = storage.list_issues(&filters)?;
-    let mut issues = if client_filters {
-        apply_client_filters(storage, issues, args)?
-    } else {
-        issues
-    };
-
-    if let Some(limit) = limit {
-        if limit > 0 && issues.len() > limit {
-            issues.truncate(limit);
-        }
-    }
-
     // Determine output format: --json flag overrides --format
     let output_format = resolve_output_format(args.format, outer_ctx.is_json(), false);
     let quiet = cli.quiet.unwrap_or(false);
     let ctx = OutputContext::from_output_format(output_format, quiet, !use_color);
+    if args.ndjson {
+        return stream_ndjson(storage, args);
+    }
+    let template = resolve_template(&config_layer, args.template.as_deref())?;
+
+    let issues = query_issues(storage, args)?;
     if matches!(ctx.mode(), OutputMode::Quiet) {
         return Ok(());
     }
+    if let Some(template) = template {
+        return template.print_rows(&with_counts(storage, issues)?);
+    }
 
     // Output
     match output_format {
         OutputFormat::Json | OutputFormat::Toon => {
-            // Fetch relations for all issues
-            let issue_ids: Vec<String> = issues.iter().map(|i| i.id.clone()).collect();
-            let mut labels_map = storage.get_labels_for_issues(&issue_ids)?;
-
-            // Use batch counting
-            let dependency_counts = storage.count_dependencies_for_issues(&issue_ids)?;
-            let dependent_counts = storage.count_dependents_for_issues(&issue_ids)?;
-
-            // Convert to IssueWithCounts
-            let issues_with_counts: Vec<IssueWithCounts> = issues
-                .into_iter()
-                .map(|mut issue| {
-                    if let Some(labels) = labels_map.remove(&issue.id) {
-                        issue.labels = labels;
-                    }
-
-                    let dependency_count = *dependency_counts.get(&issue.id).unwrap_or(&0);
-                    let dependent_count = *dependent_counts.get(&issue.id).unwrap_or(&0);
-
-                    IssueWithCounts {
-                        issue,
-                        dependency_count,
-                        dependent_count,
-                    }
-                })
-                .collect();
+            let issues_with_counts = with_counts(storage, issues)?;
+            let fields = projection::parse_fields(args.fields.as_deref());
+            let output = projection::project(&issues_with_counts, fields.as_deref())?;
 
             if matches!(output_format, OutputFormat::Toon) {
-                ctx.toon_with_stats(&issues_with_counts, args.stats);
+                ctx.toon_with_stats(&output, args.stats);
             } else {
-                ctx.json_pretty(&issues_with_counts);
+                ctx.json_pretty(&output);
             }
         }
         OutputFormat::Csv => {
@@ -165,6 +128,142 @@ pub fn execute(
     Ok(())
 }
 
+/// Run the list query for the given args, including client-side filters and limit.
+///
+/// Shared with other front-ends (e.g. `br serve`) so list semantics stay identical.
+///
+/// # Errors
+///
+/// Returns an error if filters are invalid or the database query fails.
+pub fn query_issues(storage: &SqliteStorage, args: &ListArgs) -> Result<Vec<Issue>> {
+    // Build filter from args
+    let mut filters = build_filters(args)?;
+    let client_filters = needs_client_filters(args);
+    let limit = if client_filters {
+        filters.limit.take()
+    } else {
+        None
+    };
+
+    // Validate sort key before query
+    validate_sort_key(args.sort.as_deref())?;
+
+    // Query issues
+    let issues = storage.list_issues(&filters)?;
+    let mut issues = if client_filters {
+        apply_client_filters(storage, issues, args)?
+    } else {
+        issues
+    };
+
+    if let Some(limit) = limit {
+        if limit > 0 && issues.len() > limit {
+            issues.truncate(limit);
+        }
+    }
+
+    Ok(issues)
+}
+
+/// Rows fetched per label/count lookup while streaming NDJSON.
+const NDJSON_BATCH: usize = 500;
+
+/// Stream `br list --ndjson` straight from the storage cursor.
+///
+/// Rows are buffered only up to [`NDJSON_BATCH`] at a time, so labels and
+/// counts are still fetched in bulk while memory stays bounded. Reading stops
+/// as soon as the limit is used up or the reader closes the pipe.
+fn stream_ndjson(storage: &SqliteStorage, args: &ListArgs) -> Result<()> {
+    let mut filters = build_filters(args)?;
+    validate_sort_key(args.sort.as_deref())?;
+    let client_filters = needs_client_filters(args);
+    let mut remaining = if client_filters {
+        filters.limit.take().filter(|limit| *limit > 0)
+    } else {
+        None
+    };
+
+    let mut writer = NdjsonWriter::new(projection::parse_fields(args.fields.as_deref()));
+    let mut batch = Vec::with_capacity(NDJSON_BATCH);
+    // Returns whether more rows are wanted.
+    let mut write_batch = |batch: Vec<Issue>, writer: &mut NdjsonWriter| -> Result<bool> {
+        if remaining == Some(0) || writer.is_closed() {
+            return Ok(false);
+        }
+        let mut issues = if client_filters {
+            apply_client_filters(storage, batch, args)?
+        } else {
+            batch
+        };
+        if let Some(left) = remaining.as_mut() {
+            issues.truncate(*left);
+            *left -= issues.len();
+        }
+        for issue in with_counts(storage, issues)? {
+            writer.write(&issue)?;
+        }
+        Ok(remaining != Some(0) && !writer.is_closed())
+    };
+
+    storage.for_each_issue(&filters, |issue| {
+        batch.push(issue);
+        if batch.len() >= NDJSON_BATCH && !write_batch(std::mem::take(&mut batch), &mut writer)? {
+            return Ok(ControlFlow::Break(()));
+        }
+        Ok(ControlFlow::Continue(()))
+    })?;
+    write_batch(batch, &mut writer)?;
+    writer.finish()
+}
+
+/// Attach labels and dependency counts to issues for JSON/TOON output.
+///
+/// # Errors
+///
+/// Returns an error if the label or count queries fail.
+pub fn with_counts(storage: &SqliteStorage, issues: Vec<Issue>) -> Result<Vec<IssueWithCounts>> {
+    // Fetch relations for all issues
+    let issue_ids: Vec<String> = issues.iter().map(|i| i.id.clone()).collect();
+    let mut labels_map = storage.get_labels_for_issues(&issue_ids)?;
+
+    // Use batch counting
+    let dependency_counts = storage.count_dependencies_for_issues(&issue_ids)?;
+    let dependent_counts = storage.count_dependents_for_issues(&issue_ids)?;
+
+    Ok(issues
+        .into_iter()
+        .map(|mut issue| {
+            if let Some(labels) = labels_map.remove(&issue.id) {
+                issue.labels = labels;
+            }
+
+            let dependency_count = *dependency_counts.get(&issue.id).unwrap_or(&0);
+            let dependent_count = *dependent_counts.get(&issue.id).unwrap_or(&0);
+
+            IssueWithCounts {
+                issue,
+                dependency_count,
+                dependent_count,
+            }
+        })
+        .collect())
+}
+
+/// Resolve a `--template` value (inline or a `templates.<name>` config entry).
+///
+/// # Errors
+///
+/// Returns an error if the named template is not configured or fails to parse.
+pub fn resolve_template(
+    layer: &config::ConfigLayer,
+    spec: Option<&str>,
+) -> Result<Option<Template>> {
+    spec.map(|spec| {
+        config::output_template_from_layer(layer, spec).and_then(|source| Template::parse(&source))
+    })
+    .transpose()
+}
+
 /// Convert CLI args to storage filter.
 fn build_filters(args: &ListArgs) -> Result<ListFilters> {
     // Parse status strings to Status enums
@@ -203,7 +302,12 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
         )
     };
 
+    let where_clause = args.where_.as_deref().map(WhereClause::parse).transpose()?;
+
     let include_closed = args.all
+        || where_clause
+            .as_ref()
+            .is_some_and(WhereClause::constrains_status)
         || statuses
             .as_ref()
             .is_some_and(|parsed| parsed.iter().any(Status::is_terminal));
@@ -242,6 +346,8 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
         },
         updated_before: None,
         updated_after: None,
+        closed_after: None,
+        where_clause,
     })
 }
 
@@ -259,9 +365,9 @@ fn needs_client_filters(args: &ListArgs) -> bool {
 
 fn apply_client_filters(
     storage: &SqliteStorage,
-    issues: Vec<crate::model::Issue>,
+    issues: Vec<Issue>,
     args: &ListArgs,
-) -> Result<Vec<crate::model::Issue>> {
+) -> Result<Vec<Issue>> {
     let id_filter: Option<HashSet<&str>> = if args.id.is_empty() {
         None
     } else {
@@ -0,0 +1,475 @@
+//! Git merge driver for the JSONL export.
+//!
+//! Git invokes `br merge-driver %O %A %B %P` for files marked `merge=beads` in
+//! `.gitattributes` (see `br hooks install`). The three versions are parsed as
+//! issue records and merged with [`three_way_merge`]; the result is written
+//! back to the `%A` file sorted by ID, the same order `br sync --flush-only`
+//! produces. Only delete-vs-modify cases are treated as real conflicts: the
+//! modified record is kept, a `<path>.conflicts.json` report is written next to
+//! the merged file, and the command exits non-zero so git stops the merge.
+//!
+//! Records are compared on their full serialized form rather than
+//! `content_hash`, so label, dependency and comment edits count as changes.
+
+use crate::cli::MergeDriverArgs;
+use crate::error::{BeadsError, Result};
+use crate::model::Issue;
+use crate::output::OutputContext;
+use crate::sync::{
+    ConflictResolution, ConflictType, MergeContext, read_issues_from_jsonl, three_way_merge,
+};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use tracing::{debug, info};
+
+/// Suffix appended to the merged file path for the conflict report.
+const CONFLICT_REPORT_SUFFIX: &str = ".conflicts.json";
+
+/// A conflict the driver could not resolve on its own.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeConflict {
+    pub id: String,
+    pub kind: &'static str,
+    /// Which side's record was written to the merged file.
+    pub resolution: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub base: Option<Issue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub ours: Option<Issue>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theirs: Option<Issue>,
+}
+
+/// A decision the driver made automatically that may deserve a second look.
+#[derive(Debug, Clone, Serialize)]
+pub struct MergeNote {
+    pub id: String,
+    pub note: String,
+}
+
+/// Result of merging the three JSONL versions.
+#[derive(Debug, Default)]
+pub struct MergeOutcome {
+    /// Merged records, sorted by ID.
+    pub issues: Vec<Issue>,
+    pub deleted: Vec<String>,
+    pub conflicts: Vec<MergeConflict>,
+    pub notes: Vec<MergeNote>,
+}
+
+/// Conflict report written next to the merged file.
+#[derive(Debug, Serialize)]
+struct ConflictReport<'a> {
+    path: String,
+    conflicts: &'a [MergeConflict],
+    notes: &'a [MergeNote],
+}
+
+/// Summary printed after a merge.
+#[derive(Debug, Serialize)]
+struct MergeDriverOutput<'a> {
+    merged: usize,
+    deleted: usize,
+    conflicts: &'a [MergeConflict],
+    notes: &'a [MergeNote],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    report_path: Option<String>,
+}
+
+/// Execute the merge-driver command.
+///
+/// # Errors
+///
+/// Returns an error if any of the three files cannot be parsed or the merged
+/// file cannot be written. Git treats the non-zero exit as a conflict and
+/// leaves the `%A` file untouched in that case.
+pub fn execute(args: &MergeDriverArgs, ctx: &OutputContext) -> Result<()> {
+    let outcome = merge_files(&args.base, &args.ours, &args.theirs)?;
+    write_jsonl(&args.ours, &outcome.issues)?;
+
+    let report_path = conflict_report_path(args.path.as_deref().unwrap_or(&args.ours));
+    let display_path = args
+        .path
+        .as_deref()
+        .unwrap_or(&args.ours)
+        .display()
+        .to_string();
+
+    let written_report = if outcome.conflicts.is_empty() {
+        if report_path.exists() {
+            debug!(path = %report_path.display(), "Removing stale conflict report");
+            fs::remove_file(&report_path)?;
+        }
+        None
+    } else {
+        let report = ConflictReport {
+            path: display_path.clone(),
+            conflicts: &outcome.conflicts,
+            notes: &outcome.notes,
+        };
+        let json = serde_json::to_string_pretty(&report)?;
+        fs::write(&report_path, format!("{json}\n"))?;
+        Some(report_path.display().to_string())
+    };
+
+    info!(
+        merged = outcome.issues.len(),
+        deleted = outcome.deleted.len(),
+        conflicts = outcome.conflicts.len(),
+        "Merge driver finished"
+    );
+
+    if ctx.is_json() {
+        ctx.json_pretty(&MergeDriverOutput {
+            merged: outcome.issues.len(),
+            deleted: outcome.deleted.len(),
+            conflicts: &outcome.conflicts,
+            notes: &outcome.notes,
+            report_path: written_report.clone(),
+        });
+    } else {
+        for note in &outcome.notes {
+            ctx.info(&format!("beads: {}: {}", note.id, note.note));
+        }
+        if let Some(report) = &written_report {
+            for conflict in &outcome.conflicts {
+                ctx.warning(&format!(
+                    "beads: conflict in {} ({}), {}",
+                    conflict.id, conflict.kind, conflict.resolution
+                ));
+            }
+            ctx.warning(&format!(
+                "beads: {} conflict(s) merging {display_path}; see {report}",
+                outcome.conflicts.len()
+            ));
+        } else {
+            ctx.info(&format!(
+                "beads: merged {display_path} ({} issues)",
+                outcome.issues.len()
+            ));
+        }
+    }
+
+    if written_report.is_some() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Merge the base, ours and theirs JSONL files.
+///
+/// A missing file is treated as empty (git passes an empty `%O` for add/add).
+///
+/// # Errors
+///
+/// Returns an error if a file exists but cannot be read or parsed.
+pub fn merge_files(base: &Path, ours: &Path, theirs: &Path) -> Result<MergeOutcome> {
+    Ok(merge_issues(
+        &load_side(base)?,
+        &load_side(ours)?,
+        &load_side(theirs)?,
+    ))
+}
+
+/// Merge three sets of issues keyed by ID.
+#[must_use]
+pub fn merge_issues(
+    base: &HashMap<String, Issue>,
+    ours: &HashMap<String, Issue>,
+    theirs: &HashMap<String, Issue>,
+) -> MergeOutcome {
+    let context = MergeContext::new(
+        fingerprinted(base),
+        fingerprinted(ours),
+        fingerprinted(theirs),
+    );
+    let report = three_way_merge(&context, ConflictResolution::Manual, None);
+
+    let mut outcome = MergeOutcome {
+        deleted: report.deleted,
+        notes: report
+            .notes
+            .into_iter()
+            .map(|(id, note)| MergeNote { id, note })
+            .collect(),
+        ..MergeOutcome::default()
+    };
+
+    // `three_way_merge` hands back the fingerprinted clones; map each back to
+    // the original record so the stored content_hash is preserved.
+    for kept in report.kept {
+        let from_ours = context
+            .left
+            .get(&kept.id)
+            .is_some_and(|issue| issue.content_hash == kept.content_hash);
+        let original = if from_ours {
+            ours.get(&kept.id)
+        } else {
+            theirs.get(&kept.id)
+        };
+        if let Some(issue) = original {
+            outcome.issues.push(issue.clone());
+        }
+    }
+
+    for (id, conflict) in report.conflicts {
+        let kept = ours.get(&id).or_else(|| theirs.get(&id));
+        let resolution = if ours.contains_key(&id) {
+            "kept ours"
+        } else {
+            "kept theirs"
+        };
+        if let Some(issue) = kept {
+            outcome.issues.push(issue.clone());
+        }
+        outcome.conflicts.push(MergeConflict {
+            kind: conflict_kind(conflict),
+            resolution,
+            base: base.get(&id).cloned(),
+            ours: ours.get(&id).cloned(),
+            theirs: theirs.get(&id).cloned(),
+            id,
+        });
+    }
+
+    outcome.issues.sort_by(|a, b| a.id.cmp(&b.id));
+    outcome.deleted.sort();
+    outcome.conflicts.sort_by(|a, b| a.id.cmp(&b.id));
+    outcome.notes.sort_by(|a, b| a.id.cmp(&b.id));
+    outcome
+}
+
+const fn conflict_kind(conflict: ConflictType) -> &'static str {
+    match conflict {
+        ConflictType::DeleteVsModify => "delete_vs_modify",
+        ConflictType::ConvergentCreation => "convergent_creation",
+    }
+}
+
+/// Path of the conflict report for a merged file.
+fn conflict_report_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_os_string();
+    name.push(CONFLICT_REPORT_SUFFIX);
+    PathBuf::from(name)
+}
+
+fn load_side(path: &Path) -> Result<HashMap<String, Issue>> {
+    if !path.exists() {
+        return Ok(HashMap::new());
+    }
+    let issues = read_issues_from_jsonl(path)
+        .map_err(|e| BeadsError::Config(format!("Failed to parse {}: {e}", path.display())))?;
+    Ok(issues
+        .into_iter()
+        .map(|mut issue| {
+            issue.labels.sort();
+            issue.labels.dedup();
+            (issue.id.clone(), issue)
+        })
+        .collect())
+}
+
+/// Clone issues with `content_hash` replaced by a hash of the whole record.
+///
+/// `merge_issue` detects changes by comparing `content_hash`, which only
+/// covers the issue's own fields. Hashing the serialized record makes label,
+/// dependency and comment edits visible to the merge as well.
+fn fingerprinted(issues: &HashMap<String, Issue>) -> HashMap<String, Issue> {
+    issues
+        .iter()
+        .map(|(id, issue)| {
+            let mut clone = issue.clone();
+            clone.content_hash = None;
+            let json = serde_json::to_string(&clone).unwrap_or_default();
+            clone.content_hash = Some(format!("{:x}", Sha256::digest(json.as_bytes())));
+            (id.clone(), clone)
+        })
+        .collect()
+}
+
+/// Write issues as JSONL, replacing `path` atomically.
+fn write_jsonl(path: &Path, issues: &[Issue]) -> Result<()> {
+    let mut temp_name = path.as_os_str().to_os_string();
+    temp_name.push(".tmp");
+    let temp_path = PathBuf::from(temp_name);
+
+    let mut writer = BufWriter::new(File::create(&temp_path)?);
+    for issue in issues {
+        let json = serde_json::to_string(issue)?;
+        writeln!(writer, "{json}")?;
+    }
+    writer.flush()?;
+    writer
+        .into_inner()
+        .map_err(|e| BeadsError::Io(e.into_error()))?
+        .sync_all()?;
+
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::{Duration, Utc};
+    use tempfile::TempDir;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn issue(id: &str, title: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: title.to_string(),
+            ..Issue::default()
+        }
+    }
+
+    fn keyed(issues: Vec<Issue>) -> HashMap<String, Issue> {
+        issues
+            .into_iter()
+            .map(|issue| (issue.id.clone(), issue))
+            .collect()
+    }
+
+    fn write_file(path: &Path, issues: &[Issue]) {
+        let lines: Vec<String> = issues
+            .iter()
+            .map(|issue| serde_json::to_string(issue).unwrap())
+            .collect();
+        fs::write(path, lines.join("\n")).unwrap();
+    }
+
+    #[test]
+    fn test_merge_takes_changes_from_both_sides() {
+        init_logging();
+        info!("test_merge_takes_changes_from_both_sides: starting");
+        let a = issue("bd-a", "A");
+        let b = issue("bd-b", "B");
+        let mut a_ours = a.clone();
+        a_ours.title = "A (ours)".to_string();
+        let mut b_theirs = b.clone();
+        b_theirs.labels = vec!["backend".to_string()];
+        let c_theirs = issue("bd-c", "C");
+
+        let outcome = merge_issues(
+            &keyed(vec![a.clone(), b.clone()]),
+            &keyed(vec![a_ours, b]),
+            &keyed(vec![a, b_theirs, c_theirs]),
+        );
+
+        assert!(outcome.conflicts.is_empty());
+        let ids: Vec<&str> = outcome.issues.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["bd-a", "bd-b", "bd-c"]);
+        assert_eq!(outcome.issues[0].title, "A (ours)");
+        assert_eq!(outcome.issues[1].labels, vec!["backend".to_string()]);
+        info!("test_merge_takes_changes_from_both_sides: assertions passed");
+    }
+
+    #[test]
+    fn test_merge_applies_unmodified_deletions() {
+        init_logging();
+        info!("test_merge_applies_unmodified_deletions: starting");
+        let a = issue("bd-a", "A");
+        let b = issue("bd-b", "B");
+
+        let outcome = merge_issues(
+            &keyed(vec![a.clone(), b.clone()]),
+            &keyed(vec![a.clone(), b]),
+            &keyed(vec![a]),
+        );
+
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.deleted, vec!["bd-b".to_string()]);
+        assert_eq!(outcome.issues.len(), 1);
+        info!("test_merge_applies_unmodified_deletions: assertions passed");
+    }
+
+    #[test]
+    fn test_delete_vs_modify_is_a_conflict() {
+        init_logging();
+        info!("test_delete_vs_modify_is_a_conflict: starting");
+        let base = issue("bd-a", "A");
+        let mut ours = base.clone();
+        ours.title = "A (edited)".to_string();
+        ours.updated_at = base.updated_at + Duration::minutes(5);
+
+        let outcome = merge_issues(&keyed(vec![base]), &keyed(vec![ours]), &HashMap::new());
+
+        assert_eq!(outcome.conflicts.len(), 1);
+        assert_eq!(outcome.conflicts[0].kind, "delete_vs_modify");
+        assert_eq!(outcome.conflicts[0].resolution, "kept ours");
+        assert_eq!(outcome.issues.len(), 1);
+        assert_eq!(outcome.issues[0].title, "A (edited)");
+        info!("test_delete_vs_modify_is_a_conflict: assertions passed");
+    }
+
+    #[test]
+    fn test_both_modified_keeps_newer_with_note() {
+        init_logging();
+        info!("test_both_modified_keeps_newer_with_note: starting");
+        let base = issue("bd-a", "A");
+        let mut ours = base.clone();
+        ours.title = "ours".to_string();
+        ours.updated_at = Utc::now();
+        let mut theirs = base.clone();
+        theirs.title = "theirs".to_string();
+        theirs.updated_at = ours.updated_at + Duration::minutes(1);
+
+        let outcome = merge_issues(&keyed(vec![base]), &keyed(vec![ours]), &keyed(vec![theirs]));
+
+        assert!(outcome.conflicts.is_empty());
+        assert_eq!(outcome.issues[0].title, "theirs");
+        assert_eq!(outcome.notes.len(), 1);
+        info!("test_both_modified_keeps_newer_with_note: assertions passed");
+    }
+
+    #[test]
+    fn test_merge_files_writes_sorted_output() {
+        init_logging();
+        info!("test_merge_files_writes_sorted_output: starting");
+        let temp = TempDir::new().unwrap();
+        let base_path = temp.path().join("base.jsonl");
+        let ours_path = temp.path().join("ours.jsonl");
+        let theirs_path = temp.path().join("theirs.jsonl");
+
+        fs::write(&base_path, "").unwrap();
+        write_file(&ours_path, &[issue("bd-z", "Z"), issue("bd-m", "M")]);
+        write_file(&theirs_path, &[issue("bd-a", "A")]);
+
+        let outcome = merge_files(&base_path, &ours_path, &theirs_path).unwrap();
+        write_jsonl(&ours_path, &outcome.issues).unwrap();
+
+        let merged = read_issues_from_jsonl(&ours_path).unwrap();
+        let ids: Vec<&str> = merged.iter().map(|i| i.id.as_str()).collect();
+        assert_eq!(ids, vec!["bd-a", "bd-m", "bd-z"]);
+        info!("test_merge_files_writes_sorted_output: assertions passed");
+    }
+
+    #[test]
+    fn test_merge_files_rejects_conflict_markers() {
+        init_logging();
+        info!("test_merge_files_rejects_conflict_markers: starting");
+        let temp = TempDir::new().unwrap();
+        let ours_path = temp.path().join("ours.jsonl");
+        fs::write(&ours_path, "<<<<<<< HEAD\n").unwrap();
+
+        let missing = temp.path().join("missing.jsonl");
+        let result = merge_files(&missing, &ours_path, &missing);
+        assert!(result.is_err());
+        info!("test_merge_files_rejects_conflict_markers: assertions passed");
+    }
+
+    #[test]
+    fn test_conflict_report_path() {
+        assert_eq!(
+            conflict_report_path(Path::new(".beads/issues.jsonl")),
+            PathBuf::from(".beads/issues.jsonl.conflicts.json")
+        );
+    }
+}
@@ -1,9 +1,11 @@
 pub mod agents;
 pub mod audit;
 pub mod blocked;
+pub mod board;
 pub mod changelog;
 pub mod close;
 pub mod comments;
+pub mod compact;
 pub mod completions;
 pub mod config;
 pub mod count;
@@ -12,27 +14,42 @@ pub mod defer;
 pub mod delete;
 pub mod dep;
 pub mod doctor;
+pub mod due;
+pub mod edit;
 pub mod epic;
+pub mod escalate;
+pub mod export;
+pub mod flow;
+pub mod gc;
+pub mod git;
 pub mod graph;
 pub mod history;
+pub mod hooks;
+pub mod import;
 pub mod info;
 pub mod init;
 pub mod label;
 pub mod lint;
 pub mod list;
+pub mod merge_driver;
 pub mod orphans;
 pub mod q;
 pub mod query;
 pub mod ready;
 pub mod reopen;
+pub mod scan;
 pub mod schema;
 pub mod search;
+pub mod serve;
 pub mod show;
+pub mod snapshot;
 pub mod stale;
 pub mod stats;
 pub mod sync;
+pub mod tui;
 pub mod update;
 pub mod version;
+pub mod watch;
 pub mod r#where;
 
 #[cfg(feature = "self_update")]
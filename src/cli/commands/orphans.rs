@@ -10,8 +10,7 @@ use crate::error::Result;
 use crate::model::{Issue, Status};
 use crate::output::{IssueTable, IssueTableColumns, OutputContext};
 use crate::storage::ListFilters;
-use crate::util::id::normalize_id;
-use regex::Regex;
+use crate::util::id::{issue_ref_regex, normalize_id};
 use rich_rust::prelude::*;
 use serde::Serialize;
 use std::collections::HashMap;
@@ -266,17 +265,7 @@ fn get_git_commit_refs(prefix: &str) -> Result<Vec<(String, String, String)>> {
 ///
 /// Looks for patterns like `(bd-abc123)` or `bd-abc123` in commit messages.
 fn parse_git_log<R: BufRead>(reader: R, prefix: &str) -> Result<Vec<(String, String, String)>> {
-    // Pattern matches prefix-id including hierarchical IDs like bd-abc.1
-    // We use word boundaries \b to avoid matching suffix/prefix (e.g. abd-123 or bd-123a)
-    // although matching bd-123a is technically valid if 123a is the hash.
-    // The previous regex forced parens: r"\(({}-[a-zA-Z0-9]+(?:\.[0-9]+)?)\)"
-    // Use (?i) for case-insensitive matching (user input in commits varies)
-    let pattern = format!(
-        r"(?i)\b({}-[a-z0-9]+(?:\.[0-9]+)?)\b",
-        regex::escape(prefix)
-    );
-    let re = Regex::new(&pattern)
-        .map_err(|e| crate::error::BeadsError::Config(format!("Invalid regex pattern: {e}")))?;
+    let re = issue_ref_regex(prefix)?;
 
     let mut results = Vec::new();
 
@@ -6,6 +6,7 @@ use crate::cli::{ListArgs, QueryCommands, QueryDeleteArgs, QueryRunArgs, QuerySa
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::output::{OutputContext, OutputMode};
+use crate::storage::WhereClause;
 use chrono::{DateTime, Utc};
 use rich_rust::prelude::*;
 use serde::{Deserialize, Serialize};
@@ -72,6 +73,8 @@ pub struct SavedFilters {
     pub deferred: bool,
     #[serde(default, skip_serializing_if = "is_false")]
     pub overdue: bool,
+    #[serde(default, rename = "where", skip_serializing_if = "Option::is_none")]
+    pub where_: Option<String>,
 }
 
 /// Helper for serde `skip_serializing_if` (requires reference signature).
@@ -102,6 +105,7 @@ impl From<&ListArgs> for SavedFilters {
             reverse: args.reverse,
             deferred: args.deferred,
             overdue: args.overdue,
+            where_: args.where_.clone(),
         }
     }
 }
@@ -130,6 +134,7 @@ impl SavedFilters {
             reverse: self.reverse,
             deferred: self.deferred,
             overdue: self.overdue,
+            where_: self.where_.clone(),
             // Output-related fields use defaults
             long: false,
             pretty: false,
@@ -137,6 +142,8 @@ impl SavedFilters {
             format: None,
             stats: false,
             fields: None,
+            ndjson: false,
+            template: None,
         }
     }
 
@@ -191,6 +198,11 @@ impl SavedFilters {
             reverse: cli.reverse || base.reverse,
             deferred: cli.deferred || base.deferred,
             overdue: cli.overdue || base.overdue,
+            // Expressions: both apply
+            where_: match (base.where_, &cli.where_) {
+                (Some(saved), Some(extra)) => Some(format!("({saved}) AND ({extra})")),
+                (saved, extra) => extra.clone().or(saved),
+            },
             // Output fields from CLI only
             long: cli.long,
             pretty: cli.pretty,
@@ -198,6 +210,8 @@ impl SavedFilters {
             format: cli.format,
             stats: cli.stats,
             fields: cli.fields.clone(),
+            ndjson: cli.ndjson,
+            template: cli.template.clone(),
         }
     }
 }
@@ -276,6 +290,11 @@ fn query_save(
         ));
     }
 
+    // Reject an expression that would fail every time the query runs
+    if let Some(expr) = &args.filters.where_ {
+        WhereClause::parse(expr)?;
+    }
+
     let saved_query = SavedQuery {
         name: name.to_string(),
         description: args.description.clone(),
@@ -845,9 +864,11 @@ mod tests {
             reverse: true,
             deferred: true,
             overdue: true,
+            where_: Some("label:ui OR priority<=1".to_string()),
         };
 
         let json = serde_json::to_string(&filters).unwrap();
+        assert!(json.contains("\"where\":"));
         let parsed: SavedFilters = serde_json::from_str(&json).unwrap();
 
         assert_eq!(parsed.status, filters.status);
@@ -869,6 +890,27 @@ mod tests {
         assert_eq!(parsed.reverse, filters.reverse);
         assert_eq!(parsed.deferred, filters.deferred);
         assert_eq!(parsed.overdue, filters.overdue);
+        assert_eq!(parsed.where_, filters.where_);
+    }
+
+    #[test]
+    fn test_merge_where_combines_saved_and_cli() {
+        let saved = SavedFilters {
+            where_: Some("status:open".to_string()),
+            ..Default::default()
+        };
+        let cli = ListArgs {
+            where_: Some("label:ui OR label:ux".to_string()),
+            ..Default::default()
+        };
+
+        let merged = saved.merge_with_cli(&cli);
+        assert_eq!(
+            merged.where_.as_deref(),
+            Some("(status:open) AND (label:ui OR label:ux)")
+        );
+        let merged = saved.merge_with_cli(&ListArgs::default());
+        assert_eq!(merged.where_.as_deref(), Some("status:open"));
     }
 
     #[test]
@@ -2,14 +2,18 @@
 //!
 //! Shows issues ready to work on: unblocked, not deferred, not pinned, not ephemeral.
 
+use crate::cli::commands::list::{resolve_template, with_counts};
 use crate::cli::{OutputFormat, ReadyArgs, SortPolicy, resolve_output_format_basic};
 use crate::config;
 use crate::error::Result;
+use crate::format::projection;
 use crate::format::{ReadyIssue, format_priority_badge, terminal_width, truncate_title};
-use crate::model::{IssueType, Priority};
+use crate::model::{Issue, IssueType, Priority};
 use crate::output::{IssueTable, IssueTableColumns, OutputContext, OutputMode};
-use crate::storage::{ReadyFilters, ReadySortPolicy};
+use crate::storage::{ReadyFilters, ReadySortPolicy, SqliteStorage};
+use std::collections::HashMap;
 use std::io::IsTerminal;
+use std::path::PathBuf;
 use std::str::FromStr;
 use tracing::{debug, info, trace};
 use unicode_width::UnicodeWidthStr;
@@ -41,44 +45,9 @@ pub fn execute(
     let output_format = resolve_output_format_basic(args.format, outer_ctx.is_json(), args.robot);
     let quiet = cli.quiet.unwrap_or(false);
     let ctx = OutputContext::from_output_format(output_format, quiet, !use_color);
+    let template = resolve_template(&config_layer, args.template.as_deref())?;
 
-    let filters = ReadyFilters {
-        assignee: args.assignee.clone(),
-        unassigned: args.unassigned,
-        labels_and: args.label.clone(),
-        labels_or: args.label_any.clone(),
-        types: parse_types(&args.type_)?,
-        priorities: parse_priorities(&args.priority)?,
-        include_deferred: args.include_deferred,
-        // Fetch all candidates to allow post-filtering of external blockers
-        limit: None,
-        parent: args.parent.clone(),
-        recursive: args.recursive,
-    };
-
-    let sort_policy = match args.sort {
-        SortPolicy::Hybrid => ReadySortPolicy::Hybrid,
-        SortPolicy::Priority => ReadySortPolicy::Priority,
-        SortPolicy::Oldest => ReadySortPolicy::Oldest,
-    };
-
-    info!("Fetching ready issues");
-    debug!(filters = ?filters, sort = ?sort_policy, "Applied ready filters");
-
-    // Get ready issues from storage (blocked cache only)
-    let mut ready_issues = storage.get_ready_issues(&filters, sort_policy)?;
-
-    let external_statuses =
-        storage.resolve_external_dependency_statuses(&external_db_paths, true)?;
-    let external_blockers = storage.external_blockers(&external_statuses)?;
-    if !external_blockers.is_empty() {
-        ready_issues.retain(|issue| !external_blockers.contains_key(&issue.id));
-    }
-
-    // Apply limit after external filtering
-    if args.limit > 0 && ready_issues.len() > args.limit {
-        ready_issues.truncate(args.limit);
-    }
+    let ready_issues = query_ready(storage, args, &external_db_paths)?;
 
     info!(count = ready_issues.len(), "Found ready issues");
     for issue in ready_issues.iter().take(5) {
@@ -86,17 +55,28 @@ pub fn execute(
     }
 
     // Output
+    let fields = projection::parse_fields(args.fields.as_deref());
+    if args.ndjson {
+        let ready_output: Vec<ReadyIssue> = ready_issues.iter().map(ReadyIssue::from).collect();
+        return projection::write_ndjson(&ready_output, fields);
+    }
     if matches!(ctx.mode(), OutputMode::Quiet) {
         return Ok(());
     }
+    if let Some(template) = template {
+        return template.print_rows(&with_counts(storage, ready_issues)?);
+    }
     match output_format {
         OutputFormat::Json => {
             let ready_output: Vec<ReadyIssue> = ready_issues.iter().map(ReadyIssue::from).collect();
-            ctx.json_pretty(&ready_output);
+            ctx.json_pretty(&projection::project(&ready_output, fields.as_deref())?);
         }
         OutputFormat::Toon => {
             let ready_output: Vec<ReadyIssue> = ready_issues.iter().map(ReadyIssue::from).collect();
-            ctx.toon_with_stats(&ready_output, args.stats);
+            ctx.toon_with_stats(
+                &projection::project(&ready_output, fields.as_deref())?,
+                args.stats,
+            );
         }
         OutputFormat::Text | OutputFormat::Csv => {
             if ready_issues.is_empty() {
@@ -142,9 +122,64 @@ pub fn execute(
     Ok(())
 }
 
+/// Fetch ready issues for the given args, excluding issues blocked by
+/// external project dependencies, and apply the limit.
+///
+/// Shared with other front-ends (e.g. `br serve`) so ready semantics stay identical.
+///
+/// # Errors
+///
+/// Returns an error if filters are invalid or the database query fails.
+pub fn query_ready(
+    storage: &SqliteStorage,
+    args: &ReadyArgs,
+    external_db_paths: &HashMap<String, PathBuf>,
+) -> Result<Vec<Issue>> {
+    let filters = ReadyFilters {
+        assignee: args.assignee.clone(),
+        unassigned: args.unassigned,
+        labels_and: args.label.clone(),
+        labels_or: args.label_any.clone(),
+        types: parse_types(&args.type_)?,
+        priorities: parse_priorities(&args.priority)?,
+        include_deferred: args.include_deferred,
+        // Fetch all candidates to allow post-filtering of external blockers
+        limit: None,
+        parent: args.parent.clone(),
+        recursive: args.recursive,
+    };
+
+    let sort_policy = match args.sort {
+        SortPolicy::Hybrid => ReadySortPolicy::Hybrid,
+        SortPolicy::Priority => ReadySortPolicy::Priority,
+        SortPolicy::Oldest => ReadySortPolicy::Oldest,
+        SortPolicy::Due => ReadySortPolicy::Due,
+    };
+
+    info!("Fetching ready issues");
+    debug!(filters = ?filters, sort = ?sort_policy, "Applied ready filters");
+
+    // Get ready issues from storage (blocked cache only)
+    let mut ready_issues = storage.get_ready_issues(&filters, sort_policy)?;
+
+    let external_statuses =
+        storage.resolve_external_dependency_statuses(external_db_paths, true)?;
+    let external_blockers = storage.external_blockers(&external_statuses)?;
+    if !external_blockers.is_empty() {
+        ready_issues.retain(|issue| !external_blockers.contains_key(&issue.id));
+    }
+
+    // Apply limit after external filtering
+    if args.limit > 0 && ready_issues.len() > args.limit {
+        ready_issues.truncate(args.limit);
+    }
+
+    Ok(ready_issues)
+}
+
 fn format_ready_line(
     index: usize,
-    issue: &crate::model::Issue,
+    issue: &Issue,
     use_color: bool,
     max_width: Option<usize>,
     wrap: bool,
@@ -7,6 +7,7 @@ use crate::model::Status;
 use crate::output::{OutputContext, OutputMode};
 use crate::storage::IssueUpdate;
 use crate::util::id::{IdResolver, ResolverConfig, find_matching_ids};
+use crate::util::wip::check_status_change;
 use rich_rust::prelude::*;
 use serde::Serialize;
 
@@ -56,6 +57,11 @@ pub fn execute(
 
     let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
     let actor = config::resolve_actor(&config_layer);
+    let wip_limits = if args.force {
+        Vec::new()
+    } else {
+        config::wip_limits_from_layer(&config_layer)
+    };
     let id_config = config::id_config_from_layer(&config_layer);
     let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
     let all_ids = storage_ctx.storage.get_all_ids()?;
@@ -122,7 +128,9 @@ pub fn execute(
         };
 
         // Apply update
-        storage.update_issue(id, &update, &actor)?;
+        storage.update_issue_guarded(id, &update, &actor, |reader| {
+            check_status_change(reader, &wip_limits, id, &Status::Open)
+        })?;
         tracing::info!(id = %id, reason = ?args.reason, "Issue reopened");
 
         // Add comment if reason provided
@@ -0,0 +1,698 @@
+//! Scan command implementation.
+//!
+//! Finds `TODO`/`FIXME`/`HACK` comments in the source tree and checks their
+//! issue references with the same ID matching `orphans` uses for commit
+//! messages. With `--create`, untracked comments are filed as issues. Each one
+//! gets a `scan:<fingerprint>` label and its `path:line` in the description, so
+//! later runs recognise the comment after it moves and notice when it is
+//! removed. The location is not stored in `external_ref`: that column is
+//! unique, and locations shift whenever lines are inserted above a comment.
+
+use crate::cli::commands::create::{CreateConfig, create_issue_impl};
+use crate::cli::{CreateArgs, ScanArgs};
+use crate::config;
+use crate::error::Result;
+use crate::model::{Issue, Status};
+use crate::output::OutputContext;
+use crate::storage::{IssueUpdate, SqliteStorage};
+use crate::util::id::{issue_ref_regex, normalize_id};
+use regex::{NoExpand, Regex};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::LazyLock;
+use tracing::debug;
+
+/// Label prefix carrying the fingerprint of the comment an issue was filed for.
+pub const FINGERPRINT_LABEL_PREFIX: &str = "scan:";
+
+/// Files larger than this are skipped.
+const MAX_FILE_BYTES: u64 = 1024 * 1024;
+
+/// Directories skipped when walking a tree outside git.
+const SKIP_DIRS: [&str; 3] = ["target", "node_modules", "vendor"];
+
+/// A marker directly after a comment leader: `// TODO: ...`, `# FIXME(bd-abc) ...`,
+/// `/* HACK ...`, `-- TODO`, `; TODO`, `<!-- TODO`, or a `*` continuation line.
+/// `#` and `--` only count at the start of a line or after whitespace, so
+/// `page#TODO` or `a--TODO` inside a word are not comments.
+static MARKER: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(
+        r"(?://+!?|(?:^|\s)#+|/\*+!?|<!--|(?:^|\s)--|;+|^\s*\*+)\s*(TODO|FIXME|HACK)\b(?:\(([^)]*)\))?:?\s*(.*)$",
+    )
+    .expect("valid marker regex")
+});
+
+/// Description line recording where a filed comment was last seen.
+static LOCATION_LINE: LazyLock<Regex> = LazyLock::new(|| {
+    Regex::new(r"(?m)^Found by `br scan` at `([^`]*)`:").expect("valid location regex")
+});
+
+/// A `TODO`/`FIXME`/`HACK` comment found in a source file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct TodoComment {
+    path: String,
+    line: usize,
+    kind: String,
+    text: String,
+    refs: Vec<String>,
+    fingerprint: String,
+}
+
+impl TodoComment {
+    fn location(&self) -> String {
+        format!("{}:{}", self.path, self.line)
+    }
+
+    fn finding(&self, issue_id: Option<String>) -> ScanFinding {
+        ScanFinding {
+            location: self.location(),
+            kind: self.kind.clone(),
+            text: self.text.clone(),
+            issue_id,
+        }
+    }
+}
+
+/// One reported comment or issue.
+#[derive(Debug, Clone, Serialize)]
+pub struct ScanFinding {
+    pub location: String,
+    pub kind: String,
+    pub text: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub issue_id: Option<String>,
+}
+
+/// Result of a scan run.
+#[derive(Debug, Default, Serialize)]
+pub struct ScanReport {
+    pub files_scanned: usize,
+    pub comments: usize,
+    /// Comments with an issue reference or a matching filed issue.
+    pub tracked: usize,
+    /// References to IDs that do not exist (or were deleted).
+    pub unknown_refs: Vec<ScanFinding>,
+    /// Comments still pointing at closed issues.
+    pub closed_refs: Vec<ScanFinding>,
+    /// Comments with no issue (filed when `--create` is given).
+    pub untracked: Vec<ScanFinding>,
+    pub created: Vec<ScanFinding>,
+    /// Filed comments that moved; `location` is the new place.
+    pub moved: Vec<ScanFinding>,
+    /// Open filed issues whose comment is gone; `location` is the last known place.
+    pub removed: Vec<ScanFinding>,
+    pub warnings: Vec<String>,
+}
+
+/// Execute the scan command.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened or the source tree cannot be listed.
+pub fn execute(args: &ScanArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let create_config = CreateConfig {
+        id_config: config::id_config_from_layer(&layer),
+        default_priority: config::default_priority_from_layer(&layer)?,
+        default_issue_type: config::default_issue_type_from_layer(&layer)?,
+        actor: config::resolve_actor(&layer),
+        wip_limits: config::wip_limits_from_layer(&layer),
+    };
+    let refs_re = issue_ref_regex(&create_config.id_config.prefix)?;
+
+    let roots = if args.paths.is_empty() {
+        vec![PathBuf::from(".")]
+    } else {
+        args.paths.clone()
+    };
+    let (base, files) = list_files(&roots)?;
+    debug!(files = files.len(), base = %base.display(), "Scanning source files");
+
+    let mut comments = Vec::new();
+    for file in &files {
+        if let Some(text) = read_source(&base.join(file)) {
+            comments.extend(scan_source(file, &text, &refs_re));
+        }
+    }
+
+    let mut report = ScanReport {
+        files_scanned: files.len(),
+        comments: comments.len(),
+        ..ScanReport::default()
+    };
+    let scanned: HashSet<&str> = files.iter().map(String::as_str).collect();
+    let storage = &mut storage_ctx.storage;
+    let untracked = classify(storage, &comments, &scanned, &base, &mut report)?;
+
+    if args.create {
+        file_untracked(storage, &untracked, &create_config, &mut report);
+        apply_moves(storage, &create_config.actor, &mut report)?;
+    }
+
+    render_report(&report, args.create, ctx);
+    storage_ctx.flush_no_db_if_dirty()?;
+    Ok(())
+}
+
+/// List the files under `roots`, honouring `.gitignore` inside a git work tree.
+///
+/// Returns the directory the listed paths are relative to, and the paths.
+fn list_files(roots: &[PathBuf]) -> Result<(PathBuf, Vec<String>)> {
+    if let Some(top) = git_toplevel() {
+        let output = Command::new("git")
+            .args([
+                "ls-files",
+                "-z",
+                "--cached",
+                "--others",
+                "--exclude-standard",
+                "--full-name",
+                "--",
+            ])
+            .args(roots)
+            .output()?;
+        if output.status.success() {
+            let mut files: Vec<String> = String::from_utf8_lossy(&output.stdout)
+                .split('\0')
+                .filter(|name| !name.is_empty() && !is_beads_path(name))
+                .map(str::to_string)
+                .collect();
+            files.sort();
+            files.dedup();
+            return Ok((top, files));
+        }
+    }
+
+    let mut files = Vec::new();
+    for root in roots {
+        walk(root, &mut files)?;
+    }
+    files.sort();
+    files.dedup();
+    Ok((PathBuf::new(), files))
+}
+
+/// Issue data is not source code, even when it is tracked.
+fn is_beads_path(path: &str) -> bool {
+    path.starts_with(".beads/") || path.contains("/.beads/")
+}
+
+fn git_toplevel() -> Option<PathBuf> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--show-toplevel"])
+        .output()
+        .ok()
+        .filter(|o| o.status.success())?;
+    let top = String::from_utf8_lossy(&output.stdout).trim().to_string();
+    (!top.is_empty()).then(|| PathBuf::from(top))
+}
+
+/// Walk a tree outside git, skipping hidden and build directories.
+fn walk(path: &Path, files: &mut Vec<String>) -> Result<()> {
+    if path.is_file() {
+        let name = path.to_string_lossy();
+        files.push(name.strip_prefix("./").unwrap_or(&name).to_string());
+        return Ok(());
+    }
+    if !path.is_dir() {
+        return Ok(());
+    }
+    for entry in fs::read_dir(path)? {
+        let entry = entry?;
+        let name = entry.file_name();
+        let name = name.to_string_lossy();
+        if entry.file_type()?.is_dir() && (name.starts_with('.') || SKIP_DIRS.contains(&&*name)) {
+            continue;
+        }
+        walk(&entry.path(), files)?;
+    }
+    Ok(())
+}
+
+/// Read a text file, skipping large, binary and non-UTF-8 files.
+fn read_source(path: &Path) -> Option<String> {
+    let metadata = fs::metadata(path).ok()?;
+    if !metadata.is_file() || metadata.len() > MAX_FILE_BYTES {
+        return None;
+    }
+    let bytes = fs::read(path).ok()?;
+    if bytes.iter().take(8192).any(|&b| b == 0) {
+        return None;
+    }
+    String::from_utf8(bytes).ok()
+}
+
+/// Find marker comments in one file.
+fn scan_source(path: &str, text: &str, refs_re: &Regex) -> Vec<TodoComment> {
+    let mut comments = Vec::new();
+    let mut occurrences: HashMap<String, usize> = HashMap::new();
+    let markdown = Path::new(path)
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("md") || ext.eq_ignore_ascii_case("markdown"));
+
+    for (index, line) in text.lines().enumerate() {
+        // `# TODO` in Markdown is a heading, not a comment
+        if markdown && line.trim_start().starts_with('#') {
+            continue;
+        }
+        let Some(caps) = MARKER.captures(line) else {
+            continue;
+        };
+        let kind = caps[1].to_string();
+        let tag = caps.get(2).map_or("", |m| m.as_str());
+        let body = caps.get(3).map_or("", |m| m.as_str());
+        let body = body
+            .trim_end()
+            .trim_end_matches("*/")
+            .trim_end_matches("-->")
+            .trim();
+
+        let mut refs: Vec<String> = Vec::new();
+        for cap in refs_re
+            .captures_iter(tag)
+            .chain(refs_re.captures_iter(body))
+        {
+            let id = normalize_id(&cap[1]);
+            if !refs.contains(&id) {
+                refs.push(id);
+            }
+        }
+
+        // Identical comments in one file are told apart by their order
+        let normalized = body.split_whitespace().collect::<Vec<_>>().join(" ");
+        let mut basis = format!("{path}\n{kind}\n{normalized}");
+        let seen = occurrences.entry(basis.clone()).or_insert(0);
+        if *seen > 0 {
+            basis.push_str(&format!("\n{seen}"));
+        }
+        *seen += 1;
+        let digest = format!("{:x}", Sha256::digest(basis.as_bytes()));
+
+        comments.push(TodoComment {
+            path: path.to_string(),
+            line: index + 1,
+            kind,
+            text: body.to_string(),
+            refs,
+            fingerprint: digest[..12].to_string(),
+        });
+    }
+    comments
+}
+
+/// Sort comments into the report; returns the untracked ones.
+fn classify<'a>(
+    storage: &SqliteStorage,
+    comments: &'a [TodoComment],
+    scanned: &HashSet<&str>,
+    base: &Path,
+    report: &mut ScanReport,
+) -> Result<Vec<&'a TodoComment>> {
+    let filed = load_filed_issues(storage)?;
+    let mut seen: HashSet<&str> = HashSet::new();
+    let mut untracked = Vec::new();
+
+    for comment in comments {
+        seen.insert(comment.fingerprint.as_str());
+        if let Some(issue) = filed.get(&comment.fingerprint) {
+            report.tracked += 1;
+            if issue.status == Status::Closed {
+                report
+                    .closed_refs
+                    .push(comment.finding(Some(issue.id.clone())));
+            } else if filed_location(issue) != Some(comment.location().as_str()) {
+                report.moved.push(comment.finding(Some(issue.id.clone())));
+            }
+            continue;
+        }
+        if comment.refs.is_empty() {
+            report.untracked.push(comment.finding(None));
+            untracked.push(comment);
+            continue;
+        }
+
+        report.tracked += 1;
+        for id in &comment.refs {
+            match storage.get_issue(id)? {
+                Some(issue) if issue.status == Status::Closed => {
+                    report.closed_refs.push(comment.finding(Some(id.clone())));
+                }
+                Some(issue) if issue.status != Status::Tombstone => {}
+                _ => report.unknown_refs.push(comment.finding(Some(id.clone()))),
+            }
+        }
+    }
+
+    // A filed comment is gone if its file was scanned, or no longer exists
+    let mut removed: Vec<&Issue> = filed
+        .iter()
+        .filter(|(fingerprint, issue)| {
+            !seen.contains(fingerprint.as_str()) && issue.status != Status::Closed
+        })
+        .map(|(_, issue)| issue)
+        .filter(|issue| {
+            let path = filed_location(issue)
+                .and_then(|location| location.rsplit_once(':'))
+                .map_or("", |(path, _)| path);
+            scanned.contains(path) || !base.join(path).exists()
+        })
+        .collect();
+    removed.sort_by(|a, b| a.id.cmp(&b.id));
+    report.removed = removed
+        .into_iter()
+        .map(|issue| ScanFinding {
+            location: filed_location(issue).unwrap_or_default().to_string(),
+            kind: issue.issue_type.as_str().to_string(),
+            text: issue.title.clone(),
+            issue_id: Some(issue.id.clone()),
+        })
+        .collect();
+
+    Ok(untracked)
+}
+
+/// Issues filed by earlier scans, keyed by fingerprint.
+fn load_filed_issues(storage: &SqliteStorage) -> Result<HashMap<String, Issue>> {
+    let mut filed = HashMap::new();
+    for (id, labels) in storage.get_all_labels()? {
+        let Some(fingerprint) = labels
+            .iter()
+            .find_map(|label| label.strip_prefix(FINGERPRINT_LABEL_PREFIX))
+        else {
+            continue;
+        };
+        if let Some(issue) = storage
+            .get_issue(&id)?
+            .filter(|issue| issue.status != Status::Tombstone)
+        {
+            filed.insert(fingerprint.to_string(), issue);
+        }
+    }
+    Ok(filed)
+}
+
+/// File an issue for each untracked comment.
+fn file_untracked(
+    storage: &mut SqliteStorage,
+    untracked: &[&TodoComment],
+    config: &CreateConfig,
+    report: &mut ScanReport,
+) {
+    for comment in untracked {
+        let location = comment.location();
+        let title = if comment.text.is_empty() {
+            format!("{} in {location}", comment.kind)
+        } else {
+            truncate_title(&comment.text)
+        };
+        let args = CreateArgs {
+            title: Some(title),
+            type_: (comment.kind == "FIXME").then(|| "bug".to_string()),
+            description: Some(format!(
+                "{}\n\n> {}: {}",
+                location_line(&location),
+                comment.kind,
+                comment.text
+            )),
+            labels: vec![format!("{FINGERPRINT_LABEL_PREFIX}{}", comment.fingerprint)],
+            ..CreateArgs::default()
+        };
+        match create_issue_impl(storage, &args, config) {
+            Ok(issue) => report.created.push(comment.finding(Some(issue.id))),
+            Err(err) => report
+                .warnings
+                .push(format!("{location}: could not create issue: {err}")),
+        }
+    }
+}
+
+/// Record the new location of each moved comment in its issue's description.
+fn apply_moves(storage: &mut SqliteStorage, actor: &str, report: &mut ScanReport) -> Result<()> {
+    for moved in &report.moved {
+        let Some(id) = &moved.issue_id else { continue };
+        let Some(issue) = storage.get_issue(id)? else {
+            continue;
+        };
+        let description = issue.description.as_deref().unwrap_or_default();
+        let line = location_line(&moved.location);
+        let description = if LOCATION_LINE.is_match(description) {
+            LOCATION_LINE
+                .replace(description, NoExpand(&line))
+                .into_owned()
+        } else {
+            format!("{line}\n\n{description}").trim_end().to_string()
+        };
+        let update = IssueUpdate {
+            description: Some(Some(description)),
+            ..IssueUpdate::default()
+        };
+        if let Err(err) = storage.update_issue(id, &update, actor) {
+            report
+                .warnings
+                .push(format!("{id}: could not move to {}: {err}", moved.location));
+        }
+    }
+    Ok(())
+}
+
+fn location_line(location: &str) -> String {
+    format!("Found by `br scan` at `{location}`:")
+}
+
+/// Where a filed comment was last seen, as recorded in its description.
+fn filed_location(issue: &Issue) -> Option<&str> {
+    LOCATION_LINE
+        .captures(issue.description.as_deref()?)
+        .and_then(|caps| caps.get(1))
+        .map(|m| m.as_str())
+}
+
+fn truncate_title(text: &str) -> String {
+    const MAX_CHARS: usize = 100;
+    if text.chars().count() <= MAX_CHARS {
+        return text.to_string();
+    }
+    let mut title: String = text.chars().take(MAX_CHARS - 3).collect();
+    title.push_str("...");
+    title
+}
+
+fn render_report(report: &ScanReport, create: bool, ctx: &OutputContext) {
+    if ctx.is_json() {
+        ctx.json_pretty(report);
+        return;
+    }
+
+    ctx.success(&format!(
+        "Scanned {} files: {} TODO/FIXME/HACK comments, {} tracked, {} untracked",
+        report.files_scanned,
+        report.comments,
+        report.tracked,
+        report.untracked.len()
+    ));
+
+    let list = |title: &str, findings: &[ScanFinding]| {
+        if findings.is_empty() {
+            return;
+        }
+        ctx.section(title);
+        for finding in findings {
+            let id = finding
+                .issue_id
+                .as_deref()
+                .map(|id| format!("{id} "))
+                .unwrap_or_default();
+            ctx.print(&format!(
+                "  {} {id}{}: {}",
+                finding.location, finding.kind, finding.text
+            ));
+        }
+    };
+    list("Unknown issue references", &report.unknown_refs);
+    list("Closed issues still referenced", &report.closed_refs);
+    if create {
+        list("Created", &report.created);
+        list("Moved", &report.moved);
+    } else {
+        list("Untracked (file with --create)", &report.untracked);
+        list("Moved (update with --create)", &report.moved);
+    }
+    list("Removed from code (consider closing)", &report.removed);
+
+    for warning in &report.warnings {
+        ctx.warning(warning);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Priority;
+    use crate::util::id::IdConfig;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn refs_re() -> Regex {
+        issue_ref_regex("bd").unwrap()
+    }
+
+    fn test_config() -> CreateConfig {
+        CreateConfig {
+            id_config: IdConfig::with_prefix("bd"),
+            default_priority: Priority::MEDIUM,
+            default_issue_type: crate::model::IssueType::Task,
+            actor: "tester".to_string(),
+            wip_limits: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scan_source_finds_markers() {
+        init_logging();
+        info!("test_scan_source_finds_markers: starting");
+        let source = r#"fn main() {
+    // TODO(bd-ABC): handle errors
+    let s = "TODO: not a comment";
+    # FIXME flaky on CI
+    /* HACK: works around bd-def.1 */
+    // todo lowercase is prose
+}
+"#;
+        let comments = scan_source("src/main.rs", source, &refs_re());
+        assert_eq!(comments.len(), 3);
+        assert_eq!(comments[0].kind, "TODO");
+        assert_eq!(comments[0].line, 2);
+        assert_eq!(comments[0].text, "handle errors");
+        assert_eq!(comments[0].refs, vec!["bd-abc"]);
+        assert_eq!(comments[1].kind, "FIXME");
+        assert!(comments[1].refs.is_empty());
+        assert_eq!(comments[2].text, "works around bd-def.1");
+        assert_eq!(comments[2].refs, vec!["bd-def.1"]);
+        info!("test_scan_source_finds_markers: assertions passed");
+    }
+
+    #[test]
+    fn test_scan_source_ignores_leaders_inside_words_and_markdown_headings() {
+        init_logging();
+        info!("test_scan_source_ignores_leaders_inside_words_and_markdown_headings: starting");
+        let source =
+            "let url = \"page#TODO\";\nrun a--TODO-list\nx = 1 # TODO: real\n-- FIXME: sql\n";
+        let comments = scan_source("src/main.py", source, &refs_re());
+        let texts: Vec<&str> = comments.iter().map(|c| c.text.as_str()).collect();
+        assert_eq!(texts, vec!["real", "sql"]);
+
+        let doc = "# TODO list\n\n## FIXME\n<!-- TODO: fill in -->\n";
+        let comments = scan_source("README.md", doc, &refs_re());
+        assert_eq!(comments.len(), 1);
+        assert_eq!(comments[0].text, "fill in");
+        assert_eq!(scan_source("notes.sh", doc, &refs_re()).len(), 3);
+        info!(
+            "test_scan_source_ignores_leaders_inside_words_and_markdown_headings: assertions passed"
+        );
+    }
+
+    #[test]
+    fn test_fingerprint_survives_moves() {
+        init_logging();
+        info!("test_fingerprint_survives_moves: starting");
+        let before = scan_source("a.rs", "// TODO: same\n// TODO: same\n", &refs_re());
+        let after = scan_source("a.rs", "\n\n// TODO:   same\n\n// TODO: same\n", &refs_re());
+        assert_ne!(before[0].fingerprint, before[1].fingerprint);
+        assert_eq!(before[0].fingerprint, after[0].fingerprint);
+        assert_eq!(before[1].fingerprint, after[1].fingerprint);
+        assert_ne!(before[0].line, after[0].line);
+        info!("test_fingerprint_survives_moves: assertions passed");
+    }
+
+    #[test]
+    fn test_classify_and_file_round_trip() {
+        init_logging();
+        info!("test_classify_and_file_round_trip: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let config = test_config();
+        let closed = Issue {
+            id: "bd-done".to_string(),
+            title: "Done".to_string(),
+            status: Status::Closed,
+            closed_at: Some(chrono::Utc::now()),
+            ..Issue::default()
+        };
+        storage.create_issue(&closed, "tester").unwrap();
+
+        let source = "// TODO(bd-done): still here\n// TODO(bd-nope): typo\n// FIXME: untracked\n";
+        let comments = scan_source("lib.rs", source, &refs_re());
+        let scanned: HashSet<&str> = HashSet::from(["lib.rs"]);
+        let base = Path::new("");
+
+        let mut report = ScanReport::default();
+        let untracked = classify(&storage, &comments, &scanned, base, &mut report).unwrap();
+        assert_eq!(report.closed_refs[0].issue_id.as_deref(), Some("bd-done"));
+        assert_eq!(report.unknown_refs[0].issue_id.as_deref(), Some("bd-nope"));
+        assert_eq!(untracked.len(), 1);
+
+        file_untracked(&mut storage, &untracked, &config, &mut report);
+        let id = report.created[0].issue_id.clone().unwrap();
+        let issue = storage.get_issue(&id).unwrap().unwrap();
+        assert_eq!(filed_location(&issue), Some("lib.rs:3"));
+        assert!(issue.external_ref.is_none());
+        assert_eq!(issue.title, "untracked");
+
+        // The same comment two lines lower is recognised as moved
+        let moved = scan_source("lib.rs", &format!("\n\n{source}"), &refs_re());
+        let mut report = ScanReport::default();
+        let untracked = classify(&storage, &moved, &scanned, base, &mut report).unwrap();
+        assert!(untracked.is_empty());
+        assert_eq!(report.moved[0].location, "lib.rs:5");
+
+        // Deleting it reports the issue as removed
+        let mut report = ScanReport::default();
+        classify(&storage, &moved[..2], &scanned, base, &mut report).unwrap();
+        assert_eq!(report.removed[0].issue_id.as_deref(), Some(id.as_str()));
+        info!("test_classify_and_file_round_trip: assertions passed");
+    }
+
+    #[test]
+    fn test_rescan_after_inserting_line_above_filed_todo() {
+        init_logging();
+        info!("test_rescan_after_inserting_line_above_filed_todo: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let config = test_config();
+        let scanned: HashSet<&str> = HashSet::from(["lib.rs"]);
+        let base = Path::new("");
+        let scan = |storage: &mut SqliteStorage, source: &str| {
+            let comments = scan_source("lib.rs", source, &refs_re());
+            let mut report = ScanReport::default();
+            let untracked = classify(storage, &comments, &scanned, base, &mut report).unwrap();
+            file_untracked(storage, &untracked, &config, &mut report);
+            apply_moves(storage, &config.actor, &mut report).unwrap();
+            report
+        };
+
+        let report = scan(&mut storage, "// TODO: first\n// FIXME: second\n");
+        assert_eq!(report.created.len(), 2);
+
+        // A new comment takes over `lib.rs:1` while the filed ones shift down
+        let source = "// HACK: inserted\n// TODO: first\n// FIXME: second\n";
+        let report = scan(&mut storage, source);
+        assert!(report.warnings.is_empty(), "{:?}", report.warnings);
+        assert_eq!(report.created.len(), 1);
+        assert_eq!(report.created[0].location, "lib.rs:1");
+        assert_eq!(report.moved.len(), 2);
+
+        let report = scan(&mut storage, source);
+        assert!(report.created.is_empty());
+        assert!(report.moved.is_empty());
+        assert!(report.untracked.is_empty());
+        assert_eq!(report.tracked, 3);
+        info!("test_rescan_after_inserting_line_above_filed_todo: assertions passed");
+    }
+}
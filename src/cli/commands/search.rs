@@ -2,15 +2,17 @@
 //!
 //! Classic bd-style LIKE search across title/description/id with list-like filters.
 
+use crate::cli::commands::list::resolve_template;
 use crate::cli::{ListArgs, OutputFormat, SearchArgs, resolve_output_format};
 use crate::config;
 use crate::error::{BeadsError, Result};
+use crate::format::projection;
 use crate::format::{
     IssueWithCounts, TextFormatOptions, csv, format_issue_line_with, terminal_width,
 };
 use crate::model::{IssueType, Priority, Status};
 use crate::output::{IssueTable, IssueTableColumns, OutputContext, OutputMode};
-use crate::storage::{ListFilters, SqliteStorage};
+use crate::storage::{ListFilters, SqliteStorage, WhereClause};
 use chrono::Utc;
 use regex::{Regex, RegexBuilder};
 use std::collections::{HashMap, HashSet};
@@ -54,6 +56,8 @@ pub fn execute(
         wrap: args.filters.wrap,
     };
 
+    let template = resolve_template(&config_layer, args.filters.template.as_deref())?;
+
     let mut filters = build_filters(&args.filters)?;
     let client_filters = needs_client_filters(&args.filters);
     let limit = if client_filters {
@@ -70,7 +74,9 @@ pub fn execute(
     };
 
     let output_format = resolve_output_format(args.filters.format, outer_ctx.is_json(), false);
-    let needs_counts = matches!(output_format, OutputFormat::Json | OutputFormat::Toon);
+    let needs_counts = args.filters.ndjson
+        || template.is_some()
+        || matches!(output_format, OutputFormat::Json | OutputFormat::Toon);
 
     // Batch count dependencies/dependents (JSON/TOON output only).
     let issue_ids: Vec<String> = issues.iter().map(|i| i.id.clone()).collect();
@@ -106,6 +112,26 @@ pub fn execute(
         }
     }
 
+    let fields = projection::parse_fields(args.filters.fields.as_deref());
+    if args.filters.ndjson {
+        return projection::write_ndjson(&issues_with_counts, fields);
+    }
+
+    if let Some(template) = template {
+        let ids: Vec<String> = issues_with_counts
+            .iter()
+            .map(|row| row.issue.id.clone())
+            .collect();
+        let mut labels = storage.get_labels_for_issues(&ids)?;
+        for row in &mut issues_with_counts {
+            row.issue.labels = labels.remove(&row.issue.id).unwrap_or_default();
+        }
+        if !cli.quiet.unwrap_or(false) {
+            template.print_rows(&issues_with_counts)?;
+        }
+        return Ok(());
+    }
+
     let quiet = cli.quiet.unwrap_or(false);
     let ctx = OutputContext::from_output_format(output_format, quiet, !use_color);
 
@@ -115,11 +141,17 @@ pub fn execute(
 
     match output_format {
         OutputFormat::Json => {
-            ctx.json_pretty(&issues_with_counts);
+            ctx.json_pretty(&projection::project(
+                &issues_with_counts,
+                fields.as_deref(),
+            )?);
             return Ok(());
         }
         OutputFormat::Toon => {
-            ctx.toon_with_stats(&issues_with_counts, args.filters.stats);
+            ctx.toon_with_stats(
+                &projection::project(&issues_with_counts, fields.as_deref())?,
+                args.filters.stats,
+            );
             return Ok(());
         }
         OutputFormat::Csv => {
@@ -292,7 +324,12 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
         Some(parsed)
     };
 
+    let where_clause = args.where_.as_deref().map(WhereClause::parse).transpose()?;
+
     let include_closed = args.all
+        || where_clause
+            .as_ref()
+            .is_some_and(WhereClause::constrains_status)
         || statuses
             .as_ref()
             .is_some_and(|parsed| parsed.iter().any(Status::is_terminal));
@@ -326,6 +363,8 @@ fn build_filters(args: &ListArgs) -> Result<ListFilters> {
         labels_or: None,
         updated_before: None,
         updated_after: None,
+        closed_after: None,
+        where_clause,
     })
 }
 
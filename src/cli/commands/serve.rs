@@ -0,0 +1,1843 @@
+//! Serve command implementation.
+//!
+//! Runs a small HTTP/1.1 server exposing a REST/JSON API on top of
+//! `SqliteStorage`. Read endpoints reuse the same query paths as `br list`,
+//! `br ready` and `br show`, so ready/blocked semantics are identical to the CLI.
+//!
+//! Each connection is read on its own thread and must deliver its request
+//! within [`REQUEST_DEADLINE`], so a slow client cannot hold up others.
+//! Requests are then handled one at a time against the shared storage.
+//!
+//! Routes:
+//! - `GET /issues` (list filters as query parameters)
+//! - `GET /issues/{id}` (honors `If-None-Match`)
+//! - `GET /ready`
+//! - `POST /issues`
+//! - `PATCH /issues/{id}` (honors `If-Match`)
+//! - `POST /issues/{id}/close` (honors `If-Match`)
+//! - `GET /events?since=<event-id>`
+//!
+//! ETags are a hash of the `GET /issues/{id}` body, so they change whenever
+//! anything in it does (labels, dependencies, comments, dates). Errors use
+//! the `error::structured` JSON envelope.
+//!
+//! There is no authentication, so requests a web page could forge are
+//! refused: the `Host` must be a loopback name (defeats DNS rebinding), an
+//! `Origin` header must be on the `--allow-origin` list, and `POST`/`PATCH`
+//! must send `Content-Type: application/json` (which browsers cannot send
+//! cross-origin without a preflight).
+
+use crate::cli::commands::create::{CreateConfig, create_issue_impl};
+use crate::cli::commands::{list, ready};
+use crate::cli::{CreateArgs, ListArgs, ReadyArgs, ServeArgs, SortPolicy};
+use crate::config;
+use crate::error::{BeadsError, ErrorCode, Result, StructuredError};
+use crate::format::ReadyIssue;
+use crate::model::{Issue, Status};
+use crate::output::OutputContext;
+use crate::storage::{IssueUpdate, SqliteStorage, TxReader};
+use crate::sync::{auto_flush, auto_import_if_stale};
+use crate::util::id::{IdResolver, ResolverConfig};
+use crate::util::time::parse_flexible_timestamp;
+use crate::util::wip::{WipCandidate, check_transition};
+use crate::validation::LabelValidator;
+use chrono::{DateTime, Utc};
+use clap::ValueEnum;
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Read, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream, ToSocketAddrs};
+use std::path::PathBuf;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+use tracing::{debug, info, warn};
+
+/// Maximum accepted request body size.
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+/// Maximum number of header lines per request.
+const MAX_HEADER_LINES: usize = 100;
+/// Total time a client has to send one request.
+const REQUEST_DEADLINE: Duration = Duration::from_secs(30);
+/// Connections handled at once; clients beyond this get a 503.
+const MAX_CONNECTIONS: usize = 64;
+/// Default page size for `GET /events`.
+const DEFAULT_EVENT_LIMIT: usize = 100;
+/// Default limit for `GET /ready` (matches `br ready`).
+const DEFAULT_READY_LIMIT: usize = 20;
+
+/// Execute the serve command.
+///
+/// Blocks until the process is terminated.
+///
+/// # Errors
+///
+/// Returns an error if the address is invalid, the database cannot be opened,
+/// or the listener cannot be bound.
+pub fn execute(args: &ServeArgs, cli: &config::CliOverrides, ctx: &OutputContext) -> Result<()> {
+    let addr = parse_listen_addr(&args.http, args.allow_remote)?;
+
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    if storage_ctx.no_db {
+        return Err(BeadsError::validation(
+            "no-db",
+            "br serve requires the SQLite database (remove --no-db)",
+        ));
+    }
+
+    let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+    let create_config = CreateConfig {
+        id_config: config::id_config_from_layer(&layer),
+        default_priority: config::default_priority_from_layer(&layer)?,
+        default_issue_type: config::default_issue_type_from_layer(&layer)?,
+        actor: config::resolve_actor(&layer),
+        wip_limits: config::wip_limits_from_layer(&layer),
+    };
+    let resolver = IdResolver::new(ResolverConfig::with_prefix(
+        create_config.id_config.prefix.clone(),
+    ));
+    let external_db_paths = config::external_project_db_paths(&layer, &beads_dir);
+    let sync = SyncSettings {
+        beads_dir: storage_ctx.paths.beads_dir.clone(),
+        jsonl_path: storage_ctx.paths.jsonl_path.clone(),
+        expected_prefix: storage_ctx.storage.get_config("issue_prefix")?,
+        auto_import: !cli.no_auto_import.unwrap_or(false),
+        auto_flush: !cli.no_auto_flush.unwrap_or(false),
+    };
+
+    let listener = TcpListener::bind(addr)?;
+    let local_addr = listener.local_addr()?;
+    if ctx.is_json() {
+        ctx.json(&json!({ "listening": format!("http://{local_addr}") }));
+    } else {
+        ctx.info(&format!(
+            "Serving beads API on http://{local_addr} (Ctrl-C to stop)"
+        ));
+    }
+
+    let server = ApiServer {
+        storage: storage_ctx.storage,
+        create_config,
+        resolver,
+        external_db_paths,
+        sync: Some(sync),
+        guard: RequestGuard {
+            check_host: !args.allow_remote,
+            allowed_origins: args
+                .allow_origin
+                .iter()
+                .map(|origin| origin.trim_end_matches('/').to_ascii_lowercase())
+                .collect(),
+        },
+    };
+
+    let server = Arc::new(Mutex::new(server));
+    let active = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => spawn_connection(&server, &active, stream),
+            Err(err) => warn!(error = %err, "Failed to accept connection"),
+        }
+    }
+
+    Ok(())
+}
+
+/// Serve `stream` on a worker thread, or answer 503 if too many are busy.
+fn spawn_connection(server: &Arc<Mutex<ApiServer>>, active: &Arc<AtomicUsize>, stream: TcpStream) {
+    if let Err(err) = stream.set_write_timeout(Some(REQUEST_DEADLINE)) {
+        debug!(error = %err, "Failed to set write timeout");
+    }
+    if active.fetch_add(1, Ordering::SeqCst) >= MAX_CONNECTIONS {
+        active.fetch_sub(1, Ordering::SeqCst);
+        let response = HttpError::new(503, ErrorCode::ValidationFailed, "Server busy")
+            .with_hint("Retry shortly")
+            .into_response();
+        let mut stream = stream;
+        if let Err(err) = write_response(&mut stream, &response) {
+            debug!(error = %err, "Failed to write response");
+        }
+        return;
+    }
+
+    let server = Arc::clone(server);
+    let active = Arc::clone(active);
+    let spawned = std::thread::Builder::new()
+        .name("br-serve-conn".to_string())
+        .spawn(move || {
+            serve_connection(&server, stream);
+            active.fetch_sub(1, Ordering::SeqCst);
+        });
+    if let Err(err) = spawned {
+        warn!(error = %err, "Failed to spawn connection thread");
+    }
+}
+
+/// Read one request, handle it with the server locked, and write the reply.
+fn serve_connection(server: &Mutex<ApiServer>, stream: TcpStream) {
+    let mut reader = match stream.try_clone() {
+        Ok(clone) => BufReader::new(DeadlineReader {
+            stream: clone,
+            deadline: Instant::now() + REQUEST_DEADLINE,
+        }),
+        Err(err) => {
+            warn!(error = %err, "Failed to clone connection");
+            return;
+        }
+    };
+
+    let response = match read_request(&mut reader) {
+        Ok(Some(request)) => {
+            let response = server
+                .lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .dispatch(&request);
+            info!(
+                method = %request.method,
+                path = %request.path,
+                status = response.status,
+                "Handled API request"
+            );
+            response
+        }
+        Ok(None) => return,
+        Err(err) => err.into_response(),
+    };
+
+    let mut stream = stream;
+    if let Err(err) = write_response(&mut stream, &response) {
+        debug!(error = %err, "Failed to write response");
+    }
+}
+
+/// Socket reader that fails once a fixed deadline passes, however slowly
+/// the bytes arrive.
+struct DeadlineReader {
+    stream: TcpStream,
+    deadline: Instant,
+}
+
+impl Read for DeadlineReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.deadline.saturating_duration_since(Instant::now());
+        if remaining.is_zero() {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::TimedOut,
+                "request deadline exceeded",
+            ));
+        }
+        self.stream.set_read_timeout(Some(remaining))?;
+        self.stream.read(buf)
+    }
+}
+
+/// Parse the `--http` address. A bare port binds to loopback.
+fn parse_listen_addr(value: &str, allow_remote: bool) -> Result<SocketAddr> {
+    let trimmed = value.trim();
+    let candidate = if !trimmed.is_empty() && trimmed.chars().all(|c| c.is_ascii_digit()) {
+        format!("127.0.0.1:{trimmed}")
+    } else {
+        trimmed.to_string()
+    };
+
+    let addr = candidate
+        .to_socket_addrs()
+        .ok()
+        .and_then(|mut addrs| addrs.next())
+        .ok_or_else(|| {
+            BeadsError::validation("http", format!("invalid listen address '{value}'"))
+        })?;
+
+    if !allow_remote && !addr.ip().is_loopback() {
+        return Err(BeadsError::validation(
+            "http",
+            format!(
+                "refusing to listen on non-loopback address {addr}; the API has no authentication (use --allow-remote to override)"
+            ),
+        ));
+    }
+
+    Ok(addr)
+}
+
+/// JSONL sync behavior around requests (mirrors CLI auto-import/auto-flush).
+struct SyncSettings {
+    beads_dir: PathBuf,
+    jsonl_path: PathBuf,
+    expected_prefix: Option<String>,
+    auto_import: bool,
+    auto_flush: bool,
+}
+
+/// Request handler state.
+struct ApiServer {
+    storage: SqliteStorage,
+    create_config: CreateConfig,
+    resolver: IdResolver,
+    external_db_paths: HashMap<String, PathBuf>,
+    sync: Option<SyncSettings>,
+    guard: RequestGuard,
+}
+
+/// Checks that keep browsers from reaching the unauthenticated API.
+struct RequestGuard {
+    /// Require a loopback `Host` (off with `--allow-remote`).
+    check_host: bool,
+    /// Lowercased origins from `--allow-origin`, without a trailing slash.
+    allowed_origins: Vec<String>,
+}
+
+impl RequestGuard {
+    fn check(&self, request: &HttpRequest) -> std::result::Result<(), HttpError> {
+        let forbidden = |message: String| HttpError::new(403, ErrorCode::ValidationFailed, message);
+
+        if self.check_host {
+            let host = request.header("host").unwrap_or_default();
+            if !is_loopback_host(host) {
+                return Err(
+                    forbidden(format!("Host '{host}' is not a loopback address"))
+                        .with_hint("Connect via localhost or 127.0.0.1"),
+                );
+            }
+        }
+
+        if let Some(origin) = request.header("origin") {
+            let normalized = origin.trim_end_matches('/').to_ascii_lowercase();
+            if !self.allowed_origins.contains(&normalized) {
+                return Err(forbidden(format!("Origin '{origin}' is not allowed"))
+                    .with_hint("Start the server with --allow-origin <ORIGIN> to allow it"));
+            }
+        }
+
+        if matches!(request.method.as_str(), "POST" | "PATCH") {
+            let is_json = request
+                .header("content-type")
+                .and_then(|value| value.split(';').next())
+                .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+            if !is_json {
+                return Err(HttpError::new(
+                    415,
+                    ErrorCode::ValidationFailed,
+                    format!("{} requires Content-Type: application/json", request.method),
+                ));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Whether a `Host` header names this machine (`localhost`, `127.x.x.x`, `[::1]`).
+fn is_loopback_host(host: &str) -> bool {
+    let host = host.trim();
+    let name = if let Some(rest) = host.strip_prefix('[') {
+        rest.split_once(']').map_or(rest, |(name, _)| name)
+    } else {
+        host.rsplit_once(':').map_or(host, |(name, _)| name)
+    };
+    name.eq_ignore_ascii_case("localhost")
+        || name
+            .parse::<std::net::IpAddr>()
+            .is_ok_and(|ip| ip.is_loopback())
+}
+
+type HttpResult = std::result::Result<HttpResponse, HttpError>;
+
+impl ApiServer {
+    fn dispatch(&mut self, request: &HttpRequest) -> HttpResponse {
+        if let Err(err) = self.guard.check(request) {
+            return err.into_response();
+        }
+        if let Err(err) = self.refresh() {
+            return HttpError::from(err).into_response();
+        }
+
+        match self.route(request) {
+            Ok(response) => {
+                if request.method != "GET" && response.status < 400 {
+                    self.flush();
+                }
+                response
+            }
+            Err(err) => err.into_response(),
+        }
+    }
+
+    fn route(&mut self, request: &HttpRequest) -> HttpResult {
+        let segments = request.path_segments();
+        let segments: Vec<&str> = segments.iter().map(String::as_str).collect();
+
+        match (request.method.as_str(), segments.as_slice()) {
+            ("GET", ["issues"]) => self.list_issues(request),
+            ("POST", ["issues"]) => self.create_issue(request),
+            ("GET", ["issues", id]) => self.get_issue(request, id),
+            ("PATCH", ["issues", id]) => self.patch_issue(request, id),
+            ("POST", ["issues", id, "close"]) => self.close_issue(request, id),
+            ("GET", ["ready"]) => self.ready(request),
+            ("GET", ["events"]) => self.events(request),
+            (_, ["issues" | "ready" | "events"] | ["issues", _] | ["issues", _, "close"]) => {
+                Err(HttpError::new(
+                    405,
+                    ErrorCode::ValidationFailed,
+                    format!("Method {} not allowed for {}", request.method, request.path),
+                ))
+            }
+            _ => Err(HttpError::new(
+                404,
+                ErrorCode::ValidationFailed,
+                format!("No route for {} {}", request.method, request.path),
+            )
+            .with_hint(
+                "Available routes: /issues, /issues/{id}, /issues/{id}/close, /ready, /events",
+            )),
+        }
+    }
+
+    /// Import JSONL changes made outside the server before handling a request.
+    fn refresh(&mut self) -> Result<()> {
+        let Some(sync) = &self.sync else {
+            return Ok(());
+        };
+        if !sync.auto_import {
+            return Ok(());
+        }
+        let outcome = auto_import_if_stale(
+            &mut self.storage,
+            &sync.beads_dir,
+            &sync.jsonl_path,
+            sync.expected_prefix.as_deref(),
+            false,
+            false,
+        )?;
+        if outcome.attempted {
+            debug!(
+                imported = outcome.imported_count,
+                "Auto-import before request"
+            );
+        }
+        Ok(())
+    }
+
+    /// Export dirty issues after a successful mutation (non-fatal).
+    fn flush(&mut self) {
+        let Some(sync) = &self.sync else {
+            return;
+        };
+        if !sync.auto_flush {
+            return;
+        }
+        if let Err(err) = auto_flush(&mut self.storage, &sync.beads_dir) {
+            debug!(?err, "Auto-flush failed (non-fatal)");
+        }
+    }
+
+    fn resolve_id(&self, input: &str) -> Result<String> {
+        let storage = &self.storage;
+        self.resolver
+            .resolve(
+                input,
+                |id| storage.id_exists(id).unwrap_or(false),
+                |hash| storage.find_ids_by_hash(hash).unwrap_or_default(),
+            )
+            .map(|resolved| resolved.id)
+    }
+
+    /// Issue details response, tagged with an ETag of its body.
+    fn issue_response(&self, status: u16, id: &str) -> HttpResult {
+        let details = self
+            .storage
+            .get_issue_details(id, true, false, 0)?
+            .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
+        let response = HttpResponse::json(status, &details)?;
+        let etag = etag_for(&response.body);
+        Ok(response.header("ETag", etag))
+    }
+
+    /// Current ETag of an issue, as `GET /issues/{id}` would return it.
+    fn current_etag(&self, id: &str) -> std::result::Result<String, HttpError> {
+        let response = self.issue_response(200, id)?;
+        Ok(etag_for(&response.body))
+    }
+
+    fn list_issues(&self, request: &HttpRequest) -> HttpResult {
+        let args = ListArgs {
+            status: request.query_list("status"),
+            type_: request.query_list("type"),
+            assignee: request.query_str("assignee"),
+            unassigned: request.query_flag("unassigned")?,
+            id: request.query_list("id"),
+            label: request.query_list("label"),
+            label_any: request.query_list("label_any"),
+            priority: request.query_list("priority"),
+            priority_min: request.query_parse("priority_min")?,
+            priority_max: request.query_parse("priority_max")?,
+            title_contains: request.query_str("title_contains"),
+            desc_contains: request.query_str("desc_contains"),
+            notes_contains: request.query_str("notes_contains"),
+            all: request.query_flag("all")?,
+            limit: request.query_parse("limit")?,
+            sort: request.query_str("sort"),
+            reverse: request.query_flag("reverse")?,
+            deferred: request.query_flag("deferred")?,
+            overdue: request.query_flag("overdue")?,
+            where_: request.query_str("where"),
+            ..Default::default()
+        };
+
+        let issues = list::query_issues(&self.storage, &args)?;
+        let issues = list::with_counts(&self.storage, issues)?;
+        HttpResponse::json(200, &issues).map_err(HttpError::from)
+    }
+
+    fn ready(&self, request: &HttpRequest) -> HttpResult {
+        let sort = request
+            .query_str("sort")
+            .map(|value| {
+                <SortPolicy as ValueEnum>::from_str(&value, true)
+                    .map_err(|_| BeadsError::validation("sort", format!("invalid sort '{value}'")))
+            })
+            .transpose()?
+            .unwrap_or_default();
+        let args = ReadyArgs {
+            limit: request.query_parse("limit")?.unwrap_or(DEFAULT_READY_LIMIT),
+            assignee: request.query_str("assignee"),
+            unassigned: request.query_flag("unassigned")?,
+            label: request.query_list("label"),
+            label_any: request.query_list("label_any"),
+            type_: request.query_list("type"),
+            priority: request.query_list("priority"),
+            sort,
+            include_deferred: request.query_flag("include_deferred")?,
+            parent: request.query_str("parent"),
+            recursive: request.query_flag("recursive")?,
+            ..Default::default()
+        };
+
+        let issues = ready::query_ready(&self.storage, &args, &self.external_db_paths)?;
+        let output: Vec<ReadyIssue> = issues.iter().map(ReadyIssue::from).collect();
+        HttpResponse::json(200, &output).map_err(HttpError::from)
+    }
+
+    fn events(&self, request: &HttpRequest) -> HttpResult {
+        let since: i64 = request.query_parse("since")?.unwrap_or(0);
+        let limit: usize = request.query_parse("limit")?.unwrap_or(DEFAULT_EVENT_LIMIT);
+        let events = self.storage.get_events_since(since, limit)?;
+        HttpResponse::json(200, &events).map_err(HttpError::from)
+    }
+
+    fn get_issue(&self, request: &HttpRequest, input: &str) -> HttpResult {
+        let id = self.resolve_id(input)?;
+        let response = self.issue_response(200, &id)?;
+
+        let etag = etag_for(&response.body);
+        if request
+            .header("if-none-match")
+            .is_some_and(|value| etag_matches(value, &etag))
+        {
+            return Ok(HttpResponse::not_modified(etag));
+        }
+
+        Ok(response)
+    }
+
+    fn create_issue(&mut self, request: &HttpRequest) -> HttpResult {
+        let body: CreateIssueRequest = request.json_body()?;
+        let args = CreateArgs {
+            title: Some(body.title),
+            type_: body.issue_type,
+            priority: body.priority.as_ref().map(ToString::to_string),
+            description: body.description,
+            assignee: body.assignee,
+            owner: body.owner,
+            labels: body.labels,
+            parent: body.parent,
+            deps: body.deps,
+            estimate: body.estimated_minutes,
+            due: body.due_at,
+            defer: body.defer_until,
+            external_ref: body.external_ref,
+            status: body.status,
+            ephemeral: body.ephemeral,
+            force: body.force,
+            ..Default::default()
+        };
+
+        let issue = create_issue_impl(&mut self.storage, &args, &self.create_config)?;
+        Ok(self
+            .issue_response(201, &issue.id)?
+            .header("Location", format!("/issues/{}", issue.id)))
+    }
+
+    fn patch_issue(&mut self, request: &HttpRequest, input: &str) -> HttpResult {
+        let id = self.resolve_id(input)?;
+        check_if_match(request, &id, &self.current_etag(&id)?)?;
+
+        let body: PatchIssueRequest = request.json_body()?;
+        let actor = self.create_config.actor.clone();
+        let mut update = body.to_update()?;
+
+        // Closing goes through the same checks as `POST /issues/{id}/close`
+        if update.status == Some(Status::Closed) {
+            let current = self
+                .storage
+                .get_issue(&id)?
+                .ok_or_else(|| BeadsError::IssueNotFound { id: id.clone() })?;
+            if current.status == Status::Closed {
+                update.status = None;
+                update.closed_at = None;
+            } else {
+                self.check_closable(&id, &current, body.force)?;
+                update.close_reason = Some(Some(
+                    body.close_reason
+                        .clone()
+                        .unwrap_or_else(|| "done".to_string()),
+                ));
+            }
+        }
+
+        if matches!(update.status, Some(Status::InProgress))
+            && !body.force
+            && self.storage.is_blocked(&id)?
+        {
+            let blockers = self.storage.get_blockers(&id)?;
+            return Err(HttpError::new(
+                409,
+                ErrorCode::ValidationFailed,
+                format!("Cannot start blocked issue {id}"),
+            )
+            .with_context(json!({ "id": id, "blocked_by": blockers }))
+            .with_hint("Resolve the blockers first or send \"force\": true"));
+        }
+
+        let labels = match &body.labels {
+            Some(labels) => {
+                let mut cleaned = Vec::with_capacity(labels.len());
+                for label in labels {
+                    let label = label.trim();
+                    if label.is_empty() {
+                        continue;
+                    }
+                    LabelValidator::validate(label)
+                        .map_err(|e| BeadsError::validation("labels", e.message))?;
+                    cleaned.push(label.to_string());
+                }
+                Some(cleaned)
+            }
+            None => None,
+        };
+
+        // Fields and labels land together or not at all
+        let wip_limits = if body.force {
+            &[][..]
+        } else {
+            &self.create_config.wip_limits[..]
+        };
+        self.storage.update_issue_with_labels(
+            &id,
+            &update,
+            labels.as_deref(),
+            &actor,
+            |reader| check_patch_wip(reader, wip_limits, &id, &update, labels.as_deref()),
+        )?;
+
+        self.issue_response(200, &id)
+    }
+
+    /// Refuse to close an issue that is already closed or deleted, or that
+    /// is blocked unless `force` is set, as `br close` does.
+    fn check_closable(
+        &self,
+        id: &str,
+        current: &Issue,
+        force: bool,
+    ) -> std::result::Result<(), HttpError> {
+        if current.status.is_terminal() {
+            return Err(HttpError::new(
+                409,
+                ErrorCode::ValidationFailed,
+                format!("Issue {id} is already {}", current.status.as_str()),
+            )
+            .with_context(json!({ "id": id, "status": current.status.as_str() })));
+        }
+
+        if !force && self.storage.is_blocked(id)? {
+            let mut blockers = self.storage.get_blockers(id)?;
+            if blockers.is_empty() {
+                blockers = self.storage.get_dependencies(id)?;
+            }
+            return Err(HttpError::new(
+                409,
+                ErrorCode::ValidationFailed,
+                format!("Cannot close blocked issue {id}"),
+            )
+            .with_context(json!({ "id": id, "blocked_by": blockers }))
+            .with_hint("Close the blockers first or send \"force\": true"));
+        }
+        Ok(())
+    }
+
+    fn close_issue(&mut self, request: &HttpRequest, input: &str) -> HttpResult {
+        let id = self.resolve_id(input)?;
+        let current = self
+            .storage
+            .get_issue(&id)?
+            .ok_or_else(|| BeadsError::IssueNotFound { id: id.clone() })?;
+        check_if_match(request, &id, &self.current_etag(&id)?)?;
+
+        let body: CloseIssueRequest = request.json_body()?;
+        self.check_closable(&id, &current, body.force)?;
+
+        let update = IssueUpdate {
+            status: Some(Status::Closed),
+            closed_at: Some(Some(Utc::now())),
+            close_reason: Some(Some(body.reason.unwrap_or_else(|| "done".to_string()))),
+            closed_by_session: body.session.map(Some),
+            ..Default::default()
+        };
+        self.storage
+            .update_issue(&id, &update, &self.create_config.actor)?;
+
+        self.issue_response(200, &id)
+    }
+}
+
+/// Refuse a PATCH that moves an issue past a WIP limit.
+fn check_patch_wip(
+    reader: &TxReader<'_>,
+    limits: &[config::WipLimit],
+    id: &str,
+    update: &IssueUpdate,
+    labels: Option<&[String]>,
+) -> Result<()> {
+    let (status, assignee) = reader
+        .issue_state(id)?
+        .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
+    let labels = match labels {
+        Some(labels) => labels.to_vec(),
+        None => reader.labels(id)?,
+    };
+    check_transition(
+        reader,
+        limits,
+        &WipCandidate {
+            id,
+            status: update.status.as_ref().unwrap_or(&status).as_str(),
+            assignee: update
+                .assignee
+                .as_ref()
+                .map_or(assignee.as_deref(), Option::as_deref),
+            labels: &labels,
+        },
+    )
+}
+
+/// Strong ETag for a response body.
+fn etag_for(body: &[u8]) -> String {
+    let digest = format!("{:x}", Sha256::digest(body));
+    format!("\"{}\"", &digest[..32])
+}
+
+/// Check an `If-Match`/`If-None-Match` header value against an ETag.
+fn etag_matches(header: &str, etag: &str) -> bool {
+    header.split(',').map(str::trim).any(|candidate| {
+        candidate == "*" || candidate.strip_prefix("W/").unwrap_or(candidate) == etag
+    })
+}
+
+fn check_if_match(
+    request: &HttpRequest,
+    id: &str,
+    current: &str,
+) -> std::result::Result<(), HttpError> {
+    let Some(expected) = request.header("if-match") else {
+        return Ok(());
+    };
+    if etag_matches(expected, current) {
+        return Ok(());
+    }
+    Err(HttpError::new(
+        412,
+        ErrorCode::ValidationFailed,
+        format!("Issue {id} was modified since it was fetched"),
+    )
+    .with_context(json!({
+        "id": id,
+        "expected_etag": expected,
+        "current_etag": current,
+    }))
+    .with_hint("Re-fetch the issue and retry with the new ETag"))
+}
+
+// ============================================================================
+// Request bodies
+// ============================================================================
+
+/// Priority accepted as a number (`1`) or string (`"P1"`).
+#[derive(Debug, Clone, Deserialize)]
+#[serde(untagged)]
+enum PriorityValue {
+    Number(i32),
+    Text(String),
+}
+
+impl std::fmt::Display for PriorityValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Number(n) => write!(f, "{n}"),
+            Self::Text(s) => f.write_str(s),
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CreateIssueRequest {
+    title: String,
+    description: Option<String>,
+    #[serde(rename = "type", alias = "issue_type")]
+    issue_type: Option<String>,
+    priority: Option<PriorityValue>,
+    status: Option<String>,
+    assignee: Option<String>,
+    owner: Option<String>,
+    labels: Vec<String>,
+    parent: Option<String>,
+    deps: Vec<String>,
+    estimated_minutes: Option<i32>,
+    #[serde(alias = "due")]
+    due_at: Option<String>,
+    #[serde(alias = "defer")]
+    defer_until: Option<String>,
+    external_ref: Option<String>,
+    ephemeral: bool,
+    /// Allow an initial status that is over a WIP limit.
+    force: bool,
+}
+
+/// Partial update. Absent fields are left unchanged; `null` clears optional fields.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+#[allow(clippy::option_option)]
+struct PatchIssueRequest {
+    title: Option<String>,
+    #[serde(deserialize_with = "double_option")]
+    description: Option<Option<String>>,
+    #[serde(deserialize_with = "double_option")]
+    design: Option<Option<String>>,
+    #[serde(deserialize_with = "double_option")]
+    acceptance_criteria: Option<Option<String>>,
+    #[serde(deserialize_with = "double_option")]
+    notes: Option<Option<String>>,
+    status: Option<String>,
+    priority: Option<PriorityValue>,
+    #[serde(rename = "type", alias = "issue_type")]
+    issue_type: Option<String>,
+    #[serde(deserialize_with = "double_option")]
+    assignee: Option<Option<String>>,
+    #[serde(deserialize_with = "double_option")]
+    owner: Option<Option<String>>,
+    #[serde(deserialize_with = "double_option")]
+    estimated_minutes: Option<Option<i32>>,
+    #[serde(alias = "due", deserialize_with = "double_option")]
+    due_at: Option<Option<String>>,
+    #[serde(alias = "defer", deserialize_with = "double_option")]
+    defer_until: Option<Option<String>>,
+    #[serde(deserialize_with = "double_option")]
+    external_ref: Option<Option<String>>,
+    labels: Option<Vec<String>>,
+    /// Reason recorded when `status` is `closed` (default `done`).
+    close_reason: Option<String>,
+    /// Allow starting or closing a blocked issue, or moving past a WIP limit.
+    force: bool,
+}
+
+impl PatchIssueRequest {
+    fn to_update(&self) -> Result<IssueUpdate> {
+        if self
+            .title
+            .as_deref()
+            .is_some_and(|title| title.trim().is_empty())
+        {
+            return Err(BeadsError::validation("title", "cannot be empty"));
+        }
+
+        let status: Option<Status> = self.status.as_deref().map(str::parse).transpose()?;
+        let priority = self
+            .priority
+            .as_ref()
+            .map(|p| p.to_string().parse())
+            .transpose()?;
+        let issue_type = self.issue_type.as_deref().map(str::parse).transpose()?;
+
+        let closed_at = match &status {
+            Some(Status::Closed | Status::Tombstone) => Some(Some(Utc::now())),
+            Some(Status::Open | Status::InProgress) => Some(None),
+            _ => None,
+        };
+
+        Ok(IssueUpdate {
+            title: self.title.clone(),
+            description: self.description.clone(),
+            design: self.design.clone(),
+            acceptance_criteria: self.acceptance_criteria.clone(),
+            notes: self.notes.clone(),
+            status,
+            priority,
+            issue_type,
+            assignee: self.assignee.clone(),
+            owner: self.owner.clone(),
+            estimated_minutes: self.estimated_minutes,
+            due_at: parse_date_field(self.due_at.as_ref().map(Option::as_deref), "due_at")?,
+            defer_until: parse_date_field(
+                self.defer_until.as_ref().map(Option::as_deref),
+                "defer_until",
+            )?,
+            external_ref: self.external_ref.clone(),
+            closed_at,
+            ..Default::default()
+        })
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+struct CloseIssueRequest {
+    reason: Option<String>,
+    force: bool,
+    session: Option<String>,
+}
+
+#[allow(clippy::option_option)]
+fn double_option<'de, D, T>(deserializer: D) -> std::result::Result<Option<Option<T>>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: Deserialize<'de>,
+{
+    Option::<T>::deserialize(deserializer).map(Some)
+}
+
+#[allow(clippy::option_option)]
+fn parse_date_field(
+    value: Option<Option<&str>>,
+    field: &str,
+) -> Result<Option<Option<DateTime<Utc>>>> {
+    value
+        .map(|inner| match inner {
+            None | Some("") => Ok(None),
+            Some(s) => parse_flexible_timestamp(s, field).map(Some),
+        })
+        .transpose()
+}
+
+// ============================================================================
+// HTTP plumbing
+// ============================================================================
+
+#[derive(Debug, Default)]
+struct HttpRequest {
+    method: String,
+    path: String,
+    query: Vec<(String, String)>,
+    /// Header names are lowercased.
+    headers: HashMap<String, String>,
+    body: Vec<u8>,
+}
+
+impl HttpRequest {
+    fn header(&self, name: &str) -> Option<&str> {
+        self.headers.get(name).map(String::as_str)
+    }
+
+    fn path_segments(&self) -> Vec<String> {
+        self.path
+            .split('/')
+            .filter(|segment| !segment.is_empty())
+            .map(percent_decode)
+            .collect()
+    }
+
+    /// All values for a query key; repeated keys and comma-separated values are merged.
+    fn query_list(&self, key: &str) -> Vec<String> {
+        self.query
+            .iter()
+            .filter(|(k, _)| k == key)
+            .flat_map(|(_, v)| v.split(','))
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+            .map(str::to_string)
+            .collect()
+    }
+
+    /// Last non-empty value for a query key.
+    fn query_str(&self, key: &str) -> Option<String> {
+        self.query
+            .iter()
+            .rev()
+            .find(|(k, v)| k == key && !v.is_empty())
+            .map(|(_, v)| v.clone())
+    }
+
+    /// Boolean query flag: `?all`, `?all=true`, `?all=1` are true.
+    fn query_flag(&self, key: &str) -> Result<bool> {
+        let Some((_, value)) = self.query.iter().rev().find(|(k, _)| k == key) else {
+            return Ok(false);
+        };
+        match value.trim().to_ascii_lowercase().as_str() {
+            "" | "1" | "true" | "yes" | "on" => Ok(true),
+            "0" | "false" | "no" | "off" => Ok(false),
+            other => Err(BeadsError::validation(
+                key,
+                format!("expected a boolean, got '{other}'"),
+            )),
+        }
+    }
+
+    fn query_parse<T: FromStr>(&self, key: &str) -> Result<Option<T>> {
+        self.query_str(key)
+            .map(|value| {
+                value
+                    .trim()
+                    .parse::<T>()
+                    .map_err(|_| BeadsError::validation(key, format!("invalid value '{value}'")))
+            })
+            .transpose()
+    }
+
+    /// Deserialize the JSON body; an empty body yields the default value.
+    fn json_body<T: for<'de> Deserialize<'de> + Default>(&self) -> Result<T> {
+        if self.body.iter().all(u8::is_ascii_whitespace) {
+            return Ok(T::default());
+        }
+        Ok(serde_json::from_slice(&self.body)?)
+    }
+}
+
+struct HttpResponse {
+    status: u16,
+    headers: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+}
+
+impl HttpResponse {
+    fn json<T: Serialize + ?Sized>(status: u16, value: &T) -> Result<Self> {
+        let mut body = serde_json::to_vec(value)?;
+        body.push(b'\n');
+        Ok(Self {
+            status,
+            headers: Vec::new(),
+            body,
+        })
+    }
+
+    fn not_modified(etag: String) -> Self {
+        Self {
+            status: 304,
+            headers: vec![("ETag", etag)],
+            body: Vec::new(),
+        }
+    }
+
+    fn header(mut self, name: &'static str, value: String) -> Self {
+        self.headers.push((name, value));
+        self
+    }
+}
+
+/// An error response carrying a structured error body.
+#[derive(Debug)]
+struct HttpError {
+    status: u16,
+    error: StructuredError,
+}
+
+impl HttpError {
+    fn new(status: u16, code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            status,
+            error: StructuredError {
+                code,
+                message: message.into(),
+                hint: None,
+                retryable: code.is_retryable(),
+                context: None,
+            },
+        }
+    }
+
+    fn with_hint(mut self, hint: impl Into<String>) -> Self {
+        self.error.hint = Some(hint.into());
+        self
+    }
+
+    fn with_context(mut self, context: Value) -> Self {
+        self.error.context = Some(context);
+        self
+    }
+
+    fn into_response(self) -> HttpResponse {
+        let body = self.error.to_json();
+        let mut bytes = serde_json::to_vec(&body).unwrap_or_default();
+        bytes.push(b'\n');
+        HttpResponse {
+            status: self.status,
+            headers: Vec::new(),
+            body: bytes,
+        }
+    }
+}
+
+impl From<BeadsError> for HttpError {
+    fn from(err: BeadsError) -> Self {
+        let error = StructuredError::from_error(&err);
+        Self {
+            status: status_for_code(error.code),
+            error,
+        }
+    }
+}
+
+/// Map structured error codes onto HTTP status codes.
+const fn status_for_code(code: ErrorCode) -> u16 {
+    match code {
+        ErrorCode::IssueNotFound | ErrorCode::DependencyNotFound => 404,
+        ErrorCode::AmbiguousId
+        | ErrorCode::IdCollision
+        | ErrorCode::CycleDetected
+        | ErrorCode::HasDependents
+        | ErrorCode::DuplicateDependency
+        | ErrorCode::WipLimitExceeded => 409,
+        ErrorCode::InvalidId
+        | ErrorCode::ValidationFailed
+        | ErrorCode::InvalidStatus
+        | ErrorCode::InvalidType
+        | ErrorCode::InvalidPriority
+        | ErrorCode::RequiredField
+        | ErrorCode::SelfDependency
+        | ErrorCode::JsonError => 400,
+        ErrorCode::HookRejected => 403,
+        ErrorCode::DatabaseLocked => 503,
+        _ => 500,
+    }
+}
+
+const fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        201 => "Created",
+        304 => "Not Modified",
+        400 => "Bad Request",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        408 => "Request Timeout",
+        409 => "Conflict",
+        411 => "Length Required",
+        412 => "Precondition Failed",
+        413 => "Payload Too Large",
+        415 => "Unsupported Media Type",
+        431 => "Request Header Fields Too Large",
+        503 => "Service Unavailable",
+        _ => "Internal Server Error",
+    }
+}
+
+/// A failed socket read; running out of time is a 408.
+fn read_error(err: std::io::Error) -> HttpError {
+    if matches!(
+        err.kind(),
+        std::io::ErrorKind::TimedOut | std::io::ErrorKind::WouldBlock
+    ) {
+        return HttpError::new(408, ErrorCode::ValidationFailed, "Request timed out");
+    }
+    HttpError::from(BeadsError::Io(err))
+}
+
+/// Read a single HTTP/1.1 request. Returns `Ok(None)` if the peer closed the
+/// connection before sending anything.
+fn read_request<R: BufRead>(reader: &mut R) -> std::result::Result<Option<HttpRequest>, HttpError> {
+    let bad_request =
+        |message: &str| HttpError::new(400, ErrorCode::ValidationFailed, message.to_string());
+
+    let mut request_line = String::new();
+    let read = reader.read_line(&mut request_line).map_err(read_error)?;
+    if read == 0 {
+        return Ok(None);
+    }
+
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(bad_request("Malformed request line"));
+    };
+
+    let (path, query_string) = target.split_once('?').unwrap_or((target, ""));
+    let mut request = HttpRequest {
+        method: method.to_ascii_uppercase(),
+        path: path.to_string(),
+        query: parse_query(query_string),
+        ..Default::default()
+    };
+
+    let mut header_lines = 0;
+    loop {
+        let mut line = String::new();
+        reader.read_line(&mut line).map_err(read_error)?;
+        let line = line.trim_end_matches(['\r', '\n']);
+        if line.is_empty() {
+            break;
+        }
+        header_lines += 1;
+        if header_lines > MAX_HEADER_LINES {
+            return Err(HttpError::new(
+                431,
+                ErrorCode::ValidationFailed,
+                "Too many request headers",
+            ));
+        }
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(bad_request("Malformed header line"));
+        };
+        request
+            .headers
+            .insert(name.trim().to_ascii_lowercase(), value.trim().to_string());
+    }
+
+    if request
+        .header("transfer-encoding")
+        .is_some_and(|value| value.eq_ignore_ascii_case("chunked"))
+    {
+        return Err(HttpError::new(
+            411,
+            ErrorCode::ValidationFailed,
+            "Chunked request bodies are not supported; send Content-Length",
+        ));
+    }
+
+    let content_length = request
+        .header("content-length")
+        .map(str::parse::<usize>)
+        .transpose()
+        .map_err(|_| bad_request("Invalid Content-Length"))?
+        .unwrap_or(0);
+    if content_length > MAX_BODY_BYTES {
+        return Err(HttpError::new(
+            413,
+            ErrorCode::ValidationFailed,
+            format!("Request body exceeds {MAX_BODY_BYTES} bytes"),
+        ));
+    }
+    if content_length > 0 {
+        let mut body = vec![0; content_length];
+        reader.read_exact(&mut body).map_err(read_error)?;
+        request.body = body;
+    }
+
+    Ok(Some(request))
+}
+
+fn write_response<W: Write>(writer: &mut W, response: &HttpResponse) -> std::io::Result<()> {
+    let mut head = format!(
+        "HTTP/1.1 {} {}\r\n",
+        response.status,
+        reason_phrase(response.status)
+    );
+    if !response.body.is_empty() {
+        head.push_str("Content-Type: application/json\r\n");
+    }
+    head.push_str(&format!("Content-Length: {}\r\n", response.body.len()));
+    head.push_str("Connection: close\r\n");
+    for (name, value) in &response.headers {
+        head.push_str(&format!("{name}: {value}\r\n"));
+    }
+    head.push_str("\r\n");
+
+    writer.write_all(head.as_bytes())?;
+    writer.write_all(&response.body)?;
+    writer.flush()
+}
+
+fn parse_query(query: &str) -> Vec<(String, String)> {
+    query
+        .split('&')
+        .filter(|pair| !pair.is_empty())
+        .map(|pair| {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            (
+                percent_decode(&key.replace('+', " ")),
+                percent_decode(&value.replace('+', " ")),
+            )
+        })
+        .collect()
+}
+
+/// Decode `%XX` escapes; invalid escapes are kept verbatim.
+fn percent_decode(input: &str) -> String {
+    let bytes = input.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' && i + 2 < bytes.len() {
+            let hex = std::str::from_utf8(&bytes[i + 1..i + 3]).ok();
+            if let Some(byte) = hex.and_then(|h| u8::from_str_radix(h, 16).ok()) {
+                out.push(byte);
+                i += 3;
+                continue;
+            }
+        }
+        out.push(bytes[i]);
+        i += 1;
+    }
+    String::from_utf8_lossy(&out).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{IssueType, Priority};
+    use crate::util::id::IdConfig;
+    use std::io::Cursor;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn test_server() -> ApiServer {
+        ApiServer {
+            storage: SqliteStorage::open_memory().expect("open memory storage"),
+            create_config: CreateConfig {
+                id_config: IdConfig::with_prefix("bd"),
+                default_priority: Priority::MEDIUM,
+                default_issue_type: IssueType::Task,
+                actor: "tester".to_string(),
+                wip_limits: Vec::new(),
+            },
+            resolver: IdResolver::new(ResolverConfig::with_prefix("bd")),
+            external_db_paths: HashMap::new(),
+            sync: None,
+            guard: RequestGuard {
+                check_host: true,
+                allowed_origins: vec!["http://localhost:3000".to_string()],
+            },
+        }
+    }
+
+    /// A request as a well-behaved local client sends it.
+    fn request(method: &str, target: &str, headers: &[(&str, &str)], body: &str) -> HttpRequest {
+        let mut all = vec![("Host", "localhost:7777")];
+        if matches!(method, "POST" | "PATCH") {
+            all.push(("Content-Type", "application/json"));
+        }
+        all.extend_from_slice(headers);
+        raw_request(method, target, &all, body)
+    }
+
+    /// A request with exactly the given headers.
+    fn raw_request(
+        method: &str,
+        target: &str,
+        headers: &[(&str, &str)],
+        body: &str,
+    ) -> HttpRequest {
+        let mut raw = format!("{method} {target} HTTP/1.1\r\n");
+        for (name, value) in headers {
+            raw.push_str(&format!("{name}: {value}\r\n"));
+        }
+        raw.push_str(&format!("Content-Length: {}\r\n\r\n{body}", body.len()));
+        read_request(&mut Cursor::new(raw.into_bytes()))
+            .expect("parse request")
+            .expect("request present")
+    }
+
+    fn body_json(response: &HttpResponse) -> Value {
+        serde_json::from_slice(&response.body).expect("response json")
+    }
+
+    fn header_value<'a>(response: &'a HttpResponse, name: &str) -> Option<&'a str> {
+        response
+            .headers
+            .iter()
+            .find(|(n, _)| n.eq_ignore_ascii_case(name))
+            .map(|(_, v)| v.as_str())
+    }
+
+    #[test]
+    fn test_read_request_parses_query_headers_and_body() {
+        init_logging();
+        info!("test_read_request_parses_query_headers_and_body: starting");
+        let req = request(
+            "post",
+            "/issues?status=open,in_progress&status=blocked&title_contains=a%20b",
+            &[("If-Match", "\"abc\"")],
+            "{\"title\":\"x\"}",
+        );
+        assert_eq!(req.method, "POST");
+        assert_eq!(req.path, "/issues");
+        assert_eq!(
+            req.query_list("status"),
+            vec!["open", "in_progress", "blocked"]
+        );
+        assert_eq!(req.query_str("title_contains").as_deref(), Some("a b"));
+        assert_eq!(req.header("if-match"), Some("\"abc\""));
+        assert_eq!(req.body, b"{\"title\":\"x\"}");
+        info!("test_read_request_parses_query_headers_and_body: assertions passed");
+    }
+
+    #[test]
+    fn test_read_request_rejects_oversized_body() {
+        init_logging();
+        info!("test_read_request_rejects_oversized_body: starting");
+        let raw = format!(
+            "POST /issues HTTP/1.1\r\nContent-Length: {}\r\n\r\n",
+            MAX_BODY_BYTES + 1
+        );
+        let err = read_request(&mut Cursor::new(raw.into_bytes())).expect_err("too large");
+        assert_eq!(err.status, 413);
+        info!("test_read_request_rejects_oversized_body: assertions passed");
+    }
+
+    #[test]
+    fn test_query_flag_and_parse() {
+        init_logging();
+        info!("test_query_flag_and_parse: starting");
+        let req = request("GET", "/issues?all&reverse=false&limit=5&bad=x", &[], "");
+        assert!(req.query_flag("all").unwrap());
+        assert!(!req.query_flag("reverse").unwrap());
+        assert!(!req.query_flag("missing").unwrap());
+        assert_eq!(req.query_parse::<usize>("limit").unwrap(), Some(5));
+        assert!(req.query_parse::<usize>("bad").is_err());
+        info!("test_query_flag_and_parse: assertions passed");
+    }
+
+    #[test]
+    fn test_percent_decode() {
+        init_logging();
+        info!("test_percent_decode: starting");
+        assert_eq!(percent_decode("bd-abc"), "bd-abc");
+        assert_eq!(percent_decode("a%2Fb%20c"), "a/b c");
+        assert_eq!(percent_decode("100%"), "100%");
+        assert_eq!(percent_decode("%zz"), "%zz");
+        info!("test_percent_decode: assertions passed");
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        init_logging();
+        info!("test_etag_matches: starting");
+        assert!(etag_matches("\"abc\"", "\"abc\""));
+        assert!(etag_matches("W/\"abc\"", "\"abc\""));
+        assert!(etag_matches("\"x\", \"abc\"", "\"abc\""));
+        assert!(etag_matches("*", "\"abc\""));
+        assert!(!etag_matches("\"def\"", "\"abc\""));
+        info!("test_etag_matches: assertions passed");
+    }
+
+    #[test]
+    fn test_parse_listen_addr() {
+        init_logging();
+        info!("test_parse_listen_addr: starting");
+        let addr = parse_listen_addr("127.0.0.1:8080", false).expect("loopback");
+        assert_eq!(addr.port(), 8080);
+        let addr = parse_listen_addr("9000", false).expect("bare port");
+        assert!(addr.ip().is_loopback());
+        assert!(parse_listen_addr("0.0.0.0:8080", false).is_err());
+        assert!(parse_listen_addr("0.0.0.0:8080", true).is_ok());
+        assert!(parse_listen_addr("not an address", false).is_err());
+        info!("test_parse_listen_addr: assertions passed");
+    }
+
+    #[test]
+    fn test_create_get_and_conditional_requests() {
+        init_logging();
+        info!("test_create_get_and_conditional_requests: starting");
+        let mut server = test_server();
+
+        let created = server.dispatch(&request(
+            "POST",
+            "/issues",
+            &[],
+            r#"{"title":"Serve me","priority":1,"labels":["api"]}"#,
+        ));
+        assert_eq!(created.status, 201);
+        let created_json = body_json(&created);
+        let id = created_json["id"].as_str().expect("id").to_string();
+        assert_eq!(created_json["priority"], 1);
+        let etag = header_value(&created, "ETag").expect("etag").to_string();
+        assert_eq!(
+            header_value(&created, "Location"),
+            Some(format!("/issues/{id}").as_str())
+        );
+
+        let fetched = server.dispatch(&request("GET", &format!("/issues/{id}"), &[], ""));
+        assert_eq!(fetched.status, 200);
+        assert_eq!(header_value(&fetched, "ETag"), Some(etag.as_str()));
+
+        let not_modified = server.dispatch(&request(
+            "GET",
+            &format!("/issues/{id}"),
+            &[("If-None-Match", &etag)],
+            "",
+        ));
+        assert_eq!(not_modified.status, 304);
+        assert!(not_modified.body.is_empty());
+
+        let patched = server.dispatch(&request(
+            "PATCH",
+            &format!("/issues/{id}"),
+            &[("If-Match", &etag)],
+            r#"{"title":"Serve me well","assignee":"alice"}"#,
+        ));
+        assert_eq!(patched.status, 200);
+        assert_eq!(body_json(&patched)["assignee"], "alice");
+        assert_ne!(header_value(&patched, "ETag"), Some(etag.as_str()));
+
+        // Changing only labels still changes the ETag
+        let before_labels = header_value(&patched, "ETag").unwrap().to_string();
+        let relabeled = server.dispatch(&request(
+            "PATCH",
+            &format!("/issues/{id}"),
+            &[("If-Match", &before_labels)],
+            r#"{"labels":["api","v2"]}"#,
+        ));
+        assert_eq!(relabeled.status, 200);
+        assert_ne!(
+            header_value(&relabeled, "ETag"),
+            Some(before_labels.as_str())
+        );
+        let refetched = server.dispatch(&request(
+            "GET",
+            &format!("/issues/{id}"),
+            &[("If-None-Match", &before_labels)],
+            "",
+        ));
+        assert_eq!(refetched.status, 200);
+
+        // Stale ETag is rejected
+        let stale = server.dispatch(&request(
+            "PATCH",
+            &format!("/issues/{id}"),
+            &[("If-Match", &etag)],
+            r#"{"title":"Lost update"}"#,
+        ));
+        assert_eq!(stale.status, 412);
+        assert_eq!(body_json(&stale)["error"]["code"], "VALIDATION_FAILED");
+
+        // null clears optional fields
+        let cleared = server.dispatch(&request(
+            "PATCH",
+            &format!("/issues/{id}"),
+            &[],
+            r#"{"assignee":null}"#,
+        ));
+        assert_eq!(cleared.status, 200);
+        assert!(
+            body_json(&cleared)
+                .get("assignee")
+                .is_none_or(Value::is_null)
+        );
+        info!("test_create_get_and_conditional_requests: assertions passed");
+    }
+
+    #[test]
+    fn test_ready_close_and_events() {
+        init_logging();
+        info!("test_ready_close_and_events: starting");
+        let mut server = test_server();
+
+        let created = server.dispatch(&request("POST", "/issues", &[], r#"{"title":"One"}"#));
+        let id = body_json(&created)["id"].as_str().unwrap().to_string();
+
+        let ready = server.dispatch(&request("GET", "/ready", &[], ""));
+        assert_eq!(ready.status, 200);
+        assert_eq!(body_json(&ready).as_array().unwrap().len(), 1);
+
+        let closed = server.dispatch(&request(
+            "POST",
+            &format!("/issues/{id}/close"),
+            &[],
+            r#"{"reason":"shipped"}"#,
+        ));
+        assert_eq!(closed.status, 200);
+        assert_eq!(body_json(&closed)["status"], "closed");
+
+        let again = server.dispatch(&request("POST", &format!("/issues/{id}/close"), &[], ""));
+        assert_eq!(again.status, 409);
+
+        let ready = server.dispatch(&request("GET", "/ready", &[], ""));
+        assert!(body_json(&ready).as_array().unwrap().is_empty());
+
+        let events = server.dispatch(&request("GET", "/events?since=0", &[], ""));
+        assert_eq!(events.status, 200);
+        let events = body_json(&events);
+        let events = events.as_array().unwrap();
+        assert!(events.len() >= 2);
+        let first_id = events[0]["id"].as_i64().unwrap();
+        let newer = server.dispatch(&request(
+            "GET",
+            &format!("/events?since={first_id}"),
+            &[],
+            "",
+        ));
+        assert_eq!(
+            body_json(&newer).as_array().unwrap().len(),
+            events.len() - 1
+        );
+        info!("test_ready_close_and_events: assertions passed");
+    }
+
+    #[test]
+    fn test_patch_close_uses_close_checks_and_is_atomic() {
+        init_logging();
+        info!("test_patch_close_uses_close_checks_and_is_atomic: starting");
+        let mut server = test_server();
+
+        let blocker = server.dispatch(&request("POST", "/issues", &[], r#"{"title":"Blocker"}"#));
+        let blocker_id = body_json(&blocker)["id"].as_str().unwrap().to_string();
+        let blocked = server.dispatch(&request(
+            "POST",
+            "/issues",
+            &[],
+            &format!(r#"{{"title":"Blocked","deps":["{blocker_id}"]}}"#),
+        ));
+        let blocked_id = body_json(&blocked)["id"].as_str().unwrap().to_string();
+
+        let path = format!("/issues/{blocked_id}");
+        let refused = server.dispatch(&request(
+            "PATCH",
+            &path,
+            &[],
+            r#"{"status":"closed","labels":["x"]}"#,
+        ));
+        assert_eq!(refused.status, 409);
+        let unchanged = body_json(&server.dispatch(&request("GET", &path, &[], "")));
+        assert_eq!(unchanged["status"], "open");
+        assert!(unchanged.get("labels").is_none());
+
+        let path = format!("/issues/{blocker_id}");
+        let closed = server.dispatch(&request("PATCH", &path, &[], r#"{"status":"closed"}"#));
+        assert_eq!(closed.status, 200);
+        let closed = body_json(&closed);
+        assert_eq!(closed["status"], "closed");
+        assert_eq!(closed["close_reason"], "done");
+        let closed_at = closed["closed_at"].clone();
+
+        // Re-sending the closed status leaves the close untouched
+        let again = server.dispatch(&request(
+            "PATCH",
+            &path,
+            &[],
+            r#"{"status":"closed","close_reason":"other"}"#,
+        ));
+        assert_eq!(again.status, 200);
+        assert_eq!(body_json(&again)["closed_at"], closed_at);
+        assert_eq!(body_json(&again)["close_reason"], "done");
+        info!("test_patch_close_uses_close_checks_and_is_atomic: assertions passed");
+    }
+
+    #[test]
+    fn test_deadline_reader_times_out_slow_clients() {
+        init_logging();
+        info!("test_deadline_reader_times_out_slow_clients: starting");
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let mut client = TcpStream::connect(listener.local_addr().unwrap()).unwrap();
+        let (stream, _) = listener.accept().unwrap();
+
+        // A request line that never ends
+        client
+            .write_all(b"GET /issues HTTP/1.1\r\nHost: loc")
+            .unwrap();
+        let mut reader = BufReader::new(DeadlineReader {
+            stream,
+            deadline: Instant::now() + Duration::from_millis(200),
+        });
+        let started = Instant::now();
+        let err = read_request(&mut reader).expect_err("deadline passes");
+        assert_eq!(err.status, 408);
+        assert!(started.elapsed() < Duration::from_secs(5));
+        info!("test_deadline_reader_times_out_slow_clients: assertions passed");
+    }
+
+    #[test]
+    fn test_create_and_patch_enforce_wip_limits() {
+        init_logging();
+        info!("test_create_and_patch_enforce_wip_limits: starting");
+        let mut server = test_server();
+        server.create_config.wip_limits = vec![config::WipLimit {
+            status: "in_progress".to_string(),
+            scope: config::WipScope::Total,
+            limit: 1,
+        }];
+
+        let started = server.dispatch(&request(
+            "POST",
+            "/issues",
+            &[],
+            r#"{"title":"One","status":"in_progress"}"#,
+        ));
+        assert_eq!(started.status, 201);
+        let over = server.dispatch(&request(
+            "POST",
+            "/issues",
+            &[],
+            r#"{"title":"Two","status":"in_progress"}"#,
+        ));
+        assert_eq!(over.status, 409);
+        assert_eq!(body_json(&over)["error"]["code"], "WIP_LIMIT_EXCEEDED");
+
+        let open = server.dispatch(&request("POST", "/issues", &[], r#"{"title":"Three"}"#));
+        let id = body_json(&open)["id"].as_str().unwrap().to_string();
+        let path = format!("/issues/{id}");
+        let refused = server.dispatch(&request(
+            "PATCH",
+            &path,
+            &[],
+            r#"{"status":"in_progress","labels":["x"]}"#,
+        ));
+        assert_eq!(refused.status, 409);
+        let unchanged = server.dispatch(&request("GET", &path, &[], ""));
+        assert_eq!(body_json(&unchanged)["status"], "open");
+        assert!(body_json(&unchanged).get("labels").is_none());
+
+        let forced = server.dispatch(&request(
+            "PATCH",
+            &path,
+            &[],
+            r#"{"status":"in_progress","force":true}"#,
+        ));
+        assert_eq!(forced.status, 200);
+        assert_eq!(body_json(&forced)["status"], "in_progress");
+        info!("test_create_and_patch_enforce_wip_limits: assertions passed");
+    }
+
+    #[test]
+    fn test_rejects_non_loopback_host() {
+        init_logging();
+        info!("test_rejects_non_loopback_host: starting");
+        let mut server = test_server();
+        for host in ["localhost", "127.0.0.1:7777", "[::1]:7777", "LOCALHOST:1"] {
+            let ok = server.dispatch(&raw_request("GET", "/ready", &[("Host", host)], ""));
+            assert_eq!(ok.status, 200, "{host}");
+        }
+        for host in ["evil.example:7777", "127.0.0.1.evil.example", ""] {
+            let denied = server.dispatch(&raw_request("GET", "/ready", &[("Host", host)], ""));
+            assert_eq!(denied.status, 403, "{host}");
+        }
+        let missing = server.dispatch(&raw_request("GET", "/ready", &[], ""));
+        assert_eq!(missing.status, 403);
+
+        server.guard.check_host = false;
+        let remote = server.dispatch(&raw_request(
+            "GET",
+            "/ready",
+            &[("Host", "beads.internal:7777")],
+            "",
+        ));
+        assert_eq!(remote.status, 200);
+        info!("test_rejects_non_loopback_host: assertions passed");
+    }
+
+    #[test]
+    fn test_rejects_unlisted_origin() {
+        init_logging();
+        info!("test_rejects_unlisted_origin: starting");
+        let mut server = test_server();
+        let created = server.dispatch(&request("POST", "/issues", &[], r#"{"title":"One"}"#));
+        let id = body_json(&created)["id"].as_str().unwrap().to_string();
+
+        let forged = server.dispatch(&request(
+            "POST",
+            &format!("/issues/{id}/close"),
+            &[("Origin", "https://evil.example")],
+            "",
+        ));
+        assert_eq!(forged.status, 403);
+        let fetched = server.dispatch(&request("GET", &format!("/issues/{id}"), &[], ""));
+        assert_eq!(body_json(&fetched)["status"], "open");
+
+        let read = server.dispatch(&request("GET", "/ready", &[("Origin", "null")], ""));
+        assert_eq!(read.status, 403);
+
+        let allowed = server.dispatch(&request(
+            "GET",
+            "/ready",
+            &[("Origin", "http://localhost:3000/")],
+            "",
+        ));
+        assert_eq!(allowed.status, 200);
+        info!("test_rejects_unlisted_origin: assertions passed");
+    }
+
+    #[test]
+    fn test_mutations_require_json_content_type() {
+        init_logging();
+        info!("test_mutations_require_json_content_type: starting");
+        let mut server = test_server();
+        let host = ("Host", "localhost");
+
+        let plain = server.dispatch(&raw_request(
+            "POST",
+            "/issues",
+            &[host, ("Content-Type", "text/plain")],
+            r#"{"title":"Forged"}"#,
+        ));
+        assert_eq!(plain.status, 415);
+        let missing = server.dispatch(&raw_request("POST", "/issues", &[host], r#"{"title":"x"}"#));
+        assert_eq!(missing.status, 415);
+        let listed = server.dispatch(&request("GET", "/issues", &[], ""));
+        assert!(body_json(&listed).as_array().unwrap().is_empty());
+
+        let created = server.dispatch(&raw_request(
+            "POST",
+            "/issues",
+            &[host, ("Content-Type", "Application/JSON; charset=utf-8")],
+            r#"{"title":"Real"}"#,
+        ));
+        assert_eq!(created.status, 201);
+        let id = body_json(&created)["id"].as_str().unwrap().to_string();
+
+        let close = server.dispatch(&raw_request(
+            "POST",
+            &format!("/issues/{id}/close"),
+            &[host],
+            "",
+        ));
+        assert_eq!(close.status, 415);
+        let patch = server.dispatch(&raw_request(
+            "PATCH",
+            &format!("/issues/{id}"),
+            &[host, ("Content-Type", "application/x-www-form-urlencoded")],
+            "title=x",
+        ));
+        assert_eq!(patch.status, 415);
+        info!("test_mutations_require_json_content_type: assertions passed");
+    }
+
+    #[test]
+    fn test_errors_use_structured_format() {
+        init_logging();
+        info!("test_errors_use_structured_format: starting");
+        let mut server = test_server();
+
+        let missing = server.dispatch(&request("GET", "/issues/bd-nope", &[], ""));
+        assert_eq!(missing.status, 404);
+        assert_eq!(body_json(&missing)["error"]["code"], "ISSUE_NOT_FOUND");
+
+        let unknown = server.dispatch(&request("GET", "/nowhere", &[], ""));
+        assert_eq!(unknown.status, 404);
+
+        let method = server.dispatch(&request("DELETE", "/issues", &[], ""));
+        assert_eq!(method.status, 405);
+
+        let invalid = server.dispatch(&request("POST", "/issues", &[], r#"{"bogus":1}"#));
+        assert_eq!(invalid.status, 400);
+        assert_eq!(body_json(&invalid)["error"]["code"], "JSON_ERROR");
+
+        let empty_title = server.dispatch(&request("POST", "/issues", &[], "{}"));
+        assert_eq!(empty_title.status, 400);
+        info!("test_errors_use_structured_format: assertions passed");
+    }
+}
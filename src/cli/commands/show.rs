@@ -1,8 +1,10 @@
 //! Show command implementation.
 
+use crate::cli::commands::list::resolve_template;
 use crate::cli::{ShowArgs, resolve_output_format_basic};
 use crate::config;
 use crate::error::{BeadsError, Result};
+use crate::format::projection;
 use crate::format::{format_priority_label, format_status_icon_colored};
 use crate::output::{IssuePanel, OutputContext, OutputMode};
 use crate::util::id::{IdResolver, ResolverConfig};
@@ -42,6 +44,7 @@ pub fn execute(
     let output_format = resolve_output_format_basic(args.format, outer_ctx.is_json(), false);
     let quiet = cli.quiet.unwrap_or(false);
     let ctx = OutputContext::from_output_format(output_format, quiet, !use_color);
+    let template = resolve_template(&config_layer, args.template.as_deref())?;
 
     let mut details_list = Vec::new();
     for id_input in target_ids {
@@ -59,15 +62,25 @@ pub fn execute(
         }
     }
 
+    let fields = projection::parse_fields(args.fields.as_deref());
+    if args.ndjson {
+        return projection::write_ndjson(&details_list, fields);
+    }
     if matches!(ctx.mode(), OutputMode::Quiet) {
         return Ok(());
     }
+    if let Some(template) = template {
+        return template.print_rows(&details_list);
+    }
     match output_format {
         crate::cli::OutputFormat::Json => {
-            ctx.json_pretty(&details_list);
+            ctx.json_pretty(&projection::project(&details_list, fields.as_deref())?);
         }
         crate::cli::OutputFormat::Toon => {
-            ctx.toon_with_stats(&details_list, args.stats);
+            ctx.toon_with_stats(
+                &projection::project(&details_list, fields.as_deref())?,
+                args.stats,
+            );
         }
         crate::cli::OutputFormat::Text | crate::cli::OutputFormat::Csv => {
             for (i, details) in details_list.iter().enumerate() {
@@ -169,6 +182,25 @@ fn format_issue_details(details: &crate::format::IssueDetails, use_color: bool)
         }
     }
 
+    if !details.commits.is_empty() {
+        output.push('\n');
+        let _ = writeln!(output, "Commits:");
+        for commit in &details.commits {
+            let closes = commit
+                .closes
+                .as_deref()
+                .map(|keyword| format!(" ({keyword})"))
+                .unwrap_or_default();
+            let _ = writeln!(
+                output,
+                "  {} {} {}{closes}",
+                commit.sha.get(..7).unwrap_or(&commit.sha),
+                commit.authored_at.format("%Y-%m-%d"),
+                commit.subject
+            );
+        }
+    }
+
     output
 }
 
@@ -176,7 +208,7 @@ fn format_issue_details(details: &crate::format::IssueDetails, use_color: bool)
 mod tests {
     use super::format_issue_details;
     use crate::format::{IssueDetails, IssueWithDependencyMetadata};
-    use crate::model::{Comment, Issue, IssueType, Priority, Status};
+    use crate::model::{Comment, CommitLink, Issue, IssueType, Priority, Status};
     use crate::storage::SqliteStorage;
     use crate::util::id::{IdResolver, ResolverConfig};
     use chrono::{TimeZone, Utc};
@@ -383,6 +415,7 @@ mod tests {
             comments: Vec::new(),
             events: Vec::new(),
             parent: None,
+            commits: Vec::new(),
         };
         let json = serde_json::to_string_pretty(&vec![details]).unwrap();
         let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
@@ -419,12 +452,22 @@ mod tests {
             }],
             events: Vec::new(),
             parent: None,
+            commits: vec![CommitLink {
+                issue_id: "bd-001".to_string(),
+                sha: "abc1234def5678".to_string(),
+                author: "alice".to_string(),
+                authored_at: Utc.with_ymd_and_hms(2025, 1, 3, 0, 0, 0).unwrap(),
+                subject: "Fix the thing".to_string(),
+                closes: Some("fixes".to_string()),
+            }],
         };
         let output = format_issue_details(&details, false);
         assert!(output.contains("Dependencies:"));
         assert!(output.contains("-> bd-002 (blocks) - Dep"));
         assert!(output.contains("Comments:"));
         assert!(output.contains("alice: Looks good"));
+        assert!(output.contains("Commits:"));
+        assert!(output.contains("abc1234 2025-01-03 Fix the thing (fixes)"));
         info!("test_show_text_includes_dependencies_and_comments: assertions passed");
     }
 }
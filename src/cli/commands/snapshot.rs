@@ -0,0 +1,287 @@
+//! Snapshot command implementation.
+//!
+//! `br snapshot take` records today's per-status, per-type and per-label issue
+//! counts in the `daily_snapshots` table. The first auto-importing command of
+//! each day takes one implicitly, so the history fills in without a cron job.
+//! `br stats --cfd` reads the status counts back as cumulative flow diagram
+//! data. Unlike reconstructing history from events, this stays accurate for
+//! issues that were imported without any.
+
+use crate::cli::commands::flow::resolve_window_start;
+use crate::cli::{OutputFormat, SnapshotCommands, StatsArgs};
+use crate::config;
+use crate::error::Result;
+use crate::format::csv::escape_field;
+use crate::model::{SnapshotCount, Status};
+use crate::output::OutputContext;
+use crate::storage::SqliteStorage;
+use chrono::Utc;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Cumulative flow diagram data: status counts per snapshot day.
+#[derive(Debug, Serialize)]
+pub struct CfdData {
+    /// First day of the window (YYYY-MM-DD).
+    pub since: String,
+    /// Last day of the window (today).
+    pub until: String,
+    /// Statuses present in the window, in workflow order.
+    pub statuses: Vec<String>,
+    /// One entry per day with a snapshot, oldest first.
+    pub days: Vec<CfdDay>,
+}
+
+/// Status counts for one day. Every status in [`CfdData::statuses`] is present.
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct CfdDay {
+    pub day: String,
+    pub counts: BTreeMap<String, usize>,
+}
+
+/// Execute the snapshot command.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened or written.
+pub fn execute(
+    command: &SnapshotCommands,
+    cli: &config::CliOverrides,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let mut storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+
+    match command {
+        SnapshotCommands::Take => {
+            let day = today();
+            let counts = storage_ctx.storage.take_daily_snapshot(&day)?;
+            if ctx.is_json() {
+                ctx.json_pretty(&serde_json::json!({ "day": day, "counts": counts }));
+            } else {
+                ctx.success(&format!("Recorded snapshot for {day} ({counts} counts)"));
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Today's snapshot day (UTC).
+#[must_use]
+pub fn today() -> String {
+    Utc::now().format("%Y-%m-%d").to_string()
+}
+
+/// Take today's snapshot unless one exists. Returns whether one was taken.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be read or written.
+pub fn ensure_daily_snapshot(storage: &mut SqliteStorage) -> Result<bool> {
+    let day = today();
+    if storage.has_daily_snapshot(&day)? {
+        return Ok(false);
+    }
+    storage.take_daily_snapshot(&day)?;
+    Ok(true)
+}
+
+/// Print cumulative flow data for `br stats --cfd`.
+///
+/// # Errors
+///
+/// Returns an error if `--since` cannot be parsed or the query fails.
+pub fn execute_cfd(
+    args: &StatsArgs,
+    storage: &SqliteStorage,
+    format: OutputFormat,
+    ctx: &OutputContext,
+) -> Result<()> {
+    let now = Utc::now();
+    let since = resolve_window_start(args.since.as_deref(), now)?
+        .format("%Y-%m-%d")
+        .to_string();
+    let rows = storage.get_daily_snapshots("status", &since)?;
+    let cfd = build_cfd(rows, since, today());
+
+    match format {
+        OutputFormat::Json => ctx.json_pretty(&cfd),
+        OutputFormat::Toon => ctx.toon_with_stats(&cfd, args.stats),
+        OutputFormat::Csv => print!("{}", format_cfd_csv(&cfd)),
+        OutputFormat::Text => print_cfd_text(&cfd),
+    }
+    Ok(())
+}
+
+/// Pivot status snapshot rows into one entry per day, filling missing statuses with 0.
+fn build_cfd(rows: Vec<SnapshotCount>, since: String, until: String) -> CfdData {
+    let mut statuses: Vec<String> = Vec::new();
+    let mut days: BTreeMap<String, BTreeMap<String, usize>> = BTreeMap::new();
+    for row in rows {
+        if !statuses.contains(&row.key) {
+            statuses.push(row.key.clone());
+        }
+        days.entry(row.day).or_default().insert(row.key, row.count);
+    }
+    statuses.sort_by_key(|status| (status_rank(status), status.clone()));
+
+    let days = days
+        .into_iter()
+        .map(|(day, mut counts)| {
+            for status in &statuses {
+                counts.entry(status.clone()).or_insert(0);
+            }
+            CfdDay { day, counts }
+        })
+        .collect();
+
+    CfdData {
+        since,
+        until,
+        statuses,
+        days,
+    }
+}
+
+/// Workflow position of a status: done work first, new work last, as CFD bands stack.
+fn status_rank(status: &str) -> usize {
+    [
+        Status::Closed.as_str(),
+        Status::Deferred.as_str(),
+        Status::Blocked.as_str(),
+        Status::InProgress.as_str(),
+        Status::Open.as_str(),
+    ]
+    .iter()
+    .position(|known| *known == status)
+    .unwrap_or(usize::MAX)
+}
+
+fn print_cfd_text(cfd: &CfdData) {
+    if cfd.days.is_empty() {
+        println!(
+            "No snapshots since {}. Run `br snapshot take` (or any command) to start recording.",
+            cfd.since
+        );
+        return;
+    }
+    println!(
+        "Cumulative flow {} → {} ({} days with snapshots)\n",
+        cfd.since,
+        cfd.until,
+        cfd.days.len()
+    );
+    print!("{:<10}", "day");
+    for status in &cfd.statuses {
+        let width = status.len().max(5);
+        print!("  {status:>width$}");
+    }
+    println!();
+    for day in &cfd.days {
+        print!("{:<10}", day.day);
+        for status in &cfd.statuses {
+            let width = status.len().max(5);
+            let count = day.counts.get(status).copied().unwrap_or(0);
+            print!("  {count:>width$}");
+        }
+        println!();
+    }
+}
+
+/// One CSV row per day, one column per status.
+fn format_cfd_csv(cfd: &CfdData) -> String {
+    let mut out = String::from("day");
+    for status in &cfd.statuses {
+        out.push(',');
+        out.push_str(&escape_field(status));
+    }
+    out.push('\n');
+    for day in &cfd.days {
+        out.push_str(&day.day);
+        for status in &cfd.statuses {
+            out.push_str(&format!(
+                ",{}",
+                day.counts.get(status).copied().unwrap_or(0)
+            ));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Issue, Priority};
+    use crate::storage::IssueUpdate;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_issue(id: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            priority: Priority::MEDIUM,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn test_snapshots_build_cfd() {
+        init_logging();
+        info!("test_snapshots_build_cfd: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        for id in ["bd-1", "bd-2", "bd-3"] {
+            storage.create_issue(&make_issue(id), "tester").unwrap();
+        }
+        storage.add_label("bd-1", "backend", "tester").unwrap();
+        assert_eq!(storage.take_daily_snapshot("2024-01-01").unwrap(), 3);
+
+        let update = IssueUpdate {
+            status: Some(Status::InProgress),
+            ..IssueUpdate::default()
+        };
+        storage.update_issue("bd-2", &update, "tester").unwrap();
+        storage.take_daily_snapshot("2024-01-02").unwrap();
+        // Re-taking a day replaces it.
+        storage.take_daily_snapshot("2024-01-02").unwrap();
+
+        let labels = storage.get_daily_snapshots("label", "2024-01-01").unwrap();
+        assert_eq!(labels.len(), 2);
+        assert_eq!(labels[0].key, "backend");
+
+        let rows = storage.get_daily_snapshots("status", "2024-01-01").unwrap();
+        let cfd = build_cfd(rows, "2024-01-01".to_string(), "2024-01-02".to_string());
+        assert_eq!(cfd.statuses, vec!["in_progress", "open"]);
+        assert_eq!(cfd.days.len(), 2);
+        assert_eq!(cfd.days[0].counts["open"], 3);
+        assert_eq!(cfd.days[0].counts["in_progress"], 0);
+        assert_eq!(cfd.days[1].counts["open"], 2);
+        assert_eq!(cfd.days[1].counts["in_progress"], 1);
+        assert_eq!(
+            format_cfd_csv(&cfd),
+            "day,in_progress,open\n2024-01-01,0,3\n2024-01-02,1,2\n"
+        );
+
+        let later = storage.get_daily_snapshots("status", "2024-01-02").unwrap();
+        assert_eq!(later.len(), 2);
+        info!("test_snapshots_build_cfd: assertions passed");
+    }
+
+    #[test]
+    fn test_ensure_daily_snapshot_once() {
+        init_logging();
+        info!("test_ensure_daily_snapshot_once: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage.create_issue(&make_issue("bd-1"), "tester").unwrap();
+        assert!(ensure_daily_snapshot(&mut storage).unwrap());
+        assert!(!ensure_daily_snapshot(&mut storage).unwrap());
+        assert!(storage.has_daily_snapshot(&today()).unwrap());
+        info!("test_ensure_daily_snapshot_once: assertions passed");
+    }
+}
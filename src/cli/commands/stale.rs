@@ -2,6 +2,7 @@ use crate::cli::StaleArgs;
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::format::StaleIssue;
+use crate::format::projection;
 use crate::model::{Issue, Status};
 use crate::output::{OutputContext, OutputMode};
 use crate::storage::ListFilters;
@@ -43,12 +44,17 @@ pub fn execute(args: &StaleArgs, cli: &config::CliOverrides, ctx: &OutputContext
     let stale = storage.list_issues(&filters)?;
 
     // Output based on mode
+    let fields = projection::parse_fields(args.fields.as_deref());
+    if args.ndjson {
+        let stale_output: Vec<StaleIssue> = stale.iter().map(StaleIssue::from).collect();
+        return projection::write_ndjson(&stale_output, fields);
+    }
     if matches!(ctx.mode(), OutputMode::Rich) {
         render_stale_rich(&stale, now, args.days, ctx);
     } else if ctx.is_json() {
         // Convert to StaleIssue for bd-compatible JSON output
         let stale_output: Vec<StaleIssue> = stale.iter().map(StaleIssue::from).collect();
-        ctx.json(&stale_output);
+        ctx.json(&projection::project(&stale_output, fields.as_deref())?);
     } else {
         println!(
             "Stale issues ({} not updated in {}+ days):",
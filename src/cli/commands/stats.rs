@@ -1,9 +1,12 @@
 //! Stats command implementation.
 //!
 //! Shows project statistics including issue counts by status, type, priority,
-//! assignee, and label. Also supports recent activity tracking via git.
+//! assignee, and label, plus current WIP against any configured `wip_limits`.
+//! Also supports recent activity tracking via git, and flow metrics (see
+//! [`flow`]) with `--flow` and cumulative flow data from daily snapshots (see [`snapshot`]) with `--cfd`.
 
-use crate::cli::{OutputFormat, StatsArgs, resolve_output_format_basic};
+use crate::cli::commands::{flow, snapshot};
+use crate::cli::{OutputFormat, StatsArgs, resolve_output_format};
 use crate::config;
 use crate::error::Result;
 use crate::format::{
@@ -12,6 +15,7 @@ use crate::format::{
 use crate::model::{IssueType, Status};
 use crate::output::{OutputContext, OutputMode};
 use crate::storage::{ListFilters, SqliteStorage};
+use crate::util::wip::{WipUsage, wip_usage};
 use chrono::Utc;
 use rich_rust::prelude::*;
 use std::collections::BTreeMap;
@@ -36,10 +40,18 @@ pub fn execute(
     let storage = &storage_ctx.storage;
     let config_layer = config::load_config(&beads_dir, Some(storage), cli)?;
     let use_color = config::should_use_color(&config_layer);
-    let output_format = resolve_output_format_basic(args.format, outer_ctx.is_json(), args.robot);
+    let output_format = resolve_output_format(args.format, outer_ctx.is_json(), args.robot);
     let quiet = cli.quiet.unwrap_or(false);
     let ctx = OutputContext::from_output_format(output_format, quiet, !use_color);
 
+    if args.flow {
+        let prefix = config::id_config_from_layer(&config_layer).prefix;
+        return flow::execute(args, storage, &prefix, output_format, &ctx);
+    }
+    if args.cfd {
+        return snapshot::execute_cfd(args, storage, output_format, &ctx);
+    }
+
     info!("Computing project statistics");
 
     // Get all issues including closed and tombstones for comprehensive stats
@@ -79,10 +91,13 @@ pub fn execute(
         compute_recent_activity(&beads_dir, args.activity_hours)
     };
 
+    let wip = wip_usage(storage, &config::wip_limits_from_layer(&config_layer))?;
+
     let output = Statistics {
         summary,
         breakdowns,
         recent_activity,
+        wip,
     };
 
     // Output based on mode
@@ -477,6 +492,23 @@ fn print_text_output(output: &Statistics) {
         println!("  Issues Updated:         {}", activity.issues_updated);
     }
 
+    if !output.wip.is_empty() {
+        println!("\nWIP Limits:");
+        for usage in &output.wip {
+            let over = if usage.exceeded() {
+                "  (over limit)"
+            } else {
+                ""
+            };
+            println!(
+                "  {}: {}/{}{over}",
+                wip_label(usage),
+                usage.current,
+                usage.limit
+            );
+        }
+    }
+
     // Match bd footer
     println!("\nFor more details, use 'bd list' to see individual issues.");
 }
@@ -544,8 +576,14 @@ fn render_stats_rich(output: &Statistics, ctx: &OutputContext) {
         content.append("\n\n");
     }
 
+    // === WIP Limits ===
+    render_wip_section(&mut content, &output.wip, theme);
+
     // === Health Warnings ===
     let mut warnings = Vec::new();
+    for usage in output.wip.iter().filter(|usage| usage.exceeded()) {
+        warnings.push(format!("WIP limit exceeded: {}", wip_label(usage)));
+    }
     if s.blocked_issues > 5 {
         warnings.push(format!("{} issues blocked", s.blocked_issues));
     }
@@ -581,6 +619,25 @@ fn render_stats_rich(output: &Statistics, ctx: &OutputContext) {
     console.print_renderable(&panel);
 }
 
+/// Render current WIP against each limit, highlighting any over the limit.
+fn render_wip_section(content: &mut Text, wip: &[WipUsage], theme: &crate::output::Theme) {
+    if wip.is_empty() {
+        return;
+    }
+    content.append_styled("\u{1f6a6} WIP Limits\n", theme.section.clone());
+    for usage in wip {
+        let style = if usage.exceeded() {
+            theme.warning.clone()
+        } else {
+            theme.dimmed.clone()
+        };
+        content.append_styled(&format!("   {:<32}", wip_label(usage)), style.clone());
+        content.append_styled(&format!("{}/{}", usage.current, usage.limit), style);
+        content.append("\n");
+    }
+    content.append("\n");
+}
+
 /// Render status distribution as progress bars.
 #[allow(
     clippy::cast_precision_loss,
@@ -677,6 +734,14 @@ fn render_breakdown_bars(
     }
 }
 
+/// Human label for a WIP row, e.g. `in_progress per_assignee (alice)`.
+fn wip_label(usage: &WipUsage) -> String {
+    usage.assignee.as_ref().map_or_else(
+        || format!("{} {}", usage.status, usage.scope),
+        |name| format!("{} {} ({name})", usage.status, usage.scope),
+    )
+}
+
 /// Capitalize the first letter of a string.
 fn capitalize(s: &str) -> String {
     let mut chars = s.chars();
@@ -7,6 +7,7 @@ use crate::cli::SyncArgs;
 use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::output::OutputContext;
+use crate::sync::events::{export_events, import_events};
 use crate::sync::history::HistoryConfig;
 use crate::sync::{
     ConflictResolution, ExportConfig, ExportEntityType, ExportError, ExportErrorPolicy,
@@ -38,6 +39,9 @@ pub struct FlushResult {
     pub errors: Vec<ExportError>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub manifest_path: Option<String>,
+    /// Events added to `events.jsonl` (present when `sync.events` is enabled).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exported_events: Option<usize>,
 }
 
 /// Result of an import operation.
@@ -48,6 +52,9 @@ pub struct ImportResultOutput {
     pub skipped: usize,
     pub tombstone_skipped: usize,
     pub blocked_cache_rebuilt: bool,
+    /// Events imported from `events.jsonl` (present when `sync.events` is enabled).
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub imported_events: Option<usize>,
 }
 
 /// Sync status information.
@@ -94,6 +101,8 @@ pub fn execute(
 
     let jsonl_path = paths.jsonl_path;
     let retention_days = paths.metadata.deletions_retention_days;
+    let share_events =
+        config::share_events_from_layer(&config::load_config(&beads_dir, Some(&storage), cli)?);
     let use_json = ctx.is_json() || args.robot;
     let quiet = cli.quiet.unwrap_or(false);
     let show_progress = should_show_progress(use_json, quiet);
@@ -120,8 +129,8 @@ pub fn execute(
         });
     }
 
-    if args.flush_only {
-        execute_flush(
+    let (mode, result) = if args.flush_only {
+        let result = execute_flush(
             &mut storage,
             &beads_dir,
             &path_policy,
@@ -129,10 +138,12 @@ pub fn execute(
             json,
             show_progress,
             retention_days,
+            share_events,
             ctx,
-        )
+        );
+        ("flush", result)
     } else if args.merge {
-        execute_merge(
+        let result = execute_merge(
             &mut storage,
             &path_policy,
             args,
@@ -141,19 +152,28 @@ pub fn execute(
             retention_days,
             cli,
             ctx,
-        )
+        );
+        ("merge", result)
     } else {
         // Default to import-only if no flag is specified (consistent with existing behavior)
         // or explicitly import-only
-        execute_import(
+        let result = execute_import(
             &mut storage,
             &path_policy,
             args,
             use_json,
             show_progress,
+            retention_days,
+            share_events,
             ctx,
-        )
+        );
+        ("import", result)
+    };
+
+    if let Some(hooks) = storage.hooks().filter(|_| result.is_ok()) {
+        hooks.run_post_sync(mode, &path_policy.jsonl_path);
     }
+    result
 }
 
 fn validate_sync_paths(
@@ -470,6 +490,7 @@ fn execute_flush(
     use_json: bool,
     show_progress: bool,
     retention_days: Option<u64>,
+    share_events: bool,
     ctx: &OutputContext,
 ) -> Result<()> {
     info!("Starting JSONL export");
@@ -488,6 +509,13 @@ fn execute_flush(
     let dirty_ids = storage.get_dirty_issue_ids()?;
     debug!(dirty_count = dirty_ids.len(), "Found dirty issues");
 
+    // Events are shared even when no issue changed
+    let exported_events = if share_events {
+        Some(export_events(storage, &path_policy.beads_dir)?.added)
+    } else {
+        None
+    };
+
     // If no dirty issues and no force, report nothing to do
     if dirty_ids.is_empty() && !args.force {
         // Guard against empty DB overwriting a non-empty JSONL.
@@ -560,10 +588,12 @@ fn execute_flush(
                 success_rate: 1.0,
                 errors: Vec::new(),
                 manifest_path: None,
+                exported_events,
             };
             ctx.json_pretty(&result);
         } else {
             println!("Nothing to export (no dirty issues)");
+            print_exported_events(exported_events);
         }
         return Ok(());
     }
@@ -638,6 +668,7 @@ fn execute_flush(
         success_rate: report.success_rate(),
         errors: report.errors.clone(),
         manifest_path,
+        exported_events,
     };
 
     if use_json {
@@ -691,6 +722,7 @@ fn execute_flush(
         if let Some(ref path) = result.manifest_path {
             println!("Wrote manifest to {path}");
         }
+        print_exported_events(result.exported_events);
         if report.has_errors() {
             println!();
             println!("Errors ({}):", report.errors.len());
@@ -768,6 +800,13 @@ fn render_flush_result_rich(result: &FlushResult, errors: &[ExportError], ctx: &
         text.append_styled(path, theme.muted.clone());
     }
 
+    // Shared events
+    if let Some(count) = result.exported_events {
+        text.append("\n");
+        text.append_styled("Events added  ", theme.dimmed.clone());
+        text.append(&count.to_string());
+    }
+
     let panel = Panel::from_rich_text(&text, ctx.width())
         .title(Text::new("Flush (Export)"))
         .box_style(theme.box_style);
@@ -838,13 +877,15 @@ fn should_show_progress(json: bool, quiet: bool) -> bool {
 }
 
 /// Execute the --import-only operation.
-#[allow(clippy::too_many_lines)]
+#[allow(clippy::too_many_lines, clippy::too_many_arguments)]
 fn execute_import(
     storage: &mut crate::storage::SqliteStorage,
     path_policy: &SyncPathPolicy,
     args: &SyncArgs,
     use_json: bool,
     show_progress: bool,
+    retention_days: Option<u64>,
+    share_events: bool,
     ctx: &OutputContext,
 ) -> Result<()> {
     info!("Starting JSONL import");
@@ -859,6 +900,7 @@ fn execute_import(
     // Check if JSONL exists
     if !jsonl_path.exists() {
         warn!(path = %jsonl_path.display(), "JSONL path missing, skipping import");
+        let imported_events = import_shared_events(storage, path_policy, share_events, args)?;
         if use_json {
             let result = ImportResultOutput {
                 created: 0,
@@ -866,10 +908,12 @@ fn execute_import(
                 skipped: 0,
                 tombstone_skipped: 0,
                 blocked_cache_rebuilt: false,
+                imported_events,
             };
             ctx.json_pretty(&result);
         } else {
             println!("No JSONL file found at {}", jsonl_path.display());
+            print_imported_events(imported_events);
         }
         return Ok(());
     }
@@ -889,6 +933,8 @@ fn execute_import(
                     "JSONL is current, skipping import"
                 );
 
+                let imported_events =
+                    import_shared_events(storage, path_policy, share_events, args)?;
                 if use_json {
                     let result = ImportResultOutput {
                         created: 0,
@@ -896,10 +942,12 @@ fn execute_import(
                         skipped: 0,
                         tombstone_skipped: 0,
                         blocked_cache_rebuilt: false,
+                        imported_events,
                     };
                     ctx.json_pretty(&result);
                 } else {
                     println!("JSONL is current (hash unchanged since last import)");
+                    print_imported_events(imported_events);
                 }
                 return Ok(());
             }
@@ -934,6 +982,7 @@ fn execute_import(
         beads_dir: Some(path_policy.beads_dir.clone()),
         allow_external_jsonl: args.allow_external_jsonl,
         show_progress,
+        retention_days,
     };
 
     // Get expected prefix from config, or auto-detect from JSONL
@@ -967,6 +1016,9 @@ fn execute_import(
     let content_hash = compute_jsonl_hash(jsonl_path)?;
     storage.set_metadata(METADATA_JSONL_CONTENT_HASH, &content_hash)?;
 
+    // Events reference issues, so they are imported after them
+    let imported_events = import_shared_events(storage, path_policy, share_events, args)?;
+
     // Output result
     let result = ImportResultOutput {
         created: import_result.imported_count, // We don't distinguish created vs updated yet
@@ -974,6 +1026,7 @@ fn execute_import(
         skipped: import_result.skipped_count,
         tombstone_skipped: import_result.tombstone_skipped,
         blocked_cache_rebuilt: true,
+        imported_events,
     };
 
     if use_json {
@@ -990,11 +1043,44 @@ fn execute_import(
             println!("  Tombstone protected: {} issues", result.tombstone_skipped);
         }
         println!("  Rebuilt blocked cache");
+        print_imported_events(result.imported_events);
     }
 
     Ok(())
 }
 
+/// Import `events.jsonl` when `sync.events` is enabled.
+fn import_shared_events(
+    storage: &mut crate::storage::SqliteStorage,
+    path_policy: &SyncPathPolicy,
+    share_events: bool,
+    args: &SyncArgs,
+) -> Result<Option<usize>> {
+    if !share_events {
+        return Ok(None);
+    }
+    let result = import_events(storage, &path_policy.beads_dir, args.force)?;
+    Ok(Some(result.imported))
+}
+
+fn print_imported_events(imported_events: Option<usize>) {
+    if let Some(count) = imported_events {
+        println!(
+            "  Imported {count} shared event{}",
+            if count == 1 { "" } else { "s" }
+        );
+    }
+}
+
+fn print_exported_events(exported_events: Option<usize>) {
+    if let Some(count) = exported_events {
+        println!(
+            "Added {count} event{} to events.jsonl",
+            if count == 1 { "" } else { "s" }
+        );
+    }
+}
+
 /// Render import result with rich formatting.
 fn render_import_result_rich(result: &ImportResultOutput, ctx: &OutputContext) {
     let console = Console::default();
@@ -1033,6 +1119,13 @@ fn render_import_result_rich(result: &ImportResultOutput, ctx: &OutputContext) {
         text.append("\n");
     }
 
+    // Shared events
+    if let Some(count) = result.imported_events {
+        text.append_styled("Events imported    ", theme.dimmed.clone());
+        text.append(&count.to_string());
+        text.append("\n");
+    }
+
     // Cache rebuilt
     text.append("\n");
     text.append_styled("✓ ", theme.success.clone());
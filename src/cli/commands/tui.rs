@@ -0,0 +1,820 @@
+//! Interactive terminal UI: `br tui`.
+//!
+//! A full-screen view for browsing and triaging: the ready queue or a list
+//! filtered by a `--where` expression on the left, and the selected issue's
+//! details (markdown rendered via [`crate::format::markdown`]) or dependency
+//! tree on the right. Edits go through [`update::apply_update`] and
+//! `SqliteStorage::add_comment`, so a claim from the TUI hits the same
+//! blocked and WIP-limit checks as `br update --claim`. The screen reloads
+//! whenever `PRAGMA data_version` reports a write from another connection
+//! (an agent, `br sync`, ...).
+
+use crate::cli::commands::{ready, update};
+use crate::cli::{ReadyArgs, TuiArgs, UpdateArgs};
+use crate::config::{self, OpenStorageResult};
+use crate::error::{BeadsError, Result};
+use crate::format::context::{OutputContext as RenderContext, OutputMode as RenderMode};
+use crate::format::markdown::render_markdown;
+use crate::format::{format_status_icon, truncate_title, wrap_text};
+use crate::model::Issue;
+use crate::output::OutputContext;
+use crate::storage::{ListFilters, SqliteStorage, WhereClause};
+use crate::util::id::{IdResolver, ResolverConfig};
+use crossterm::cursor::{Hide, MoveTo, Show};
+use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use crossterm::style::{Attribute, Print, SetAttribute};
+use crossterm::terminal::{
+    self, BeginSynchronizedUpdate, Clear, ClearType, EndSynchronizedUpdate, EnterAlternateScreen,
+    LeaveAlternateScreen,
+};
+use crossterm::{execute, queue};
+use std::collections::HashMap;
+use std::io::{self, IsTerminal, Write};
+use std::path::PathBuf;
+use std::time::Duration;
+use unicode_width::UnicodeWidthChar;
+
+/// Lower bound for the refresh interval to avoid spinning.
+const MIN_INTERVAL_MS: u64 = 100;
+
+/// How deep the dependency pane follows `depends on` edges.
+const MAX_TREE_DEPTH: usize = 6;
+
+const HELP: &str = "q quit  tab view  j/k move  J/K scroll  d deps  / filter  c claim  s status  p priority  m comment  l label  r reload";
+
+/// Execute the tui command.
+///
+/// # Errors
+///
+/// Returns an error if stdout is not a terminal, the database cannot be
+/// opened, or the terminal cannot be driven.
+pub fn execute(args: &TuiArgs, cli: &config::CliOverrides, _ctx: &OutputContext) -> Result<()> {
+    if !io::stdout().is_terminal() {
+        return Err(BeadsError::validation(
+            "tui",
+            "br tui needs an interactive terminal",
+        ));
+    }
+    let mut session = Session::open(cli)?;
+    let mut app = App::new(session.use_color);
+    if let Some(expr) = &args.where_ {
+        app.filter = Some(WhereClause::parse(expr)?);
+        app.filter_text = expr.clone();
+        app.view = View::List;
+    }
+    app.reload(&session)?;
+    let interval = Duration::from_millis(args.interval.max(MIN_INTERVAL_MS));
+
+    let _guard = TerminalGuard::enter()?;
+    let mut out = io::stdout();
+    let mut last_version = session.storage().data_version()?;
+    let mut dirty = true;
+    loop {
+        if dirty {
+            app.prepare(&session)?;
+            app.draw(&mut out)?;
+            dirty = false;
+        }
+        if event::poll(interval)? {
+            match event::read()? {
+                Event::Key(key) if key.kind == KeyEventKind::Press => {
+                    if app.handle_key(key, &mut session)? == Flow::Quit {
+                        return Ok(());
+                    }
+                    dirty = true;
+                }
+                Event::Resize(..) => dirty = true,
+                _ => {}
+            }
+        }
+        let version = session.storage().data_version()?;
+        if version != last_version {
+            last_version = version;
+            app.reload(&session)?;
+            dirty = true;
+        }
+    }
+}
+
+/// Raw mode and the alternate screen, restored on drop (including on error)
+/// and on panic.
+struct TerminalGuard;
+
+impl TerminalGuard {
+    /// Switch to raw mode and the alternate screen.
+    ///
+    /// Release builds abort on panic, so `Drop` never runs then; a panic hook
+    /// restores the terminal first and then chains to the previous hook.
+    fn enter() -> Result<Self> {
+        let previous = std::panic::take_hook();
+        std::panic::set_hook(Box::new(move |info| {
+            restore_terminal();
+            previous(info);
+        }));
+        terminal::enable_raw_mode()?;
+        execute!(io::stdout(), EnterAlternateScreen, Hide)?;
+        Ok(Self)
+    }
+}
+
+impl Drop for TerminalGuard {
+    fn drop(&mut self) {
+        restore_terminal();
+    }
+}
+
+fn restore_terminal() {
+    let _ = execute!(io::stdout(), Show, LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+}
+
+/// Storage plus the config the mutations need.
+struct Session {
+    storage_ctx: OpenStorageResult,
+    beads_dir: PathBuf,
+    actor: String,
+    wip_limits: Vec<config::WipLimit>,
+    resolver: IdResolver,
+    external_db_paths: HashMap<String, PathBuf>,
+    use_color: bool,
+    auto_flush: bool,
+}
+
+impl Session {
+    fn open(cli: &config::CliOverrides) -> Result<Self> {
+        let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+        let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+        let layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
+        let id_config = config::id_config_from_layer(&layer);
+        Ok(Self {
+            actor: config::resolve_actor(&layer),
+            wip_limits: config::wip_limits_from_layer(&layer),
+            resolver: IdResolver::new(ResolverConfig::with_prefix(id_config.prefix)),
+            external_db_paths: config::external_project_db_paths(&layer, &beads_dir),
+            use_color: config::should_use_color(&layer),
+            auto_flush: !cli.no_auto_flush.unwrap_or(false),
+            storage_ctx,
+            beads_dir,
+        })
+    }
+
+    const fn storage(&self) -> &SqliteStorage {
+        &self.storage_ctx.storage
+    }
+
+    /// Apply an update through the same path as `br update`.
+    fn update(&mut self, id: &str, args: &UpdateArgs) -> Result<()> {
+        let changes = update::build_update(args, &self.actor)?;
+        update::apply_update(
+            &mut self.storage_ctx.storage,
+            id,
+            args,
+            &changes,
+            &self.actor,
+            &self.wip_limits,
+            &self.resolver,
+        )?;
+        crate::util::set_last_touched_id(&self.beads_dir, id);
+        self.flush()
+    }
+
+    fn comment(&mut self, id: &str, text: &str) -> Result<()> {
+        self.storage_ctx
+            .storage
+            .add_comment(id, &self.actor, text)?;
+        self.flush()
+    }
+
+    /// Keep the JSONL export current while the session runs.
+    fn flush(&mut self) -> Result<()> {
+        self.storage_ctx.flush_no_db_if_dirty()?;
+        if self.auto_flush && !self.storage_ctx.no_db {
+            crate::sync::auto_flush(&mut self.storage_ctx.storage, &self.beads_dir)?;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum View {
+    Ready,
+    List,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Pane {
+    Details,
+    Deps,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Prompt {
+    Filter,
+    Status,
+    Priority,
+    Comment,
+    Label,
+}
+
+impl Prompt {
+    const fn label(self) -> &'static str {
+        match self {
+            Self::Filter => "where",
+            Self::Status => "status (open, in_progress, blocked, deferred, closed)",
+            Self::Priority => "priority (0-4)",
+            Self::Comment => "comment",
+            Self::Label => "label (-name removes)",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Flow {
+    Continue,
+    Quit,
+}
+
+/// One line of the right-hand pane.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Line {
+    Plain(String),
+    Heading(String),
+    /// Pre-styled (and pre-wrapped) output from the markdown renderer.
+    Styled(String),
+}
+
+struct App {
+    view: View,
+    pane: Pane,
+    issues: Vec<Issue>,
+    selected: usize,
+    offset: usize,
+    scroll: usize,
+    filter: Option<WhereClause>,
+    filter_text: String,
+    input: Option<(Prompt, String)>,
+    message: Option<String>,
+    /// Right pane for (issue, pane, width); cleared on reload.
+    detail: Option<(String, Pane, usize, Vec<Line>)>,
+    use_color: bool,
+}
+
+impl App {
+    const fn new(use_color: bool) -> Self {
+        Self {
+            view: View::Ready,
+            pane: Pane::Details,
+            issues: Vec::new(),
+            selected: 0,
+            offset: 0,
+            scroll: 0,
+            filter: None,
+            filter_text: String::new(),
+            input: None,
+            message: None,
+            detail: None,
+            use_color,
+        }
+    }
+
+    fn selected_id(&self) -> Option<String> {
+        self.issues.get(self.selected).map(|issue| issue.id.clone())
+    }
+
+    /// Re-query the current view, keeping the selection on the same issue.
+    fn reload(&mut self, session: &Session) -> Result<()> {
+        let keep = self.selected_id();
+        self.issues = match self.view {
+            View::Ready => ready::query_ready(
+                session.storage(),
+                &ReadyArgs::default(),
+                &session.external_db_paths,
+            )?,
+            View::List => {
+                let filters = ListFilters {
+                    include_closed: self
+                        .filter
+                        .as_ref()
+                        .is_some_and(WhereClause::constrains_status),
+                    where_clause: self.filter.clone(),
+                    ..ListFilters::default()
+                };
+                session.storage().list_issues(&filters)?
+            }
+        };
+        self.selected = keep
+            .and_then(|id| self.issues.iter().position(|issue| issue.id == id))
+            .unwrap_or_else(|| self.selected.min(self.issues.len().saturating_sub(1)));
+        self.detail = None;
+        Ok(())
+    }
+
+    /// Build the right pane for the current selection if it is stale.
+    fn prepare(&mut self, session: &Session) -> Result<()> {
+        let (cols, _) = terminal::size()?;
+        let width = pane_widths(usize::from(cols)).1;
+        let Some(id) = self.selected_id() else {
+            self.detail = None;
+            return Ok(());
+        };
+        let fresh = self
+            .detail
+            .as_ref()
+            .is_some_and(|(cached, pane, w, _)| *cached == id && *pane == self.pane && *w == width);
+        if !fresh {
+            let lines = match self.pane {
+                Pane::Details => detail_lines(session.storage(), &id, width, self.use_color)?,
+                Pane::Deps => dependency_lines(session.storage(), &id)?,
+            };
+            self.detail = Some((id, self.pane, width, lines));
+        }
+        Ok(())
+    }
+
+    fn move_selection(&mut self, delta: isize) {
+        let last = self.issues.len().saturating_sub(1);
+        self.selected = self.selected.saturating_add_signed(delta).min(last);
+        self.scroll = 0;
+    }
+
+    fn switch_view(&mut self, view: View, session: &Session) -> Result<()> {
+        if self.view != view {
+            self.view = view;
+            self.selected = 0;
+            self.offset = 0;
+            self.scroll = 0;
+            self.reload(session)?;
+        }
+        Ok(())
+    }
+
+    fn handle_key(&mut self, key: KeyEvent, session: &mut Session) -> Result<Flow> {
+        if self.input.is_some() {
+            self.handle_input(key, session)?;
+            return Ok(Flow::Continue);
+        }
+        self.message = None;
+        match key.code {
+            KeyCode::Char('q') | KeyCode::Esc => return Ok(Flow::Quit),
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                return Ok(Flow::Quit);
+            }
+            KeyCode::Tab => {
+                let next = match self.view {
+                    View::Ready => View::List,
+                    View::List => View::Ready,
+                };
+                self.switch_view(next, session)?;
+            }
+            KeyCode::Char('1') => self.switch_view(View::Ready, session)?,
+            KeyCode::Char('2') => self.switch_view(View::List, session)?,
+            KeyCode::Down | KeyCode::Char('j') => self.move_selection(1),
+            KeyCode::Up | KeyCode::Char('k') => self.move_selection(-1),
+            KeyCode::PageDown => self.move_selection(10),
+            KeyCode::PageUp => self.move_selection(-10),
+            KeyCode::Home | KeyCode::Char('g') => self.move_selection(isize::MIN),
+            KeyCode::End | KeyCode::Char('G') => self.move_selection(isize::MAX),
+            KeyCode::Char('J') => self.scroll = self.scroll.saturating_add(1),
+            KeyCode::Char('K') => self.scroll = self.scroll.saturating_sub(1),
+            KeyCode::Char('d') => {
+                self.pane = match self.pane {
+                    Pane::Details => Pane::Deps,
+                    Pane::Deps => Pane::Details,
+                };
+                self.scroll = 0;
+            }
+            KeyCode::Char('r') => self.reload(session)?,
+            KeyCode::Char('c') => {
+                let args = UpdateArgs {
+                    claim: true,
+                    ..UpdateArgs::default()
+                };
+                self.mutate(session, "Claimed", |session, id| session.update(id, &args))?;
+            }
+            KeyCode::Char('/') => self.input = Some((Prompt::Filter, self.filter_text.clone())),
+            KeyCode::Char('s') => self.input = Some((Prompt::Status, String::new())),
+            KeyCode::Char('p') => self.input = Some((Prompt::Priority, String::new())),
+            KeyCode::Char('m') => self.input = Some((Prompt::Comment, String::new())),
+            KeyCode::Char('l') => self.input = Some((Prompt::Label, String::new())),
+            KeyCode::Char('?') => self.message = Some(HELP.to_string()),
+            _ => {}
+        }
+        Ok(Flow::Continue)
+    }
+
+    fn handle_input(&mut self, key: KeyEvent, session: &mut Session) -> Result<()> {
+        let Some((prompt, mut text)) = self.input.take() else {
+            return Ok(());
+        };
+        match key.code {
+            KeyCode::Esc => return Ok(()),
+            KeyCode::Enter => return self.submit(prompt, text.trim(), session),
+            KeyCode::Backspace => {
+                text.pop();
+            }
+            KeyCode::Char(c) => text.push(c),
+            _ => {}
+        }
+        self.input = Some((prompt, text));
+        Ok(())
+    }
+
+    fn submit(&mut self, prompt: Prompt, text: &str, session: &mut Session) -> Result<()> {
+        let mut args = UpdateArgs::default();
+        let done = match prompt {
+            Prompt::Filter => return self.apply_filter(text, session),
+            _ if text.is_empty() => return Ok(()),
+            Prompt::Status => {
+                args.status = Some(text.to_string());
+                "Status set"
+            }
+            Prompt::Priority => {
+                args.priority = Some(text.to_string());
+                "Priority set"
+            }
+            Prompt::Label => {
+                if let Some(label) = text.strip_prefix('-') {
+                    args.remove_label.push(label.to_string());
+                    "Label removed"
+                } else {
+                    args.add_label.push(text.to_string());
+                    "Label added"
+                }
+            }
+            Prompt::Comment => {
+                return self.mutate(session, "Comment added", |session, id| {
+                    session.comment(id, text)
+                });
+            }
+        };
+        self.mutate(session, done, |session, id| session.update(id, &args))
+    }
+
+    fn apply_filter(&mut self, text: &str, session: &Session) -> Result<()> {
+        if text.is_empty() {
+            self.filter = None;
+        } else {
+            match WhereClause::parse(text) {
+                Ok(clause) => self.filter = Some(clause),
+                Err(err) => {
+                    self.message = Some(err.to_string());
+                    self.input = Some((Prompt::Filter, text.to_string()));
+                    return Ok(());
+                }
+            }
+        }
+        self.filter_text = text.to_string();
+        self.view = View::List;
+        self.selected = 0;
+        self.offset = 0;
+        self.reload(session)
+    }
+
+    /// Run a mutation on the selected issue. Refusals (blocked, WIP limit,
+    /// bad input) are shown on the status line rather than ending the session.
+    fn mutate(
+        &mut self,
+        session: &mut Session,
+        done: &str,
+        apply: impl FnOnce(&mut Session, &str) -> Result<()>,
+    ) -> Result<()> {
+        let Some(id) = self.selected_id() else {
+            return Ok(());
+        };
+        self.message = Some(match apply(session, &id) {
+            Ok(()) => format!("{done}: {id}"),
+            Err(err) => format!("{id}: {err}"),
+        });
+        self.reload(session)
+    }
+
+    fn draw(&mut self, out: &mut impl Write) -> Result<()> {
+        let (cols, rows) = terminal::size()?;
+        let (cols, rows) = (usize::from(cols), usize::from(rows));
+        let (left, right) = pane_widths(cols);
+        let body = rows.saturating_sub(2);
+        if self.selected < self.offset {
+            self.offset = self.selected;
+        } else if body > 0 && self.selected >= self.offset + body {
+            self.offset = self.selected + 1 - body;
+        }
+
+        queue!(
+            out,
+            BeginSynchronizedUpdate,
+            Clear(ClearType::All),
+            MoveTo(0, 0)
+        )?;
+        queue!(
+            out,
+            SetAttribute(Attribute::Bold),
+            Print(truncate_title(&self.header(), cols)),
+            SetAttribute(Attribute::Reset)
+        )?;
+
+        let detail = self
+            .detail
+            .as_ref()
+            .map_or(&[][..], |(.., lines)| lines.as_slice());
+        for row in 0..body {
+            let y = to_u16(row + 1);
+            if let Some(issue) = self.issues.get(self.offset + row) {
+                let line = pad(&issue_line(issue, left), left);
+                queue!(out, MoveTo(0, y))?;
+                if self.offset + row == self.selected {
+                    queue!(
+                        out,
+                        SetAttribute(Attribute::Reverse),
+                        Print(line),
+                        SetAttribute(Attribute::Reset)
+                    )?;
+                } else {
+                    queue!(out, Print(line))?;
+                }
+            }
+            queue!(out, MoveTo(to_u16(left), y), Print('│'))?;
+            if let Some(line) = detail.get(self.scroll + row) {
+                queue!(out, MoveTo(to_u16(left + 1), y))?;
+                draw_line(out, line, right)?;
+            }
+        }
+
+        queue!(out, MoveTo(0, to_u16(rows.saturating_sub(1))))?;
+        let footer = match (&self.input, &self.message) {
+            (Some((prompt, text)), _) => format!("{}: {text}", prompt.label()),
+            (None, Some(message)) => message.clone(),
+            (None, None) => HELP.to_string(),
+        };
+        queue!(
+            out,
+            Print(truncate_title(&footer, cols)),
+            EndSynchronizedUpdate
+        )?;
+        out.flush()?;
+        Ok(())
+    }
+
+    fn header(&self) -> String {
+        let count = self.issues.len();
+        match self.view {
+            View::Ready => format!("[1] Ready ({count})   2 List"),
+            View::List if self.filter_text.is_empty() => {
+                format!("1 Ready   [2] List ({count})")
+            }
+            View::List => format!("1 Ready   [2] List ({count})   where {}", self.filter_text),
+        }
+    }
+}
+
+/// Split the screen into list and detail columns (plus a separator).
+fn pane_widths(cols: usize) -> (usize, usize) {
+    let left = (cols * 2 / 5).max(20).min(cols.saturating_sub(1));
+    (left, cols.saturating_sub(left + 1))
+}
+
+fn to_u16(value: usize) -> u16 {
+    u16::try_from(value).unwrap_or(u16::MAX)
+}
+
+fn pad(text: &str, width: usize) -> String {
+    let used: usize = text.chars().filter_map(UnicodeWidthChar::width).sum();
+    format!("{text}{}", " ".repeat(width.saturating_sub(used)))
+}
+
+fn issue_line(issue: &Issue, width: usize) -> String {
+    let line = format!(
+        "{} {} P{} {}",
+        format_status_icon(&issue.status),
+        issue.id,
+        issue.priority.0,
+        issue.title
+    );
+    truncate_title(&line, width)
+}
+
+fn draw_line(out: &mut impl Write, line: &Line, width: usize) -> Result<()> {
+    match line {
+        Line::Plain(text) => queue!(out, Print(truncate_title(text, width)))?,
+        Line::Heading(text) => queue!(
+            out,
+            SetAttribute(Attribute::Bold),
+            Print(truncate_title(text, width)),
+            SetAttribute(Attribute::Reset)
+        )?,
+        Line::Styled(text) => queue!(out, Print(text), SetAttribute(Attribute::Reset))?,
+    }
+    Ok(())
+}
+
+fn push_markdown(lines: &mut Vec<Line>, content: &str, width: usize, use_color: bool) {
+    let mode = if use_color {
+        RenderMode::Rich
+    } else {
+        RenderMode::Plain
+    };
+    let rendered = render_markdown(content, &RenderContext::new(mode, width, None, true));
+    for line in rendered.lines() {
+        if use_color {
+            lines.push(Line::Styled(line.to_string()));
+        } else {
+            lines.extend(wrap_text(line, width).into_iter().map(Line::Plain));
+        }
+    }
+}
+
+fn detail_lines(
+    storage: &SqliteStorage,
+    id: &str,
+    width: usize,
+    use_color: bool,
+) -> Result<Vec<Line>> {
+    let Some(details) = storage.get_issue_details(id, true, false, 0)? else {
+        return Ok(vec![Line::Plain(format!("{id} no longer exists"))]);
+    };
+    let issue = &details.issue;
+    let mut lines = vec![
+        Line::Heading(format!("{} {}", issue.id, issue.title)),
+        Line::Plain(format!(
+            "{} · P{} · {} · {}",
+            issue.status.as_str(),
+            issue.priority.0,
+            issue.issue_type.as_str(),
+            issue.assignee.as_deref().unwrap_or("unassigned")
+        )),
+    ];
+    if !details.labels.is_empty() {
+        lines.push(Line::Plain(format!(
+            "Labels: {}",
+            details.labels.join(", ")
+        )));
+    }
+    if let Some(due_at) = issue.due_at {
+        lines.push(Line::Plain(format!("Due: {}", due_at.format("%Y-%m-%d"))));
+    }
+    let blockers = storage.get_blockers(id)?;
+    if !blockers.is_empty() {
+        lines.push(Line::Plain(format!("Blocked by: {}", blockers.join(", "))));
+    }
+
+    let sections = [
+        ("Description", &issue.description),
+        ("Design", &issue.design),
+        ("Acceptance Criteria", &issue.acceptance_criteria),
+        ("Notes", &issue.notes),
+    ];
+    for (heading, text) in sections {
+        if let Some(text) = text.as_deref().filter(|text| !text.trim().is_empty()) {
+            lines.push(Line::Plain(String::new()));
+            lines.push(Line::Heading(heading.to_string()));
+            push_markdown(&mut lines, text, width, use_color);
+        }
+    }
+
+    if !details.comments.is_empty() {
+        lines.push(Line::Plain(String::new()));
+        lines.push(Line::Heading(format!(
+            "Comments ({})",
+            details.comments.len()
+        )));
+        for comment in &details.comments {
+            lines.push(Line::Plain(format!(
+                "{} · {}",
+                comment.author,
+                comment.created_at.format("%Y-%m-%d %H:%M")
+            )));
+            push_markdown(&mut lines, &comment.body, width, use_color);
+        }
+    }
+    Ok(lines)
+}
+
+/// The selected issue's blockers as a tree, followed by what it blocks.
+fn dependency_lines(storage: &SqliteStorage, id: &str) -> Result<Vec<Line>> {
+    let mut lines = vec![Line::Heading(format!("{id} depends on"))];
+    let before = lines.len();
+    push_dependency_tree(storage, id, 1, &mut vec![id.to_string()], &mut lines)?;
+    if lines.len() == before {
+        lines.push(Line::Plain("  (nothing)".to_string()));
+    }
+
+    lines.push(Line::Plain(String::new()));
+    lines.push(Line::Heading(format!("{id} blocks")));
+    let dependents = storage.get_dependents(id)?;
+    if dependents.is_empty() {
+        lines.push(Line::Plain("  (nothing)".to_string()));
+    }
+    for dependent in dependents {
+        lines.push(Line::Plain(format!(
+            "  {}",
+            node_label(storage, &dependent)?
+        )));
+    }
+    Ok(lines)
+}
+
+fn push_dependency_tree(
+    storage: &SqliteStorage,
+    id: &str,
+    depth: usize,
+    path: &mut Vec<String>,
+    lines: &mut Vec<Line>,
+) -> Result<()> {
+    let mut dependencies = storage.get_dependencies(id)?;
+    dependencies.sort();
+    for dep_id in dependencies {
+        let indent = "  ".repeat(depth);
+        if path.contains(&dep_id) {
+            lines.push(Line::Plain(format!("{indent}↺ {dep_id} (cycle)")));
+            continue;
+        }
+        lines.push(Line::Plain(format!(
+            "{indent}{}",
+            node_label(storage, &dep_id)?
+        )));
+        if depth < MAX_TREE_DEPTH {
+            path.push(dep_id.clone());
+            push_dependency_tree(storage, &dep_id, depth + 1, path, lines)?;
+            path.pop();
+        }
+    }
+    Ok(())
+}
+
+fn node_label(storage: &SqliteStorage, id: &str) -> Result<String> {
+    Ok(storage.get_issue(id)?.map_or_else(
+        || id.to_string(),
+        |issue| {
+            format!(
+                "{} {} P{} {}",
+                format_status_icon(&issue.status),
+                issue.id,
+                issue.priority.0,
+                issue.title
+            )
+        },
+    ))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Priority;
+    use chrono::Utc;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_issue(id: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            priority: Priority::MEDIUM,
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            ..Issue::default()
+        }
+    }
+
+    #[test]
+    fn test_pane_widths() {
+        init_logging();
+        info!("test_pane_widths: starting");
+        assert_eq!(pane_widths(100), (40, 59));
+        info!("test_pane_widths: assertions passed");
+    }
+
+    #[test]
+    fn test_dependency_lines_show_tree_and_dependents() {
+        init_logging();
+        info!("test_dependency_lines_show_tree_and_dependents: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        for id in ["bd-1", "bd-2", "bd-3", "bd-4"] {
+            storage.create_issue(&make_issue(id), "tester").unwrap();
+        }
+        storage
+            .add_dependency("bd-1", "bd-2", "blocks", "tester")
+            .unwrap();
+        storage
+            .add_dependency("bd-2", "bd-3", "blocks", "tester")
+            .unwrap();
+        storage
+            .add_dependency("bd-4", "bd-1", "blocks", "tester")
+            .unwrap();
+
+        let lines = dependency_lines(&storage, "bd-1").unwrap();
+        let text: Vec<String> = lines
+            .iter()
+            .map(|line| match line {
+                Line::Plain(text) | Line::Heading(text) | Line::Styled(text) => text.clone(),
+            })
+            .collect();
+        assert_eq!(text[0], "bd-1 depends on");
+        assert!(text[1].starts_with("  ") && text[1].contains("bd-2 P2 Issue bd-2"));
+        assert!(text[2].starts_with("    ") && text[2].contains("bd-3"));
+        assert_eq!(text[4], "bd-1 blocks");
+        assert!(text[5].contains("bd-4"));
+        info!("test_dependency_lines_show_tree_and_dependents: assertions passed");
+    }
+}
@@ -5,9 +5,10 @@ use crate::config;
 use crate::error::{BeadsError, Result};
 use crate::model::{DependencyType, Issue, Status};
 use crate::output::OutputContext;
-use crate::storage::{IssueUpdate, SqliteStorage};
+use crate::storage::{IssueUpdate, SqliteStorage, TxReader};
 use crate::util::id::{IdResolver, ResolverConfig};
 use crate::util::time::parse_flexible_timestamp;
+use crate::util::wip::{WipCandidate, check_transition};
 use crate::validation::LabelValidator;
 use chrono::{DateTime, Utc};
 use serde::Serialize;
@@ -46,6 +47,7 @@ pub fn execute(args: &UpdateArgs, cli: &config::CliOverrides, ctx: &OutputContex
 
     let config_layer = config::load_config(&beads_dir, Some(&storage_ctx.storage), cli)?;
     let actor = config::resolve_actor(&config_layer);
+    let wip_limits = config::wip_limits_from_layer(&config_layer);
     let resolver = build_resolver(&config_layer, &storage_ctx.storage);
     let resolved_ids = resolve_target_ids(args, &beads_dir, &resolver, &storage_ctx.storage)?;
 
@@ -61,74 +63,8 @@ pub fn execute(args: &UpdateArgs, cli: &config::CliOverrides, ctx: &OutputContex
     let storage = &mut storage_ctx.storage;
 
     for id in &resolved_ids {
-        // Get issue before update for change tracking
-        let issue_before = storage.get_issue(id)?;
-
-        if args.claim {
-            if let Some(ref issue) = issue_before {
-                if let Some(ref current_assignee) = issue.assignee {
-                    if current_assignee != &actor {
-                        return Err(BeadsError::validation(
-                            "claim",
-                            format!("issue already assigned to {current_assignee}"),
-                        ));
-                    }
-                }
-            }
-        }
-
-        // Check if transitioning to in_progress (via --claim or --status in_progress)
-        // and if so, validate that the issue is not blocked
-        let transitioning_to_in_progress = args.claim
-            || args
-                .status
-                .as_ref()
-                .is_some_and(|s| s.eq_ignore_ascii_case("in_progress"));
-
-        if transitioning_to_in_progress && !args.force && storage.is_blocked(id)? {
-            let blockers = storage.get_blockers(id)?;
-            let blocker_list = if blockers.is_empty() {
-                "blocking dependencies".to_string()
-            } else {
-                blockers.join(", ")
-            };
-            return Err(BeadsError::validation(
-                "claim",
-                format!("cannot claim blocked issue: {blocker_list}"),
-            ));
-        }
-
-        // Apply basic field updates
-        if !update.is_empty() {
-            storage.update_issue(id, &update, &actor)?;
-        }
-
-        // Apply labels
-        for label in &args.add_label {
-            LabelValidator::validate(label)
-                .map_err(|e| BeadsError::validation("label", e.message))?;
-            storage.add_label(id, label, &actor)?;
-        }
-        for label in &args.remove_label {
-            storage.remove_label(id, label, &actor)?;
-        }
-        if !args.set_labels.is_empty() {
-            // Remove all then add new
-            storage.remove_all_labels(id, &actor)?;
-            // Join all flag values, then split by comma (handles both --set-labels a,b and --set-labels a --set-labels b)
-            let combined = args.set_labels.join(",");
-            for label in combined.split(',') {
-                let label = label.trim();
-                if !label.is_empty() {
-                    LabelValidator::validate(label)
-                        .map_err(|e| BeadsError::validation("label", e.message))?;
-                    storage.add_label(id, label, &actor)?;
-                }
-            }
-        }
-
-        // Apply parent
-        apply_parent_update(storage, id, args.parent.as_deref(), &resolver, &actor)?;
+        let issue_before =
+            apply_update(storage, id, args, &update, &actor, &wip_limits, &resolver)?;
 
         // Update last touched
         crate::util::set_last_touched_id(&beads_dir, id);
@@ -155,6 +91,159 @@ pub fn execute(args: &UpdateArgs, cli: &config::CliOverrides, ctx: &OutputContex
     Ok(())
 }
 
+/// Apply `args` to one issue the way `br update` does: the claim, blocked and
+/// WIP-limit checks (unless `--force`), field updates, labels and parent.
+/// Returns the issue as it was before the update.
+///
+/// Shared with `br tui` so interactive edits obey the same rules.
+///
+/// # Errors
+///
+/// Returns an error if a check refuses the change, a label is invalid, or the
+/// database update fails.
+pub fn apply_update(
+    storage: &mut SqliteStorage,
+    id: &str,
+    args: &UpdateArgs,
+    update: &IssueUpdate,
+    actor: &str,
+    wip_limits: &[config::WipLimit],
+    resolver: &IdResolver,
+) -> Result<Option<Issue>> {
+    // Get issue before update for change tracking
+    let issue_before = storage.get_issue(id)?;
+
+    if args.claim {
+        if let Some(ref issue) = issue_before {
+            if let Some(ref current_assignee) = issue.assignee {
+                if current_assignee != actor {
+                    return Err(BeadsError::validation(
+                        "claim",
+                        format!("issue already assigned to {current_assignee}"),
+                    ));
+                }
+            }
+        }
+    }
+
+    if !args.force {
+        check_can_start(storage, id, args)?;
+    }
+
+    // Apply basic field updates; the WIP check shares their transaction
+    if !update.is_empty() {
+        let wip_limits = if args.force { &[][..] } else { wip_limits };
+        let labels = match issue_before.as_ref() {
+            Some(_) if !wip_limits.is_empty() => labels_after_update(storage, id, args)?,
+            _ => Vec::new(),
+        };
+        storage.update_issue_guarded(id, update, actor, |reader| {
+            check_update_wip(reader, wip_limits, issue_before.as_ref(), update, &labels)
+        })?;
+    }
+
+    // Apply labels
+    for label in &args.add_label {
+        LabelValidator::validate(label).map_err(|e| BeadsError::validation("label", e.message))?;
+        storage.add_label(id, label, actor)?;
+    }
+    for label in &args.remove_label {
+        storage.remove_label(id, label, actor)?;
+    }
+    if !args.set_labels.is_empty() {
+        // Remove all then add new
+        storage.remove_all_labels(id, actor)?;
+        // Join all flag values, then split by comma (handles both --set-labels a,b and --set-labels a --set-labels b)
+        let combined = args.set_labels.join(",");
+        for label in combined.split(',') {
+            let label = label.trim();
+            if !label.is_empty() {
+                LabelValidator::validate(label)
+                    .map_err(|e| BeadsError::validation("label", e.message))?;
+                storage.add_label(id, label, actor)?;
+            }
+        }
+    }
+
+    // Apply parent
+    apply_parent_update(storage, id, args.parent.as_deref(), resolver, actor)?;
+    Ok(issue_before)
+}
+
+/// Refuse to move a blocked issue to `in_progress` (via --claim or --status).
+fn check_can_start(storage: &SqliteStorage, id: &str, args: &UpdateArgs) -> Result<()> {
+    let transitioning_to_in_progress = args.claim
+        || args
+            .status
+            .as_ref()
+            .is_some_and(|s| s.eq_ignore_ascii_case("in_progress"));
+
+    if transitioning_to_in_progress && storage.is_blocked(id)? {
+        let blockers = storage.get_blockers(id)?;
+        let blocker_list = if blockers.is_empty() {
+            "blocking dependencies".to_string()
+        } else {
+            blockers.join(", ")
+        };
+        return Err(BeadsError::validation(
+            "claim",
+            format!("cannot claim blocked issue: {blocker_list}"),
+        ));
+    }
+    Ok(())
+}
+
+/// Enforce WIP limits when an update moves an issue into a status or hands
+/// it to a new assignee there. Other edits to counted issues pass through.
+fn check_update_wip(
+    reader: &TxReader<'_>,
+    limits: &[config::WipLimit],
+    before: Option<&Issue>,
+    update: &IssueUpdate,
+    labels: &[String],
+) -> Result<()> {
+    let Some(before) = before.filter(|_| !limits.is_empty()) else {
+        return Ok(());
+    };
+    let status = update.status.as_ref().unwrap_or(&before.status);
+    let assignee = update
+        .assignee
+        .as_ref()
+        .map_or(before.assignee.as_deref(), Option::as_deref);
+    check_transition(
+        reader,
+        limits,
+        &WipCandidate {
+            id: &before.id,
+            status: status.as_str(),
+            assignee,
+            labels,
+        },
+    )
+}
+
+/// The labels an issue will carry once `args` is applied.
+fn labels_after_update(
+    storage: &SqliteStorage,
+    id: &str,
+    args: &UpdateArgs,
+) -> Result<Vec<String>> {
+    if !args.set_labels.is_empty() {
+        return Ok(args
+            .set_labels
+            .join(",")
+            .split(',')
+            .map(str::trim)
+            .filter(|label| !label.is_empty())
+            .map(str::to_string)
+            .collect());
+    }
+    let mut labels = storage.get_labels(id)?;
+    labels.retain(|label| !args.remove_label.contains(label));
+    labels.extend(args.add_label.iter().cloned());
+    Ok(labels)
+}
+
 /// Print a summary of what changed for the issue.
 fn print_update_summary(id: &str, title: &str, before: Option<&Issue>, after: &Issue) {
     println!("Updated {id}: {title}");
@@ -227,7 +316,13 @@ fn resolve_target_ids(
     Ok(resolved_ids.into_iter().map(|r| r.id).collect())
 }
 
-fn build_update(args: &UpdateArgs, actor: &str) -> Result<IssueUpdate> {
+/// Build the field changes for `args`; `--claim` sets `in_progress` and
+/// assigns `actor`.
+///
+/// # Errors
+///
+/// Returns an error if a status, priority, type or date does not parse.
+pub fn build_update(args: &UpdateArgs, actor: &str) -> Result<IssueUpdate> {
     let status = if args.claim {
         Some(Status::InProgress)
     } else {
@@ -0,0 +1,411 @@
+//! Watch command implementation.
+//!
+//! Streams changes as NDJSON: audit events (including comments), sync imports,
+//! and blocked/unblocked transitions derived from the blocked cache. Change
+//! detection polls `PRAGMA data_version` and uses the event ID as a high-water
+//! mark, so an idle poll costs a single pragma read.
+
+use crate::cli::WatchArgs;
+use crate::config;
+use crate::error::Result;
+use crate::model::Event;
+use crate::output::OutputContext;
+use crate::storage::SqliteStorage;
+use crate::sync::METADATA_LAST_IMPORT_TIME;
+use crate::util::id::{IdResolver, ResolverConfig};
+use serde::Serialize;
+use std::collections::{HashMap, HashSet};
+use std::io::{self, Write};
+use std::thread;
+use std::time::Duration;
+use tracing::debug;
+
+/// Lower bound for the poll interval to avoid spinning.
+const MIN_INTERVAL_MS: u64 = 50;
+
+/// Execute the watch command.
+///
+/// Runs until interrupted (or until stdout is closed), unless `--once` is set.
+///
+/// # Errors
+///
+/// Returns an error if the database cannot be opened or a query fails.
+pub fn execute(args: &WatchArgs, cli: &config::CliOverrides, _ctx: &OutputContext) -> Result<()> {
+    let beads_dir = config::discover_beads_dir_with_cli(cli)?;
+    let storage_ctx = config::open_storage_with_cli(&beads_dir, cli)?;
+    let storage = &storage_ctx.storage;
+
+    let config_layer = config::load_config(&beads_dir, Some(storage), cli)?;
+    let id_config = config::id_config_from_layer(&config_layer);
+    let resolver = IdResolver::new(ResolverConfig::with_prefix(id_config.prefix));
+    let mut issue_ids = HashSet::new();
+    for input in &args.issue {
+        let resolved = resolver.resolve(
+            input,
+            |id| storage.id_exists(id).unwrap_or(false),
+            |hash| storage.find_ids_by_hash(hash).unwrap_or_default(),
+        )?;
+        issue_ids.insert(resolved.id);
+    }
+
+    let filter = WatchFilter::new(args, issue_ids);
+    let since = match args.since {
+        Some(since) => since,
+        None => storage.max_event_id()?,
+    };
+    let mut watcher = Watcher::new(storage, since)?;
+    let interval = Duration::from_millis(args.interval.max(MIN_INTERVAL_MS));
+
+    debug!(since, interval_ms = ?interval, "Starting watch");
+
+    let mut stdout = io::stdout().lock();
+    let mut last_version = None;
+    loop {
+        let version = storage.data_version()?;
+        if last_version != Some(version) {
+            last_version = Some(version);
+            for record in watcher.poll(storage)? {
+                if !filter.matches(&record, storage)? {
+                    continue;
+                }
+                if !write_record(&mut stdout, &record)? {
+                    // Reader went away (e.g. `br watch | head`)
+                    return Ok(());
+                }
+            }
+        }
+
+        if args.once {
+            return Ok(());
+        }
+        thread::sleep(interval);
+    }
+}
+
+/// A single NDJSON record emitted by `br watch`.
+#[derive(Debug, Clone, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+enum WatchRecord {
+    /// An audit event from the events table (comments arrive as `commented`).
+    Event(Event),
+    /// JSONL was imported into the database.
+    SyncImport { imported_at: String },
+    /// An issue entered the blocked set.
+    Blocked {
+        issue_id: String,
+        blocked_by: Vec<String>,
+    },
+    /// An open issue left the blocked set.
+    Unblocked { issue_id: String },
+}
+
+impl WatchRecord {
+    /// Name used by `--type` filtering: the event type, or the record kind.
+    fn kind_name(&self) -> &str {
+        match self {
+            Self::Event(event) => event.event_type.as_str(),
+            Self::SyncImport { .. } => "sync_import",
+            Self::Blocked { .. } => "blocked",
+            Self::Unblocked { .. } => "unblocked",
+        }
+    }
+
+    fn issue_id(&self) -> Option<&str> {
+        match self {
+            Self::Event(event) => Some(&event.issue_id),
+            Self::Blocked { issue_id, .. } | Self::Unblocked { issue_id } => Some(issue_id),
+            Self::SyncImport { .. } => None,
+        }
+    }
+}
+
+/// Tracks what has already been reported.
+struct Watcher {
+    high_water_mark: i64,
+    last_import: Option<String>,
+    blocked: HashMap<String, Vec<String>>,
+}
+
+impl Watcher {
+    fn new(storage: &SqliteStorage, since: i64) -> Result<Self> {
+        Ok(Self {
+            high_water_mark: since,
+            last_import: storage.get_metadata(METADATA_LAST_IMPORT_TIME)?,
+            blocked: blocked_snapshot(storage)?,
+        })
+    }
+
+    /// Collect records for everything that changed since the previous poll.
+    fn poll(&mut self, storage: &SqliteStorage) -> Result<Vec<WatchRecord>> {
+        let mut records = Vec::new();
+
+        for event in storage.get_events_since(self.high_water_mark, 0)? {
+            self.high_water_mark = self.high_water_mark.max(event.id);
+            records.push(WatchRecord::Event(event));
+        }
+
+        let last_import = storage.get_metadata(METADATA_LAST_IMPORT_TIME)?;
+        if last_import != self.last_import {
+            if let Some(imported_at) = &last_import {
+                records.push(WatchRecord::SyncImport {
+                    imported_at: imported_at.clone(),
+                });
+            }
+            self.last_import = last_import;
+        }
+
+        let blocked = blocked_snapshot(storage)?;
+        let mut newly_blocked: Vec<(&String, &Vec<String>)> = blocked
+            .iter()
+            .filter(|(id, _)| !self.blocked.contains_key(*id))
+            .collect();
+        newly_blocked.sort_by(|a, b| a.0.cmp(b.0));
+        for (issue_id, blocked_by) in newly_blocked {
+            records.push(WatchRecord::Blocked {
+                issue_id: issue_id.clone(),
+                blocked_by: blocked_by.clone(),
+            });
+        }
+
+        let mut released: Vec<&String> = self
+            .blocked
+            .keys()
+            .filter(|id| !blocked.contains_key(*id))
+            .collect();
+        released.sort();
+        for issue_id in released {
+            // Closing or deleting a blocked issue also drops it from the cache;
+            // only report issues that are still actionable.
+            let still_open = storage
+                .get_issue(issue_id)?
+                .is_some_and(|issue| !issue.status.is_terminal());
+            if still_open {
+                records.push(WatchRecord::Unblocked {
+                    issue_id: issue_id.clone(),
+                });
+            }
+        }
+        self.blocked = blocked;
+
+        Ok(records)
+    }
+}
+
+fn blocked_snapshot(storage: &SqliteStorage) -> Result<HashMap<String, Vec<String>>> {
+    Ok(storage
+        .get_blocked_issues()?
+        .into_iter()
+        .map(|(issue, blockers)| (issue.id, blockers))
+        .collect())
+}
+
+/// Record filters from CLI args.
+struct WatchFilter {
+    issues: HashSet<String>,
+    kinds: HashSet<String>,
+    actor: Option<String>,
+    labels: Vec<String>,
+}
+
+impl WatchFilter {
+    fn new(args: &WatchArgs, issues: HashSet<String>) -> Self {
+        Self {
+            issues,
+            kinds: args
+                .kinds
+                .iter()
+                .map(|kind| kind.trim().to_lowercase().replace('-', "_"))
+                .filter(|kind| !kind.is_empty())
+                .collect(),
+            actor: args.actor.clone(),
+            labels: args.label.clone(),
+        }
+    }
+
+    fn matches(&self, record: &WatchRecord, storage: &SqliteStorage) -> Result<bool> {
+        if !self.kinds.is_empty() && !self.kinds.contains(record.kind_name()) {
+            return Ok(false);
+        }
+
+        if let Some(actor) = &self.actor {
+            let WatchRecord::Event(event) = record else {
+                return Ok(false);
+            };
+            if &event.actor != actor {
+                return Ok(false);
+            }
+        }
+
+        if self.issues.is_empty() && self.labels.is_empty() {
+            return Ok(true);
+        }
+        let Some(issue_id) = record.issue_id() else {
+            return Ok(false);
+        };
+        if !self.issues.is_empty() && !self.issues.contains(issue_id) {
+            return Ok(false);
+        }
+        if !self.labels.is_empty() {
+            let labels = storage.get_labels(issue_id)?;
+            if !self.labels.iter().all(|label| labels.contains(label)) {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+/// Write one NDJSON line. Returns `Ok(false)` if the reader closed the pipe.
+fn write_record<W: Write>(out: &mut W, record: &WatchRecord) -> Result<bool> {
+    let mut line = serde_json::to_vec(record)?;
+    line.push(b'\n');
+    match out.write_all(&line).and_then(|()| out.flush()) {
+        Ok(()) => Ok(true),
+        Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(false),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{EventType, Issue, Status};
+    use crate::storage::IssueUpdate;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_issue(id: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            ..Issue::default()
+        }
+    }
+
+    fn kinds(records: &[WatchRecord]) -> Vec<String> {
+        records.iter().map(|r| r.kind_name().to_string()).collect()
+    }
+
+    #[test]
+    fn test_poll_reports_events_and_block_transitions() {
+        init_logging();
+        info!("test_poll_reports_events_and_block_transitions: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let mut watcher = Watcher::new(&storage, 0).unwrap();
+        assert!(watcher.poll(&storage).unwrap().is_empty());
+
+        storage.create_issue(&make_issue("bd-1"), "alice").unwrap();
+        storage.create_issue(&make_issue("bd-2"), "alice").unwrap();
+        let records = watcher.poll(&storage).unwrap();
+        assert_eq!(kinds(&records), vec!["created", "created"]);
+
+        storage
+            .add_dependency("bd-2", "bd-1", "blocks", "alice")
+            .unwrap();
+        let records = watcher.poll(&storage).unwrap();
+        assert_eq!(kinds(&records), vec!["dependency_added", "blocked"]);
+
+        let close = IssueUpdate {
+            status: Some(Status::Closed),
+            ..Default::default()
+        };
+        storage.update_issue("bd-1", &close, "bob").unwrap();
+        let records = watcher.poll(&storage).unwrap();
+        assert!(records.iter().any(|r| matches!(
+            r,
+            WatchRecord::Event(e) if e.event_type == EventType::Closed && e.issue_id == "bd-1"
+        )));
+        assert!(matches!(
+            records.last(),
+            Some(WatchRecord::Unblocked { issue_id }) if issue_id == "bd-2"
+        ));
+
+        // Nothing new on the next poll
+        assert!(watcher.poll(&storage).unwrap().is_empty());
+        info!("test_poll_reports_events_and_block_transitions: assertions passed");
+    }
+
+    #[test]
+    fn test_poll_respects_since_and_sync_import() {
+        init_logging();
+        info!("test_poll_respects_since_and_sync_import: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage.create_issue(&make_issue("bd-1"), "alice").unwrap();
+        storage.create_issue(&make_issue("bd-2"), "alice").unwrap();
+        let since = storage.max_event_id().unwrap();
+        storage.add_comment("bd-2", "bob", "hello").unwrap();
+
+        let mut watcher = Watcher::new(&storage, since).unwrap();
+        let records = watcher.poll(&storage).unwrap();
+        assert_eq!(kinds(&records), vec!["commented"]);
+
+        storage
+            .set_metadata(METADATA_LAST_IMPORT_TIME, "2026-01-01T00:00:00Z")
+            .unwrap();
+        let records = watcher.poll(&storage).unwrap();
+        assert_eq!(kinds(&records), vec!["sync_import"]);
+        info!("test_poll_respects_since_and_sync_import: assertions passed");
+    }
+
+    #[test]
+    fn test_filter_matches() {
+        init_logging();
+        info!("test_filter_matches: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage.create_issue(&make_issue("bd-1"), "alice").unwrap();
+        storage.add_label("bd-1", "backend", "alice").unwrap();
+        let mut watcher = Watcher::new(&storage, 0).unwrap();
+        let records = watcher.poll(&storage).unwrap();
+        let created = &records[0];
+        let import = WatchRecord::SyncImport {
+            imported_at: "now".to_string(),
+        };
+
+        let args = WatchArgs {
+            kinds: vec!["Created".to_string()],
+            ..Default::default()
+        };
+        let filter = WatchFilter::new(&args, HashSet::new());
+        assert!(filter.matches(created, &storage).unwrap());
+        assert!(!filter.matches(&import, &storage).unwrap());
+
+        let args = WatchArgs {
+            actor: Some("bob".to_string()),
+            ..Default::default()
+        };
+        let filter = WatchFilter::new(&args, HashSet::new());
+        assert!(!filter.matches(created, &storage).unwrap());
+
+        let args = WatchArgs {
+            label: vec!["backend".to_string()],
+            ..Default::default()
+        };
+        let filter = WatchFilter::new(&args, HashSet::new());
+        assert!(filter.matches(created, &storage).unwrap());
+        assert!(!filter.matches(&import, &storage).unwrap());
+
+        let filter = WatchFilter::new(
+            &WatchArgs::default(),
+            HashSet::from(["bd-other".to_string()]),
+        );
+        assert!(!filter.matches(created, &storage).unwrap());
+        info!("test_filter_matches: assertions passed");
+    }
+
+    #[test]
+    fn test_write_record_is_ndjson() {
+        init_logging();
+        info!("test_write_record_is_ndjson: starting");
+        let mut out = Vec::new();
+        let record = WatchRecord::Unblocked {
+            issue_id: "bd-1".to_string(),
+        };
+        assert!(write_record(&mut out, &record).unwrap());
+        let text = String::from_utf8(out).unwrap();
+        assert_eq!(text, "{\"kind\":\"unblocked\",\"issue_id\":\"bd-1\"}\n");
+        info!("test_write_record_is_ndjson: assertions passed");
+    }
+}
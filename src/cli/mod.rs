@@ -736,6 +736,9 @@ pub enum Commands {
     /// Update an issue
     Update(UpdateArgs),
 
+    /// Edit an issue in $EDITOR
+    Edit(EditArgs),
+
     /// Close an issue
     Close(CloseArgs),
 
@@ -782,12 +785,30 @@ pub enum Commands {
     /// Alias for stats
     Status(StatsArgs),
 
+    /// Record daily issue count snapshots for cumulative flow
+    Snapshot {
+        #[command(subcommand)]
+        command: SnapshotCommands,
+    },
+
     /// Count issues with optional grouping
     Count(CountArgs),
 
+    /// Show issues as a kanban board (columns by status, assignee or label prefix)
+    Board(BoardArgs),
+
     /// List stale issues
     Stale(StaleArgs),
 
+    /// List open issues past their due date
+    Overdue(OverdueArgs),
+
+    /// List open issues coming due soon
+    Due(DueArgs),
+
+    /// Apply due-date escalation rules from config
+    Escalate(EscalateArgs),
+
     /// Check issues for missing template sections
     Lint(LintArgs),
 
@@ -864,7 +885,7 @@ EXAMPLES:
     #[command(alias = "completion")]
     Completions(CompletionsArgs),
 
-    /// Record and label agent interactions (append-only JSONL)
+    /// Record, label and verify agent interactions (hash-chained JSONL)
     Audit {
         #[command(subcommand)]
         command: AuditCommands,
@@ -874,6 +895,8 @@ EXAMPLES:
     History(HistoryArgs),
     /// List orphan issues (referenced in commits but open)
     Orphans(OrphansArgs),
+    /// Check TODO/FIXME/HACK comments against issues
+    Scan(ScanArgs),
     /// Generate changelog from closed issues
     Changelog(ChangelogArgs),
 
@@ -888,6 +911,45 @@ EXAMPLES:
 
     /// Manage AGENTS.md workflow instructions
     Agents(AgentsArgs),
+
+    /// Serve a local HTTP/JSON API (issues, ready queue, events)
+    Serve(ServeArgs),
+
+    /// Stream new events as NDJSON (events, comments, sync imports, unblocks)
+    Watch(WatchArgs),
+
+    /// Full-screen terminal UI for browsing and triaging issues
+    Tui(TuiArgs),
+
+    /// Install git hooks and the JSONL merge driver
+    Hooks {
+        #[command(subcommand)]
+        command: HooksCommands,
+    },
+
+    /// Index commits that mention issues and apply closing trailers
+    Git {
+        #[command(subcommand)]
+        command: GitCommands,
+    },
+
+    /// Git merge driver for the JSONL export (invoked by git, see `br hooks install`)
+    MergeDriver(MergeDriverArgs),
+
+    /// Shrink old closed issues, archiving the originals
+    Compact(CompactArgs),
+
+    /// Purge expired tombstones and old bookkeeping rows, then vacuum the database
+    Gc(GcArgs),
+
+    /// Import issues from other trackers' offline exports
+    Import {
+        #[command(subcommand)]
+        command: ImportCommands,
+    },
+
+    /// Export issues as an editable markdown plan
+    Export(ExportArgs),
 }
 
 /// Arguments for the completions command.
@@ -920,6 +982,7 @@ pub enum ShellType {
 }
 
 #[derive(Args, Debug, Default)]
+#[allow(clippy::struct_excessive_bools)]
 pub struct CreateArgs {
     /// Issue title
     pub title: Option<String>,
@@ -992,6 +1055,10 @@ pub struct CreateArgs {
     #[arg(long)]
     pub silent: bool,
 
+    /// Create even if the initial status is over a WIP limit
+    #[arg(long)]
+    pub force: bool,
+
     /// Create issues from a markdown file (bulk import)
     #[arg(long, short = 'f')]
     pub file: Option<std::path::PathBuf>,
@@ -1066,7 +1133,7 @@ pub struct UpdateArgs {
     #[arg(long)]
     pub claim: bool,
 
-    /// Force update even if issue is blocked
+    /// Force update even if issue is blocked or over a WIP limit
     #[arg(long)]
     pub force: bool,
 
@@ -1349,6 +1416,10 @@ pub struct ListArgs {
     #[arg(long)]
     pub notes_contains: Option<String>,
 
+    /// Filter expression, e.g. `status:open AND (label:ui OR priority<=1)`
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_: Option<String>,
+
     /// Include closed issues (default excludes closed)
     #[arg(long, short = 'a')]
     pub all: bool,
@@ -1393,15 +1464,25 @@ pub struct ListArgs {
     #[arg(long)]
     pub stats: bool,
 
-    /// CSV fields to include (comma-separated)
+    /// Fields to include (comma-separated)
     ///
-    /// Available: id, title, description, status, priority, `issue_type`,
+    /// For CSV: id, title, description, status, priority, `issue_type`,
     /// assignee, owner, `created_at`, `updated_at`, `closed_at`, `due_at`,
     /// `defer_until`, notes, `external_ref`
+    /// (default: id, title, status, priority, `issue_type`, assignee, `created_at`, `updated_at`).
     ///
-    /// Default: id, title, status, priority, `issue_type`, assignee, `created_at`, `updated_at`
+    /// For JSON, TOON and NDJSON: any top-level key of the output rows, e.g. `id,title,labels`.
     #[arg(long, value_name = "FIELDS", add = ArgValueCompleter::new(csv_fields_completer))]
     pub fields: Option<String>,
+
+    /// Stream JSON output as one object per line
+    #[arg(long)]
+    pub ndjson: bool,
+
+    /// Render each issue through a template, e.g. `'{{id}} {{title|truncate(40)}}'`,
+    /// or the name of a `templates.<name>` config entry
+    #[arg(long, value_name = "TEMPLATE")]
+    pub template: Option<String>,
 }
 
 /// Arguments for the search command.
@@ -1414,6 +1495,18 @@ pub struct SearchArgs {
     pub filters: ListArgs,
 }
 
+/// Arguments for the edit command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct EditArgs {
+    /// Issue ID (default: last touched issue)
+    #[arg(add = ArgValueCompleter::new(issue_id_completer))]
+    pub id: Option<String>,
+
+    /// Save even if the new status or assignee is over a WIP limit
+    #[arg(long)]
+    pub force: bool,
+}
+
 /// Arguments for the show command.
 #[derive(Args, Debug, Clone, Default)]
 pub struct ShowArgs {
@@ -1432,6 +1525,19 @@ pub struct ShowArgs {
     /// Show token savings stats when using TOON output
     #[arg(long)]
     pub stats: bool,
+
+    /// Only include these top-level keys in JSON/TOON output (comma-separated)
+    #[arg(long, value_name = "FIELDS")]
+    pub fields: Option<String>,
+
+    /// Stream JSON output as one object per line
+    #[arg(long)]
+    pub ndjson: bool,
+
+    /// Render each issue through a template, e.g. `'{{id}} {{title|truncate(40)}}'`,
+    /// or the name of a `templates.<name>` config entry
+    #[arg(long, value_name = "TEMPLATE")]
+    pub template: Option<String>,
 }
 
 #[derive(Subcommand, Debug)]
@@ -1678,6 +1784,10 @@ pub enum AuditCommands {
     Log(AuditLogArgs),
     /// View audit summary
     Summary(AuditSummaryArgs),
+    /// Verify the interaction log's hash chain
+    Verify(AuditVerifyArgs),
+    /// Export the interaction log, optionally with a signed manifest
+    Export(AuditExportArgs),
 }
 
 #[derive(Args, Debug, Clone)]
@@ -1748,6 +1858,28 @@ pub struct AuditSummaryArgs {
     pub days: u32,
 }
 
+#[derive(Args, Debug, Clone, Default)]
+pub struct AuditVerifyArgs {
+    /// Accept entries chained to an earlier entry, as left by a union merge
+    #[arg(long)]
+    pub allow_merged: bool,
+}
+
+#[derive(Args, Debug, Clone, Default)]
+pub struct AuditExportArgs {
+    /// Write the export to this file (default: stdout)
+    #[arg(long, short = 'o')]
+    pub output: Option<PathBuf>,
+
+    /// Also write `<output>.manifest.json`, signed with `BEADS_AUDIT_SIGNING_KEY`
+    #[arg(long, requires = "output")]
+    pub signed_manifest: bool,
+
+    /// Accept entries chained to an earlier entry, as left by a union merge
+    #[arg(long)]
+    pub allow_merged: bool,
+}
+
 #[derive(Args, Debug, Clone)]
 #[allow(clippy::struct_excessive_bools)]
 pub struct CountArgs {
@@ -1806,6 +1938,10 @@ pub struct CountArgs {
     /// Title contains substring
     #[arg(long)]
     pub title_contains: Option<String>,
+
+    /// Filter expression (same syntax as `br list --where`)
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_: Option<String>,
 }
 
 #[derive(ValueEnum, Debug, Clone, Copy, Eq, PartialEq)]
@@ -1817,6 +1953,58 @@ pub enum CountBy {
     Label,
 }
 
+/// Arguments for the board command.
+#[derive(Args, Debug, Clone)]
+pub struct BoardArgs {
+    /// What the columns are
+    #[arg(long, value_enum, default_value_t = BoardBy::Status)]
+    pub by: BoardBy,
+
+    /// Label prefix for `--by label-prefix` (e.g. `stage:`); columns are the rest of the label
+    #[arg(long, value_name = "PREFIX")]
+    pub prefix: Option<String>,
+
+    /// How far back the closed column reaches (e.g. 7d, 2w)
+    #[arg(long, value_name = "DURATION", default_value = "7d")]
+    pub recent: String,
+
+    /// Maximum cards per column (0 = unlimited)
+    #[arg(long, default_value_t = 20)]
+    pub limit: usize,
+
+    /// Filter by assignee
+    #[arg(long, add = ArgValueCompleter::new(assignee_completer))]
+    pub assignee: Option<String>,
+
+    /// Filter by label (repeatable, AND)
+    #[arg(long, short = 'l', add = ArgValueCompleter::new(label_completer))]
+    pub label: Vec<String>,
+
+    /// Filter by issue type (repeatable or comma-separated)
+    #[arg(long = "type", short = 't', value_delimiter = ',', add = ArgValueCompleter::new(issue_type_completer_delimited))]
+    pub types: Vec<String>,
+
+    /// Filter by priority (0-4 or P0-P4; repeatable or comma-separated)
+    #[arg(long, short = 'p', value_delimiter = ',', add = ArgValueCompleter::new(priority_completer_delimited))]
+    pub priority: Vec<String>,
+
+    /// Filter expression (same syntax as `br list --where`)
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_: Option<String>,
+}
+
+/// Column grouping for the board command.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum BoardBy {
+    /// open, `in_progress`, blocked, deferred, recently closed
+    #[default]
+    Status,
+    /// One column per assignee
+    Assignee,
+    /// One column per label with `--prefix`
+    LabelPrefix,
+}
+
 #[derive(Args, Debug, Clone)]
 pub struct StaleArgs {
     /// Minimum days since last update
@@ -1826,6 +2014,42 @@ pub struct StaleArgs {
     /// Filter by status (repeatable or comma-separated)
     #[arg(long, value_delimiter = ',', add = ArgValueCompleter::new(status_completer_delimited))]
     pub status: Vec<String>,
+
+    /// Only include these top-level keys in JSON/TOON output (comma-separated)
+    #[arg(long, value_name = "FIELDS")]
+    pub fields: Option<String>,
+
+    /// Stream JSON output as one object per line
+    #[arg(long)]
+    pub ndjson: bool,
+}
+
+/// Arguments for the overdue command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct OverdueArgs {
+    /// Only show issues assigned to this person
+    #[arg(long, add = ArgValueCompleter::new(assignee_completer))]
+    pub assignee: Option<String>,
+}
+
+/// Arguments for the due command.
+#[derive(Args, Debug, Clone)]
+pub struct DueArgs {
+    /// How far ahead to look (e.g. 3d, 2w, 36h)
+    #[arg(long, default_value = "7d")]
+    pub within: String,
+
+    /// Only show issues assigned to this person
+    #[arg(long, add = ArgValueCompleter::new(assignee_completer))]
+    pub assignee: Option<String>,
+}
+
+/// Arguments for the escalate command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct EscalateArgs {
+    /// Show which escalations would fire without applying them
+    #[arg(long)]
+    pub dry_run: bool,
 }
 
 #[derive(Args, Debug, Clone, Default)]
@@ -1854,6 +2078,10 @@ pub struct DeferArgs {
     #[arg(long)]
     pub until: Option<String>,
 
+    /// Defer even if it puts the deferred status over a WIP limit
+    #[arg(long)]
+    pub force: bool,
+
     /// Machine-readable output (alias for --json)
     #[arg(long)]
     pub robot: bool,
@@ -1866,6 +2094,10 @@ pub struct UndeferArgs {
     #[arg(add = ArgValueCompleter::new(open_issue_id_completer))]
     pub ids: Vec<String>,
 
+    /// Undefer even if it puts the open status over a WIP limit
+    #[arg(long)]
+    pub force: bool,
+
     /// Machine-readable output (alias for --json)
     #[arg(long)]
     pub robot: bool,
@@ -1931,6 +2163,19 @@ pub struct ReadyArgs {
     #[arg(long)]
     pub stats: bool,
 
+    /// Only include these top-level keys in JSON/TOON output (comma-separated)
+    #[arg(long, value_name = "FIELDS")]
+    pub fields: Option<String>,
+
+    /// Stream JSON output as one object per line
+    #[arg(long)]
+    pub ndjson: bool,
+
+    /// Render each issue through a template, e.g. `'{{id}} {{title|truncate(40)}}'`,
+    /// or the name of a `templates.<name>` config entry
+    #[arg(long, value_name = "TEMPLATE")]
+    pub template: Option<String>,
+
     /// Machine-readable output (alias for --json)
     #[arg(long)]
     pub robot: bool,
@@ -1972,6 +2217,14 @@ pub struct BlockedArgs {
     #[arg(long)]
     pub stats: bool,
 
+    /// Only include these top-level keys in JSON/TOON output (comma-separated)
+    #[arg(long, value_name = "FIELDS")]
+    pub fields: Option<String>,
+
+    /// Stream JSON output as one object per line
+    #[arg(long)]
+    pub ndjson: bool,
+
     /// Machine-readable output (alias for --json)
     #[arg(long)]
     pub robot: bool,
@@ -2016,6 +2269,10 @@ pub struct ReopenArgs {
     #[arg(long, short = 'r')]
     pub reason: Option<String>,
 
+    /// Reopen even if it puts the open status over a WIP limit
+    #[arg(long)]
+    pub force: bool,
+
     /// Machine-readable output (alias for --json)
     #[arg(long)]
     pub robot: bool,
@@ -2031,6 +2288,8 @@ pub enum SortPolicy {
     Priority,
     /// Sort by `created_at` ASC only
     Oldest,
+    /// Earliest `due_at` first (no due date last), then priority
+    Due,
 }
 
 /// Arguments for the sync command.
@@ -2182,9 +2441,30 @@ pub struct StatsArgs {
     #[arg(long, default_value_t = 24)]
     pub activity_hours: u32,
 
-    /// Output format (text, json, toon). Env: BR_OUTPUT_FORMAT, TOON_DEFAULT_FORMAT.
+    /// Show flow metrics: lead/cycle time, throughput, WIP, burndown
+    #[arg(long)]
+    pub flow: bool,
+
+    /// Show cumulative flow diagram data from daily snapshots
+    #[arg(long, conflicts_with = "flow")]
+    pub cfd: bool,
+
+    /// Window for time-series stats (e.g. 60d, 12w, or a date) [default: 90d]
+    #[arg(long)]
+    pub since: Option<String>,
+
+    /// Limit flow metrics to an epic and its descendants
+    #[arg(long, requires = "flow", conflicts_with = "label")]
+    pub epic: Option<String>,
+
+    /// Limit flow metrics to issues with this label
+    #[arg(long, requires = "flow")]
+    pub label: Option<String>,
+
+    /// Output format (text, json, csv, toon); csv applies to flow metrics.
+    /// Env: BR_OUTPUT_FORMAT, TOON_DEFAULT_FORMAT.
     #[arg(long, value_enum)]
-    pub format: Option<OutputFormatBasic>,
+    pub format: Option<OutputFormat>,
 
     /// Show token savings stats when using TOON output
     #[arg(long)]
@@ -2278,6 +2558,17 @@ pub struct OrphansArgs {
     pub robot: bool,
 }
 
+/// Arguments for the scan command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct ScanArgs {
+    /// Files or directories to scan [default: current directory]
+    pub paths: Vec<PathBuf>,
+
+    /// File issues for untracked comments and record moved ones
+    #[arg(long)]
+    pub create: bool,
+}
+
 /// Arguments for the changelog command.
 #[derive(Args, Debug, Clone, Default)]
 pub struct ChangelogArgs {
@@ -2293,11 +2584,54 @@ pub struct ChangelogArgs {
     #[arg(long, conflicts_with_all = ["since", "since_tag"])]
     pub since_commit: Option<String>,
 
+    /// End at a git tag/commit date, or a date (RFC3339, YYYY-MM-DD, relative)
+    #[arg(long)]
+    pub until: Option<String>,
+
+    /// Render as a document: keep-a-changelog, markdown, conventional
+    #[arg(long, value_enum)]
+    pub format: Option<ChangelogFormat>,
+
+    /// Group entries by issue type, first label, or parent epic
+    #[arg(long, value_enum, default_value_t = ChangelogGroupBy::Type)]
+    pub group_by: ChangelogGroupBy,
+
+    /// Release name for the section heading [default: Unreleased]
+    #[arg(long)]
+    pub release: Option<String>,
+
+    /// Insert the section into this changelog file, replacing a section for the same release
+    #[arg(long, requires = "format")]
+    pub prepend: Option<PathBuf>,
+
     /// Machine-readable output (alias for --json)
     #[arg(long)]
     pub robot: bool,
 }
 
+/// Document formats for `br changelog --format`.
+#[derive(ValueEnum, Debug, Clone, Copy, Eq, PartialEq)]
+pub enum ChangelogFormat {
+    /// Keep a Changelog sections (Added, Changed, Fixed, ...)
+    KeepAChangelog,
+    /// Plain markdown grouped as with --group-by
+    Markdown,
+    /// Conventional-commit style entries (feat:, fix:, ...)
+    Conventional,
+}
+
+/// How `br changelog` groups entries.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ChangelogGroupBy {
+    /// Issue type
+    #[default]
+    Type,
+    /// First label in alphabetical order
+    Label,
+    /// Parent epic
+    Epic,
+}
+
 /// Subcommands for the query command.
 #[derive(Subcommand, Debug)]
 pub enum QueryCommands {
@@ -2390,3 +2724,271 @@ pub struct AgentsArgs {
     #[arg(long, short = 'f')]
     pub force: bool,
 }
+
+/// Arguments for the serve command.
+#[derive(Args, Debug, Clone)]
+pub struct ServeArgs {
+    /// Address to listen on (host:port, or a bare port for 127.0.0.1)
+    #[arg(long, value_name = "ADDR", default_value = "127.0.0.1:7777")]
+    pub http: String,
+
+    /// Allow listening on a non-loopback address (the API has no authentication)
+    #[arg(long)]
+    pub allow_remote: bool,
+
+    /// Accept browser requests from this origin (repeatable, e.g. http://localhost:3000)
+    #[arg(long = "allow-origin", value_name = "ORIGIN")]
+    pub allow_origin: Vec<String>,
+}
+
+/// Arguments for the watch command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct WatchArgs {
+    /// Replay events after this event ID (default: only new events)
+    #[arg(long, value_name = "EVENT_ID")]
+    pub since: Option<i64>,
+
+    /// Only emit records for these issue IDs
+    #[arg(long, add = ArgValueCompleter::new(issue_id_completer))]
+    pub issue: Vec<String>,
+
+    /// Only emit these kinds (event types like `closed`, or `blocked`, `unblocked`, `sync_import`)
+    #[arg(long = "type", short = 't', value_name = "KIND", value_delimiter = ',')]
+    pub kinds: Vec<String>,
+
+    /// Only emit events recorded by this actor
+    #[arg(long)]
+    pub actor: Option<String>,
+
+    /// Only emit records for issues with this label (repeatable, AND)
+    #[arg(long, short = 'l', add = ArgValueCompleter::new(label_completer))]
+    pub label: Vec<String>,
+
+    /// Poll interval in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    pub interval: u64,
+
+    /// Emit pending records and exit instead of streaming
+    #[arg(long)]
+    pub once: bool,
+}
+
+/// Arguments for the tui command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct TuiArgs {
+    /// Start in the list view filtered by this expression (see `br list --where`)
+    #[arg(long = "where", value_name = "EXPR")]
+    pub where_: Option<String>,
+
+    /// How often to check the database for changes, in milliseconds
+    #[arg(long, value_name = "MS", default_value_t = 500)]
+    pub interval: u64,
+}
+
+/// Subcommands for the snapshot command.
+#[derive(Subcommand, Debug, Clone)]
+pub enum SnapshotCommands {
+    /// Record today's per-status, per-type and per-label counts (replaces today's)
+    Take,
+}
+
+/// Subcommands for the git command.
+#[derive(Subcommand, Debug, Clone)]
+pub enum GitCommands {
+    /// Record which commits mention which issues (incremental)
+    Index(GitIndexArgs),
+    /// Close issues named in `Closes:`/`Fixes:`/`Resolves:` commit trailers
+    ApplyTrailers(GitApplyTrailersArgs),
+}
+
+/// Arguments for the git index command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct GitIndexArgs {
+    /// Drop the existing index and scan the whole history again
+    #[arg(long)]
+    pub rebuild: bool,
+}
+
+/// Arguments for the git apply-trailers command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct GitApplyTrailersArgs {
+    /// Show which issues would be closed without closing them
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Close issues even if they are blocked by open dependencies
+    #[arg(long)]
+    pub force: bool,
+}
+
+/// Subcommands for the hooks command.
+#[derive(Subcommand, Debug, Clone)]
+pub enum HooksCommands {
+    /// Install git hooks and register the JSONL merge driver
+    Install(HooksInstallArgs),
+    /// Remove br-managed git hooks and the merge driver registration
+    Uninstall,
+    /// Show which git hooks and merge driver settings are installed
+    Status,
+    /// Allow this workspace's event hooks (`.beads/hooks/`, `hooks.*` config) to run on this machine
+    Trust(HooksTrustArgs),
+}
+
+/// Arguments for the hooks trust command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct HooksTrustArgs {
+    /// Stop trusting this workspace's event hooks
+    #[arg(long)]
+    pub revoke: bool,
+}
+
+/// Arguments for the hooks install command.
+#[derive(Args, Debug, Clone, Default)]
+pub struct HooksInstallArgs {
+    /// Replace existing hooks not managed by br (the original is kept as a backup)
+    #[arg(long, short = 'f')]
+    pub force: bool,
+
+    /// Install hooks only, without registering the merge driver
+    #[arg(long)]
+    pub no_merge_driver: bool,
+}
+
+/// Import subcommands.
+#[derive(Subcommand, Debug)]
+pub enum ImportCommands {
+    /// Import a GitHub issue export (`gh issue list --json ...` or REST API JSON)
+    Github(ImportGithubArgs),
+    /// Import a Jira, Linear or generic CSV export
+    Csv(ImportCsvArgs),
+}
+
+/// Arguments for the import github command.
+#[derive(Args, Debug, Clone)]
+pub struct ImportGithubArgs {
+    /// JSON file to import
+    pub file: PathBuf,
+
+    /// Repository (`owner/name`) the export came from [default: taken from issue URLs]
+    #[arg(long)]
+    pub repo: Option<String>,
+
+    /// Show what would be imported without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Arguments for the import csv command.
+#[derive(Args, Debug, Clone)]
+pub struct ImportCsvArgs {
+    /// CSV file to import
+    pub file: PathBuf,
+
+    /// Column layout of the export
+    #[arg(long, value_enum, default_value_t)]
+    pub preset: CsvPreset,
+
+    /// Read a column into a br field, e.g. `--map "Story Key=external_ref"` (repeatable)
+    #[arg(long = "map", value_name = "COLUMN=FIELD")]
+    pub map: Vec<String>,
+
+    /// Show what would be imported without writing anything
+    #[arg(long)]
+    pub dry_run: bool,
+}
+
+/// Column layouts understood by `br import csv`.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum CsvPreset {
+    /// Jira issue search export (`Issue key`, `Summary`, ...)
+    Jira,
+    /// Linear export (`ID`, `Title`, ...)
+    Linear,
+    /// Columns named after br fields (`external_ref`, `title`, ...)
+    #[default]
+    Generic,
+}
+
+/// Arguments for the export command.
+#[derive(Args, Debug, Clone)]
+pub struct ExportArgs {
+    /// Output format
+    #[arg(long, value_enum, default_value_t)]
+    pub format: ExportFormat,
+
+    /// Write to this file instead of stdout
+    #[arg(short, long)]
+    pub output: Option<PathBuf>,
+
+    /// Include closed issues
+    #[arg(long)]
+    pub all: bool,
+}
+
+/// Formats understood by `br export`.
+#[derive(ValueEnum, Debug, Clone, Copy, Default, Eq, PartialEq)]
+pub enum ExportFormat {
+    /// Markdown plan that `br create --file` reads back, updating issues by ID
+    #[default]
+    Markdown,
+}
+
+/// Arguments for the merge-driver command.
+#[derive(Args, Debug, Clone)]
+pub struct MergeDriverArgs {
+    /// Common ancestor version of the JSONL file (git's %O)
+    pub base: PathBuf,
+
+    /// Current branch version (git's %A); the merge result is written here
+    pub ours: PathBuf,
+
+    /// Other branch version (git's %B)
+    pub theirs: PathBuf,
+
+    /// Path of the merged file in the repository (git's %P), used to place the conflict report
+    pub path: Option<PathBuf>,
+}
+
+/// Arguments for the compact command.
+#[derive(Args, Debug, Clone)]
+pub struct CompactArgs {
+    /// Only compact issues closed longer ago than this (e.g. 90d, 12w)
+    #[arg(long, value_name = "AGE", default_value = "90d")]
+    pub older_than: String,
+
+    /// Compaction level: 1 summarizes text and comments, 2 keeps only a one-line summary
+    #[arg(long, default_value_t = 1, value_parser = clap::value_parser!(u8).range(1..=2))]
+    pub level: u8,
+
+    /// Maximum number of issues to compact
+    #[arg(long)]
+    pub limit: Option<usize>,
+
+    /// Show what would be compacted without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Restore the original content of compacted issues from the archive
+    #[arg(long, value_name = "ID", num_args = 1.., add = ArgValueCompleter::new(issue_id_completer))]
+    pub restore: Vec<String>,
+}
+
+/// Arguments for the gc command.
+#[derive(Args, Debug, Clone)]
+pub struct GcArgs {
+    /// Prune events, dirty flags and export hashes older than this (e.g. 365d, 12w)
+    #[arg(long, value_name = "AGE", default_value = "365d")]
+    pub older_than: String,
+
+    /// Tombstone retention in days (default: `deletions_retention_days` from metadata.json)
+    #[arg(long, value_name = "DAYS")]
+    pub tombstone_days: Option<u64>,
+
+    /// Report what would be removed without changing anything
+    #[arg(long)]
+    pub dry_run: bool,
+
+    /// Skip VACUUM and ANALYZE
+    #[arg(long)]
+    pub no_vacuum: bool,
+}
@@ -12,6 +12,7 @@
 pub mod routing;
 
 use crate::error::{BeadsError, Result};
+use crate::hooks::{DEFAULT_HOOK_TIMEOUT, HookRunner, HookTrust};
 use crate::model::{IssueType, Priority};
 use crate::storage::SqliteStorage;
 use crate::sync::{
@@ -19,12 +20,13 @@ use crate::sync::{
 };
 use crate::util::id::IdConfig;
 use serde::{Deserialize, Serialize};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::env;
 use std::fs;
 use std::io::{BufRead, IsTerminal};
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
+use std::time::Duration;
 use tracing::warn;
 
 /// Default database filename used when metadata is missing.
@@ -35,14 +37,17 @@ const DEFAULT_JSONL_FILENAME: &str = "issues.jsonl";
 const LEGACY_JSONL_FILENAME: &str = "beads.jsonl";
 
 /// JSONL files that should never be treated as the main export file.
-/// Includes merge artifacts, deletion logs, and interaction logs.
+/// Includes merge artifacts, deletion logs, interaction logs, the shared
+/// event log, and the compaction archive.
 const EXCLUDED_JSONL_FILES: &[&str] = &[
     "deletions.jsonl",
     "interactions.jsonl",
+    "events.jsonl",
     "beads.base.jsonl",
     "beads.left.jsonl",
     "beads.right.jsonl",
     "sync_base.jsonl",
+    "compaction-archive.jsonl",
 ];
 
 /// Startup metadata describing DB + JSONL paths.
@@ -394,13 +399,19 @@ pub fn open_storage_with_cli(beads_dir: &Path, cli: &CliOverrides) -> Result<Ope
             )?;
         }
 
+        if let Some(hooks) = hook_runner_from_layer(&merged_layer, beads_dir) {
+            storage.set_hooks(hooks);
+        }
         Ok(OpenStorageResult {
             storage,
             paths,
             no_db,
         })
     } else {
-        let storage = SqliteStorage::open_with_timeout(&paths.db_path, resolved_lock_timeout)?;
+        let mut storage = SqliteStorage::open_with_timeout(&paths.db_path, resolved_lock_timeout)?;
+        if let Some(hooks) = hook_runner_from_layer(&merged_layer, beads_dir) {
+            storage.set_hooks(hooks);
+        }
         Ok(OpenStorageResult {
             storage,
             paths,
@@ -960,6 +971,339 @@ pub fn external_project_db_paths(
     db_paths
 }
 
+/// Hook commands from config (`hooks.<name>: <shell command>`), keyed by hook name.
+///
+/// Accepts `on_close`, `on-close` and env-style (`BD_HOOKS_ON_CLOSE`) spellings.
+#[must_use]
+pub fn hook_commands_from_layer(layer: &ConfigLayer) -> HashMap<String, String> {
+    let mut commands = HashMap::new();
+    for (key, value) in layer.runtime.iter().chain(layer.startup.iter()) {
+        let normalized = normalize_key(key);
+        let Some(name) = normalized.strip_prefix("hooks.") else {
+            continue;
+        };
+        if name == "timeout" {
+            continue;
+        }
+        commands.insert(name.replace(['-', '.'], "_"), value.trim().to_string());
+    }
+    commands
+}
+
+/// Per-hook timeout from config (`hooks.timeout`, in seconds).
+#[must_use]
+pub fn hook_timeout_from_layer(layer: &ConfigLayer) -> Duration {
+    get_startup_value(layer, &["hooks.timeout"])
+        .and_then(|value| value.trim().parse::<u64>().ok())
+        .map_or(DEFAULT_HOOK_TIMEOUT, Duration::from_secs)
+}
+
+/// Build the event hook runner for a workspace, if any hooks are installed.
+///
+/// Installed hooks are returned regardless of trust; see
+/// [`hook_runner_from_layer`] for the runner mutations use.
+#[must_use]
+pub fn discover_hooks_from_layer(layer: &ConfigLayer, beads_dir: &Path) -> Option<HookRunner> {
+    HookRunner::discover(
+        beads_dir,
+        hook_commands_from_layer(layer),
+        hook_timeout_from_layer(layer),
+    )
+}
+
+/// Build the event hook runner for a workspace, if hooks are installed and
+/// trusted on this machine (`br hooks trust`).
+#[must_use]
+pub fn hook_runner_from_layer(layer: &ConfigLayer, beads_dir: &Path) -> Option<HookRunner> {
+    let runner = discover_hooks_from_layer(layer, beads_dir)?;
+    let trusted = HookTrust::default_path()
+        .map(|path| HookTrust::load(&path))
+        .transpose()
+        .unwrap_or_else(|err| {
+            warn!(error = %err, "Failed to read hook trust file");
+            None
+        })
+        .is_some_and(|trust| trust.is_trusted(beads_dir, &runner.fingerprint()));
+    if !trusted {
+        warn!(
+            hooks = ?runner.installed(),
+            "Event hooks are not trusted on this machine and will not run; review them and run `br hooks trust`"
+        );
+        return None;
+    }
+    Some(runner)
+}
+
+/// Whether audit events are shared through `events.jsonl` (`sync.events`, default off).
+#[must_use]
+pub fn share_events_from_layer(layer: &ConfigLayer) -> bool {
+    get_startup_value(layer, &["sync.events"])
+        .and_then(|value| parse_bool(value))
+        .unwrap_or(false)
+}
+
+/// Resolve a value mapping for `br import csv` from config.
+///
+/// Reads `import.<table>.<source value>` keys, e.g. `import.status.Won't Do`
+/// set to `closed`. Source values are lowercased so lookups can ignore case.
+#[must_use]
+pub fn import_mapping_from_layer(layer: &ConfigLayer, table: &str) -> HashMap<String, String> {
+    let prefix = format!("import.{table}.");
+    let mut map = HashMap::new();
+
+    for (key, value) in layer.runtime.iter().chain(layer.startup.iter()) {
+        let key = key.to_lowercase();
+        let Some(source) = key.strip_prefix(&prefix).map(str::trim) else {
+            continue;
+        };
+        if !source.is_empty() {
+            map.insert(source.to_string(), value.trim().to_string());
+        }
+    }
+
+    map
+}
+
+/// Rules for the semver bump `br changelog` suggests.
+///
+/// An issue whose type or label is listed under `major` forces a major bump,
+/// and likewise for `minor`; any other closed issue is a patch.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BumpRules {
+    pub major_types: Vec<String>,
+    pub major_labels: Vec<String>,
+    pub minor_types: Vec<String>,
+    pub minor_labels: Vec<String>,
+}
+
+impl Default for BumpRules {
+    fn default() -> Self {
+        Self {
+            major_types: Vec::new(),
+            major_labels: vec!["breaking".to_string(), "breaking-change".to_string()],
+            minor_types: vec!["feature".to_string()],
+            minor_labels: Vec::new(),
+        }
+    }
+}
+
+/// Resolve changelog bump rules from config.
+///
+/// Reads comma-separated `changelog.major-types`, `changelog.major-labels`,
+/// `changelog.minor-types` and `changelog.minor-labels`; unset keys keep
+/// their defaults and an empty value clears the list.
+#[must_use]
+pub fn bump_rules_from_layer(layer: &ConfigLayer) -> BumpRules {
+    let list = |name: &str, default: Vec<String>| {
+        let keys = [
+            format!("changelog.{name}"),
+            format!("changelog.{}", name.replace('-', "_")),
+        ];
+        get_value(layer, &[keys[0].as_str(), keys[1].as_str()]).map_or(default, |value| {
+            value
+                .split(',')
+                .map(|item| item.trim().to_lowercase())
+                .filter(|item| !item.is_empty())
+                .collect()
+        })
+    };
+    let defaults = BumpRules::default();
+    BumpRules {
+        major_types: list("major-types", defaults.major_types),
+        major_labels: list("major-labels", defaults.major_labels),
+        minor_types: list("minor-types", defaults.minor_types),
+        minor_labels: list("minor-labels", defaults.minor_labels),
+    }
+}
+
+/// What a WIP limit counts against.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum WipScope {
+    /// All issues in the status.
+    Total,
+    /// Each assignee separately.
+    PerAssignee,
+    /// One named assignee.
+    Assignee(String),
+    /// Issues carrying one label.
+    Label(String),
+}
+
+impl std::fmt::Display for WipScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Total => write!(f, "total"),
+            Self::PerAssignee => write!(f, "per_assignee"),
+            Self::Assignee(name) => write!(f, "assignee:{name}"),
+            Self::Label(label) => write!(f, "label:{label}"),
+        }
+    }
+}
+
+/// A work-in-progress cap on how many issues may sit in a status.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WipLimit {
+    pub status: String,
+    pub scope: WipScope,
+    pub limit: usize,
+}
+
+/// Resolve WIP limits from config.
+///
+/// Reads `wip_limits.<status>.<scope>` keys, where scope is `total`,
+/// `per_assignee`, `assignee:<name>` or `label:<name>`. Entries with an
+/// unknown scope or a non-numeric limit are ignored.
+#[must_use]
+pub fn wip_limits_from_layer(layer: &ConfigLayer) -> Vec<WipLimit> {
+    let mut limits: BTreeMap<(String, WipScope), usize> = BTreeMap::new();
+
+    for (key, value) in layer.runtime.iter().chain(layer.startup.iter()) {
+        let Some(rest) = key
+            .strip_prefix("wip_limits.")
+            .or_else(|| key.strip_prefix("wip-limits."))
+        else {
+            continue;
+        };
+        let Some((status, scope)) = rest.split_once('.') else {
+            continue;
+        };
+        let Some(scope) = parse_wip_scope(scope.trim()) else {
+            continue;
+        };
+        let Ok(limit) = value.trim().parse::<usize>() else {
+            continue;
+        };
+        let status = status.trim().to_lowercase().replace('-', "_");
+        // Runtime keys come first, so they win over startup ones.
+        limits.entry((status, scope)).or_insert(limit);
+    }
+
+    limits
+        .into_iter()
+        .map(|((status, scope), limit)| WipLimit {
+            status,
+            scope,
+            limit,
+        })
+        .collect()
+}
+
+fn parse_wip_scope(scope: &str) -> Option<WipScope> {
+    if let Some(label) = scope.strip_prefix("label:") {
+        return Some(WipScope::Label(label.trim().to_string()));
+    }
+    if let Some(name) = scope.strip_prefix("assignee:") {
+        return Some(WipScope::Assignee(name.trim().to_string()));
+    }
+    match scope.to_lowercase().replace('-', "_").as_str() {
+        "total" => Some(WipScope::Total),
+        "per_assignee" => Some(WipScope::PerAssignee),
+        _ => None,
+    }
+}
+
+/// A due-date escalation rule from `escalation.<name>.*` config keys.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EscalationRule {
+    pub name: String,
+    /// When the rule fires relative to the due date: negative is before.
+    pub offset: chrono::Duration,
+    /// Raise priority to at least this level.
+    pub priority: Option<Priority>,
+    /// Add this label.
+    pub label: Option<String>,
+}
+
+/// Resolve due-date escalation rules from config, sorted by name.
+///
+/// Each rule reads `escalation.<name>.before` or `escalation.<name>.after`
+/// (a duration such as `2d`; neither means on the due date) plus at least one
+/// action: `escalation.<name>.priority` and/or `escalation.<name>.label`.
+/// Rules with an invalid duration or priority, or no action, are ignored.
+#[must_use]
+pub fn escalation_rules_from_layer(layer: &ConfigLayer) -> Vec<EscalationRule> {
+    let mut fields: BTreeMap<String, HashMap<String, String>> = BTreeMap::new();
+    for (key, value) in layer.startup.iter().chain(layer.runtime.iter()) {
+        let Some((name, field)) = key
+            .strip_prefix("escalation.")
+            .and_then(|rest| rest.split_once('.'))
+        else {
+            continue;
+        };
+        // Runtime keys come last, so they win over startup ones.
+        fields
+            .entry(name.trim().to_string())
+            .or_default()
+            .insert(field.trim().to_lowercase(), value.trim().to_string());
+    }
+
+    fields
+        .into_iter()
+        .filter_map(|(name, fields)| {
+            let offset = match (fields.get("before"), fields.get("after")) {
+                (Some(before), None) => chrono::Duration::zero()
+                    .checked_sub(&crate::util::time::parse_duration(before)?)?,
+                (None, Some(after)) => crate::util::time::parse_duration(after)?,
+                (None, None) => chrono::Duration::zero(),
+                (Some(_), Some(_)) => return None,
+            };
+            let priority = fields
+                .get("priority")
+                .map(|value| value.parse::<Priority>())
+                .transpose()
+                .ok()?;
+            let label = fields
+                .get("label")
+                .filter(|label| !label.is_empty())
+                .cloned();
+            if priority.is_none() && label.is_none() {
+                return None;
+            }
+            Some(EscalationRule {
+                name,
+                offset,
+                priority,
+                label,
+            })
+        })
+        .collect()
+}
+
+/// Whether escalation rules run automatically before commands (`escalation.auto`).
+#[must_use]
+pub fn escalation_auto_from_layer(layer: &ConfigLayer) -> bool {
+    get_value(layer, &["escalation.auto"])
+        .or_else(|| get_startup_value(layer, &["escalation.auto"]))
+        .and_then(|value| parse_bool(value))
+        .unwrap_or(false)
+}
+
+/// Resolve a `--template` argument.
+///
+/// Anything containing `{{` is an inline template; otherwise the argument
+/// names a `templates.<name>` config entry, e.g.
+/// `templates.oneline: "{{id}} {{title}}"`.
+///
+/// # Errors
+///
+/// Returns a validation error if no template with that name is configured.
+pub fn output_template_from_layer(layer: &ConfigLayer, spec: &str) -> Result<String> {
+    if spec.contains("{{") {
+        return Ok(spec.to_string());
+    }
+    let name = spec.trim();
+    let key = format!("templates.{name}");
+    get_value(layer, &[key.as_str()])
+        .or_else(|| get_startup_value(layer, &[key.as_str()]))
+        .cloned()
+        .ok_or_else(|| {
+            BeadsError::validation(
+                "template",
+                format!("no template named '{name}' (set templates.{name} in config)"),
+            )
+        })
+}
+
 /// Resolve actor from a merged config layer.
 #[must_use]
 pub fn actor_from_layer(layer: &ConfigLayer) -> Option<String> {
@@ -996,6 +1340,7 @@ pub fn is_startup_key(key: &str) -> bool {
         || normalized.starts_with("directory.")
         || normalized.starts_with("sync.")
         || normalized.starts_with("external-projects.")
+        || normalized.starts_with("hooks.")
     {
         return true;
     }
@@ -1238,6 +1583,27 @@ labels:
         assert_eq!(layer.runtime.get("labels").unwrap(), "backend,api");
     }
 
+    #[test]
+    fn yaml_hooks_map_to_hook_commands() {
+        let yaml = r"
+hooks:
+  on_close: ./scripts/notify.sh
+  pre-update: ./scripts/policy.sh
+  timeout: 3
+";
+        let value: serde_yaml::Value = serde_yaml::from_str(yaml).expect("parse yaml");
+        let layer = layer_from_yaml_value(&value);
+        let commands = hook_commands_from_layer(&layer);
+        assert_eq!(commands.len(), 2);
+        assert_eq!(commands["on_close"], "./scripts/notify.sh");
+        assert_eq!(commands["pre_update"], "./scripts/policy.sh");
+        assert_eq!(hook_timeout_from_layer(&layer), Duration::from_secs(3));
+        assert_eq!(
+            hook_timeout_from_layer(&ConfigLayer::default()),
+            DEFAULT_HOOK_TIMEOUT
+        );
+    }
+
     #[test]
     fn id_config_parses_numeric_overrides() {
         let mut layer = ConfigLayer::default();
@@ -1624,6 +1990,7 @@ labels:
         assert!(is_startup_key("lock-timeout"));
         assert!(is_startup_key("git.branch")); // prefix check
         assert!(is_startup_key("routing.policy")); // prefix check
+        assert!(is_startup_key("hooks.on_close")); // prefix check
     }
 
     #[test]
@@ -1787,6 +2154,106 @@ routing:
         assert!(layer.startup.contains_key("routing.policy"));
     }
 
+    #[test]
+    fn share_events_from_layer_defaults_to_off() {
+        let layer = ConfigLayer::default();
+        assert!(!share_events_from_layer(&layer));
+
+        let mut enabled = ConfigLayer::default();
+        enabled
+            .startup
+            .insert("sync.events".to_string(), "true".to_string());
+        assert!(share_events_from_layer(&enabled));
+    }
+
+    #[test]
+    fn import_mapping_from_layer_reads_table() {
+        let mut layer = ConfigLayer::default();
+        layer
+            .runtime
+            .insert("import.status.Won't Do".to_string(), "closed".to_string());
+        layer
+            .runtime
+            .insert("import.priority.P1".to_string(), "1".to_string());
+
+        let status = import_mapping_from_layer(&layer, "status");
+        assert_eq!(status.len(), 1);
+        assert_eq!(status.get("won't do").map(String::as_str), Some("closed"));
+        assert!(import_mapping_from_layer(&layer, "type").is_empty());
+    }
+
+    #[test]
+    fn wip_limits_from_layer_parses_scopes() {
+        let mut layer = ConfigLayer::default();
+        for (key, value) in [
+            ("wip_limits.in_progress.per_assignee", "2"),
+            ("wip_limits.in_progress.label:backend", "5"),
+            ("wip-limits.review.total", "3"),
+            ("wip_limits.in_progress.bogus", "1"),
+            ("wip_limits.in_progress.total", "many"),
+        ] {
+            layer.runtime.insert(key.to_string(), value.to_string());
+        }
+
+        let limits = wip_limits_from_layer(&layer);
+        assert_eq!(limits.len(), 3);
+        assert!(limits.contains(&WipLimit {
+            status: "in_progress".to_string(),
+            scope: WipScope::PerAssignee,
+            limit: 2,
+        }));
+        assert!(limits.contains(&WipLimit {
+            status: "in_progress".to_string(),
+            scope: WipScope::Label("backend".to_string()),
+            limit: 5,
+        }));
+        assert_eq!(limits[2].scope.to_string(), "total");
+    }
+
+    #[test]
+    fn escalation_rules_from_layer_parses_rules() {
+        let mut layer = ConfigLayer::default();
+        for (key, value) in [
+            ("escalation.auto", "true"),
+            ("escalation.due-soon.before", "2d"),
+            ("escalation.due-soon.priority", "P1"),
+            ("escalation.overdue.after", "1d"),
+            ("escalation.overdue.label", "overdue"),
+            ("escalation.noop.before", "1d"),
+            ("escalation.bad.after", "soon"),
+            ("escalation.bad.label", "x"),
+        ] {
+            layer.runtime.insert(key.to_string(), value.to_string());
+        }
+
+        let rules = escalation_rules_from_layer(&layer);
+        assert_eq!(rules.len(), 2);
+        assert_eq!(rules[0].name, "due-soon");
+        assert_eq!(rules[0].offset, -chrono::Duration::days(2));
+        assert_eq!(rules[0].priority, Some(Priority(1)));
+        assert_eq!(rules[1].label.as_deref(), Some("overdue"));
+        assert!(escalation_auto_from_layer(&layer));
+    }
+
+    #[test]
+    fn output_template_from_layer_resolves_names() {
+        let mut layer = ConfigLayer::default();
+        layer.runtime.insert(
+            "templates.oneline".to_string(),
+            "{{id}} {{title}}".to_string(),
+        );
+
+        assert_eq!(
+            output_template_from_layer(&layer, "oneline").unwrap(),
+            "{{id}} {{title}}"
+        );
+        assert_eq!(
+            output_template_from_layer(&layer, "{{id}}").unwrap(),
+            "{{id}}"
+        );
+        assert!(output_template_from_layer(&layer, "missing").is_err());
+    }
+
     #[test]
     fn actor_from_layer_returns_none_for_empty() {
         let layer = ConfigLayer::default();
@@ -2011,6 +2478,11 @@ routing:
         assert!(is_excluded_jsonl("interactions.jsonl"));
     }
 
+    #[test]
+    fn is_excluded_jsonl_detects_event_log() {
+        assert!(is_excluded_jsonl("events.jsonl"));
+    }
+
     #[test]
     fn is_excluded_jsonl_allows_valid_files() {
         assert!(!is_excluded_jsonl("issues.jsonl"));
@@ -76,6 +76,19 @@ pub enum BeadsError {
     #[error("Invalid status: {status}")]
     InvalidStatus { status: String },
 
+    /// A pre-mutation hook vetoed the operation.
+    #[error("Hook '{hook}' rejected the operation: {reason}")]
+    HookRejected { hook: String, reason: String },
+
+    /// Moving an issue into a status would exceed a configured WIP limit.
+    #[error("WIP limit exceeded for {status} ({scope}): {current}/{limit}")]
+    WipLimitExceeded {
+        status: String,
+        scope: String,
+        current: usize,
+        limit: usize,
+    },
+
     /// Invalid issue type value.
     #[error("Invalid issue type: {issue_type}")]
     InvalidType { issue_type: String },
@@ -212,6 +225,7 @@ impl BeadsError {
             Self::HasDependents { .. }
                 | Self::ImportCollision { .. }
                 | Self::AlreadyInitialized { .. }
+                | Self::WipLimitExceeded { .. }
         )
     }
 
@@ -234,6 +248,12 @@ impl BeadsError {
                 Some("Valid statuses: open, in_progress, blocked, deferred, closed")
             }
             Self::InvalidType { .. } => Some("Valid types: task, bug, feature, epic, chore"),
+            Self::HookRejected { .. } => {
+                Some("Address the hook's objection, or adjust .beads/hooks/")
+            }
+            Self::WipLimitExceeded { .. } => {
+                Some("Finish in-progress work first, or use --force to exceed the limit")
+            }
             _ => None,
         }
     }
@@ -65,6 +65,10 @@ pub enum ErrorCode {
     InvalidPriority,
     /// Required field missing
     RequiredField,
+    /// A pre-mutation hook vetoed the operation
+    HookRejected,
+    /// Transition would exceed a configured WIP limit
+    WipLimitExceeded,
 
     // === Dependency Errors (exit code 5) ===
     /// Dependency cycle detected
@@ -134,6 +138,8 @@ impl ErrorCode {
             Self::InvalidType => "INVALID_TYPE",
             Self::InvalidPriority => "INVALID_PRIORITY",
             Self::RequiredField => "REQUIRED_FIELD",
+            Self::HookRejected => "HOOK_REJECTED",
+            Self::WipLimitExceeded => "WIP_LIMIT_EXCEEDED",
             // Dependency
             Self::CycleDetected => "CYCLE_DETECTED",
             Self::DependencyNotFound => "DEPENDENCY_NOT_FOUND",
@@ -206,7 +212,9 @@ impl ErrorCode {
             | Self::InvalidStatus
             | Self::InvalidType
             | Self::InvalidPriority
-            | Self::RequiredField => 4,
+            | Self::RequiredField
+            | Self::HookRejected
+            | Self::WipLimitExceeded => 4,
             // Dependency (5)
             Self::CycleDetected
             | Self::DependencyNotFound
@@ -520,6 +528,24 @@ impl StructuredError {
                         .collect::<Vec<_>>()
                 })),
             ),
+            BeadsError::HookRejected { hook, reason } => (
+                ErrorCode::HookRejected,
+                Some(json!({"hook": hook, "reason": reason})),
+            ),
+            BeadsError::WipLimitExceeded {
+                status,
+                scope,
+                current,
+                limit,
+            } => (
+                ErrorCode::WipLimitExceeded,
+                Some(json!({
+                    "status": status,
+                    "scope": scope,
+                    "current": current,
+                    "limit": limit,
+                })),
+            ),
             BeadsError::InvalidStatus { status } => {
                 let hint = detect_status_intent(status)
                     .map(|detected| format!("Did you mean --status {detected}?"));
@@ -1029,6 +1055,31 @@ mod tests {
         assert!(err.context.as_ref().unwrap()["matches"].is_array());
     }
 
+    #[test]
+    fn test_structured_error_hook_rejected() {
+        let err = StructuredError::from_error(&BeadsError::HookRejected {
+            hook: "pre_close".to_string(),
+            reason: "exited with status 1".to_string(),
+        });
+        assert_eq!(err.code, ErrorCode::HookRejected);
+        assert_eq!(err.code.exit_code(), 4);
+        assert!(!err.retryable);
+        assert_eq!(err.context.as_ref().unwrap()["hook"], "pre_close");
+    }
+
+    #[test]
+    fn test_structured_error_wip_limit_exceeded() {
+        let err = StructuredError::from_error(&BeadsError::WipLimitExceeded {
+            status: "in_progress".to_string(),
+            scope: "assignee alice".to_string(),
+            current: 2,
+            limit: 2,
+        });
+        assert_eq!(err.code, ErrorCode::WipLimitExceeded);
+        assert_eq!(err.code.exit_code(), 4);
+        assert_eq!(err.context.as_ref().unwrap()["limit"], 2);
+    }
+
     #[test]
     fn test_to_human_output() {
         let err = StructuredError {
@@ -1,7 +1,8 @@
 //! CSV formatting for `beads_rust`.
 //!
 //! Provides CSV output for list/export commands. Handles proper escaping
-//! of fields containing commas, quotes, or newlines.
+//! of fields containing commas, quotes, or newlines, and reads such files
+//! back for `br import csv`.
 
 use crate::model::Issue;
 use std::io::{self, Write};
@@ -143,6 +144,85 @@ pub fn format_csv(issues: &[Issue], fields: &[&str]) -> String {
     String::from_utf8_lossy(&output).into_owned()
 }
 
+/// Parse CSV text into records.
+///
+/// Follows RFC 4180: fields may be quoted, quoted fields may contain commas,
+/// doubled quotes and line breaks, and records end in LF or CRLF. A leading
+/// byte order mark is skipped and blank lines are ignored. Stray quotes inside
+/// unquoted fields are kept as-is rather than rejected.
+///
+/// # Errors
+///
+/// Returns an `InvalidData` error if a quoted field is never closed.
+pub fn read_records(input: &str) -> io::Result<Vec<Vec<String>>> {
+    let input = input.strip_prefix('\u{feff}').unwrap_or(input);
+    let mut records = Vec::new();
+    let mut record = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut quoted = false;
+    let mut line = 1;
+    let mut quote_line = 1;
+
+    let mut chars = input.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '\n' {
+            line += 1;
+        }
+        if in_quotes {
+            if c != '"' {
+                field.push(c);
+            } else if chars.peek() == Some(&'"') {
+                chars.next();
+                field.push('"');
+            } else {
+                in_quotes = false;
+            }
+            continue;
+        }
+        match c {
+            '"' if field.is_empty() && !quoted => {
+                in_quotes = true;
+                quoted = true;
+                quote_line = line;
+            }
+            ',' => {
+                record.push(std::mem::take(&mut field));
+                quoted = false;
+            }
+            '\r' if chars.peek() == Some(&'\n') => {}
+            '\n' | '\r' => {
+                end_record(&mut records, &mut record, &mut field, quoted);
+                quoted = false;
+            }
+            _ => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("unterminated quoted field starting on line {quote_line}"),
+        ));
+    }
+    end_record(&mut records, &mut record, &mut field, quoted);
+    Ok(records)
+}
+
+fn end_record(
+    records: &mut Vec<Vec<String>>,
+    record: &mut Vec<String>,
+    field: &mut String,
+    quoted: bool,
+) {
+    // A line with nothing on it is not a record with one empty field
+    if record.is_empty() && field.is_empty() && !quoted {
+        return;
+    }
+    record.push(std::mem::take(field));
+    records.push(std::mem::take(record));
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -291,4 +371,40 @@ mod tests {
         write_header(&mut output, &["id", "title", "status"]).unwrap();
         assert_eq!(String::from_utf8(output).unwrap(), "id,title,status\n");
     }
+
+    #[test]
+    fn test_read_records_plain() {
+        let records = read_records("a,b,c\n1,2,3\n").unwrap();
+        assert_eq!(records, vec![vec!["a", "b", "c"], vec!["1", "2", "3"]]);
+    }
+
+    #[test]
+    fn test_read_records_quoted_fields() {
+        let input = "id,title\r\n1,\"Fix, then \"\"ship\"\"\"\r\n2,\"two\nlines\"\r\n";
+        let records = read_records(input).unwrap();
+        assert_eq!(records[1], vec!["1", "Fix, then \"ship\""]);
+        assert_eq!(records[2], vec!["2", "two\nlines"]);
+    }
+
+    #[test]
+    fn test_read_records_skips_bom_and_blank_lines() {
+        let records = read_records("\u{feff}a,b\n\n1,\n\"\"\n").unwrap();
+        assert_eq!(records, vec![vec!["a", "b"], vec!["1", ""], vec![""]]);
+    }
+
+    #[test]
+    fn test_read_records_round_trips_format_csv() {
+        let mut issue = make_test_issue("bd-1", "Comma, \"quote\"\nnewline");
+        issue.description = Some("d".to_string());
+        let csv = format_csv(&[issue], &["id", "title", "description"]);
+        let records = read_records(&csv).unwrap();
+        assert_eq!(records[1], vec!["bd-1", "Comma, \"quote\"\nnewline", "d"]);
+    }
+
+    #[test]
+    fn test_read_records_unterminated_quote() {
+        let err = read_records("a,b\n1,\"open\n").unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+        assert!(err.to_string().contains("line 2"));
+    }
 }
@@ -18,6 +18,16 @@
 //! - Configurable field selection via `--fields`
 //! - Proper escaping of commas, quotes, and newlines
 //!
+//! # Projection
+//!
+//! The [`projection`] module trims JSON/TOON rows to the `--fields` requested
+//! and streams `--ndjson` output one row per line.
+//!
+//! # Templates
+//!
+//! The [`template`] module renders rows through user `--template` strings such
+//! as `{{id}} [{{priority}}] {{title|truncate(40)}}`.
+//!
 //! # Rich Output
 //!
 //! The [`rich`] module provides enhanced terminal output using `rich_rust`:
@@ -36,8 +46,10 @@ pub mod context;
 pub mod csv;
 pub mod markdown;
 mod output;
+pub mod projection;
 pub mod rich;
 pub mod syntax;
+pub mod template;
 mod text;
 pub mod theme;
 
@@ -50,7 +62,7 @@ pub use text::{
     TextFormatOptions, format_issue_line, format_issue_line_with, format_priority,
     format_priority_badge, format_priority_label, format_status_icon, format_status_icon_colored,
     format_status_label, format_type_badge, format_type_badge_colored, terminal_width,
-    truncate_title,
+    truncate_title, wrap_text,
 };
 
 // Rich output support
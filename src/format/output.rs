@@ -1,4 +1,5 @@
-use crate::model::{Comment, Event, Issue, IssueType, Priority, Status};
+use crate::model::{Comment, CommitLink, Event, Issue, IssueType, Priority, Status};
+use crate::util::wip::WipUsage;
 use chrono::{DateTime, Utc};
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
@@ -127,6 +128,9 @@ pub struct IssueDetails {
     pub events: Vec<Event>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub parent: Option<String>,
+    /// Commits that mention the issue (see `br git index`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub commits: Vec<CommitLink>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
@@ -209,6 +213,9 @@ pub struct Statistics {
     pub breakdowns: Vec<Breakdown>,
     #[serde(skip_serializing_if = "Option::is_none")]
     pub recent_activity: Option<RecentActivity>,
+    /// Current WIP against each configured `wip_limits` entry.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub wip: Vec<WipUsage>,
 }
 
 #[cfg(test)]
@@ -286,6 +293,7 @@ mod tests {
             comments: vec![],
             events: vec![],
             parent: Some("bd-parent".to_string()),
+            commits: vec![],
         };
 
         let json = serde_json::to_string(&details).unwrap();
@@ -0,0 +1,183 @@
+//! Field projection and NDJSON output for machine-readable listings.
+//!
+//! `--fields id,title,labels` keeps only the named top-level keys of each
+//! JSON/TOON row, and `--ndjson` writes one row per line instead of a single
+//! array. Keys are matched after serialization, so projection works for every
+//! row type (`IssueWithCounts`, `ReadyIssue`, `IssueDetails`, ...); a requested
+//! key that a row omits (an unset `assignee`, say) is simply left out.
+
+use crate::error::Result;
+use serde::Serialize;
+use serde_json::{Map, Value};
+use std::io::{self, BufWriter, StdoutLock, Write};
+
+/// Parse a `--fields` list. Returns `None` (no projection) when unset or empty.
+#[must_use]
+pub fn parse_fields(spec: Option<&str>) -> Option<Vec<String>> {
+    let fields: Vec<String> = spec?
+        .split(',')
+        .map(str::trim)
+        .filter(|field| !field.is_empty())
+        .map(str::to_string)
+        .collect();
+    (!fields.is_empty()).then_some(fields)
+}
+
+/// Serialize `value`, keeping only `fields` of each object. A top-level array
+/// is projected element by element.
+///
+/// # Errors
+///
+/// Returns an error if `value` cannot be serialized.
+pub fn project<T: Serialize + ?Sized>(value: &T, fields: Option<&[String]>) -> Result<Value> {
+    let value = serde_json::to_value(value)?;
+    Ok(match fields {
+        Some(fields) => project_value(value, fields),
+        None => value,
+    })
+}
+
+fn project_value(value: Value, fields: &[String]) -> Value {
+    match value {
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| project_value(item, fields))
+                .collect(),
+        ),
+        Value::Object(mut object) => {
+            let mut projected = Map::new();
+            for field in fields {
+                if let Some(value) = object.remove(field) {
+                    projected.insert(field.clone(), value);
+                }
+            }
+            Value::Object(projected)
+        }
+        other => other,
+    }
+}
+
+/// Writes one JSON object per line to stdout as rows become available.
+pub struct NdjsonWriter {
+    out: BufWriter<StdoutLock<'static>>,
+    fields: Option<Vec<String>>,
+    closed: bool,
+}
+
+impl NdjsonWriter {
+    #[must_use]
+    pub fn new(fields: Option<Vec<String>>) -> Self {
+        Self {
+            out: BufWriter::new(io::stdout().lock()),
+            fields,
+            closed: false,
+        }
+    }
+
+    /// Write one row. Once the reader goes away (`br list --ndjson | head`),
+    /// further rows are dropped silently.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the row cannot be serialized or stdout fails.
+    pub fn write<T: Serialize + ?Sized>(&mut self, row: &T) -> Result<()> {
+        if self.closed {
+            return Ok(());
+        }
+        let written = match self.fields.as_deref() {
+            Some(fields) => serde_json::to_writer(&mut self.out, &project(row, Some(fields))?),
+            None => serde_json::to_writer(&mut self.out, row),
+        };
+        let result = written
+            .map_err(io::Error::from)
+            .and_then(|()| self.out.write_all(b"\n"));
+        self.settle(result)
+    }
+
+    /// Whether the reader has gone away, so further rows would be dropped.
+    #[must_use]
+    pub const fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Flush buffered rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if stdout fails.
+    pub fn finish(mut self) -> Result<()> {
+        let result = self.out.flush();
+        self.settle(result)
+    }
+
+    fn settle(&mut self, result: io::Result<()>) -> Result<()> {
+        match result {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => {
+                self.closed = true;
+                Ok(())
+            }
+            other => other.map_err(Into::into),
+        }
+    }
+}
+
+/// Write `rows` as NDJSON, projected to `fields`.
+///
+/// # Errors
+///
+/// Returns an error if a row cannot be serialized or stdout fails.
+pub fn write_ndjson<T: Serialize>(rows: &[T], fields: Option<Vec<String>>) -> Result<()> {
+    let mut writer = NdjsonWriter::new(fields);
+    for row in rows {
+        writer.write(row)?;
+    }
+    writer.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    #[test]
+    fn test_parse_fields() {
+        init_logging();
+        info!("test_parse_fields: starting");
+        assert_eq!(parse_fields(None), None);
+        assert_eq!(parse_fields(Some(" , ")), None);
+        assert_eq!(
+            parse_fields(Some("id, title,,labels")),
+            Some(vec![
+                "id".to_string(),
+                "title".to_string(),
+                "labels".to_string()
+            ])
+        );
+        info!("test_parse_fields: assertions passed");
+    }
+
+    #[test]
+    fn test_project_keeps_requested_keys() {
+        init_logging();
+        info!("test_project_keeps_requested_keys: starting");
+        let rows = json!([
+            {"id": "bd-1", "title": "One", "status": "open", "labels": ["ui"]},
+            {"id": "bd-2", "title": "Two", "status": "closed"}
+        ]);
+        let fields = parse_fields(Some("id,labels")).unwrap();
+
+        let projected = project(&rows, Some(&fields)).unwrap();
+        assert_eq!(
+            projected,
+            json!([{"id": "bd-1", "labels": ["ui"]}, {"id": "bd-2"}])
+        );
+        assert_eq!(project(&rows, None).unwrap(), rows);
+        info!("test_project_keeps_requested_keys: assertions passed");
+    }
+}
@@ -0,0 +1,338 @@
+//! User-defined output templates for `--template`.
+//!
+//! `{{field}}` is replaced by a key of the row's JSON form (`IssueWithCounts`
+//! for list/ready/search, `IssueDetails` for show); dotted paths such as
+//! `{{dependencies.0.id}}` reach into nested objects and arrays. Filters are
+//! chained with `|`:
+//!
+//! - `truncate(n)` - shorten to `n` columns
+//! - `date` / `date("%b %d")` - reformat a timestamp in local time
+//!   (chrono `strftime` syntax, default `%Y-%m-%d`)
+//! - `default("-")` - fallback when the value is missing, null or empty
+//! - `join(", ")` - join an array (arrays join with `, ` when printed as-is)
+//! - `upper`, `lower`, `len`
+//!
+//! Missing fields render as an empty string. Named templates live in config
+//! under `templates.<name>` (see [`crate::config::output_template_from_layer`]).
+
+use super::text::truncate_title;
+use crate::error::{BeadsError, Result};
+use chrono::format::{Item, StrftimeItems};
+use chrono::{DateTime, Local};
+use serde::Serialize;
+use serde_json::Value;
+use std::io::{self, BufWriter, Write};
+
+/// A parsed output template.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Template {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Text(String),
+    Field {
+        path: Vec<String>,
+        filters: Vec<Filter>,
+    },
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Filter {
+    Truncate(usize),
+    Date(String),
+    Default(String),
+    Join(String),
+    Upper,
+    Lower,
+    Len,
+}
+
+fn invalid(reason: impl Into<String>) -> BeadsError {
+    BeadsError::validation("template", reason)
+}
+
+impl Template {
+    /// Parse a template string.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error for an unclosed `{{`, an empty field or an
+    /// unknown or malformed filter.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut segments = Vec::new();
+        let mut rest = source;
+        while let Some(open) = rest.find("{{") {
+            if open > 0 {
+                segments.push(Segment::Text(rest[..open].to_string()));
+            }
+            let after = &rest[open + 2..];
+            let close = find_close(after).ok_or_else(|| invalid("unclosed '{{'"))?;
+            segments.push(parse_field(&after[..close])?);
+            rest = &after[close + 2..];
+        }
+        if !rest.is_empty() {
+            segments.push(Segment::Text(rest.to_string()));
+        }
+        Ok(Self { segments })
+    }
+
+    /// Render one row.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `row` cannot be serialized.
+    pub fn render<T: Serialize + ?Sized>(&self, row: &T) -> Result<String> {
+        let row = serde_json::to_value(row)?;
+        let mut out = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Text(text) => out.push_str(text),
+                Segment::Field { path, filters } => {
+                    let mut value = lookup(&row, path).cloned().unwrap_or(Value::Null);
+                    for filter in filters {
+                        value = filter.apply(value);
+                    }
+                    out.push_str(&to_text(&value));
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    /// Render `rows` to stdout, one per line. Once the reader goes away
+    /// (`br list --template ... | head`), the remaining rows are dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a row cannot be serialized or stdout fails.
+    pub fn print_rows<T: Serialize>(&self, rows: &[T]) -> Result<()> {
+        let mut out = BufWriter::new(io::stdout().lock());
+        let mut result = Ok(());
+        for row in rows {
+            let line = self.render(row)?;
+            result = writeln!(out, "{line}");
+            if result.is_err() {
+                break;
+            }
+        }
+        match result.and_then(|()| out.flush()) {
+            Err(err) if err.kind() == io::ErrorKind::BrokenPipe => Ok(()),
+            other => other.map_err(Into::into),
+        }
+    }
+}
+
+/// Find the `}}` closing a field, skipping quoted filter arguments.
+fn find_close(input: &str) -> Option<usize> {
+    let mut quote = None;
+    for (idx, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '}') if input[idx..].starts_with("}}") => return Some(idx),
+            (None, _) => {}
+        }
+    }
+    None
+}
+
+/// Split on `|` outside quotes.
+fn split_pipes(input: &str) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut quote = None;
+    let mut start = 0;
+    for (idx, c) in input.char_indices() {
+        match (quote, c) {
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '|') => {
+                parts.push(&input[start..idx]);
+                start = idx + 1;
+            }
+            (None, _) => {}
+        }
+    }
+    parts.push(&input[start..]);
+    parts
+}
+
+fn parse_field(expr: &str) -> Result<Segment> {
+    let mut parts = split_pipes(expr).into_iter();
+    let name = parts.next().unwrap_or_default().trim();
+    if name.is_empty() {
+        return Err(invalid("empty field in '{{}}'"));
+    }
+    let path = name.split('.').map(str::to_string).collect();
+    let filters = parts.map(parse_filter).collect::<Result<Vec<_>>>()?;
+    Ok(Segment::Field { path, filters })
+}
+
+fn unquote(arg: &str) -> &str {
+    let arg = arg.trim();
+    for quote in ['"', '\''] {
+        if let Some(inner) = arg
+            .strip_prefix(quote)
+            .and_then(|rest| rest.strip_suffix(quote))
+        {
+            return inner;
+        }
+    }
+    arg
+}
+
+fn parse_filter(spec: &str) -> Result<Filter> {
+    let spec = spec.trim();
+    let (name, arg) = match spec.find('(') {
+        Some(open) => {
+            let inner = spec[open + 1..]
+                .strip_suffix(')')
+                .ok_or_else(|| invalid(format!("missing ')' in filter '{spec}'")))?;
+            (spec[..open].trim(), Some(unquote(inner)))
+        }
+        None => (spec, None),
+    };
+    match (name, arg) {
+        ("truncate", Some(width)) => width
+            .parse()
+            .map(Filter::Truncate)
+            .map_err(|_| invalid(format!("truncate expects a width, got '{width}'"))),
+        ("date", format) => {
+            let format = format.unwrap_or("%Y-%m-%d");
+            if StrftimeItems::new(format).any(|item| matches!(item, Item::Error)) {
+                return Err(invalid(format!("invalid date format '{format}'")));
+            }
+            Ok(Filter::Date(format.to_string()))
+        }
+        ("default", Some(fallback)) => Ok(Filter::Default(fallback.to_string())),
+        ("join", separator) => Ok(Filter::Join(separator.unwrap_or(", ").to_string())),
+        ("upper", None) => Ok(Filter::Upper),
+        ("lower", None) => Ok(Filter::Lower),
+        ("len", None) => Ok(Filter::Len),
+        _ => Err(invalid(format!(
+            "unknown filter '{spec}' (known: truncate(n), date(fmt), default(value), join(sep), upper, lower, len)"
+        ))),
+    }
+}
+
+impl Filter {
+    fn apply(&self, value: Value) -> Value {
+        match self {
+            Self::Truncate(width) => Value::String(truncate_title(&to_text(&value), *width)),
+            Self::Date(format) => match value.as_str().map(DateTime::parse_from_rfc3339) {
+                Some(Ok(at)) => Value::String(at.with_timezone(&Local).format(format).to_string()),
+                _ => value,
+            },
+            Self::Default(fallback) => {
+                if to_text(&value).is_empty() {
+                    Value::String(fallback.clone())
+                } else {
+                    value
+                }
+            }
+            Self::Join(separator) => match value {
+                Value::Array(items) => Value::String(
+                    items
+                        .iter()
+                        .map(to_text)
+                        .collect::<Vec<_>>()
+                        .join(separator),
+                ),
+                other => other,
+            },
+            Self::Upper => Value::String(to_text(&value).to_uppercase()),
+            Self::Lower => Value::String(to_text(&value).to_lowercase()),
+            Self::Len => Value::from(match &value {
+                Value::Array(items) => items.len(),
+                Value::Object(map) => map.len(),
+                other => to_text(other).chars().count(),
+            }),
+        }
+    }
+}
+
+fn lookup<'a>(row: &'a Value, path: &[String]) -> Option<&'a Value> {
+    path.iter().try_fold(row, |value, key| match value {
+        Value::Object(map) => map.get(key),
+        Value::Array(items) => key.parse::<usize>().ok().and_then(|idx| items.get(idx)),
+        _ => None,
+    })
+}
+
+fn to_text(value: &Value) -> String {
+    match value {
+        Value::Null => String::new(),
+        Value::String(text) => text.clone(),
+        Value::Array(items) => items.iter().map(to_text).collect::<Vec<_>>().join(", "),
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    #[test]
+    fn test_render_fields_and_filters() {
+        init_logging();
+        info!("test_render_fields_and_filters: starting");
+        let row = json!({
+            "id": "bd-1",
+            "title": "A fairly long issue title",
+            "priority": 1,
+            "labels": ["ui", "backend"],
+            "created_at": "2025-03-04T12:00:00Z",
+            "dependencies": [{"id": "bd-0"}]
+        });
+
+        let template =
+            Template::parse(r#"{{id}} [P{{priority}}] {{title}} ({{labels|join(",")}})"#).unwrap();
+        assert_eq!(
+            template.render(&row).unwrap(),
+            "bd-1 [P1] A fairly long issue title (ui,backend)"
+        );
+
+        let template = Template::parse(
+            "{{title|truncate(10)}}|{{assignee|default('-')|upper}}|{{labels|len}}|{{dependencies.0.id}}|{{created_at|date('%Y')}}|{{labels}}",
+        )
+        .unwrap();
+        assert_eq!(
+            template.render(&row).unwrap(),
+            "A fairl...|-|2|bd-0|2025|ui, backend"
+        );
+        info!("test_render_fields_and_filters: assertions passed");
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        init_logging();
+        info!("test_parse_errors: starting");
+        for bad in [
+            "{{id",
+            "{{}}",
+            "{{id|shout}}",
+            "{{title|truncate(x)}}",
+            "{{title|truncate(3}}",
+            "{{created_at|date('%Q')}}",
+        ] {
+            assert!(Template::parse(bad).is_err(), "expected error for {bad:?}");
+        }
+        assert_eq!(
+            Template::parse("plain text")
+                .unwrap()
+                .render(&json!({}))
+                .unwrap(),
+            "plain text"
+        );
+        info!("test_parse_errors: assertions passed");
+    }
+}
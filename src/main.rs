@@ -3,7 +3,7 @@ use beads_rust::cli::{Cli, Commands};
 use beads_rust::config;
 use beads_rust::logging::init_logging;
 use beads_rust::output::OutputContext;
-use beads_rust::sync::{auto_flush, auto_import_if_stale};
+use beads_rust::sync::{auto_flush, auto_import_if_stale, events};
 use beads_rust::{BeadsError, Result, StructuredError};
 use clap::{CommandFactory, Parser};
 use clap_complete::CompleteEnv;
@@ -30,7 +30,14 @@ fn main() {
     let is_mutating = is_mutating_command(&cli.command);
 
     if should_auto_import(&cli.command) && !cli.no_db {
-        if let Err(e) = run_auto_import(&overrides, cli.allow_stale, cli.no_auto_import) {
+        // `br escalate` reports its own escalations, so don't apply them first.
+        let auto_escalate = !matches!(cli.command, Commands::Escalate(_));
+        if let Err(e) = run_auto_import(
+            &overrides,
+            cli.allow_stale,
+            cli.no_auto_import,
+            auto_escalate,
+        ) {
             handle_error(&e, cli.json);
         }
     }
@@ -43,6 +50,7 @@ fn main() {
         } => commands::init::execute(prefix, force, None, &output_ctx),
         Commands::Create(args) => commands::create::execute(&args, &overrides, &output_ctx),
         Commands::Update(args) => commands::update::execute(&args, &overrides, &output_ctx),
+        Commands::Edit(args) => commands::edit::execute(&args, &overrides, &output_ctx),
         Commands::Delete(args) => {
             commands::delete::execute(&args, cli.json, &overrides, &output_ctx)
         }
@@ -71,7 +79,11 @@ fn main() {
             commands::label::execute(&command, cli.json, &overrides, &output_ctx)
         }
         Commands::Count(args) => commands::count::execute(&args, cli.json, &overrides, &output_ctx),
+        Commands::Board(args) => commands::board::execute(&args, &overrides, &output_ctx),
         Commands::Stale(args) => commands::stale::execute(&args, &overrides, &output_ctx),
+        Commands::Overdue(args) => commands::due::execute_overdue(&args, &overrides, &output_ctx),
+        Commands::Due(args) => commands::due::execute_due(&args, &overrides, &output_ctx),
+        Commands::Escalate(args) => commands::escalate::execute(&args, &overrides, &output_ctx),
         Commands::Lint(args) => commands::lint::execute(&args, cli.json, &overrides, &output_ctx),
         Commands::Ready(args) => commands::ready::execute(&args, cli.json, &overrides, &output_ctx),
         Commands::Blocked(args) => {
@@ -106,6 +118,7 @@ fn main() {
         Commands::Orphans(args) => {
             commands::orphans::execute(&args, cli.json || args.robot, &overrides, &output_ctx)
         }
+        Commands::Scan(args) => commands::scan::execute(&args, &overrides, &output_ctx),
         Commands::Changelog(args) => {
             commands::changelog::execute(&args, cli.json || args.robot, &overrides, &output_ctx)
         }
@@ -122,6 +135,21 @@ fn main() {
             };
             commands::agents::execute(&agents_args, &output_ctx)
         }
+        Commands::Serve(args) => commands::serve::execute(&args, &overrides, &output_ctx),
+        Commands::Watch(args) => commands::watch::execute(&args, &overrides, &output_ctx),
+        Commands::Tui(args) => commands::tui::execute(&args, &overrides, &output_ctx),
+        Commands::Hooks { command } => commands::hooks::execute(&command, &overrides, &output_ctx),
+        Commands::Git { command } => commands::git::execute(&command, &overrides, &output_ctx),
+        Commands::Snapshot { command } => {
+            commands::snapshot::execute(&command, &overrides, &output_ctx)
+        }
+        Commands::MergeDriver(args) => commands::merge_driver::execute(&args, &output_ctx),
+        Commands::Compact(args) => commands::compact::execute(&args, &overrides, &output_ctx),
+        Commands::Gc(args) => commands::gc::execute(&args, &overrides, &output_ctx),
+        Commands::Import { command } => {
+            commands::import::execute(&command, &overrides, &output_ctx)
+        }
+        Commands::Export(args) => commands::export::execute(&args, &overrides, &output_ctx),
     };
 
     // Handle command result
@@ -140,6 +168,7 @@ const fn is_mutating_command(cmd: &Commands) -> bool {
     match cmd {
         Commands::Create(_)
         | Commands::Update(_)
+        | Commands::Edit(_)
         | Commands::Delete(_)
         | Commands::Close(_)
         | Commands::Reopen(_)
@@ -147,8 +176,17 @@ const fn is_mutating_command(cmd: &Commands) -> bool {
         | Commands::Dep { .. }
         | Commands::Label { .. }
         | Commands::Comments(_)
+        | Commands::Tui(_)
         | Commands::Defer(_)
-        | Commands::Undefer(_) => true,
+        | Commands::Undefer(_)
+        | Commands::Compact(_)
+        | Commands::Import { .. } => true,
+        Commands::Scan(args) => args.create,
+        Commands::Escalate(args) => !args.dry_run,
+        Commands::Git { command } => matches!(
+            command,
+            beads_rust::cli::GitCommands::ApplyTrailers(args) if !args.dry_run
+        ),
         Commands::Epic { command } => matches!(
             command,
             beads_rust::cli::EpicCommands::CloseEligible(args) if !args.dry_run
@@ -169,15 +207,23 @@ const fn should_auto_import(cmd: &Commands) -> bool {
         | Commands::Ready(_)
         | Commands::Blocked(_)
         | Commands::Count(_)
+        | Commands::Board(_)
         | Commands::Stale(_)
+        | Commands::Overdue(_)
+        | Commands::Due(_)
+        | Commands::Escalate(_)
         | Commands::Lint(_)
         | Commands::Stats(_)
         | Commands::Status(_)
+        | Commands::Snapshot { .. }
         | Commands::Orphans(_)
+        | Commands::Scan(_)
+        | Commands::Git { .. }
         | Commands::Changelog(_)
         | Commands::Graph(_)
         | Commands::Create(_)
         | Commands::Update(_)
+        | Commands::Edit(_)
         | Commands::Delete(_)
         | Commands::Close(_)
         | Commands::Reopen(_)
@@ -188,7 +234,14 @@ const fn should_auto_import(cmd: &Commands) -> bool {
         | Commands::Dep { .. }
         | Commands::Label { .. }
         | Commands::Epic { .. }
-        | Commands::Query { .. } => true,
+        | Commands::Query { .. }
+        | Commands::Serve(_)
+        | Commands::Watch(_)
+        | Commands::Tui(_)
+        | Commands::Compact(_)
+        | Commands::Gc(_)
+        | Commands::Import { .. }
+        | Commands::Export(_) => true,
 
         // Explicitly excluded: init, sync, diagnostic, and config commands
         Commands::Init { .. }
@@ -202,7 +255,9 @@ const fn should_auto_import(cmd: &Commands) -> bool {
         | Commands::Audit { .. }
         | Commands::Config { .. }
         | Commands::History(_)
-        | Commands::Agents(_) => false,
+        | Commands::Agents(_)
+        | Commands::Hooks { .. }
+        | Commands::MergeDriver(_) => false,
 
         #[cfg(feature = "self_update")]
         Commands::Upgrade(_) => false,
@@ -214,6 +269,7 @@ fn run_auto_import(
     overrides: &config::CliOverrides,
     allow_stale: bool,
     no_auto_import: bool,
+    auto_escalate: bool,
 ) -> Result<()> {
     // If not initialized, skip auto-import (e.g. running 'br init')
     let beads_dir = match config::discover_beads_dir(Some(Path::new("."))) {
@@ -249,6 +305,41 @@ fn run_auto_import(
         );
     }
 
+    let layer = config::load_config(&beads_dir, Some(&storage), overrides)?;
+    if !no_auto_import && config::share_events_from_layer(&layer) {
+        match events::import_events(&mut storage, &paths.beads_dir, false) {
+            Ok(events) if events.attempted => {
+                debug!(imported = events.imported, "Shared events imported");
+            }
+            Ok(_) => {}
+            Err(e) => debug!(error = %e, "Shared events import skipped"),
+        }
+    }
+
+    match commands::snapshot::ensure_daily_snapshot(&mut storage) {
+        Ok(true) => debug!("Recorded daily snapshot"),
+        Ok(false) => {}
+        Err(e) => debug!(error = %e, "Daily snapshot skipped"),
+    }
+
+    // Governed by `escalation.auto` alone (`BD_ESCALATION_AUTO=0` turns it
+    // off for one command), not by `--no-auto-import`.
+    if auto_escalate {
+        match commands::escalate::auto_escalate(&mut storage, &layer) {
+            Ok(0) => {}
+            Ok(count) => {
+                debug!(count, "Applied due-date escalations");
+                // Export right away so read-only commands don't leave them
+                // unflushed, unless `--no-auto-flush` was given.
+                if overrides.no_auto_flush != Some(true) {
+                    drop(storage);
+                    run_auto_flush(overrides);
+                }
+            }
+            Err(e) => debug!(error = %e, "Escalation skipped"),
+        }
+    }
+
     Ok(())
 }
 
@@ -288,6 +379,7 @@ fn run_auto_flush(overrides: &config::CliOverrides) {
                     hash = %result.content_hash,
                     "Auto-flush completed"
                 );
+                flush_shared_events(&mut storage, &beads_dir, overrides);
             }
         }
         Err(e) => {
@@ -297,6 +389,23 @@ fn run_auto_flush(overrides: &config::CliOverrides) {
     }
 }
 
+/// Export audit events to `events.jsonl` when `sync.events` is enabled.
+fn flush_shared_events(
+    storage: &mut beads_rust::storage::SqliteStorage,
+    beads_dir: &Path,
+    overrides: &config::CliOverrides,
+) {
+    let enabled = config::load_config(beads_dir, Some(storage), overrides)
+        .is_ok_and(|layer| config::share_events_from_layer(&layer));
+    if !enabled {
+        return;
+    }
+    match events::export_events(storage, beads_dir) {
+        Ok(result) => debug!(added = result.added, "Shared events exported"),
+        Err(e) => debug!(?e, "Event export failed (non-fatal)"),
+    }
+}
+
 /// Handle errors with structured output support.
 ///
 /// When --json is set or stdout is not a TTY, outputs structured JSON to stderr.
@@ -365,6 +474,7 @@ mod tests {
             ephemeral: false,
             dry_run: false,
             silent: false,
+            force: false,
             file: None,
         }
     }
@@ -635,6 +635,31 @@ pub struct Comment {
     pub created_at: DateTime<Utc>,
 }
 
+/// A git commit that mentions an issue, recorded by `br git index`.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct CommitLink {
+    pub issue_id: String,
+    pub sha: String,
+    pub author: String,
+    pub authored_at: DateTime<Utc>,
+    pub subject: String,
+    /// Closing trailer keyword (e.g. `closes`, `fixes`) when the commit closes the issue.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub closes: Option<String>,
+}
+
+/// One count from a daily snapshot, e.g. 12 issues with status `open` on a day.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
+pub struct SnapshotCount {
+    /// Day the snapshot was taken (YYYY-MM-DD, UTC).
+    pub day: String,
+    /// What was counted: `status`, `type` or `label`.
+    pub dimension: String,
+    /// Status, type or label name.
+    pub key: String,
+    pub count: usize,
+}
+
 /// An event in the issue's history (audit log).
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq, JsonSchema)]
 pub struct Event {
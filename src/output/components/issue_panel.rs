@@ -165,6 +165,29 @@ impl<'a> IssuePanel<'a> {
             }
         }
 
+        // Commits recorded by `br git index`
+        if let Some(details) = self.details.filter(|d| !d.commits.is_empty()) {
+            content.append_styled("\nCommits:\n", self.theme.emphasis.clone());
+            for commit in &details.commits {
+                content.append("  ");
+                content.append_styled(
+                    commit.sha.get(..7).unwrap_or(&commit.sha),
+                    self.theme.issue_id.clone(),
+                );
+                content.append(" ");
+                content.append_styled(
+                    &commit.authored_at.format("%Y-%m-%d").to_string(),
+                    self.theme.timestamp.clone(),
+                );
+                content.append(" ");
+                content.append(&commit.subject);
+                if let Some(keyword) = &commit.closes {
+                    content.append_styled(&format!(" ({keyword})"), self.theme.dimmed.clone());
+                }
+                content.append("\n");
+            }
+        }
+
         // Build and print panel
         let panel_width = if wrap { ctx.width() } else { 80 };
         let content = if wrap {
@@ -5,7 +5,8 @@
 //! - Event retrieval (newest first, DESC ordering)
 //! - Schema definitions for the events table
 //!
-//! Events are local DB only - never exported to JSONL.
+//! Events live in the local DB. Sharing them through `events.jsonl` is opt-in
+//! (`sync.events: true`) and handled by `sync::events`.
 
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use rusqlite::{Connection, Transaction, params};
@@ -83,6 +84,34 @@ pub fn insert_event(
     Ok(tx.last_insert_rowid())
 }
 
+/// Insert an event that was recorded elsewhere, keeping its original timestamp.
+///
+/// Used when importing shared events. The event is skipped (returns `false`)
+/// if its issue does not exist locally.
+///
+/// # Errors
+///
+/// Returns an error if the database insert fails.
+pub fn insert_imported_event(tx: &Transaction<'_>, event: &Event) -> Result<bool> {
+    let inserted = tx.execute(
+        r"
+        INSERT INTO events (issue_id, event_type, actor, old_value, new_value, comment, created_at)
+        SELECT ?1, ?2, ?3, ?4, ?5, ?6, ?7
+        WHERE EXISTS (SELECT 1 FROM issues WHERE id = ?1)
+        ",
+        params![
+            event.issue_id,
+            event.event_type.as_str(),
+            event.actor,
+            event.old_value,
+            event.new_value,
+            event.comment,
+            event.created_at.to_rfc3339(),
+        ],
+    )?;
+    Ok(inserted > 0)
+}
+
 /// Insert a "created" event for a new issue.
 ///
 /// # Errors
@@ -457,6 +486,56 @@ pub fn get_all_events(conn: &Connection, limit: usize) -> Result<Vec<Event>> {
     Ok(events)
 }
 
+/// Get events with an ID greater than `since_id`, ordered by ID ASC (oldest first).
+///
+/// Event IDs are monotonically increasing, so callers can use the last ID they
+/// saw as a high-water mark to page through new events.
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn get_events_since(conn: &Connection, since_id: i64, limit: usize) -> Result<Vec<Event>> {
+    let query = if limit > 0 {
+        r"
+            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at
+            FROM events
+            WHERE id > ?1
+            ORDER BY id ASC
+            LIMIT ?2
+            "
+    } else {
+        r"
+            SELECT id, issue_id, event_type, actor, old_value, new_value, comment, created_at
+            FROM events
+            WHERE id > ?1
+            ORDER BY id ASC
+            "
+    };
+
+    let mut stmt = conn.prepare(query)?;
+    let events: Vec<Event> = if limit > 0 {
+        stmt.query_map(params![since_id, limit], event_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?
+    } else {
+        stmt.query_map(params![since_id], event_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?
+    };
+
+    Ok(events)
+}
+
+/// Get the highest event ID recorded so far (0 if the table is empty).
+///
+/// # Errors
+///
+/// Returns an error if the database query fails.
+pub fn get_max_event_id(conn: &Connection) -> Result<i64> {
+    let max: i64 = conn.query_row("SELECT COALESCE(MAX(id), 0) FROM events", [], |row| {
+        row.get(0)
+    })?;
+    Ok(max)
+}
+
 /// Get event count for an issue.
 ///
 /// # Errors
@@ -789,4 +868,30 @@ mod tests {
         assert_eq!(events[2].event_type, EventType::StatusChanged);
         assert_eq!(events[3].event_type, EventType::Created);
     }
+
+    #[test]
+    fn test_get_events_since_uses_high_water_mark() {
+        let conn = setup_test_db();
+
+        let tx = conn.unchecked_transaction().expect("Failed to start tx");
+        let first = insert_created_event(&tx, "test-001", "alice").expect("Created");
+        insert_status_changed_event(&tx, "test-001", "alice", "open", "in_progress")
+            .expect("Status change");
+        insert_closed_event(&tx, "test-001", "alice", Some("Done")).expect("Closed");
+        tx.commit().expect("Commit");
+
+        let events = get_events_since(&conn, first, 0).expect("Failed to get events");
+        assert_eq!(events.len(), 2);
+        // Oldest first
+        assert_eq!(events[0].event_type, EventType::StatusChanged);
+        assert_eq!(events[1].event_type, EventType::Closed);
+
+        let limited = get_events_since(&conn, 0, 1).expect("Failed to get events");
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0].id, first);
+
+        let none = get_events_since(&conn, events[1].id, 0).expect("Failed to get events");
+        assert!(none.is_empty());
+        assert_eq!(get_max_event_id(&conn).expect("max id"), events[1].id);
+    }
 }
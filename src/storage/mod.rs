@@ -9,11 +9,16 @@
 //! # Submodules
 //!
 //! - [`events`] - Audit event storage (insertion, retrieval)
+//! - [`query_dsl`] - `--where` filter expressions compiled to SQL
 //! - [`schema`] - Database schema definitions
 //! - [`sqlite`] - Main `SQLite` storage implementation
 
 pub mod events;
+pub mod query_dsl;
 pub mod schema;
 pub mod sqlite;
 
-pub use sqlite::{IssueUpdate, ListFilters, ReadyFilters, ReadySortPolicy, SqliteStorage};
+pub use query_dsl::WhereClause;
+pub use sqlite::{
+    GcStats, IssueUpdate, ListFilters, ReadyFilters, ReadySortPolicy, SqliteStorage, TxReader,
+};
@@ -0,0 +1,949 @@
+//! Filter expressions for `--where`.
+//!
+//! An expression such as
+//! `status:open AND (label:ui OR priority<=1) AND updated>-7d AND NOT assignee:bot-*`
+//! is compiled into a parameterized SQL condition over the `issues` table,
+//! with `EXISTS` subqueries against `labels`, `dependencies` and `comments`.
+//!
+//! Grammar:
+//!
+//! - `a AND b`, `a OR b`, `NOT a`, parentheses; adjacent terms are ANDed.
+//!   Keywords are case-insensitive.
+//! - `field<op>value` with `:` (glob match, `*` and `?`, case-insensitive),
+//!   `=`, `!=`, `<`, `<=`, `>`, `>=` and `~` (substring). Values may be quoted:
+//!   `title~"login page"`.
+//! - Dates (`created`, `updated`, `closed`, `due`, `defer`) accept RFC3339,
+//!   `YYYY-MM-DD` (the whole local day) or offsets like `-7d` and `+2w`.
+//! - `label`, `parent`, `dep` (issues this one depends on) and `blocks`
+//!   (issues depending on this one) match related rows.
+//! - `has:<field>` tests that a field, `parent`, `children`, `deps`,
+//!   `dependents`, `labels` or `comments` is present.
+//! - `blocked` and `overdue` are bare predicates (`is:blocked` also works).
+//!
+//! Fields are a fixed list: `id`, `title`, `description`, `design`,
+//! `acceptance`, `notes`, `status`, `type`, `priority`, `assignee`, `owner`,
+//! `created_by`, `close_reason`, `external_ref`, `source_system`, `sender`,
+//! `estimate` and the dates above. Other columns of `issues` are not
+//! addressable.
+//!
+//! Naming `status` lifts the default exclusion of closed and deferred
+//! issues, but tombstones stay excluded unless the expression names the
+//! `tombstone` status itself.
+
+use crate::error::{BeadsError, Result};
+use crate::model::Priority;
+use crate::util::time::{parse_duration, parse_flexible_timestamp};
+use chrono::{DateTime, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use rusqlite::ToSql;
+use rusqlite::types::ToSqlOutput;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// A bound parameter of a compiled expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SqlParam {
+    Text(String),
+    Int(i64),
+}
+
+impl ToSql for SqlParam {
+    fn to_sql(&self) -> rusqlite::Result<ToSqlOutput<'_>> {
+        match self {
+            Self::Text(value) => value.to_sql(),
+            Self::Int(value) => value.to_sql(),
+        }
+    }
+}
+
+/// A parsed `--where` expression, compiled to SQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WhereClause {
+    sql: String,
+    params: Vec<SqlParam>,
+    constrains_status: bool,
+}
+
+impl WhereClause {
+    /// Parse an expression, resolving relative dates against the current time.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error describing the first problem in `input`.
+    pub fn parse(input: &str) -> Result<Self> {
+        Self::parse_at(input, Utc::now())
+    }
+
+    /// Parse an expression, resolving relative dates against `now`.
+    ///
+    /// # Errors
+    ///
+    /// Returns a validation error describing the first problem in `input`.
+    pub fn parse_at(input: &str, now: DateTime<Utc>) -> Result<Self> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(invalid("expression is empty"));
+        }
+        let mut parser = Parser {
+            tokens,
+            pos: 0,
+            now,
+            params: Vec::new(),
+            constrains_status: false,
+            names_tombstone: false,
+        };
+        let mut sql = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(invalid(format!("unexpected {}", token.describe())));
+        }
+        // Callers stop excluding terminal statuses when the expression
+        // constrains status, so keep deleted issues out unless asked for.
+        if parser.constrains_status && !parser.names_tombstone {
+            sql = format!("({sql}) AND issues.status != 'tombstone'");
+        }
+        Ok(Self {
+            sql,
+            params: parser.params,
+            constrains_status: parser.constrains_status,
+        })
+    }
+
+    /// SQL condition, with `?` placeholders for [`Self::params`] in order.
+    #[must_use]
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    #[must_use]
+    pub fn params(&self) -> &[SqlParam] {
+        &self.params
+    }
+
+    /// Whether the expression filters on status itself, in which case the
+    /// default exclusion of closed and deferred issues should not apply.
+    ///
+    /// Tombstones are excluded by the expression itself unless it names the
+    /// `tombstone` status.
+    #[must_use]
+    pub const fn constrains_status(&self) -> bool {
+        self.constrains_status
+    }
+}
+
+fn invalid(reason: impl Into<String>) -> BeadsError {
+    BeadsError::validation("where", reason)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Op {
+    Match,
+    Eq,
+    Ne,
+    Lt,
+    Le,
+    Gt,
+    Ge,
+    Contains,
+}
+
+impl Op {
+    /// Recognize an operator starting at `c`, returning it and whether it
+    /// also consumes `next`.
+    const fn start(c: char, next: Option<char>) -> Option<(Self, bool)> {
+        Some(match (c, next) {
+            ('<', Some('=')) => (Self::Le, true),
+            ('>', Some('=')) => (Self::Ge, true),
+            ('!', Some('=')) => (Self::Ne, true),
+            ('<', _) => (Self::Lt, false),
+            ('>', _) => (Self::Gt, false),
+            ('=', _) => (Self::Eq, false),
+            (':', _) => (Self::Match, false),
+            ('~', _) => (Self::Contains, false),
+            _ => return None,
+        })
+    }
+
+    const fn symbol(self) -> &'static str {
+        match self {
+            Self::Match => ":",
+            Self::Eq => "=",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+            Self::Contains => "~",
+        }
+    }
+
+    /// SQL comparison operator for ordered comparisons.
+    const fn sql_comparison(self) -> &'static str {
+        match self {
+            Self::Match | Self::Eq | Self::Contains => "=",
+            Self::Ne => "!=",
+            Self::Lt => "<",
+            Self::Le => "<=",
+            Self::Gt => ">",
+            Self::Ge => ">=",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Term {
+    field: String,
+    op: Option<Op>,
+    value: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Open,
+    Close,
+    And,
+    Or,
+    Not,
+    Term(Term),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Self::Open => "'('".to_string(),
+            Self::Close => "')'".to_string(),
+            Self::And => "AND".to_string(),
+            Self::Or => "OR".to_string(),
+            Self::Not => "NOT".to_string(),
+            Self::Term(term) => match term.op {
+                Some(op) => format!("'{}{}{}'", term.field, op.symbol(), term.value),
+                None => format!("'{}'", term.field),
+            },
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            _ => tokens.push(read_term(&mut chars)?),
+        }
+    }
+    Ok(tokens)
+}
+
+/// Read one word: a keyword, a bare predicate or `field<op>value`.
+fn read_term(chars: &mut Peekable<Chars<'_>>) -> Result<Token> {
+    let mut field = String::new();
+    let mut value = String::new();
+    let mut op = None;
+    let mut quoted = false;
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() || c == '(' || c == ')' {
+            break;
+        }
+        chars.next();
+        let target = if op.is_some() { &mut value } else { &mut field };
+        if c == '"' {
+            quoted = true;
+            loop {
+                match chars.next() {
+                    Some('"') => break,
+                    Some('\\') => target.extend(chars.next()),
+                    Some(other) => target.push(other),
+                    None => return Err(invalid("unterminated quote")),
+                }
+            }
+            continue;
+        }
+        if op.is_none() {
+            if let Some((found, two_chars)) = Op::start(c, chars.peek().copied()) {
+                if two_chars {
+                    chars.next();
+                }
+                op = Some(found);
+                continue;
+            }
+        }
+        target.push(c);
+    }
+
+    if op.is_none() && !quoted {
+        match field.to_ascii_uppercase().as_str() {
+            "AND" | "&&" => return Ok(Token::And),
+            "OR" | "||" => return Ok(Token::Or),
+            "NOT" => return Ok(Token::Not),
+            _ => {}
+        }
+    }
+    Ok(Token::Term(Term { field, op, value }))
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Text,
+    Number,
+    Priority,
+    Date,
+    Label,
+    Parent,
+    DependsOn,
+    Blocks,
+}
+
+struct Field {
+    names: &'static [&'static str],
+    column: &'static str,
+    kind: Kind,
+}
+
+const FIELDS: &[Field] = &[
+    Field {
+        names: &["id"],
+        column: "id",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["title"],
+        column: "title",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["description", "desc"],
+        column: "description",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["design"],
+        column: "design",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["acceptance", "acceptance_criteria"],
+        column: "acceptance_criteria",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["notes"],
+        column: "notes",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["status"],
+        column: "status",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["type", "issue_type"],
+        column: "issue_type",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["priority"],
+        column: "priority",
+        kind: Kind::Priority,
+    },
+    Field {
+        names: &["assignee"],
+        column: "assignee",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["owner"],
+        column: "owner",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["created_by"],
+        column: "created_by",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["close_reason"],
+        column: "close_reason",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["external_ref"],
+        column: "external_ref",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["source_system"],
+        column: "source_system",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["sender"],
+        column: "sender",
+        kind: Kind::Text,
+    },
+    Field {
+        names: &["estimate", "estimated_minutes"],
+        column: "estimated_minutes",
+        kind: Kind::Number,
+    },
+    Field {
+        names: &["created", "created_at"],
+        column: "created_at",
+        kind: Kind::Date,
+    },
+    Field {
+        names: &["updated", "updated_at"],
+        column: "updated_at",
+        kind: Kind::Date,
+    },
+    Field {
+        names: &["closed", "closed_at"],
+        column: "closed_at",
+        kind: Kind::Date,
+    },
+    Field {
+        names: &["due", "due_at"],
+        column: "due_at",
+        kind: Kind::Date,
+    },
+    Field {
+        names: &["defer", "defer_until"],
+        column: "defer_until",
+        kind: Kind::Date,
+    },
+    Field {
+        names: &["label", "labels"],
+        column: "",
+        kind: Kind::Label,
+    },
+    Field {
+        names: &["parent"],
+        column: "",
+        kind: Kind::Parent,
+    },
+    Field {
+        names: &["dep", "depends_on"],
+        column: "",
+        kind: Kind::DependsOn,
+    },
+    Field {
+        names: &["blocks"],
+        column: "",
+        kind: Kind::Blocks,
+    },
+];
+
+/// Dependency types that block readiness (see
+/// [`crate::model::DependencyType::affects_ready_work`]), minus parent-child.
+const BLOCKING_TYPES: &str = "('blocks', 'conditional-blocks', 'waits-for')";
+
+fn lookup_field(name: &str) -> Option<&'static Field> {
+    let name = name.to_ascii_lowercase().replace('-', "_");
+    FIELDS
+        .iter()
+        .find(|field| field.names.contains(&name.as_str()))
+}
+
+fn field_names() -> String {
+    FIELDS
+        .iter()
+        .map(|field| field.names[0])
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Escape LIKE metacharacters (used with `ESCAPE '\'`).
+fn escape_like(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('%', "\\%")
+        .replace('_', "\\_")
+}
+
+/// Turn a `*`/`?` glob into a LIKE pattern.
+fn glob_to_like(value: &str) -> String {
+    escape_like(value).replace('*', "%").replace('?', "_")
+}
+
+fn local_midnight(day: NaiveDate) -> Result<DateTime<Utc>> {
+    Local
+        .from_local_datetime(&day.and_time(NaiveTime::MIN))
+        .earliest()
+        .map(|dt| dt.with_timezone(&Utc))
+        .ok_or_else(|| invalid(format!("no local midnight on {day}")))
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    now: DateTime<Utc>,
+    params: Vec<SqlParam>,
+    constrains_status: bool,
+    names_tombstone: bool,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.peek() == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn parse_or(&mut self) -> Result<String> {
+        let mut parts = vec![self.parse_and()?];
+        while self.eat(&Token::Or) {
+            parts.push(self.parse_and()?);
+        }
+        Ok(join(parts, " OR "))
+    }
+
+    fn parse_and(&mut self) -> Result<String> {
+        let mut parts = vec![self.parse_unary()?];
+        loop {
+            if self.eat(&Token::And)
+                || matches!(self.peek(), Some(Token::Term(_) | Token::Not | Token::Open))
+            {
+                parts.push(self.parse_unary()?);
+            } else {
+                break;
+            }
+        }
+        Ok(join(parts, " AND "))
+    }
+
+    fn parse_unary(&mut self) -> Result<String> {
+        if self.eat(&Token::Not) {
+            return Ok(format!("NOT {}", self.parse_unary()?));
+        }
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            return Err(invalid("expression ends unexpectedly"));
+        };
+        self.pos += 1;
+        match token {
+            Token::Open => {
+                let inner = self.parse_or()?;
+                if !self.eat(&Token::Close) {
+                    return Err(invalid("missing ')'"));
+                }
+                Ok(format!("({inner})"))
+            }
+            Token::Term(term) => self.compile_term(&term),
+            other => Err(invalid(format!("unexpected {}", other.describe()))),
+        }
+    }
+
+    fn push_text(&mut self, value: impl Into<String>) {
+        self.params.push(SqlParam::Text(value.into()));
+    }
+
+    fn compile_term(&mut self, term: &Term) -> Result<String> {
+        let Some(op) = term.op else {
+            return compile_predicate(&term.field);
+        };
+        match term.field.to_ascii_lowercase().as_str() {
+            "has" if op == Op::Match => return compile_has(&term.value),
+            "is" if op == Op::Match => return compile_predicate(&term.value),
+            _ => {}
+        }
+        let field = lookup_field(&term.field).ok_or_else(|| {
+            invalid(format!(
+                "unknown field '{}' (known: {})",
+                term.field,
+                field_names()
+            ))
+        })?;
+        if term.value.is_empty() {
+            return Err(invalid(format!("missing value after '{}'", term.field)));
+        }
+        if field.column == "status" {
+            self.constrains_status = true;
+            self.names_tombstone |= term.value.eq_ignore_ascii_case("tombstone");
+        }
+        let unsupported = || {
+            invalid(format!(
+                "operator '{}' is not supported for '{}'",
+                op.symbol(),
+                term.field
+            ))
+        };
+        match field.kind {
+            Kind::Text => Ok(self.compile_text(field.column, op, &term.value)),
+            Kind::Number | Kind::Priority => {
+                if op == Op::Contains {
+                    return Err(unsupported());
+                }
+                let number = if field.kind == Kind::Priority {
+                    term.value
+                        .parse::<Priority>()
+                        .map(|priority| i64::from(priority.0))
+                        .map_err(|_| invalid(format!("invalid priority '{}'", term.value)))?
+                } else {
+                    term.value
+                        .parse::<i64>()
+                        .map_err(|_| invalid(format!("invalid number '{}'", term.value)))?
+                };
+                self.params.push(SqlParam::Int(number));
+                Ok(format!(
+                    "IFNULL(issues.{} {} ?, 0)",
+                    field.column,
+                    op.sql_comparison()
+                ))
+            }
+            Kind::Date => {
+                if op == Op::Contains {
+                    return Err(unsupported());
+                }
+                self.compile_date(field.column, op, &term.value)
+            }
+            Kind::Label | Kind::Parent | Kind::DependsOn | Kind::Blocks => {
+                if matches!(op, Op::Lt | Op::Le | Op::Gt | Op::Ge) {
+                    return Err(unsupported());
+                }
+                Ok(self.compile_related(field.kind, op, &term.value))
+            }
+        }
+    }
+
+    /// Compile a match against labels or dependency rows.
+    fn compile_related(&mut self, kind: Kind, op: Op, value: &str) -> String {
+        let cmp = match op {
+            Op::Match => {
+                self.push_text(glob_to_like(value));
+                "LIKE ? ESCAPE '\\'"
+            }
+            Op::Contains => {
+                self.push_text(format!("%{}%", escape_like(value)));
+                "LIKE ? ESCAPE '\\'"
+            }
+            _ => {
+                self.push_text(value);
+                "= ?"
+            }
+        };
+        let exists = match kind {
+            Kind::Label => format!(
+                "EXISTS (SELECT 1 FROM labels l WHERE l.issue_id = issues.id AND l.label {cmp})"
+            ),
+            Kind::Parent => format!(
+                "EXISTS (SELECT 1 FROM dependencies d WHERE d.issue_id = issues.id \
+                 AND d.type = 'parent-child' AND d.depends_on_id {cmp})"
+            ),
+            Kind::DependsOn => format!(
+                "EXISTS (SELECT 1 FROM dependencies d WHERE d.issue_id = issues.id \
+                 AND d.type IN {BLOCKING_TYPES} AND d.depends_on_id {cmp})"
+            ),
+            _ => format!(
+                "EXISTS (SELECT 1 FROM dependencies d WHERE d.depends_on_id = issues.id \
+                 AND d.type IN {BLOCKING_TYPES} AND d.issue_id {cmp})"
+            ),
+        };
+        if op == Op::Ne {
+            format!("NOT {exists}")
+        } else {
+            exists
+        }
+    }
+
+    fn compile_text(&mut self, column: &str, op: Op, value: &str) -> String {
+        let column = format!("COALESCE(issues.{column}, '')");
+        match op {
+            Op::Match => {
+                self.push_text(glob_to_like(value));
+                format!("{column} LIKE ? ESCAPE '\\'")
+            }
+            Op::Contains => {
+                self.push_text(format!("%{}%", escape_like(value)));
+                format!("{column} LIKE ? ESCAPE '\\'")
+            }
+            _ => {
+                self.push_text(value);
+                format!("{column} {} ?", op.sql_comparison())
+            }
+        }
+    }
+
+    fn compile_date(&mut self, column: &str, op: Op, value: &str) -> Result<String> {
+        let column = format!("datetime(issues.{column})");
+
+        // A plain date stands for the whole local day.
+        if let Ok(day) = NaiveDate::parse_from_str(value, "%Y-%m-%d") {
+            let start = local_midnight(day)?.to_rfc3339();
+            let next = day
+                .succ_opt()
+                .ok_or_else(|| invalid(format!("date '{value}' is out of range")))?;
+            let end = local_midnight(next)?.to_rfc3339();
+            let within = format!("IFNULL({column} >= datetime(?) AND {column} < datetime(?), 0)");
+            let (sql, bound) = match op {
+                Op::Match | Op::Eq | Op::Contains => (within, vec![start, end]),
+                Op::Ne => (format!("NOT {within}"), vec![start, end]),
+                Op::Lt => (format!("IFNULL({column} < datetime(?), 0)"), vec![start]),
+                Op::Le => (format!("IFNULL({column} < datetime(?), 0)"), vec![end]),
+                Op::Gt => (format!("IFNULL({column} >= datetime(?), 0)"), vec![end]),
+                Op::Ge => (format!("IFNULL({column} >= datetime(?), 0)"), vec![start]),
+            };
+            for param in bound {
+                self.push_text(param);
+            }
+            return Ok(sql);
+        }
+
+        let out_of_range = || invalid(format!("date offset '{value}' is out of range"));
+        let at = match value.strip_prefix(['+', '-']).and_then(parse_duration) {
+            Some(offset) if value.starts_with('-') => self
+                .now
+                .checked_sub_signed(offset)
+                .ok_or_else(out_of_range)?,
+            Some(offset) => self
+                .now
+                .checked_add_signed(offset)
+                .ok_or_else(out_of_range)?,
+            None => parse_flexible_timestamp(value, "where")?,
+        };
+        self.push_text(at.to_rfc3339());
+        Ok(format!(
+            "IFNULL({column} {} datetime(?), 0)",
+            op.sql_comparison()
+        ))
+    }
+}
+
+fn join(mut parts: Vec<String>, separator: &str) -> String {
+    if parts.len() == 1 {
+        parts.remove(0)
+    } else {
+        format!("({})", parts.join(separator))
+    }
+}
+
+fn compile_predicate(name: &str) -> Result<String> {
+    match name.to_ascii_lowercase().as_str() {
+        "blocked" => Ok(
+            "EXISTS (SELECT 1 FROM blocked_issues_cache b WHERE b.issue_id = issues.id)"
+                .to_string(),
+        ),
+        "overdue" => Ok("IFNULL(datetime(issues.due_at) < datetime('now') \
+             AND issues.status NOT IN ('closed', 'tombstone'), 0)"
+            .to_string()),
+        _ => Err(invalid(format!(
+            "unknown term '{name}' (expected field:value, blocked or overdue)"
+        ))),
+    }
+}
+
+fn compile_has(name: &str) -> Result<String> {
+    let sql = match name.to_ascii_lowercase().as_str() {
+        "parent" => "EXISTS (SELECT 1 FROM dependencies d WHERE d.issue_id = issues.id \
+                     AND d.type = 'parent-child')"
+            .to_string(),
+        "children" => "EXISTS (SELECT 1 FROM dependencies d WHERE d.depends_on_id = issues.id \
+                       AND d.type = 'parent-child')"
+            .to_string(),
+        "deps" | "dependencies" => format!(
+            "EXISTS (SELECT 1 FROM dependencies d WHERE d.issue_id = issues.id \
+             AND d.type IN {BLOCKING_TYPES})"
+        ),
+        "dependents" => format!(
+            "EXISTS (SELECT 1 FROM dependencies d WHERE d.depends_on_id = issues.id \
+             AND d.type IN {BLOCKING_TYPES})"
+        ),
+        "labels" | "label" => {
+            "EXISTS (SELECT 1 FROM labels l WHERE l.issue_id = issues.id)".to_string()
+        }
+        "comments" => "EXISTS (SELECT 1 FROM comments c WHERE c.issue_id = issues.id)".to_string(),
+        other => match lookup_field(other) {
+            Some(field) if !field.column.is_empty() => format!(
+                "IFNULL(issues.{column} IS NOT NULL AND issues.{column} != '', 0)",
+                column = field.column
+            ),
+            _ => {
+                return Err(invalid(format!(
+                    "unknown has: target '{name}' (try parent, children, deps, dependents, labels, comments or a field)"
+                )));
+            }
+        },
+    };
+    Ok(sql)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Issue, Status};
+    use crate::storage::{ListFilters, SqliteStorage};
+    use chrono::Duration;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_issue(id: &str, title: &str, priority: i32) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: title.to_string(),
+            priority: Priority(priority),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            ..Issue::default()
+        }
+    }
+
+    fn ids(storage: &SqliteStorage, expr: &str) -> Vec<String> {
+        let clause = WhereClause::parse(expr).unwrap();
+        let filters = ListFilters {
+            include_closed: clause.constrains_status(),
+            include_deferred: clause.constrains_status(),
+            where_clause: Some(clause),
+            ..ListFilters::default()
+        };
+        let mut ids: Vec<String> = storage
+            .list_issues(&filters)
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+        ids.sort();
+        ids
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        init_logging();
+        info!("test_parse_errors: starting");
+        for bad in [
+            "",
+            "status:open AND",
+            "(status:open",
+            "status:open)",
+            "colour:red",
+            "priority:urgent",
+            "title~\"open",
+            "label<ui",
+            "nonsense",
+            "has:whatever",
+            "due<+1000000000d",
+            "updated>-1000000000d",
+            "due<+99999999999999w",
+        ] {
+            assert!(
+                WhereClause::parse(bad).is_err(),
+                "expected error for {bad:?}"
+            );
+        }
+        info!("test_parse_errors: assertions passed");
+    }
+
+    #[test]
+    fn test_compiles_to_parameterized_sql() {
+        init_logging();
+        info!("test_compiles_to_parameterized_sql: starting");
+        let now = Utc::now();
+        let clause = WhereClause::parse_at(
+            "status:open (label:ui or priority<=P1) updated>-7d not assignee:bot-*",
+            now,
+        )
+        .unwrap();
+        assert!(clause.constrains_status());
+        assert_eq!(clause.sql().matches('?').count(), clause.params().len());
+        assert_eq!(
+            clause.params(),
+            &[
+                SqlParam::Text("open".to_string()),
+                SqlParam::Text("ui".to_string()),
+                SqlParam::Int(1),
+                SqlParam::Text((now - Duration::days(7)).to_rfc3339()),
+                SqlParam::Text("bot-%".to_string()),
+            ]
+        );
+        assert!(clause.sql().contains(" OR "));
+        assert!(clause.sql().contains("NOT COALESCE(issues.assignee, '')"));
+
+        let quoted = WhereClause::parse("title~\"50% off\"").unwrap();
+        assert_eq!(
+            quoted.params(),
+            &[SqlParam::Text("%50\\% off%".to_string())]
+        );
+        assert!(!quoted.constrains_status());
+        info!("test_compiles_to_parameterized_sql: assertions passed");
+    }
+
+    #[test]
+    fn test_status_expressions_keep_tombstones_out() {
+        init_logging();
+        info!("test_status_expressions_keep_tombstones_out: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage
+            .create_issue(&make_issue("bd-1", "Live", 1), "tester")
+            .unwrap();
+        storage
+            .create_issue(&make_issue("bd-2", "Deleted", 1), "tester")
+            .unwrap();
+        storage
+            .delete_issue("bd-2", "tester", "duplicate", None)
+            .unwrap();
+
+        assert_eq!(ids(&storage, "status!=closed"), vec!["bd-1"]);
+        assert_eq!(ids(&storage, "NOT status:open"), Vec::<String>::new());
+        assert_eq!(ids(&storage, "status:tombstone"), vec!["bd-2"]);
+        assert_eq!(
+            ids(&storage, "status:open OR status=TOMBSTONE"),
+            vec!["bd-1", "bd-2"]
+        );
+        info!("test_status_expressions_keep_tombstones_out: assertions passed");
+    }
+
+    #[test]
+    fn test_where_clause_filters_issues() {
+        init_logging();
+        info!("test_where_clause_filters_issues: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage
+            .create_issue(&make_issue("bd-1", "Login page", 1), "tester")
+            .unwrap();
+        let mut bot = make_issue("bd-2", "Dependency bump", 3);
+        bot.assignee = Some("bot-renovate".to_string());
+        storage.create_issue(&bot, "tester").unwrap();
+        let mut done = make_issue("bd-3", "Old login bug", 2);
+        done.status = Status::Closed;
+        done.closed_at = Some(Utc::now());
+        storage.create_issue(&done, "tester").unwrap();
+        storage.add_label("bd-2", "deps", "tester").unwrap();
+        storage
+            .add_dependency("bd-2", "bd-1", "blocks", "tester")
+            .unwrap();
+
+        assert_eq!(ids(&storage, "title:login*"), vec!["bd-1"]);
+        assert_eq!(ids(&storage, "status:closed"), vec!["bd-3"]);
+        assert_eq!(ids(&storage, "title~login"), vec!["bd-1"]);
+        assert_eq!(ids(&storage, "NOT assignee:bot-*"), vec!["bd-1"]);
+        assert_eq!(
+            ids(&storage, "label:deps OR priority<=1"),
+            vec!["bd-1", "bd-2"]
+        );
+        assert_eq!(ids(&storage, "blocked"), vec!["bd-2"]);
+        assert_eq!(ids(&storage, "dep:bd-1"), vec!["bd-2"]);
+        assert_eq!(ids(&storage, "has:dependents"), vec!["bd-1"]);
+        assert_eq!(ids(&storage, "has:assignee"), vec!["bd-2"]);
+        assert_eq!(
+            ids(&storage, "updated>-1d AND NOT has:parent"),
+            vec!["bd-1", "bd-2"]
+        );
+        assert!(ids(&storage, "updated<-1d").is_empty());
+        info!("test_where_clause_filters_issues: assertions passed");
+    }
+}
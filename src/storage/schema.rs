@@ -191,6 +191,41 @@ pub const SCHEMA_SQL: &str = r"
         last_child INTEGER NOT NULL DEFAULT 0,
         FOREIGN KEY (parent_id) REFERENCES issues(id) ON DELETE CASCADE
     );
+
+    -- Commit Links (local index of git commits mentioning issues; not exported)
+    CREATE TABLE IF NOT EXISTS commit_links (
+        issue_id TEXT NOT NULL,
+        sha TEXT NOT NULL,
+        author TEXT NOT NULL DEFAULT '',
+        authored_at DATETIME NOT NULL,
+        subject TEXT NOT NULL DEFAULT '',
+        closes TEXT,  -- closing trailer keyword, NULL for plain mentions
+        applied_at DATETIME,  -- when the closing trailer was applied
+        PRIMARY KEY (issue_id, sha),
+        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+    );
+    CREATE INDEX IF NOT EXISTS idx_commit_links_sha ON commit_links(sha);
+
+    -- Daily Snapshots (per-status/type/label counts for cumulative flow; not exported)
+    CREATE TABLE IF NOT EXISTS daily_snapshots (
+        day TEXT NOT NULL,  -- YYYY-MM-DD (UTC)
+        dimension TEXT NOT NULL,  -- status, type or label
+        key TEXT NOT NULL,
+        count INTEGER NOT NULL,
+        taken_at DATETIME NOT NULL,
+        PRIMARY KEY (day, dimension, key)
+    );
+
+    -- Escalations (due-date rules already applied per due date; local record,
+    -- exported as a comment on the issue)
+    CREATE TABLE IF NOT EXISTS escalations (
+        issue_id TEXT NOT NULL,
+        rule TEXT NOT NULL,
+        due_at DATETIME NOT NULL,
+        applied_at DATETIME NOT NULL,
+        PRIMARY KEY (issue_id, rule, due_at),
+        FOREIGN KEY (issue_id) REFERENCES issues(id) ON DELETE CASCADE
+    );
 ";
 
 /// Apply the schema to the database.
@@ -538,6 +573,9 @@ mod tests {
         assert!(tables.contains(&"dependencies".to_string()));
         assert!(tables.contains(&"config".to_string()));
         assert!(tables.contains(&"dirty_issues".to_string()));
+        assert!(tables.contains(&"commit_links".to_string()));
+        assert!(tables.contains(&"daily_snapshots".to_string()));
+        assert!(tables.contains(&"escalations".to_string()));
 
         // Verify pragmas
         let journal_mode: String = conn
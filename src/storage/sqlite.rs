@@ -2,13 +2,19 @@
 
 use crate::error::{BeadsError, Result};
 use crate::format::{IssueDetails, IssueWithDependencyMetadata};
-use crate::model::{Comment, DependencyType, Event, EventType, Issue, IssueType, Priority, Status};
+use crate::hooks::HookRunner;
+use crate::model::{
+    Comment, CommitLink, DependencyType, Event, EventType, Issue, IssueType, Priority,
+    SnapshotCount, Status,
+};
 use crate::storage::events::get_events;
+use crate::storage::query_dsl::WhereClause;
 use crate::storage::schema::apply_schema;
 use chrono::{DateTime, NaiveDateTime, TimeZone, Utc};
 use rusqlite::{Connection, OpenFlags, OptionalExtension, Transaction};
 use std::collections::{HashMap, HashSet};
 use std::fmt::Write as _;
+use std::ops::ControlFlow;
 use std::path::{Path, PathBuf};
 use std::time::Duration;
 use tracing::warn;
@@ -17,6 +23,7 @@ use tracing::warn;
 #[derive(Debug)]
 pub struct SqliteStorage {
     conn: Connection,
+    hooks: Option<HookRunner>,
 }
 
 /// Context for a mutation operation, tracking side effects.
@@ -83,6 +90,86 @@ impl MutationContext {
     }
 }
 
+/// Read access to the database for checks that run inside a write
+/// transaction.
+///
+/// The guards of [`SqliteStorage::create_issue_guarded`] and
+/// [`SqliteStorage::update_issue_guarded`] get one, so what they read cannot
+/// change before the write lands.
+pub struct TxReader<'a> {
+    conn: &'a Connection,
+}
+
+impl TxReader<'_> {
+    /// An issue's status and assignee, or `None` if it does not exist.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn issue_state(&self, id: &str) -> Result<Option<(Status, Option<String>)>> {
+        let state = self
+            .conn
+            .query_row(
+                "SELECT status, assignee FROM issues WHERE id = ?",
+                [id],
+                |row| {
+                    Ok((
+                        parse_status(row.get::<_, Option<String>>(0)?.as_deref()),
+                        SqliteStorage::empty_to_none(row.get(1)?),
+                    ))
+                },
+            )
+            .optional()?;
+        Ok(state)
+    }
+
+    /// Labels of an issue, sorted.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn labels(&self, id: &str) -> Result<Vec<String>> {
+        let mut stmt = self
+            .conn
+            .prepare_cached("SELECT label FROM labels WHERE issue_id = ? ORDER BY label")?;
+        let labels = stmt
+            .query_map([id], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(labels)
+    }
+
+    /// ID, assignee and labels of every non-template issue in `status`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn issues_in_status(
+        &self,
+        status: &str,
+    ) -> Result<Vec<(String, Option<String>, Vec<String>)>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT i.id, i.assignee, l.label FROM issues i
+             LEFT JOIN labels l ON l.issue_id = i.id
+             WHERE i.status = ? AND (i.is_template = 0 OR i.is_template IS NULL)
+             ORDER BY i.id, l.label",
+        )?;
+        let mut rows = stmt.query([status])?;
+        let mut issues: Vec<(String, Option<String>, Vec<String>)> = Vec::new();
+        while let Some(row) = rows.next()? {
+            let id: String = row.get(0)?;
+            let label: Option<String> = row.get(2)?;
+            if issues.last().is_none_or(|(last, _, _)| *last != id) {
+                let assignee = SqliteStorage::empty_to_none(row.get(1)?);
+                issues.push((id, assignee, Vec::new()));
+            }
+            if let (Some(label), Some((_, _, labels))) = (label, issues.last_mut()) {
+                labels.push(label);
+            }
+        }
+        Ok(issues)
+    }
+}
+
 impl SqliteStorage {
     /// Open a new connection to the database at the given path.
     ///
@@ -104,7 +191,7 @@ impl SqliteStorage {
             conn.busy_timeout(Duration::from_millis(timeout))?;
         }
         apply_schema(&conn)?;
-        Ok(Self { conn })
+        Ok(Self { conn, hooks: None })
     }
 
     /// Open an in-memory database for testing.
@@ -115,7 +202,7 @@ impl SqliteStorage {
     pub fn open_memory() -> Result<Self> {
         let conn = Connection::open_in_memory()?;
         apply_schema(&conn)?;
-        Ok(Self { conn })
+        Ok(Self { conn, hooks: None })
     }
 
     /// Get audit events for a specific issue.
@@ -136,6 +223,61 @@ impl SqliteStorage {
         crate::storage::events::get_all_events(&self.conn, limit)
     }
 
+    /// Get events newer than the given event ID (oldest first).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_events_since(&self, since_id: i64, limit: usize) -> Result<Vec<Event>> {
+        crate::storage::events::get_events_since(&self.conn, since_id, limit)
+    }
+
+    /// Insert events recorded in another clone, in one transaction.
+    ///
+    /// Events for issues that do not exist locally are skipped. Returns the
+    /// number of events inserted. Callers are responsible for deduplication.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database insert fails.
+    pub fn import_events(&mut self, events: &[Event]) -> Result<usize> {
+        let tx = self
+            .conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+        let mut inserted = 0;
+        for event in events {
+            if crate::storage::events::insert_imported_event(&tx, event)? {
+                inserted += 1;
+            }
+        }
+        tx.commit()?;
+        Ok(inserted)
+    }
+
+    /// Get the highest event ID recorded so far (0 if there are no events).
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn max_event_id(&self) -> Result<i64> {
+        crate::storage::events::get_max_event_id(&self.conn)
+    }
+
+    /// Read `PRAGMA data_version` for this connection.
+    ///
+    /// The value changes whenever another connection commits to the database,
+    /// so long-running readers can poll it cheaply to detect changes.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pragma query fails.
+    pub fn data_version(&self) -> Result<i64> {
+        let version: i64 = self
+            .conn
+            .query_row("PRAGMA data_version", [], |row| row.get(0))?;
+        Ok(version)
+    }
+
     /// Execute a mutation with the 4-step transaction protocol.
     ///
     /// # Errors
@@ -153,8 +295,14 @@ impl SqliteStorage {
 
         let result = f(&tx, &mut ctx)?;
 
+        // Pre-hooks may veto; returning here drops (rolls back) the transaction.
+        // They run with the write lock held, so they must not write through `br`.
+        if let Some(hooks) = &self.hooks {
+            hooks.run_pre(&ctx.op_name, &ctx.actor, &ctx.events)?;
+        }
+
         // Write events
-        for event in ctx.events {
+        for event in &mut ctx.events {
             tx.execute(
                 "INSERT INTO events (issue_id, event_type, actor, old_value, new_value, comment, created_at)
                  VALUES (?, ?, ?, ?, ?, ?, ?)",
@@ -168,10 +316,11 @@ impl SqliteStorage {
                     event.created_at.to_rfc3339()
                 ],
             )?;
+            event.id = tx.last_insert_rowid();
         }
 
         // Mark dirty
-        for id in ctx.dirty_ids {
+        for id in &ctx.dirty_ids {
             tx.execute(
                 "INSERT OR REPLACE INTO dirty_issues (issue_id, marked_at) VALUES (?, ?)",
                 rusqlite::params![id, Utc::now().to_rfc3339()],
@@ -188,17 +337,47 @@ impl SqliteStorage {
 
         tx.commit()?;
 
+        if let Some(hooks) = &self.hooks {
+            hooks.run_post(&ctx.op_name, &ctx.actor, &ctx.events);
+        }
+
         Ok(result)
     }
 
+    /// Install event hooks that run around every mutation.
+    pub fn set_hooks(&mut self, hooks: HookRunner) {
+        self.hooks = Some(hooks);
+    }
+
+    /// Installed event hooks, if any.
+    #[must_use]
+    pub const fn hooks(&self) -> Option<&HookRunner> {
+        self.hooks.as_ref()
+    }
+
     /// Create a new issue.
     ///
     /// # Errors
     ///
     /// Returns an error if the issue cannot be inserted (e.g. ID collision).
-    #[allow(clippy::too_many_lines)]
     pub fn create_issue(&mut self, issue: &Issue, actor: &str) -> Result<()> {
+        self.create_issue_guarded(issue, actor, |_| Ok(()))
+    }
+
+    /// Create a new issue once `guard` passes, holding the write lock from
+    /// the guard's reads through the insert.
+    ///
+    /// # Errors
+    ///
+    /// Returns the guard's error, or an error if the issue cannot be inserted.
+    #[allow(clippy::too_many_lines)]
+    pub fn create_issue_guarded<G>(&mut self, issue: &Issue, actor: &str, guard: G) -> Result<()>
+    where
+        G: FnOnce(&TxReader<'_>) -> Result<()>,
+    {
         self.mutate("create_issue", actor, |tx, ctx| {
+            guard(&TxReader { conn: tx })?;
+
             let status_str = issue.status.as_str();
             let issue_type_str = issue.issue_type.as_str();
             let created_at_str = issue.created_at.to_rfc3339();
@@ -384,225 +563,286 @@ impl SqliteStorage {
     /// # Errors
     ///
     /// Returns an error if the issue doesn't exist or the update fails.
-    #[allow(clippy::too_many_lines)]
     pub fn update_issue(&mut self, id: &str, updates: &IssueUpdate, actor: &str) -> Result<Issue> {
+        self.update_issue_guarded(id, updates, actor, |_| Ok(()))
+    }
+
+    /// Update an issue's fields once `guard` passes, holding the write lock
+    /// from the guard's reads through the update. The guard does not run when
+    /// there is nothing to update.
+    ///
+    /// # Errors
+    ///
+    /// Returns the guard's error, or an error if the issue doesn't exist or
+    /// the update fails.
+    pub fn update_issue_guarded<G>(
+        &mut self,
+        id: &str,
+        updates: &IssueUpdate,
+        actor: &str,
+        guard: G,
+    ) -> Result<Issue>
+    where
+        G: FnOnce(&TxReader<'_>) -> Result<()>,
+    {
+        self.update_issue_with_labels(id, updates, None, actor, guard)
+    }
+
+    /// [`Self::update_issue_guarded`] that also replaces the issue's labels
+    /// when `labels` is given, in the same transaction.
+    ///
+    /// # Errors
+    ///
+    /// Returns the guard's error, or an error if the issue doesn't exist or
+    /// a write fails; nothing is written then.
+    pub fn update_issue_with_labels<G>(
+        &mut self,
+        id: &str,
+        updates: &IssueUpdate,
+        labels: Option<&[String]>,
+        actor: &str,
+        guard: G,
+    ) -> Result<Issue>
+    where
+        G: FnOnce(&TxReader<'_>) -> Result<()>,
+    {
         let mut issue = self
             .get_issue(id)?
             .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })?;
 
-        if updates.is_empty() {
+        if updates.is_empty() && labels.is_none() {
             return Ok(issue);
         }
 
         self.mutate("update_issue", actor, |tx, ctx| {
-            let mut set_clauses: Vec<String> = vec![];
-            let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
-
-            // Helper to add update
-            let mut add_update = |field: &str, val: Box<dyn rusqlite::ToSql>| {
-                set_clauses.push(format!("{field} = ?"));
-                params.push(val);
-            };
-
-            // Title
-            if let Some(ref title) = updates.title {
-                let old_title = issue.title.clone();
-                issue.title.clone_from(title);
-                add_update("title", Box::new(title.clone()));
-                ctx.record_field_change(
-                    EventType::Updated,
-                    id,
-                    Some(old_title),
-                    Some(title.clone()),
-                    Some("Title changed".to_string()),
-                );
-            }
-
-            // Simple text fields - use empty string instead of NULL for bd compatibility
-            if let Some(ref val) = updates.description {
-                issue.description.clone_from(val);
-                add_update(
-                    "description",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
-            }
-            if let Some(ref val) = updates.design {
-                issue.design.clone_from(val);
-                add_update("design", Box::new(val.as_deref().unwrap_or("").to_string()));
+            guard(&TxReader { conn: tx })?;
+            if !updates.is_empty() {
+                Self::write_update(tx, ctx, id, &mut issue, updates)?;
             }
-            if let Some(ref val) = updates.acceptance_criteria {
-                issue.acceptance_criteria.clone_from(val);
-                add_update(
-                    "acceptance_criteria",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
-            }
-            if let Some(ref val) = updates.notes {
-                issue.notes.clone_from(val);
-                add_update("notes", Box::new(val.as_deref().unwrap_or("").to_string()));
+            if let Some(labels) = labels {
+                Self::replace_labels(tx, ctx, id, labels)?;
             }
+            Ok(())
+        })?;
 
-            // Status
-            if let Some(ref status) = updates.status {
-                let old_status = issue.status.as_str().to_string();
-                issue.status.clone_from(status);
-                add_update("status", Box::new(status.as_str().to_string()));
-                ctx.record_field_change(
-                    EventType::StatusChanged,
-                    id,
-                    Some(old_status),
-                    Some(status.as_str().to_string()),
-                    None,
-                );
+        // Return updated issue
+        self.get_issue(id)?
+            .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })
+    }
 
-                // Record Closed event if status is now Closed
-                if *status == Status::Closed {
-                    let reason = updates.close_reason.as_ref().and_then(Clone::clone);
-                    ctx.record_event(EventType::Closed, id, reason);
+    /// Write `updates` to issue `id`, whose current state is `issue`, and
+    /// bring `issue` up to date.
+    #[allow(clippy::too_many_lines)]
+    fn write_update(
+        tx: &Connection,
+        ctx: &mut MutationContext,
+        id: &str,
+        issue: &mut Issue,
+        updates: &IssueUpdate,
+    ) -> Result<()> {
+        let mut set_clauses: Vec<String> = vec![];
+        let mut params: Vec<Box<dyn rusqlite::ToSql>> = vec![];
 
-                    // Auto-set closed_at if not provided
-                    if updates.closed_at.is_none() && issue.closed_at.is_none() {
-                        let now = Utc::now();
-                        issue.closed_at = Some(now);
-                        add_update("closed_at", Box::new(Some(now.to_rfc3339())));
-                    }
-                } else if issue.closed_at.is_some() && updates.closed_at.is_none() {
-                    // Reopening (or fixing state): Clear closed_at if it was set
-                    issue.closed_at = None;
-                    add_update("closed_at", Box::new(None::<String>));
-                }
+        // Helper to add update
+        let mut add_update = |field: &str, val: Box<dyn rusqlite::ToSql>| {
+            set_clauses.push(format!("{field} = ?"));
+            params.push(val);
+        };
 
-                if !updates.skip_cache_rebuild {
-                    ctx.invalidate_cache();
-                }
-            }
+        // Title
+        if let Some(ref title) = updates.title {
+            let old_title = issue.title.clone();
+            issue.title.clone_from(title);
+            add_update("title", Box::new(title.clone()));
+            ctx.record_field_change(
+                EventType::Updated,
+                id,
+                Some(old_title),
+                Some(title.clone()),
+                Some("Title changed".to_string()),
+            );
+        }
 
-            // Priority
-            if let Some(priority) = updates.priority {
-                let old_priority = issue.priority.0;
-                issue.priority = priority;
-                add_update("priority", Box::new(priority.0));
-                if priority.0 != old_priority {
-                    ctx.record_field_change(
-                        EventType::PriorityChanged,
-                        id,
-                        Some(old_priority.to_string()),
-                        Some(priority.0.to_string()),
-                        None,
-                    );
-                }
-            }
+        // Simple text fields - use empty string instead of NULL for bd compatibility
+        if let Some(ref val) = updates.description {
+            issue.description.clone_from(val);
+            add_update(
+                "description",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
+        if let Some(ref val) = updates.design {
+            issue.design.clone_from(val);
+            add_update("design", Box::new(val.as_deref().unwrap_or("").to_string()));
+        }
+        if let Some(ref val) = updates.acceptance_criteria {
+            issue.acceptance_criteria.clone_from(val);
+            add_update(
+                "acceptance_criteria",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
+        if let Some(ref val) = updates.notes {
+            issue.notes.clone_from(val);
+            add_update("notes", Box::new(val.as_deref().unwrap_or("").to_string()));
+        }
 
-            // Issue type
-            if let Some(ref issue_type) = updates.issue_type {
-                issue.issue_type.clone_from(issue_type);
-                add_update("issue_type", Box::new(issue_type.as_str().to_string()));
-            }
+        // Status
+        if let Some(ref status) = updates.status {
+            let old_status = issue.status.as_str().to_string();
+            issue.status.clone_from(status);
+            add_update("status", Box::new(status.as_str().to_string()));
+            ctx.record_field_change(
+                EventType::StatusChanged,
+                id,
+                Some(old_status),
+                Some(status.as_str().to_string()),
+                None,
+            );
 
-            // Assignee
-            if let Some(ref assignee_opt) = updates.assignee {
-                let old_assignee = issue.assignee.clone();
-                issue.assignee.clone_from(assignee_opt);
-                add_update("assignee", Box::new(assignee_opt.clone()));
-                if old_assignee != *assignee_opt {
-                    ctx.record_field_change(
-                        EventType::AssigneeChanged,
-                        id,
-                        old_assignee,
-                        assignee_opt.clone(),
-                        None,
-                    );
+            // Record Closed event if status is now Closed
+            if *status == Status::Closed {
+                let reason = updates.close_reason.as_ref().and_then(Clone::clone);
+                ctx.record_event(EventType::Closed, id, reason);
+
+                // Auto-set closed_at if not provided
+                if updates.closed_at.is_none() && issue.closed_at.is_none() {
+                    let now = Utc::now();
+                    issue.closed_at = Some(now);
+                    add_update("closed_at", Box::new(Some(now.to_rfc3339())));
                 }
+            } else if issue.closed_at.is_some() && updates.closed_at.is_none() {
+                // Reopening (or fixing state): Clear closed_at if it was set
+                issue.closed_at = None;
+                add_update("closed_at", Box::new(None::<String>));
             }
 
-            // Simple Option fields - use empty string instead of NULL for bd compatibility
-            if let Some(ref val) = updates.owner {
-                issue.owner.clone_from(val);
-                add_update("owner", Box::new(val.as_deref().unwrap_or("").to_string()));
-            }
-            if let Some(ref val) = updates.estimated_minutes {
-                issue.estimated_minutes = *val;
-                add_update("estimated_minutes", Box::new(*val));
-            }
-            if let Some(ref val) = updates.external_ref {
-                issue.external_ref.clone_from(val);
-                add_update("external_ref", Box::new(val.clone()));
-            }
-            // Use empty string instead of NULL for bd compatibility
-            if let Some(ref val) = updates.close_reason {
-                issue.close_reason.clone_from(val);
-                add_update(
-                    "close_reason",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
-            }
-            if let Some(ref val) = updates.closed_by_session {
-                issue.closed_by_session.clone_from(val);
-                add_update(
-                    "closed_by_session",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
-                );
+            if !updates.skip_cache_rebuild {
+                ctx.invalidate_cache();
             }
+        }
 
-            // Tombstone fields
-            if let Some(ref val) = updates.deleted_at {
-                issue.deleted_at = *val;
-                add_update("deleted_at", Box::new(val.map(|d| d.to_rfc3339())));
-            }
-            // Use empty string instead of NULL for bd compatibility
-            if let Some(ref val) = updates.deleted_by {
-                issue.deleted_by.clone_from(val);
-                add_update(
-                    "deleted_by",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
+        // Priority
+        if let Some(priority) = updates.priority {
+            let old_priority = issue.priority.0;
+            issue.priority = priority;
+            add_update("priority", Box::new(priority.0));
+            if priority.0 != old_priority {
+                ctx.record_field_change(
+                    EventType::PriorityChanged,
+                    id,
+                    Some(old_priority.to_string()),
+                    Some(priority.0.to_string()),
+                    None,
                 );
             }
-            if let Some(ref val) = updates.delete_reason {
-                issue.delete_reason.clone_from(val);
-                add_update(
-                    "delete_reason",
-                    Box::new(val.as_deref().unwrap_or("").to_string()),
+        }
+
+        // Issue type
+        if let Some(ref issue_type) = updates.issue_type {
+            issue.issue_type.clone_from(issue_type);
+            add_update("issue_type", Box::new(issue_type.as_str().to_string()));
+        }
+
+        // Assignee
+        if let Some(ref assignee_opt) = updates.assignee {
+            let old_assignee = issue.assignee.clone();
+            issue.assignee.clone_from(assignee_opt);
+            add_update("assignee", Box::new(assignee_opt.clone()));
+            if old_assignee != *assignee_opt {
+                ctx.record_field_change(
+                    EventType::AssigneeChanged,
+                    id,
+                    old_assignee,
+                    assignee_opt.clone(),
+                    None,
                 );
             }
+        }
 
-            // Date fields
-            if let Some(ref val) = updates.due_at {
-                issue.due_at = *val;
-                add_update("due_at", Box::new(val.map(|d| d.to_rfc3339())));
-            }
-            if let Some(ref val) = updates.defer_until {
-                issue.defer_until = *val;
-                add_update("defer_until", Box::new(val.map(|d| d.to_rfc3339())));
-            }
-            if let Some(ref val) = updates.closed_at {
-                issue.closed_at = *val;
-                add_update("closed_at", Box::new(val.map(|d| d.to_rfc3339())));
-            }
+        // Simple Option fields - use empty string instead of NULL for bd compatibility
+        if let Some(ref val) = updates.owner {
+            issue.owner.clone_from(val);
+            add_update("owner", Box::new(val.as_deref().unwrap_or("").to_string()));
+        }
+        if let Some(ref val) = updates.estimated_minutes {
+            issue.estimated_minutes = *val;
+            add_update("estimated_minutes", Box::new(*val));
+        }
+        if let Some(ref val) = updates.external_ref {
+            issue.external_ref.clone_from(val);
+            add_update("external_ref", Box::new(val.clone()));
+        }
+        // Use empty string instead of NULL for bd compatibility
+        if let Some(ref val) = updates.close_reason {
+            issue.close_reason.clone_from(val);
+            add_update(
+                "close_reason",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
+        if let Some(ref val) = updates.closed_by_session {
+            issue.closed_by_session.clone_from(val);
+            add_update(
+                "closed_by_session",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
 
-            // Always update updated_at
-            set_clauses.push("updated_at = ?".to_string());
-            params.push(Box::new(Utc::now().to_rfc3339()));
+        // Tombstone fields
+        if let Some(ref val) = updates.deleted_at {
+            issue.deleted_at = *val;
+            add_update("deleted_at", Box::new(val.map(|d| d.to_rfc3339())));
+        }
+        // Use empty string instead of NULL for bd compatibility
+        if let Some(ref val) = updates.deleted_by {
+            issue.deleted_by.clone_from(val);
+            add_update(
+                "deleted_by",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
+        if let Some(ref val) = updates.delete_reason {
+            issue.delete_reason.clone_from(val);
+            add_update(
+                "delete_reason",
+                Box::new(val.as_deref().unwrap_or("").to_string()),
+            );
+        }
 
-            // Update content hash
-            let new_hash = issue.compute_content_hash();
-            set_clauses.push("content_hash = ?".to_string());
-            params.push(Box::new(new_hash));
+        // Date fields
+        if let Some(ref val) = updates.due_at {
+            issue.due_at = *val;
+            add_update("due_at", Box::new(val.map(|d| d.to_rfc3339())));
+        }
+        if let Some(ref val) = updates.defer_until {
+            issue.defer_until = *val;
+            add_update("defer_until", Box::new(val.map(|d| d.to_rfc3339())));
+        }
+        if let Some(ref val) = updates.closed_at {
+            issue.closed_at = *val;
+            add_update("closed_at", Box::new(val.map(|d| d.to_rfc3339())));
+        }
 
-            // Build and execute SQL
-            let sql = format!("UPDATE issues SET {} WHERE id = ? ", set_clauses.join(", "));
-            params.push(Box::new(id.to_string()));
+        // Always update updated_at
+        set_clauses.push("updated_at = ?".to_string());
+        params.push(Box::new(Utc::now().to_rfc3339()));
 
-            let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
-            tx.execute(&sql, params_refs.as_slice())?;
+        // Update content hash
+        let new_hash = issue.compute_content_hash();
+        set_clauses.push("content_hash = ?".to_string());
+        params.push(Box::new(new_hash));
 
-            ctx.mark_dirty(id);
+        // Build and execute SQL
+        let sql = format!("UPDATE issues SET {} WHERE id = ? ", set_clauses.join(", "));
+        params.push(Box::new(id.to_string()));
 
-            Ok(())
-        })?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
+        tx.execute(&sql, params_refs.as_slice())?;
 
-        // Return updated issue
-        self.get_issue(id)?
-            .ok_or_else(|| BeadsError::IssueNotFound { id: id.to_string() })
+        ctx.mark_dirty(id);
+
+        Ok(())
     }
 
     /// Delete an issue by creating a tombstone.
@@ -732,8 +972,44 @@ impl SqliteStorage {
     /// # Errors
     ///
     /// Returns an error if the database query fails.
-    #[allow(clippy::too_many_lines)]
     pub fn list_issues(&self, filters: &ListFilters) -> Result<Vec<Issue>> {
+        let mut issues = Vec::new();
+        self.for_each_issue(filters, |issue| {
+            issues.push(issue);
+            Ok(ControlFlow::Continue(()))
+        })?;
+        Ok(issues)
+    }
+
+    /// Stream the issues [`Self::list_issues`] would return to `f`, one row
+    /// at a time, without collecting them. `f` returns
+    /// [`ControlFlow::Break`] to stop reading early. Returns the number of
+    /// rows visited.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails or `f` fails.
+    pub fn for_each_issue<F>(&self, filters: &ListFilters, mut f: F) -> Result<usize>
+    where
+        F: FnMut(Issue) -> Result<ControlFlow<()>>,
+    {
+        let (sql, params) = Self::list_issues_query(filters);
+        let mut stmt = self.conn.prepare(&sql)?;
+        let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
+        let mut rows = stmt.query(params_refs.as_slice())?;
+        let mut count = 0;
+        while let Some(row) = rows.next()? {
+            count += 1;
+            if f(self.issue_from_row(row)?)?.is_break() {
+                break;
+            }
+        }
+        Ok(count)
+    }
+
+    /// Build the SQL and parameters for a filtered issue listing.
+    #[allow(clippy::too_many_lines)]
+    fn list_issues_query(filters: &ListFilters) -> (String, Vec<Box<dyn rusqlite::ToSql>>) {
         let mut sql = String::from(
             r"SELECT id, content_hash, title, description, design, acceptance_criteria, notes,
                      status, priority, issue_type, assignee, owner, estimated_minutes,
@@ -836,6 +1112,13 @@ impl SqliteStorage {
             params.push(Box::new(ts.to_rfc3339()));
         }
 
+        if let Some(ts) = filters.closed_after {
+            sql.push_str(" AND (status NOT IN ('closed', 'tombstone') OR closed_at >= ?)");
+            params.push(Box::new(ts.to_rfc3339()));
+        }
+
+        push_where_clause(&mut sql, &mut params, filters);
+
         // Apply custom sort if provided
         if let Some(ref sort_field) = filters.sort {
             let order = if filters.reverse { "DESC" } else { "ASC" };
@@ -879,13 +1162,7 @@ impl SqliteStorage {
             }
         }
 
-        let mut stmt = self.conn.prepare(&sql)?;
-        let params_refs: Vec<&dyn rusqlite::ToSql> = params.iter().map(AsRef::as_ref).collect();
-        let issues = stmt
-            .query_map(params_refs.as_slice(), |row| self.issue_from_row(row))?
-            .collect::<std::result::Result<Vec<_>, _>>()?;
-
-        Ok(issues)
+        (sql, params)
     }
 
     /// Search issues by query with optional filters.
@@ -998,6 +1275,8 @@ impl SqliteStorage {
             params.push(Box::new(format!("%{escaped}%")));
         }
 
+        push_where_clause(&mut sql, &mut params, filters);
+
         sql.push_str(" ORDER BY priority ASC, created_at DESC");
 
         if let Some(limit) = filters.limit {
@@ -1173,6 +1452,9 @@ impl SqliteStorage {
             ReadySortPolicy::Oldest => {
                 sql.push_str(" ORDER BY created_at ASC");
             }
+            ReadySortPolicy::Due => {
+                sql.push_str(" ORDER BY due_at IS NULL, due_at ASC, priority ASC, created_at ASC");
+            }
         }
 
         // Apply limit in SQL to avoid fetching extra rows.
@@ -1279,6 +1561,52 @@ impl SqliteStorage {
         }
     }
 
+    /// Get the blockers for multiple issues from the blocked issues cache.
+    ///
+    /// Like [`Self::get_blockers`], but in one query per chunk of IDs. Issues
+    /// that are not blocked are absent from the map.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_blockers_for_issues(
+        &self,
+        issue_ids: &[String],
+    ) -> Result<HashMap<String, Vec<String>>> {
+        const SQLITE_VAR_LIMIT: usize = 900;
+
+        let mut map: HashMap<String, Vec<String>> = HashMap::new();
+        for chunk in issue_ids.chunks(SQLITE_VAR_LIMIT) {
+            let placeholders: Vec<&str> = chunk.iter().map(|_| "?").collect();
+            let sql = format!(
+                "SELECT issue_id, blocked_by FROM blocked_issues_cache WHERE issue_id IN ({})",
+                placeholders.join(",")
+            );
+
+            let params: Vec<&dyn rusqlite::ToSql> =
+                chunk.iter().map(|s| s as &dyn rusqlite::ToSql).collect();
+
+            let mut stmt = self.conn.prepare(&sql)?;
+            let rows = stmt.query_map(params.as_slice(), |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            })?;
+
+            for row in rows {
+                let (issue_id, json) = row?;
+                let blockers: Vec<String> = serde_json::from_str(&json).unwrap_or_default();
+                map.insert(
+                    issue_id,
+                    blockers
+                        .into_iter()
+                        .map(|b| b.split(':').next().unwrap_or(&b).to_string())
+                        .collect(),
+                );
+            }
+        }
+
+        Ok(map)
+    }
+
     /// Rebuild the blocked issues cache from scratch.
     ///
     /// This computes which issues are blocked based on their dependencies
@@ -1934,36 +2262,46 @@ impl SqliteStorage {
     /// Returns an error if the database update fails.
     pub fn add_label(&mut self, issue_id: &str, label: &str, actor: &str) -> Result<bool> {
         self.mutate("add_label", actor, |tx, ctx| {
-            let exists: i64 = tx.query_row(
-                "SELECT count(*) FROM labels WHERE issue_id = ? AND label = ?",
-                rusqlite::params![issue_id, label],
-                |row| row.get(0),
-            )?;
+            Self::insert_label(tx, ctx, issue_id, label)
+        })
+    }
 
-            if exists > 0 {
-                return Ok(false);
-            }
+    /// Add a label inside an open mutation; returns false if it was already set.
+    fn insert_label(
+        tx: &Connection,
+        ctx: &mut MutationContext,
+        issue_id: &str,
+        label: &str,
+    ) -> Result<bool> {
+        let exists: i64 = tx.query_row(
+            "SELECT count(*) FROM labels WHERE issue_id = ? AND label = ?",
+            rusqlite::params![issue_id, label],
+            |row| row.get(0),
+        )?;
 
-            tx.execute(
-                "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
-                rusqlite::params![issue_id, label],
-            )?;
+        if exists > 0 {
+            return Ok(false);
+        }
 
-            ctx.record_event(
-                EventType::LabelAdded,
-                issue_id,
-                Some(format!("Added label {label}")),
-            );
-            ctx.mark_dirty(issue_id);
+        tx.execute(
+            "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
+            rusqlite::params![issue_id, label],
+        )?;
 
-            // Update timestamp
-            tx.execute(
-                "UPDATE issues SET updated_at = ? WHERE id = ?",
-                rusqlite::params![Utc::now().to_rfc3339(), issue_id],
-            )?;
+        ctx.record_event(
+            EventType::LabelAdded,
+            issue_id,
+            Some(format!("Added label {label}")),
+        );
+        ctx.mark_dirty(issue_id);
 
-            Ok(true)
-        })
+        // Update timestamp
+        tx.execute(
+            "UPDATE issues SET updated_at = ? WHERE id = ?",
+            rusqlite::params![Utc::now().to_rfc3339(), issue_id],
+        )?;
+
+        Ok(true)
     }
 
     /// Remove a label from an issue.
@@ -2035,63 +2373,73 @@ impl SqliteStorage {
     /// Returns an error if the database update fails.
     pub fn set_labels(&mut self, issue_id: &str, labels: &[String], actor: &str) -> Result<()> {
         self.mutate("set_labels", actor, |tx, ctx| {
-            let mut stmt = tx.prepare("SELECT label FROM labels WHERE issue_id = ?")?;
-            let old_labels: Vec<String> = stmt
-                .query_map([issue_id], |row| row.get(0))?
-                .collect::<std::result::Result<Vec<_>, _>>()?;
-            drop(stmt);
+            Self::replace_labels(tx, ctx, issue_id, labels)
+        })
+    }
 
-            tx.execute("DELETE FROM labels WHERE issue_id = ?", [issue_id])?;
+    /// Replace the labels of `issue_id`, recording what changed.
+    fn replace_labels(
+        tx: &Connection,
+        ctx: &mut MutationContext,
+        issue_id: &str,
+        labels: &[String],
+    ) -> Result<()> {
+        let mut stmt = tx.prepare("SELECT label FROM labels WHERE issue_id = ?")?;
+        let old_labels: Vec<String> = stmt
+            .query_map([issue_id], |row| row.get(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        drop(stmt);
 
-            for label in labels {
-                tx.execute(
-                    "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
-                    rusqlite::params![issue_id, label],
-                )?;
-            }
+        tx.execute("DELETE FROM labels WHERE issue_id = ?", [issue_id])?;
 
-            // Record changes
-            let removed: Vec<_> = old_labels.iter().filter(|l| !labels.contains(l)).collect();
-            let added: Vec<_> = labels.iter().filter(|l| !old_labels.contains(l)).collect();
-
-            if !removed.is_empty() || !added.is_empty() {
-                let mut details = Vec::new();
-                if !removed.is_empty() {
-                    details.push(format!(
-                        "removed: {}",
-                        removed
-                            .iter()
-                            .map(|s| s.as_str())
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    ));
-                }
-                if !added.is_empty() {
-                    details.push(format!(
-                        "added: {}",
-                        added
-                            .iter()
-                            .map(|s| s.as_str())
-                            .collect::<Vec<_>>()
-                            .join(", ")
-                    ));
-                }
-                ctx.record_event(
-                    EventType::Updated,
-                    issue_id,
-                    Some(format!("Labels {}", details.join("; "))),
-                );
-                ctx.mark_dirty(issue_id);
+        for label in labels {
+            tx.execute(
+                "INSERT INTO labels (issue_id, label) VALUES (?, ?)",
+                rusqlite::params![issue_id, label],
+            )?;
+        }
 
-                // Bump updated_at
-                tx.execute(
-                    "UPDATE issues SET updated_at = ? WHERE id = ?",
-                    rusqlite::params![Utc::now().to_rfc3339(), issue_id],
-                )?;
+        // Record changes
+        let removed: Vec<_> = old_labels.iter().filter(|l| !labels.contains(l)).collect();
+        let added: Vec<_> = labels.iter().filter(|l| !old_labels.contains(l)).collect();
+
+        if !removed.is_empty() || !added.is_empty() {
+            let mut details = Vec::new();
+            if !removed.is_empty() {
+                details.push(format!(
+                    "removed: {}",
+                    removed
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
+            }
+            if !added.is_empty() {
+                details.push(format!(
+                    "added: {}",
+                    added
+                        .iter()
+                        .map(|s| s.as_str())
+                        .collect::<Vec<_>>()
+                        .join(", ")
+                ));
             }
+            ctx.record_event(
+                EventType::Updated,
+                issue_id,
+                Some(format!("Labels {}", details.join("; "))),
+            );
+            ctx.mark_dirty(issue_id);
 
-            Ok(())
-        })
+            // Bump updated_at
+            tx.execute(
+                "UPDATE issues SET updated_at = ? WHERE id = ?",
+                rusqlite::params![Utc::now().to_rfc3339(), issue_id],
+            )?;
+        }
+
+        Ok(())
     }
 
     /// Get labels for an issue.
@@ -2100,13 +2448,13 @@ impl SqliteStorage {
     ///
     /// Returns an error if the database query fails.
     pub fn get_labels(&self, issue_id: &str) -> Result<Vec<String>> {
-        let mut stmt = self
-            .conn
-            .prepare_cached("SELECT label FROM labels WHERE issue_id = ? ORDER BY label")?;
-        let labels = stmt
-            .query_map([issue_id], |row| row.get(0))?
-            .collect::<std::result::Result<Vec<_>, _>>()?;
-        Ok(labels)
+        self.reader().labels(issue_id)
+    }
+
+    /// The [`TxReader`] queries, outside any write transaction.
+    #[must_use]
+    pub const fn reader(&self) -> TxReader<'_> {
+        TxReader { conn: &self.conn }
     }
 
     /// Get labels for multiple issues efficiently.
@@ -2308,6 +2656,67 @@ impl SqliteStorage {
         })
     }
 
+    /// Replace an issue's compactable content in a single mutation.
+    ///
+    /// Writes description, design, notes, the compaction metadata fields and
+    /// the full comment list from `issue`. Used by `br compact` both to compact
+    /// an issue and to restore it from the compaction archive.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the issue doesn't exist or the update fails.
+    pub fn apply_compaction(
+        &mut self,
+        issue: &Issue,
+        event_type: EventType,
+        detail: &str,
+        actor: &str,
+    ) -> Result<()> {
+        self.mutate("compact_issue", actor, |tx, ctx| {
+            let updated = tx.execute(
+                "UPDATE issues SET
+                    description = ?, design = ?, notes = ?,
+                    compaction_level = ?, compacted_at = ?, compacted_at_commit = ?,
+                    original_size = ?, content_hash = ?, updated_at = ?
+                 WHERE id = ?",
+                rusqlite::params![
+                    issue.description.as_deref().unwrap_or(""),
+                    issue.design.as_deref().unwrap_or(""),
+                    issue.notes.as_deref().unwrap_or(""),
+                    issue.compaction_level.unwrap_or(0),
+                    issue.compacted_at.map(|dt| dt.to_rfc3339()),
+                    issue.compacted_at_commit,
+                    issue.original_size.unwrap_or(0),
+                    issue.compute_content_hash(),
+                    Utc::now().to_rfc3339(),
+                    issue.id,
+                ],
+            )?;
+            if updated == 0 {
+                return Err(BeadsError::IssueNotFound {
+                    id: issue.id.clone(),
+                });
+            }
+
+            tx.execute("DELETE FROM comments WHERE issue_id = ?", [&issue.id])?;
+            for comment in &issue.comments {
+                tx.execute(
+                    "INSERT INTO comments (issue_id, author, text, created_at) VALUES (?, ?, ?, ?)",
+                    rusqlite::params![
+                        issue.id,
+                        comment.author,
+                        comment.body,
+                        comment.created_at.to_rfc3339()
+                    ],
+                )?;
+            }
+
+            ctx.record_event(event_type, &issue.id, Some(detail.to_string()));
+            ctx.mark_dirty(&issue.id);
+            Ok(())
+        })
+    }
+
     /// Get dependencies with metadata.
     ///
     /// # Errors
@@ -2930,6 +3339,382 @@ impl SqliteStorage {
         Ok(ids)
     }
 
+    /// Hard-delete tombstones and prune bookkeeping rows.
+    ///
+    /// Deletes the given tombstones (their labels, comments and events go with
+    /// them via `ON DELETE CASCADE`, and dependencies pointing at them are
+    /// removed), events created before `cutoff`, export hashes recorded before
+    /// `cutoff`, dirty flags older than `cutoff` whose content has already been
+    /// exported, and rows left behind for issues that no longer exist. With
+    /// `dry_run` the same statements run and are rolled back, so the counts
+    /// are exact.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database statement fails.
+    pub fn garbage_collect(
+        &mut self,
+        tombstone_ids: &[String],
+        cutoff: DateTime<Utc>,
+        dry_run: bool,
+    ) -> Result<GcStats> {
+        let tx = self
+            .conn
+            .transaction_with_behavior(rusqlite::TransactionBehavior::Immediate)?;
+        let cutoff = cutoff.to_rfc3339();
+        let events_pruned = tx.execute(
+            "DELETE FROM events WHERE datetime(created_at) < datetime(?)",
+            [&cutoff],
+        )?;
+        let mut stats = GcStats {
+            events_pruned,
+            ..GcStats::default()
+        };
+
+        for id in tombstone_ids {
+            stats.dependencies_removed +=
+                tx.execute("DELETE FROM dependencies WHERE depends_on_id = ?", [id])?;
+            stats.tombstones_purged += tx.execute(
+                "DELETE FROM issues WHERE id = ? AND status = 'tombstone'",
+                [id],
+            )?;
+        }
+
+        stats.dirty_pruned = tx.execute(
+            "DELETE FROM dirty_issues
+             WHERE issue_id NOT IN (SELECT id FROM issues)
+                OR (datetime(marked_at) < datetime(?1)
+                    AND EXISTS (
+                        SELECT 1 FROM export_hashes eh JOIN issues i ON i.id = eh.issue_id
+                        WHERE eh.issue_id = dirty_issues.issue_id
+                          AND eh.content_hash = i.content_hash))",
+            [&cutoff],
+        )?;
+        stats.export_hashes_pruned = tx.execute(
+            "DELETE FROM export_hashes
+             WHERE issue_id NOT IN (SELECT id FROM issues)
+                OR datetime(exported_at) < datetime(?)",
+            [&cutoff],
+        )?;
+        stats.child_counters_pruned = tx.execute(
+            "DELETE FROM child_counters WHERE parent_id NOT IN (SELECT id FROM issues)",
+            [],
+        )?;
+
+        if dry_run {
+            tx.rollback()?;
+        } else {
+            if stats.tombstones_purged > 0 {
+                Self::rebuild_blocked_cache_impl(&tx)?;
+            }
+            tx.commit()?;
+        }
+        Ok(stats)
+    }
+
+    /// Reclaim free pages and refresh query planner statistics.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if `VACUUM` or `ANALYZE` fails.
+    pub fn vacuum_and_analyze(&self) -> Result<()> {
+        self.conn
+            .query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+        self.conn.execute_batch("VACUUM; ANALYZE;")?;
+        Ok(())
+    }
+
+    /// Bytes held by free pages, which `VACUUM` would return to the filesystem.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the pragma queries fail.
+    pub fn reclaimable_bytes(&self) -> Result<u64> {
+        let free_pages: i64 = self
+            .conn
+            .query_row("PRAGMA freelist_count", [], |row| row.get(0))?;
+        let page_size: i64 = self
+            .conn
+            .query_row("PRAGMA page_size", [], |row| row.get(0))?;
+        Ok(u64::try_from(free_pages.saturating_mul(page_size)).unwrap_or(0))
+    }
+
+    /// Record commits that mention issues.
+    ///
+    /// Links to issues that do not exist are skipped, and links already in the
+    /// index are left alone, so re-indexing a commit range is harmless.
+    /// Returns the number of new links.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database statement fails.
+    pub fn add_commit_links(&mut self, links: &[CommitLink]) -> Result<usize> {
+        let tx = self.conn.transaction()?;
+        let mut added = 0;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT OR IGNORE INTO commit_links (issue_id, sha, author, authored_at, subject, closes)
+                 SELECT ?1, ?2, ?3, ?4, ?5, ?6 WHERE EXISTS (SELECT 1 FROM issues WHERE id = ?1)",
+            )?;
+            for link in links {
+                added += stmt.execute(rusqlite::params![
+                    link.issue_id,
+                    link.sha,
+                    link.author,
+                    link.authored_at.to_rfc3339(),
+                    link.subject,
+                    link.closes,
+                ])?;
+            }
+        }
+        tx.commit()?;
+        Ok(added)
+    }
+
+    /// Remove commit links so the index can be rebuilt from scratch.
+    ///
+    /// Closing trailers that were already applied are kept, so a rebuild does
+    /// not close an issue that was reopened after its commit closed it.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database statement fails.
+    pub fn clear_commit_links(&mut self) -> Result<usize> {
+        Ok(self
+            .conn
+            .execute("DELETE FROM commit_links WHERE applied_at IS NULL", [])?)
+    }
+
+    /// Get the commits that mention an issue, newest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_commit_links(&self, issue_id: &str) -> Result<Vec<CommitLink>> {
+        let mut stmt = self.conn.prepare_cached(
+            "SELECT issue_id, sha, author, authored_at, subject, closes
+             FROM commit_links
+             WHERE issue_id = ?
+             ORDER BY authored_at DESC, sha",
+        )?;
+        let links = stmt
+            .query_map([issue_id], commit_link_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    /// Get closing trailers that have not been applied yet, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_pending_commit_closes(&self) -> Result<Vec<CommitLink>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT issue_id, sha, author, authored_at, subject, closes
+             FROM commit_links
+             WHERE closes IS NOT NULL AND applied_at IS NULL
+             ORDER BY authored_at ASC, sha",
+        )?;
+        let links = stmt
+            .query_map([], commit_link_from_row)?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(links)
+    }
+
+    /// Mark a closing trailer as handled so it is not applied again after a reopen.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database update fails.
+    pub fn mark_commit_close_applied(&mut self, issue_id: &str, sha: &str) -> Result<()> {
+        self.conn.execute(
+            "UPDATE commit_links SET applied_at = ? WHERE issue_id = ? AND sha = ?",
+            [Utc::now().to_rfc3339().as_str(), issue_id, sha],
+        )?;
+        Ok(())
+    }
+
+    /// Record per-status, per-type and per-label counts of live issues for `day`.
+    ///
+    /// Tombstones and templates are not counted. Taking a snapshot again on the
+    /// same day replaces it. Returns the number of counts recorded.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a database statement fails.
+    pub fn take_daily_snapshot(&mut self, day: &str) -> Result<usize> {
+        let taken_at = Utc::now().to_rfc3339();
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM daily_snapshots WHERE day = ?", [day])?;
+        let mut recorded = 0;
+        for (dimension, select) in [
+            (
+                "status",
+                "SELECT status, COUNT(*) FROM issues
+                 WHERE status != 'tombstone' AND (is_template = 0 OR is_template IS NULL)
+                 GROUP BY status",
+            ),
+            (
+                "type",
+                "SELECT issue_type, COUNT(*) FROM issues
+                 WHERE status != 'tombstone' AND (is_template = 0 OR is_template IS NULL)
+                 GROUP BY issue_type",
+            ),
+            (
+                "label",
+                "SELECT l.label, COUNT(DISTINCT l.issue_id) FROM labels l
+                 JOIN issues i ON i.id = l.issue_id
+                 WHERE i.status != 'tombstone' AND (i.is_template = 0 OR i.is_template IS NULL)
+                 GROUP BY l.label",
+            ),
+        ] {
+            recorded += tx.execute(
+                &format!(
+                    "INSERT INTO daily_snapshots (day, dimension, key, count, taken_at)
+                     SELECT ?1, ?2, counts.*, ?3 FROM ({select}) AS counts"
+                ),
+                rusqlite::params![day, dimension, taken_at],
+            )?;
+        }
+        tx.commit()?;
+        Ok(recorded)
+    }
+
+    /// Whether an escalation rule already fired for an issue at this due date.
+    ///
+    /// Checks the local record and the issue's comments, so a firing made in
+    /// another clone counts once its comment has been imported.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn has_escalation(&self, issue_id: &str, rule: &str, due_at: &str) -> Result<bool> {
+        let pattern = format!(
+            "{}%",
+            escape_like_pattern(&escalation_comment_prefix(rule, due_at))
+        );
+        let exists = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM escalations WHERE issue_id = ?1 AND rule = ?2 AND due_at = ?3
+                 UNION ALL
+                 SELECT 1 FROM comments WHERE issue_id = ?1 AND text LIKE ?4 ESCAPE '\\'
+                 LIMIT 1",
+                rusqlite::params![issue_id, rule, due_at, pattern],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(exists.is_some())
+    }
+
+    /// Apply an escalation rule to an issue and record that it fired at this
+    /// due date.
+    ///
+    /// Raises the priority and/or adds the label, then adds a comment naming
+    /// the rule and due date followed by `changes`, and an `escalated` event
+    /// with the same text, all in one transaction. The comment is exported
+    /// with the issue, which is what keeps other clones from firing the rule
+    /// again. Moving the due date lets the rule fire again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the issue does not exist or the database update
+    /// fails; nothing is written in that case.
+    #[allow(clippy::too_many_arguments)]
+    pub fn apply_escalation(
+        &mut self,
+        issue_id: &str,
+        rule: &str,
+        due_at: &str,
+        priority: Option<Priority>,
+        label: Option<&str>,
+        changes: &str,
+        actor: &str,
+    ) -> Result<()> {
+        let mut issue = self
+            .get_issue(issue_id)?
+            .ok_or_else(|| BeadsError::IssueNotFound {
+                id: issue_id.to_string(),
+            })?;
+        let text = format!("{}: {changes}", escalation_comment_prefix(rule, due_at));
+        self.mutate("apply_escalation", actor, |tx, ctx| {
+            if let Some(priority) = priority {
+                let update = IssueUpdate {
+                    priority: Some(priority),
+                    ..IssueUpdate::default()
+                };
+                Self::write_update(tx, ctx, issue_id, &mut issue, &update)?;
+            }
+            if let Some(label) = label {
+                Self::insert_label(tx, ctx, issue_id, label)?;
+            }
+            tx.execute(
+                "INSERT OR IGNORE INTO escalations (issue_id, rule, due_at, applied_at)
+                 VALUES (?, ?, ?, ?)",
+                rusqlite::params![issue_id, rule, due_at, Utc::now().to_rfc3339()],
+            )?;
+            insert_comment_row(tx, issue_id, actor, &text)?;
+            tx.execute(
+                "UPDATE issues SET updated_at = ? WHERE id = ?",
+                rusqlite::params![Utc::now().to_rfc3339(), issue_id],
+            )?;
+            ctx.record_field_change(
+                EventType::Custom("escalated".to_string()),
+                issue_id,
+                None,
+                Some(rule.to_string()),
+                Some(text.clone()),
+            );
+            ctx.mark_dirty(issue_id);
+            Ok(())
+        })
+    }
+
+    /// Whether a snapshot was already taken for `day`.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn has_daily_snapshot(&self, day: &str) -> Result<bool> {
+        let exists = self
+            .conn
+            .query_row(
+                "SELECT 1 FROM daily_snapshots WHERE day = ? LIMIT 1",
+                [day],
+                |_| Ok(()),
+            )
+            .optional()?;
+        Ok(exists.is_some())
+    }
+
+    /// Get snapshot counts for one dimension from `since_day` on, oldest first.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the database query fails.
+    pub fn get_daily_snapshots(
+        &self,
+        dimension: &str,
+        since_day: &str,
+    ) -> Result<Vec<SnapshotCount>> {
+        let mut stmt = self.conn.prepare(
+            "SELECT day, dimension, key, count FROM daily_snapshots
+             WHERE dimension = ? AND day >= ?
+             ORDER BY day, key",
+        )?;
+        let counts = stmt
+            .query_map([dimension, since_day], |row| {
+                Ok(SnapshotCount {
+                    day: row.get(0)?,
+                    dimension: row.get(1)?,
+                    key: row.get(2)?,
+                    count: usize::try_from(row.get::<_, i64>(3)?).unwrap_or(0),
+                })
+            })?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(counts)
+    }
+
     /// Get a metadata value by key.
     ///
     /// # Errors
@@ -3015,6 +3800,7 @@ impl SqliteStorage {
             vec![]
         };
         let parent = self.get_parent_id(id)?;
+        let commits = self.get_commit_links(id)?;
 
         Ok(Some(IssueDetails {
             issue,
@@ -3024,6 +3810,7 @@ impl SqliteStorage {
             comments,
             events,
             parent,
+            commits,
         }))
     }
 
@@ -3145,6 +3932,21 @@ pub struct ListFilters {
     pub updated_before: Option<DateTime<Utc>>,
     /// Filter by `updated_at` >= timestamp
     pub updated_after: Option<DateTime<Utc>>,
+    /// Only include closed issues with `closed_at` >= timestamp
+    pub closed_after: Option<DateTime<Utc>>,
+    /// Filter by a `--where` expression
+    pub where_clause: Option<WhereClause>,
+}
+
+/// Row counts removed by [`SqliteStorage::garbage_collect`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize)]
+pub struct GcStats {
+    pub tombstones_purged: usize,
+    pub dependencies_removed: usize,
+    pub events_pruned: usize,
+    pub dirty_pruned: usize,
+    pub export_hashes_pruned: usize,
+    pub child_counters_pruned: usize,
 }
 
 /// Fields to update on an issue.
@@ -3228,6 +4030,8 @@ pub enum ReadySortPolicy {
     Priority,
     /// Sort by `created_at` ASC only
     Oldest,
+    /// Earliest `due_at` first (no due date last), then priority, then `created_at`
+    Due,
 }
 
 fn parse_status(s: Option<&str>) -> Status {
@@ -3300,6 +4104,17 @@ fn query_external_project_capabilities(
     Ok(satisfied)
 }
 
+fn commit_link_from_row(row: &rusqlite::Row) -> rusqlite::Result<CommitLink> {
+    Ok(CommitLink {
+        issue_id: row.get(0)?,
+        sha: row.get(1)?,
+        author: row.get(2)?,
+        authored_at: parse_datetime(&row.get::<_, String>(3)?),
+        subject: row.get(4)?,
+        closes: row.get(5)?,
+    })
+}
+
 fn parse_datetime(s: &str) -> DateTime<Utc> {
     if let Ok(dt) = chrono::DateTime::parse_from_rfc3339(s) {
         return dt.with_timezone(&Utc);
@@ -3317,9 +4132,28 @@ fn parse_datetime(s: &str) -> DateTime<Utc> {
     Utc::now()
 }
 
+/// Append a `--where` expression from `filters`, if any.
+fn push_where_clause(
+    sql: &mut String,
+    params: &mut Vec<Box<dyn rusqlite::ToSql>>,
+    filters: &ListFilters,
+) {
+    if let Some(ref clause) = filters.where_clause {
+        let _ = write!(sql, " AND ({})", clause.sql());
+        for param in clause.params() {
+            params.push(Box::new(param.clone()));
+        }
+    }
+}
+
 /// Escape special LIKE pattern characters (%, _, \) for literal matching.
 ///
 /// Use with `LIKE ? ESCAPE '\\'` in SQL queries.
+/// Start of the comment [`SqliteStorage::apply_escalation`] adds.
+fn escalation_comment_prefix(rule: &str, due_at: &str) -> String {
+    format!("Escalation rule '{rule}' fired for due date {due_at}")
+}
+
 fn escape_like_pattern(s: &str) -> String {
     s.replace('\\', "\\\\")
         .replace('%', "\\%")
@@ -4695,6 +5529,46 @@ mod tests {
         assert_eq!(issues[0].id, "bd-s1");
     }
 
+    #[test]
+    fn test_for_each_issue_streams_list_order() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let t1 = Utc.with_ymd_and_hms(2025, 8, 1, 0, 0, 0).unwrap();
+        for (id, priority) in [("bd-f1", 3), ("bd-f2", 1), ("bd-f3", 2)] {
+            let issue = make_issue(id, id, Status::Open, priority, None, t1, None);
+            storage.create_issue(&issue, "tester").unwrap();
+        }
+        let filters = ListFilters::default();
+
+        let mut streamed = Vec::new();
+        let visited = storage
+            .for_each_issue(&filters, |issue| {
+                streamed.push(issue.id);
+                Ok(ControlFlow::Continue(()))
+            })
+            .unwrap();
+        let listed: Vec<String> = storage
+            .list_issues(&filters)
+            .unwrap()
+            .into_iter()
+            .map(|issue| issue.id)
+            .collect();
+
+        assert_eq!(visited, 3);
+        assert_eq!(streamed, listed);
+        assert_eq!(streamed, vec!["bd-f2", "bd-f3", "bd-f1"]);
+
+        // Breaking stops the cursor after the current row
+        let mut first = Vec::new();
+        let visited = storage
+            .for_each_issue(&filters, |issue| {
+                first.push(issue.id);
+                Ok(ControlFlow::Break(()))
+            })
+            .unwrap();
+        assert_eq!(visited, 1);
+        assert_eq!(first, vec!["bd-f2"]);
+    }
+
     #[test]
     fn test_list_issues_reverse_default_sort() {
         let mut storage = SqliteStorage::open_memory().unwrap();
@@ -0,0 +1,486 @@
+//! Shared audit event log (`events.jsonl`).
+//!
+//! Events are recorded in each clone's local database. When `sync.events` is
+//! enabled they are also exported to `.beads/events.jsonl`, so the activity
+//! history travels with the repository through git. Each line carries a
+//! stable hash of the event's content (everything except the local row id),
+//! which is used to deduplicate on both export and import.
+//!
+//! The file is only ever extended: export merges the database's events into
+//! the existing lines, so events another clone exported but this clone has not
+//! imported yet are kept. Lines are sorted by time, then hash, which keeps
+//! the output deterministic and git merges line-oriented.
+
+use crate::error::{BeadsError, Result};
+use crate::model::{Event, EventType};
+use crate::storage::SqliteStorage;
+use crate::sync::{
+    compute_jsonl_hash, ensure_no_conflict_markers, require_safe_sync_overwrite_path,
+    require_valid_sync_path,
+};
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs::{self, File};
+use std::io::{BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+/// File name of the shared event log within `.beads/`.
+pub const EVENTS_JSONL_FILE: &str = "events.jsonl";
+
+/// Metadata key holding the content hash of `events.jsonl` at the last sync.
+pub const METADATA_EVENTS_JSONL_HASH: &str = "events_jsonl_hash";
+
+/// One line of `events.jsonl`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EventRecord {
+    pub hash: String,
+    pub issue_id: String,
+    pub event_type: EventType,
+    pub actor: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub old_value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub new_value: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub comment: Option<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+impl EventRecord {
+    /// Build a record from a database event.
+    #[must_use]
+    pub fn from_event(event: &Event) -> Self {
+        Self {
+            hash: event_hash(event),
+            issue_id: event.issue_id.clone(),
+            event_type: event.event_type.clone(),
+            actor: event.actor.clone(),
+            old_value: event.old_value.clone(),
+            new_value: event.new_value.clone(),
+            comment: event.comment.clone(),
+            created_at: event.created_at,
+        }
+    }
+
+    /// Convert back into an event (without a local row id).
+    #[must_use]
+    pub fn into_event(self) -> Event {
+        Event {
+            id: 0,
+            issue_id: self.issue_id,
+            event_type: self.event_type,
+            actor: self.actor,
+            old_value: self.old_value,
+            new_value: self.new_value,
+            comment: self.comment,
+            created_at: self.created_at,
+        }
+    }
+}
+
+/// Result of exporting events.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct EventsExportResult {
+    /// Total events in the file after export.
+    pub total: usize,
+    /// Events added to the file by this export.
+    pub added: usize,
+}
+
+/// Result of importing events.
+#[derive(Debug, Default, Clone, Serialize)]
+pub struct EventsImportResult {
+    /// Whether the file was read (false if missing or unchanged since last sync).
+    pub attempted: bool,
+    /// Events inserted into the database.
+    pub imported: usize,
+    /// Events already present, or for issues this clone does not have.
+    pub skipped: usize,
+}
+
+/// Stable content hash of an event.
+///
+/// Covers every field except the local row id, so the same event has the
+/// same hash in every clone.
+#[must_use]
+pub fn event_hash(event: &Event) -> String {
+    let mut hasher = Sha256::new();
+    let created_at = event.created_at.to_rfc3339();
+    let fields = [
+        Some(event.issue_id.as_str()),
+        Some(event.event_type.as_str()),
+        Some(event.actor.as_str()),
+        event.old_value.as_deref(),
+        event.new_value.as_deref(),
+        event.comment.as_deref(),
+        Some(created_at.as_str()),
+    ];
+    for field in fields {
+        match field {
+            Some(value) => {
+                hasher.update(b"s");
+                hasher.update(value.as_bytes());
+            }
+            None => hasher.update(b"n"),
+        }
+        hasher.update([0]);
+    }
+    format!("{:x}", hasher.finalize())
+}
+
+/// Path of the shared event log for a workspace.
+#[must_use]
+pub fn events_jsonl_path(beads_dir: &Path) -> PathBuf {
+    beads_dir.join(EVENTS_JSONL_FILE)
+}
+
+/// Read all records from an events JSONL file (empty if the file is missing).
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or contains invalid JSON.
+pub fn read_events_jsonl(path: &Path) -> Result<Vec<EventRecord>> {
+    if !path.exists() {
+        return Ok(Vec::new());
+    }
+    let reader = BufReader::new(File::open(path)?);
+    let mut records = Vec::new();
+    for (line_num, line) in reader.lines().enumerate() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let record: EventRecord = serde_json::from_str(&line).map_err(|e| {
+            BeadsError::Config(format!(
+                "Invalid JSON in {} at line {}: {}",
+                path.display(),
+                line_num + 1,
+                e
+            ))
+        })?;
+        records.push(record);
+    }
+    Ok(records)
+}
+
+/// Export the database's events into `events.jsonl`.
+///
+/// Existing lines are kept; events not yet in the file are added. The file
+/// is only rewritten when something was added.
+///
+/// # Errors
+///
+/// Returns an error if the path fails sync validation, the file is
+/// unreadable or contains conflict markers, or the write fails.
+pub fn export_events(storage: &mut SqliteStorage, beads_dir: &Path) -> Result<EventsExportResult> {
+    let path = events_jsonl_path(beads_dir);
+    require_safe_sync_overwrite_path(&path, beads_dir, false, "write events")?;
+    if path.exists() {
+        ensure_no_conflict_markers(&path)?;
+    }
+
+    let mut records: BTreeMap<String, EventRecord> = read_events_jsonl(&path)?
+        .into_iter()
+        .map(|record| (record.hash.clone(), record))
+        .collect();
+    let existing = records.len();
+    let events = storage.get_all_events(0)?;
+    let mut added = 0;
+    for event in &events {
+        let record = EventRecord::from_event(event);
+        if records.contains_key(&record.hash) {
+            continue;
+        }
+        records.insert(record.hash.clone(), record);
+        added += 1;
+    }
+    let total = records.len();
+
+    if added > 0 || !path.exists() {
+        write_events_jsonl(&path, beads_dir, records.into_values().collect())?;
+    }
+    // Only mark the file as synced if it holds nothing the database lacks;
+    // otherwise the next import still has to pick up the other clones' events.
+    if existing + added <= events.len() {
+        storage.set_metadata(METADATA_EVENTS_JSONL_HASH, &compute_jsonl_hash(&path)?)?;
+    }
+
+    tracing::debug!(total, added, "Exported events");
+    Ok(EventsExportResult { total, added })
+}
+
+/// Import events from `events.jsonl` that the database does not have yet.
+///
+/// Skipped entirely when the file is missing or unchanged since the last
+/// export or import, unless `force` is set. Events for issues this clone
+/// does not have yet leave the file marked as changed, so they are retried
+/// on the next import.
+///
+/// # Errors
+///
+/// Returns an error if the path fails sync validation, the file is
+/// unreadable or contains conflict markers, or a database insert fails.
+pub fn import_events(
+    storage: &mut SqliteStorage,
+    beads_dir: &Path,
+    force: bool,
+) -> Result<EventsImportResult> {
+    let path = events_jsonl_path(beads_dir);
+    if !path.exists() {
+        return Ok(EventsImportResult::default());
+    }
+    require_valid_sync_path(&path, beads_dir)?;
+    ensure_no_conflict_markers(&path)?;
+
+    let content_hash = compute_jsonl_hash(&path)?;
+    if !force && storage.get_metadata(METADATA_EVENTS_JSONL_HASH)?.as_deref() == Some(&content_hash)
+    {
+        tracing::debug!("events.jsonl unchanged since last sync, skipping import");
+        return Ok(EventsImportResult::default());
+    }
+
+    let mut known: HashSet<String> = storage.get_all_events(0)?.iter().map(event_hash).collect();
+    let records = read_events_jsonl(&path)?;
+    let total = records.len();
+    let mut fresh = Vec::new();
+    for record in records {
+        let event = record.into_event();
+        // Hash from content rather than trusting the stored value
+        if known.insert(event_hash(&event)) {
+            fresh.push(event);
+        }
+    }
+
+    let imported = storage.import_events(&fresh)?;
+    let unresolved = fresh.len() - imported;
+    if unresolved == 0 {
+        storage.set_metadata(METADATA_EVENTS_JSONL_HASH, &content_hash)?;
+    } else {
+        tracing::debug!(
+            unresolved,
+            "Events for unknown issues, will retry on next import"
+        );
+    }
+
+    tracing::debug!(imported, total, "Imported events");
+    Ok(EventsImportResult {
+        attempted: true,
+        imported,
+        skipped: total - imported,
+    })
+}
+
+/// Drop lines older than `cutoff` or for the given purged issues.
+///
+/// Keeps `events.jsonl` consistent with `br gc`, so pruned events are not
+/// imported again. Returns the number of lines removed.
+///
+/// # Errors
+///
+/// Returns an error if the file cannot be read or rewritten, or the sync
+/// metadata cannot be updated.
+pub fn prune_events_jsonl(
+    storage: &mut SqliteStorage,
+    beads_dir: &Path,
+    cutoff: DateTime<Utc>,
+    purged_ids: &[String],
+) -> Result<usize> {
+    let path = events_jsonl_path(beads_dir);
+    if !path.exists() {
+        return Ok(0);
+    }
+    require_safe_sync_overwrite_path(&path, beads_dir, false, "prune events")?;
+    ensure_no_conflict_markers(&path)?;
+
+    let records = read_events_jsonl(&path)?;
+    let total = records.len();
+    let kept: Vec<EventRecord> = records
+        .into_iter()
+        .filter(|record| record.created_at >= cutoff && !purged_ids.contains(&record.issue_id))
+        .collect();
+    let removed = total - kept.len();
+
+    if removed > 0 {
+        write_events_jsonl(&path, beads_dir, kept)?;
+        storage.set_metadata(METADATA_EVENTS_JSONL_HASH, &compute_jsonl_hash(&path)?)?;
+    }
+    Ok(removed)
+}
+
+/// Write records sorted by time, then hash, via a temp file and rename.
+fn write_events_jsonl(path: &Path, beads_dir: &Path, mut records: Vec<EventRecord>) -> Result<()> {
+    records.sort_by(|a, b| {
+        a.created_at
+            .cmp(&b.created_at)
+            .then_with(|| a.hash.cmp(&b.hash))
+    });
+
+    let temp_path = path.with_extension("jsonl.tmp");
+    require_safe_sync_overwrite_path(&temp_path, beads_dir, false, "write events temp file")?;
+    {
+        let mut writer = BufWriter::new(File::create(&temp_path)?);
+        for record in &records {
+            serde_json::to_writer(&mut writer, record)?;
+            writer.write_all(b"\n")?;
+        }
+        writer
+            .into_inner()
+            .map_err(|e| BeadsError::Io(e.into_error()))?
+            .sync_all()?;
+    }
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Issue;
+    use tempfile::TempDir;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn storage_with_issue(id: &str) -> SqliteStorage {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let issue = Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            ..Issue::default()
+        };
+        storage.create_issue(&issue, "alice").unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_event_hash_ignores_local_id() {
+        init_logging();
+        info!("test_event_hash_ignores_local_id: starting");
+        let storage = storage_with_issue("bd-1");
+        let mut event = storage.get_events("bd-1", 0).unwrap().remove(0);
+        let hash = event_hash(&event);
+        event.id += 100;
+        assert_eq!(event_hash(&event), hash);
+        event.actor = "bob".to_string();
+        assert_ne!(event_hash(&event), hash);
+        info!("test_event_hash_ignores_local_id: assertions passed");
+    }
+
+    #[test]
+    fn test_export_then_import_into_other_clone() {
+        init_logging();
+        info!("test_export_then_import_into_other_clone: starting");
+        let temp = TempDir::new().unwrap();
+        let beads_dir = temp.path().join(".beads");
+        fs::create_dir_all(&beads_dir).unwrap();
+
+        let mut ours = storage_with_issue("bd-1");
+        ours.add_label("bd-1", "backend", "alice").unwrap();
+        let exported = export_events(&mut ours, &beads_dir).unwrap();
+        assert_eq!(exported.total, 2);
+        assert_eq!(exported.added, 2);
+
+        // Re-export adds nothing, and our own file is not re-imported
+        assert_eq!(export_events(&mut ours, &beads_dir).unwrap().added, 0);
+        assert!(
+            !import_events(&mut ours, &beads_dir, false)
+                .unwrap()
+                .attempted
+        );
+
+        // The other clone has the issue (via issues.jsonl) and its own created event
+        let mut theirs = storage_with_issue("bd-1");
+        let imported = import_events(&mut theirs, &beads_dir, false).unwrap();
+        assert!(imported.attempted);
+        assert_eq!(imported.imported, 2);
+        assert_eq!(theirs.get_events("bd-1", 0).unwrap().len(), 3);
+
+        // Importing again is a no-op even when forced
+        let again = import_events(&mut theirs, &beads_dir, true).unwrap();
+        assert_eq!(again.imported, 0);
+        assert_eq!(again.skipped, 2);
+
+        // Exporting from the other clone keeps our events and adds theirs
+        let merged = export_events(&mut theirs, &beads_dir).unwrap();
+        assert_eq!(merged.added, 1);
+        assert_eq!(merged.total, 3);
+        info!("test_export_then_import_into_other_clone: assertions passed");
+    }
+
+    #[test]
+    fn test_import_skips_unknown_issues() {
+        init_logging();
+        info!("test_import_skips_unknown_issues: starting");
+        let temp = TempDir::new().unwrap();
+        let beads_dir = temp.path().join(".beads");
+        fs::create_dir_all(&beads_dir).unwrap();
+
+        let mut source = storage_with_issue("bd-1");
+        export_events(&mut source, &beads_dir).unwrap();
+
+        let mut other = storage_with_issue("bd-2");
+        let result = import_events(&mut other, &beads_dir, false).unwrap();
+        assert_eq!(result.imported, 0);
+        assert_eq!(result.skipped, 1);
+        assert!(other.get_events("bd-1", 0).unwrap().is_empty());
+        assert_eq!(
+            other.get_metadata(METADATA_EVENTS_JSONL_HASH).unwrap(),
+            None
+        );
+
+        // Once the issue arrives, an unforced import picks the event up
+        other
+            .create_issue(
+                &Issue {
+                    id: "bd-1".to_string(),
+                    title: "Issue bd-1".to_string(),
+                    ..Issue::default()
+                },
+                "bob",
+            )
+            .unwrap();
+        let retried = import_events(&mut other, &beads_dir, false).unwrap();
+        assert!(retried.attempted);
+        assert_eq!(retried.imported, 1);
+        assert!(
+            other
+                .get_metadata(METADATA_EVENTS_JSONL_HASH)
+                .unwrap()
+                .is_some()
+        );
+        info!("test_import_skips_unknown_issues: assertions passed");
+    }
+
+    #[test]
+    fn test_prune_events_jsonl_drops_purged_issues() {
+        init_logging();
+        info!("test_prune_events_jsonl_drops_purged_issues: starting");
+        let temp = TempDir::new().unwrap();
+        let beads_dir = temp.path().join(".beads");
+        fs::create_dir_all(&beads_dir).unwrap();
+
+        let mut storage = storage_with_issue("bd-1");
+        export_events(&mut storage, &beads_dir).unwrap();
+
+        let past = Utc::now() - chrono::Duration::days(1);
+        assert_eq!(
+            prune_events_jsonl(&mut storage, &beads_dir, past, &[]).unwrap(),
+            0
+        );
+        let purged = vec!["bd-1".to_string()];
+        assert_eq!(
+            prune_events_jsonl(&mut storage, &beads_dir, past, &purged).unwrap(),
+            1
+        );
+        assert!(
+            read_events_jsonl(&events_jsonl_path(&beads_dir))
+                .unwrap()
+                .is_empty()
+        );
+        info!("test_prune_events_jsonl_drops_purged_issues: assertions passed");
+    }
+}
@@ -6,7 +6,9 @@
 //! - Dirty tracking for incremental exports
 //! - Collision detection during imports
 //! - Path validation and allowlist enforcement
+//! - Opt-in sharing of audit events through `events.jsonl`
 
+pub mod events;
 pub mod history;
 pub mod path;
 
@@ -262,6 +264,9 @@ pub struct ImportConfig {
     pub allow_external_jsonl: bool,
     /// Show progress indicators for long-running operations.
     pub show_progress: bool,
+    /// Tombstone retention in days. Expired tombstones that are not already in
+    /// the database are skipped, so tombstones purged by `br gc` stay gone.
+    pub retention_days: Option<u64>,
 }
 
 impl Default for ImportConfig {
@@ -275,6 +280,7 @@ impl Default for ImportConfig {
             beads_dir: None,
             allow_external_jsonl: false,
             show_progress: false,
+            retention_days: None,
         }
     }
 }
@@ -1627,6 +1633,9 @@ pub fn auto_import_if_stale(
         beads_dir: Some(beads_dir.to_path_buf()),
         allow_external_jsonl: false,
         show_progress: false,
+        retention_days: crate::config::Metadata::load(beads_dir)
+            .ok()
+            .and_then(|metadata| metadata.deletions_retention_days),
         ..Default::default()
     };
 
@@ -2154,6 +2163,14 @@ pub fn import_from_jsonl(
             continue;
         }
 
+        // An expired tombstone missing from the database was purged by `br gc`;
+        // importing it from an older JSONL would bring it back.
+        if issue.is_expired_tombstone(config.retention_days) && !storage.id_exists(&issue.id)? {
+            result.tombstone_skipped += 1;
+            progress.inc(1);
+            continue;
+        }
+
         let mut effective_issue = issue.clone();
 
         // Handle external ref duplicates before collision detection
@@ -3291,6 +3308,27 @@ mod tests {
         assert_eq!(still_tombstone.status, Status::Tombstone);
     }
 
+    #[test]
+    fn test_import_skips_purged_expired_tombstone() {
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("issues.jsonl");
+
+        let mut tombstone = make_issue_at("test-001", "Purged", fixed_time(100));
+        tombstone.status = Status::Tombstone;
+        tombstone.deleted_at = Some(Utc::now() - chrono::Duration::days(60));
+        let json = serde_json::to_string(&tombstone).unwrap();
+        fs::write(&path, format!("{json}\n")).unwrap();
+
+        let config = ImportConfig {
+            retention_days: Some(30),
+            ..Default::default()
+        };
+        let result = import_from_jsonl(&mut storage, &path, &config, Some("test-")).unwrap();
+        assert_eq!(result.tombstone_skipped, 1);
+        assert!(storage.get_issue("test-001").unwrap().is_none());
+    }
+
     #[test]
     fn test_import_new_issue_creates() {
         let mut storage = SqliteStorage::open_memory().unwrap();
@@ -17,7 +17,7 @@
 //! | `.beads/*.db` | `SQLite` database files |
 //! | `.beads/*.db-wal` | `SQLite` WAL files |
 //! | `.beads/*.db-shm` | `SQLite` shared memory files |
-//! | `.beads/*.jsonl` | `JSONL` export files |
+//! | `.beads/*.jsonl` | `JSONL` export files (including the shared `events.jsonl`) |
 //! | `.beads/*.jsonl.tmp` | Temp files for atomic writes |
 //! | `.beads/.manifest.json` | Export manifest |
 //! | `.beads/metadata.json` | Workspace metadata |
@@ -673,6 +673,17 @@ mod tests {
         assert!(result.is_allowed(), "JSONL files should be allowed");
     }
 
+    #[test]
+    fn test_allowed_events_jsonl_file() {
+        let (_temp, beads_dir) = setup_test_beads_dir();
+        let path = beads_dir.join("events.jsonl");
+        std::fs::write(&path, "").expect("write");
+
+        let result = validate_sync_path(&path, &beads_dir);
+        assert!(result.is_allowed(), "Shared event log should be allowed");
+        assert!(validate_sync_path(&beads_dir.join("events.jsonl.tmp"), &beads_dir).is_allowed());
+    }
+
     #[test]
     fn test_allowed_db_file() {
         let (_temp, beads_dir) = setup_test_beads_dir();
@@ -480,6 +480,24 @@ pub fn normalize_id(id: &str) -> String {
     id.to_lowercase()
 }
 
+/// Build a regex that finds `prefix` issue IDs in free text.
+///
+/// Used for commit messages and code comments. Matches hierarchical IDs like
+/// `bd-abc.1` and is case-insensitive; pass captures through [`normalize_id`].
+///
+/// # Errors
+///
+/// Returns an error if the pattern cannot be compiled.
+pub fn issue_ref_regex(prefix: &str) -> Result<regex::Regex> {
+    // Word boundaries avoid matching inside other tokens (e.g. abd-123)
+    let pattern = format!(
+        r"(?i)\b({}-[a-z0-9]+(?:\.[0-9]+)*)\b",
+        regex::escape(prefix)
+    );
+    regex::Regex::new(&pattern)
+        .map_err(|e| BeadsError::Config(format!("Invalid regex pattern: {e}")))
+}
+
 /// Check if a string looks like a valid issue ID format.
 #[must_use]
 pub fn is_valid_id_format(id: &str) -> bool {
@@ -1,15 +1,22 @@
 //! Markdown bulk import parser for `br create --file`.
 //!
-//! Parses a markdown file with a specific grammar to create multiple issues.
+//! Parses a markdown file with a specific grammar to create multiple issues,
+//! and renders issues back into that grammar for `br export --format markdown`.
 //!
 //! # Markdown Grammar
 //!
 //! - Each issue starts with an H2 line: `## Issue Title`
+//! - An optional `<!-- id: bd-xxx -->` marker under the title ties the block
+//!   to an existing issue, which is then updated instead of duplicated
 //! - Per-issue sections are H3 lines: `### Section Name`
 //! - Recognized sections (case-insensitive):
-//!   - Priority, Type, Description, Design, Acceptance Criteria (alias Acceptance),
-//!     Assignee, Labels, Dependencies (alias Deps)
+//!   - Status, Priority, Type, Description, Design, Acceptance Criteria
+//!     (alias Acceptance), Notes, Assignee, Labels, Dependencies (alias Deps)
 //! - Unknown sections are ignored
+//! - Heading lines inside fenced code blocks are section content
+//! - Outside fences, a section line starting with `\## ` or `\### ` is the
+//!   literal `## `/`### ` line; export adds the backslash so text containing
+//!   headings survives a round trip (each extra backslash is kept)
 //!
 //! # Known Quirk (matches bd behavior)
 //!
@@ -17,7 +24,7 @@
 //! but **only the first non-empty line** is captured; subsequent lines are ignored.
 
 use crate::error::{BeadsError, Result};
-use crate::model::DependencyType;
+use crate::model::{DependencyType, Issue};
 use std::fs;
 use std::path::{Component, Path};
 use std::str::FromStr;
@@ -25,8 +32,12 @@ use std::str::FromStr;
 /// A parsed issue from the markdown file.
 #[derive(Debug, Default, Clone)]
 pub struct ParsedIssue {
+    /// Issue ID from the `<!-- id: ... -->` marker, if present.
+    pub id: Option<String>,
     /// Issue title from the H2 header.
     pub title: String,
+    /// Status string (e.g., "open", "closed").
+    pub status: Option<String>,
     /// Priority string (e.g., "0", "P1", "2").
     pub priority: Option<String>,
     /// Issue type (e.g., "task", "bug", "feature").
@@ -37,6 +48,8 @@ pub struct ParsedIssue {
     pub design: Option<String>,
     /// Acceptance criteria content.
     pub acceptance_criteria: Option<String>,
+    /// Notes section content.
+    pub notes: Option<String>,
     /// Assignee name.
     pub assignee: Option<String>,
     /// Labels list.
@@ -50,11 +63,13 @@ pub struct ParsedIssue {
 enum Section {
     /// Before any H3, capturing implicit description
     BeforeH3,
+    Status,
     Priority,
     Type,
     Description,
     Design,
     AcceptanceCriteria,
+    Notes,
     Assignee,
     Labels,
     Dependencies,
@@ -65,11 +80,13 @@ impl Section {
     fn from_header(header: &str) -> Self {
         let normalized = header.trim().to_lowercase();
         match normalized.as_str() {
+            "status" => Self::Status,
             "priority" => Self::Priority,
             "type" => Self::Type,
             "description" => Self::Description,
             "design" => Self::Design,
             "acceptance criteria" | "acceptance" => Self::AcceptanceCriteria,
+            "notes" => Self::Notes,
             "assignee" => Self::Assignee,
             "labels" => Self::Labels,
             "dependencies" | "deps" => Self::Dependencies,
@@ -144,8 +161,19 @@ pub fn parse_markdown_content(content: &str) -> Result<Vec<ParsedIssue>> {
     let mut current_section = Section::BeforeH3;
     let mut section_lines: Vec<String> = Vec::new();
     let mut captured_implicit_desc = false;
+    let mut in_fence = false;
 
     for line in content.lines() {
+        // Fenced code inside a section is content, headings included
+        let fence = is_fence(line);
+        if in_fence || (fence && current_issue.is_some() && current_section != Section::BeforeH3) {
+            if fence {
+                in_fence = !in_fence;
+            }
+            section_lines.push(line.to_string());
+            continue;
+        }
+
         // Check for H2 (new issue)
         if line.starts_with("## ") && !line.starts_with("### ") {
             // Save previous issue
@@ -180,6 +208,17 @@ pub fn parse_markdown_content(content: &str) -> Result<Vec<ParsedIssue>> {
             continue;
         }
 
+        // The first ID marker before any H3 ties the block to an existing issue
+        if let Some(issue) = current_issue
+            .as_mut()
+            .filter(|issue| issue.id.is_none() && current_section == Section::BeforeH3)
+        {
+            if let Some(id) = parse_id_marker(line) {
+                issue.id = Some(id);
+                continue;
+            }
+        }
+
         // Collect content for current section
         if current_issue.is_some() {
             // Handle the quirk: before H3, only capture first non-empty line as description
@@ -190,7 +229,7 @@ pub fn parse_markdown_content(content: &str) -> Result<Vec<ParsedIssue>> {
                 }
                 // Ignore subsequent lines before H3
             } else {
-                section_lines.push(line.to_string());
+                section_lines.push(unescape_heading(line));
             }
         }
     }
@@ -225,6 +264,9 @@ fn apply_section_to_issue(issue: &mut ParsedIssue, section: Section, lines: &[St
                 issue.description = Some(content);
             }
         }
+        Section::Status => {
+            issue.status = Some(content);
+        }
         Section::Priority => {
             issue.priority = Some(content);
         }
@@ -240,6 +282,9 @@ fn apply_section_to_issue(issue: &mut ParsedIssue, section: Section, lines: &[St
         Section::AcceptanceCriteria => {
             issue.acceptance_criteria = Some(content);
         }
+        Section::Notes => {
+            issue.notes = Some(content);
+        }
         Section::Assignee => {
             issue.assignee = Some(content);
         }
@@ -255,6 +300,111 @@ fn apply_section_to_issue(issue: &mut ParsedIssue, section: Section, lines: &[St
     }
 }
 
+/// Split a line into its leading backslashes and the rest, if the rest would
+/// be read as an issue title or section heading.
+fn heading_escape(line: &str) -> Option<(usize, &str)> {
+    let rest = line.trim_start_matches('\\');
+    (rest.starts_with("## ") || rest.starts_with("### ")).then(|| (line.len() - rest.len(), rest))
+}
+
+/// Escape heading lines outside fences so text can hold `## `/`### ` lines.
+pub(crate) fn escape_headings(text: &str) -> String {
+    let mut in_fence = false;
+    let lines: Vec<String> = text
+        .lines()
+        .map(|line| {
+            if is_fence(line) {
+                in_fence = !in_fence;
+            }
+            if in_fence {
+                return line.to_string();
+            }
+            heading_escape(line).map_or_else(|| line.to_string(), |_| format!("\\{line}"))
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Undo [`escape_headings`] for one line.
+pub(crate) fn unescape_heading(line: &str) -> String {
+    match heading_escape(line) {
+        Some((slashes, _)) if slashes > 0 => line[1..].to_string(),
+        _ => line.to_string(),
+    }
+}
+
+fn is_fence(line: &str) -> bool {
+    let trimmed = line.trim_start();
+    trimmed.starts_with("```") || trimmed.starts_with("~~~")
+}
+
+/// Extract the ID from a `<!-- id: bd-xxx -->` marker line.
+fn parse_id_marker(line: &str) -> Option<String> {
+    let inner = line
+        .trim()
+        .strip_prefix("<!--")?
+        .strip_suffix("-->")?
+        .trim()
+        .strip_prefix("id:")?
+        .trim();
+    (!inner.is_empty() && !inner.contains(char::is_whitespace)).then(|| inner.to_string())
+}
+
+/// Render issues as a markdown plan document in the grammar above.
+///
+/// Every issue carries its ID marker, so feeding the edited document back to
+/// `br create --file` updates the issues in place. Text fields always go into
+/// explicit sections, which keeps multi-line descriptions intact.
+#[must_use]
+pub fn format_markdown_plan(issues: &[Issue]) -> String {
+    let mut out = String::from(
+        "# Issues\n\n<!-- Exported by br. Edit and apply with `br create --file <this file>`. -->\n",
+    );
+    for issue in issues {
+        out.push('\n');
+        out.push_str(&format_markdown_issue(issue));
+    }
+    out
+}
+
+/// Render one issue as a `## Title` block with its ID marker.
+#[must_use]
+pub fn format_markdown_issue(issue: &Issue) -> String {
+    let mut out = format!("## {}\n<!-- id: {} -->\n", issue.title, issue.id);
+    let mut section = |name: &str, content: &str| {
+        if !content.trim().is_empty() {
+            out.push_str(&format!("\n### {name}\n{}\n", content.trim_end()));
+        }
+    };
+
+    section("Status", issue.status.as_str());
+    section("Priority", &format!("P{}", issue.priority.0));
+    section("Type", issue.issue_type.as_str());
+    section("Assignee", issue.assignee.as_deref().unwrap_or(""));
+
+    let mut labels = issue.labels.clone();
+    labels.sort();
+    section("Labels", &labels.join(", "));
+
+    let mut deps: Vec<String> = issue
+        .dependencies
+        .iter()
+        .map(|dep| format!("{}:{}", dep.dep_type.as_str(), dep.depends_on_id))
+        .collect();
+    deps.sort();
+    section("Dependencies", &deps.join(", "));
+
+    let text = |value: Option<&str>| escape_headings(value.unwrap_or(""));
+    section("Description", &text(issue.description.as_deref()));
+    section("Design", &text(issue.design.as_deref()));
+    section(
+        "Acceptance Criteria",
+        &text(issue.acceptance_criteria.as_deref()),
+    );
+    section("Notes", &text(issue.notes.as_deref()));
+    out
+}
+
 /// Split content on commas or whitespace for labels/deps.
 fn split_list_content(content: &str) -> Vec<String> {
     // First try splitting on commas
@@ -313,6 +463,7 @@ pub fn parse_dependency(dep_str: &str) -> (String, String, bool) {
 mod tests {
     use super::*;
     use crate::error::BeadsError;
+    use crate::model::{Dependency, IssueType, Priority, Status};
     use std::path::Path;
 
     #[test]
@@ -524,4 +675,91 @@ Explicit description content
             Some("Explicit description content".to_string())
         );
     }
+
+    #[test]
+    fn test_id_marker_and_fenced_headings() {
+        let content = r"## Tracked Issue
+<!-- id: bd-abc1 -->
+
+### Status
+in_progress
+
+### Description
+Run this:
+```sh
+### not a section
+```
+
+### Notes
+<!-- id: bd-other -->
+";
+        let issues = parse_markdown_content(content).unwrap();
+        assert_eq!(issues[0].id.as_deref(), Some("bd-abc1"));
+        assert_eq!(issues[0].status.as_deref(), Some("in_progress"));
+        assert_eq!(
+            issues[0].description.as_deref(),
+            Some("Run this:\n```sh\n### not a section\n```")
+        );
+        assert_eq!(issues[0].notes.as_deref(), Some("<!-- id: bd-other -->"));
+    }
+
+    #[test]
+    fn test_format_markdown_plan_round_trips() {
+        let mut issue = Issue {
+            id: "bd-abc1".to_string(),
+            title: "Round trip".to_string(),
+            description: Some("First line\n\nSecond paragraph".to_string()),
+            notes: Some("A note".to_string()),
+            status: Status::InProgress,
+            priority: Priority(1),
+            issue_type: IssueType::Bug,
+            assignee: Some("alice".to_string()),
+            labels: vec!["ui".to_string(), "backend".to_string()],
+            ..Issue::default()
+        };
+        issue.dependencies.push(Dependency {
+            issue_id: "bd-abc1".to_string(),
+            depends_on_id: "bd-def2".to_string(),
+            dep_type: DependencyType::Blocks,
+            created_at: issue.created_at,
+            created_by: None,
+            metadata: None,
+            thread_id: None,
+        });
+
+        let rendered = format_markdown_plan(std::slice::from_ref(&issue));
+        let parsed = parse_markdown_content(&rendered).unwrap();
+        assert_eq!(parsed.len(), 1);
+        let parsed = &parsed[0];
+        assert_eq!(parsed.id.as_deref(), Some("bd-abc1"));
+        assert_eq!(parsed.title, "Round trip");
+        assert_eq!(parsed.description, issue.description);
+        assert_eq!(parsed.notes, issue.notes);
+        assert_eq!(parsed.status.as_deref(), Some("in_progress"));
+        assert_eq!(parsed.priority.as_deref(), Some("P1"));
+        assert_eq!(parsed.issue_type.as_deref(), Some("bug"));
+        assert_eq!(parsed.assignee.as_deref(), Some("alice"));
+        assert_eq!(parsed.labels, vec!["backend", "ui"]);
+        assert_eq!(parsed.dependencies, vec!["blocks:bd-def2"]);
+    }
+
+    #[test]
+    fn test_format_markdown_plan_round_trips_headings_in_text() {
+        let description = "Crashes on save.\n\n### Steps to reproduce\n1. Open\n\n## Not an issue\n\\### Already escaped\n```\n### inside a fence\n```\n#### Deeper heading";
+        let issue = Issue {
+            id: "bd-abc1".to_string(),
+            title: "Headings".to_string(),
+            description: Some(description.to_string()),
+            notes: Some("## Follow-up".to_string()),
+            ..Issue::default()
+        };
+
+        let rendered = format_markdown_plan(std::slice::from_ref(&issue));
+        assert!(rendered.contains("\n\\### Steps to reproduce\n"));
+        assert!(rendered.contains("\n### inside a fence\n"));
+        let parsed = parse_markdown_content(&rendered).unwrap();
+        assert_eq!(parsed.len(), 1);
+        assert_eq!(parsed[0].description.as_deref(), Some(description));
+        assert_eq!(parsed[0].notes.as_deref(), Some("## Follow-up"));
+    }
 }
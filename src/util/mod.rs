@@ -7,12 +7,14 @@
 //! - ID generation (base36 adaptive)
 //! - Last-touched tracking
 //! - Progress indicators (for long-running operations)
+//! - WIP limit checks
 
 mod hash;
 pub mod id;
 pub mod markdown_import;
 pub mod progress;
 pub mod time;
+pub mod wip;
 
 pub use hash::{ContentHashable, content_hash, content_hash_from_parts};
 pub use id::{
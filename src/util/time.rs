@@ -49,10 +49,10 @@ pub fn parse_flexible_timestamp(s: &str, field_name: &str) -> Result<DateTime<Ut
             if let Ok(amount) = amount_str.parse::<i64>() {
                 let amount = if is_negative { -amount } else { amount };
                 let duration = match unit_char {
-                    'm' => Duration::minutes(amount),
-                    'h' => Duration::hours(amount),
-                    'd' => Duration::days(amount),
-                    'w' => Duration::weeks(amount),
+                    'm' => Duration::try_minutes(amount),
+                    'h' => Duration::try_hours(amount),
+                    'd' => Duration::try_days(amount),
+                    'w' => Duration::try_weeks(amount),
                     _ => {
                         return Err(BeadsError::validation(
                             field_name,
@@ -60,7 +60,9 @@ pub fn parse_flexible_timestamp(s: &str, field_name: &str) -> Result<DateTime<Ut
                         ));
                     }
                 };
-                return Ok(Utc::now() + duration);
+                return duration
+                    .and_then(|duration| Utc::now().checked_add_signed(duration))
+                    .ok_or_else(|| BeadsError::validation(field_name, "offset is out of range"));
             }
         }
     }
@@ -101,28 +103,20 @@ pub fn parse_flexible_timestamp(s: &str, field_name: &str) -> Result<DateTime<Ut
 /// - Relative duration: `+1h`, `+2d`, `+1w`, `+30m`, `-7d`
 /// - Keywords: `tomorrow`, `next-week`
 ///
-/// Returns `None` if the input cannot be parsed as a relative time.
+/// Returns `None` if the input cannot be parsed as a relative time or lands
+/// outside the representable date range.
 #[must_use]
 pub fn parse_relative_time(s: &str) -> Option<DateTime<Utc>> {
     let s = s.trim();
 
     // Try relative duration (+1h, -2d, +1w, -30m)
     if let Some(rest) = s.strip_prefix(['+', '-'].as_ref()) {
-        let is_negative = s.starts_with('-');
-        if let Some(unit_char) = rest.chars().last() {
-            let amount_str = &rest[..rest.len() - unit_char.len_utf8()];
-            if let Ok(amount) = amount_str.parse::<i64>() {
-                let amount = if is_negative { -amount } else { amount };
-                let duration = match unit_char {
-                    'm' => Duration::minutes(amount),
-                    'h' => Duration::hours(amount),
-                    'd' => Duration::days(amount),
-                    'w' => Duration::weeks(amount),
-                    _ => return None,
-                };
-                return Some(Utc::now() + duration);
-            }
-        }
+        let duration = parse_duration(rest)?;
+        return if s.starts_with('-') {
+            Utc::now().checked_sub_signed(duration)
+        } else {
+            Utc::now().checked_add_signed(duration)
+        };
     }
 
     // Try keywords
@@ -150,6 +144,27 @@ pub fn parse_relative_time(s: &str) -> Option<DateTime<Utc>> {
     }
 }
 
+/// Parse an unsigned duration such as `30m`, `36h`, `90d` or `12w`.
+///
+/// Returns `None` if the amount is not a non-negative integer, the unit is
+/// not one of m, h, d, w, or the duration is too large to represent.
+#[must_use]
+pub fn parse_duration(s: &str) -> Option<Duration> {
+    let s = s.trim();
+    let unit_char = s.chars().last()?;
+    let amount = s[..s.len() - unit_char.len_utf8()].parse::<i64>().ok()?;
+    if amount < 0 {
+        return None;
+    }
+    match unit_char {
+        'm' => Duration::try_minutes(amount),
+        'h' => Duration::try_hours(amount),
+        'd' => Duration::try_days(amount),
+        'w' => Duration::try_weeks(amount),
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +213,29 @@ mod tests {
         assert!(parse_relative_time("invalid").is_none());
         assert!(parse_relative_time("2025-01-15").is_none());
     }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90d"), Some(Duration::days(90)));
+        assert_eq!(parse_duration("2w"), Some(Duration::weeks(2)));
+        assert_eq!(parse_duration("36h"), Some(Duration::hours(36)));
+        assert!(parse_duration("-1d").is_none());
+        assert!(parse_duration("10y").is_none());
+        assert!(parse_duration("").is_none());
+        assert!(parse_duration("99999999999999w").is_none());
+        assert!(parse_duration(&format!("{}m", i64::MAX)).is_none());
+    }
+
+    #[test]
+    fn test_parse_flexible_offset_out_of_range() {
+        assert!(parse_flexible_timestamp("+99999999999999w", "test").is_err());
+        assert!(parse_flexible_timestamp("-1000000000d", "test").is_err());
+    }
+
+    #[test]
+    fn test_parse_relative_time_out_of_range() {
+        assert!(parse_relative_time("+1000000000d").is_none());
+        assert!(parse_relative_time("-1000000000d").is_none());
+        assert!(parse_relative_time("+1d").is_some());
+    }
 }
@@ -0,0 +1,500 @@
+//! Work-in-progress limit checks.
+//!
+//! Limits come from `wip_limits.<status>.<scope>` config keys (see
+//! [`crate::config::wip_limits_from_layer`]). Every path that sets a
+//! non-closed status or an assignee (`create`, `update`, `edit`, `reopen`,
+//! `defer`, `undefer`, `create --file`, `scan`, the TUI and `br serve`)
+//! runs [`check_transition`] as the guard of
+//! [`SqliteStorage::create_issue_guarded`] or
+//! [`SqliteStorage::update_issue_guarded`], unless forced. The count and the
+//! write then share one write transaction, so concurrent claims cannot both
+//! slip under a limit. `br stats` reports usage.
+
+use crate::config::{WipLimit, WipScope};
+use crate::error::{BeadsError, Result};
+use crate::model::Status;
+use crate::storage::{SqliteStorage, TxReader};
+use schemars::JsonSchema;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::collections::btree_map::Entry;
+
+/// Current usage of one WIP limit.
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema, PartialEq, Eq)]
+pub struct WipUsage {
+    pub status: String,
+    /// Configured scope, e.g. `per_assignee` or `label:backend`.
+    pub scope: String,
+    /// The assignee a `per_assignee` limit is counted for.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub assignee: Option<String>,
+    pub current: usize,
+    pub limit: usize,
+}
+
+impl WipUsage {
+    #[must_use]
+    pub const fn exceeded(&self) -> bool {
+        self.current > self.limit
+    }
+}
+
+/// An issue counted towards a status's WIP.
+struct WipEntry {
+    id: String,
+    assignee: Option<String>,
+    labels: Vec<String>,
+}
+
+/// An issue about to enter (or change hands within) a limited status.
+#[derive(Debug, Clone, Copy)]
+pub struct WipCandidate<'a> {
+    pub id: &'a str,
+    pub status: &'a str,
+    pub assignee: Option<&'a str>,
+    pub labels: &'a [String],
+}
+
+fn load_entries(reader: &TxReader<'_>, status: &str) -> Result<Vec<WipEntry>> {
+    Ok(reader
+        .issues_in_status(status)?
+        .into_iter()
+        .map(|(id, assignee, labels)| WipEntry {
+            id,
+            assignee,
+            labels,
+        })
+        .collect())
+}
+
+fn matches_scope(scope: &WipScope, assignee: Option<&str>, labels: &[String]) -> bool {
+    match scope {
+        WipScope::Total => true,
+        WipScope::PerAssignee => assignee.is_some(),
+        WipScope::Assignee(name) => assignee == Some(name.as_str()),
+        WipScope::Label(label) => labels.iter().any(|l| l == label),
+    }
+}
+
+/// Check that moving `candidate` into its status keeps every limit satisfied.
+///
+/// The candidate itself is excluded from the current counts, so re-saving an
+/// issue that is already counted does not trip its own limit.
+///
+/// # Errors
+///
+/// Returns [`BeadsError::WipLimitExceeded`] for the first limit that would be
+/// exceeded, or a database error if the counts cannot be read.
+pub fn check_wip_limits(
+    reader: &TxReader<'_>,
+    limits: &[WipLimit],
+    candidate: &WipCandidate<'_>,
+) -> Result<()> {
+    let applicable: Vec<&WipLimit> = limits
+        .iter()
+        .filter(|limit| limit.status == candidate.status)
+        .filter(|limit| matches_scope(&limit.scope, candidate.assignee, candidate.labels))
+        .collect();
+    if applicable.is_empty() {
+        return Ok(());
+    }
+
+    let entries = load_entries(reader, candidate.status)?;
+    for limit in applicable {
+        let current = entries
+            .iter()
+            .filter(|entry| entry.id != candidate.id)
+            .filter(|entry| match &limit.scope {
+                WipScope::PerAssignee => entry.assignee.as_deref() == candidate.assignee,
+                scope => matches_scope(scope, entry.assignee.as_deref(), &entry.labels),
+            })
+            .count();
+        if current >= limit.limit {
+            let scope = match (&limit.scope, candidate.assignee) {
+                (WipScope::PerAssignee, Some(name)) => format!("per_assignee {name}"),
+                (scope, _) => scope.to_string(),
+            };
+            return Err(BeadsError::WipLimitExceeded {
+                status: limit.status.clone(),
+                scope,
+                current,
+                limit: limit.limit,
+            });
+        }
+    }
+    Ok(())
+}
+
+/// Check the limits for the candidate's issue going from its stored status
+/// and assignee (none for a new issue) to those in `candidate`.
+///
+/// A change that keeps both the status and the assignee passes, so other
+/// edits to an issue already over a limit still go through.
+///
+/// # Errors
+///
+/// Returns [`BeadsError::WipLimitExceeded`] as [`check_wip_limits`] does.
+pub fn check_transition(
+    reader: &TxReader<'_>,
+    limits: &[WipLimit],
+    candidate: &WipCandidate<'_>,
+) -> Result<()> {
+    if limits.is_empty() {
+        return Ok(());
+    }
+    if reader
+        .issue_state(candidate.id)?
+        .is_some_and(|(status, assignee)| {
+            status.as_str() == candidate.status && assignee.as_deref() == candidate.assignee
+        })
+    {
+        return Ok(());
+    }
+    check_wip_limits(reader, limits, candidate)
+}
+
+/// [`check_transition`] for a command that only moves issue `id` to
+/// `status`, keeping its assignee and labels.
+///
+/// # Errors
+///
+/// Returns [`BeadsError::WipLimitExceeded`] as [`check_wip_limits`] does.
+pub fn check_status_change(
+    reader: &TxReader<'_>,
+    limits: &[WipLimit],
+    id: &str,
+    status: &Status,
+) -> Result<()> {
+    if limits.is_empty() {
+        return Ok(());
+    }
+    let Some((_, assignee)) = reader.issue_state(id)? else {
+        return Ok(());
+    };
+    let labels = reader.labels(id)?;
+    check_transition(
+        reader,
+        limits,
+        &WipCandidate {
+            id,
+            status: status.as_str(),
+            assignee: assignee.as_deref(),
+            labels: &labels,
+        },
+    )
+}
+
+/// Report current usage for each configured limit.
+///
+/// A `per_assignee` limit yields one row per assignee with work in the
+/// status, or a single zero row when nobody has any.
+///
+/// # Errors
+///
+/// Returns an error if the counts cannot be read.
+pub fn wip_usage(storage: &SqliteStorage, limits: &[WipLimit]) -> Result<Vec<WipUsage>> {
+    let mut by_status: BTreeMap<&str, Vec<WipEntry>> = BTreeMap::new();
+    for limit in limits {
+        if let Entry::Vacant(slot) = by_status.entry(limit.status.as_str()) {
+            slot.insert(load_entries(&storage.reader(), &limit.status)?);
+        }
+    }
+
+    let mut usage = Vec::new();
+    for limit in limits {
+        let entries = &by_status[limit.status.as_str()];
+        if limit.scope == WipScope::PerAssignee {
+            let mut per_assignee: BTreeMap<&str, usize> = BTreeMap::new();
+            for name in entries.iter().filter_map(|entry| entry.assignee.as_deref()) {
+                *per_assignee.entry(name).or_default() += 1;
+            }
+            if per_assignee.is_empty() {
+                usage.push(WipUsage {
+                    status: limit.status.clone(),
+                    scope: limit.scope.to_string(),
+                    assignee: None,
+                    current: 0,
+                    limit: limit.limit,
+                });
+            }
+            for (name, current) in per_assignee {
+                usage.push(WipUsage {
+                    status: limit.status.clone(),
+                    scope: limit.scope.to_string(),
+                    assignee: Some(name.to_string()),
+                    current,
+                    limit: limit.limit,
+                });
+            }
+            continue;
+        }
+        let current = entries
+            .iter()
+            .filter(|entry| matches_scope(&limit.scope, entry.assignee.as_deref(), &entry.labels))
+            .count();
+        usage.push(WipUsage {
+            status: limit.status.clone(),
+            scope: limit.scope.to_string(),
+            assignee: None,
+            current,
+            limit: limit.limit,
+        });
+    }
+    Ok(usage)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::{Issue, Priority};
+    use chrono::Utc;
+    use tracing::info;
+
+    fn init_logging() {
+        crate::logging::init_test_logging();
+    }
+
+    fn make_issue(id: &str, assignee: &str) -> Issue {
+        Issue {
+            id: id.to_string(),
+            title: format!("Issue {id}"),
+            priority: Priority::MEDIUM,
+            status: Status::InProgress,
+            assignee: Some(assignee.to_string()),
+            created_at: Utc::now(),
+            updated_at: Utc::now(),
+            ..Issue::default()
+        }
+    }
+
+    fn limit(scope: WipScope, limit: usize) -> WipLimit {
+        WipLimit {
+            status: "in_progress".to_string(),
+            scope,
+            limit,
+        }
+    }
+
+    #[test]
+    fn test_check_wip_limits() {
+        init_logging();
+        info!("test_check_wip_limits: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage
+            .create_issue(&make_issue("bd-1", "alice"), "tester")
+            .unwrap();
+        storage
+            .create_issue(&make_issue("bd-2", "alice"), "tester")
+            .unwrap();
+        storage.add_label("bd-2", "backend", "tester").unwrap();
+        let limits = vec![
+            limit(WipScope::PerAssignee, 2),
+            limit(WipScope::Label("backend".to_string()), 1),
+        ];
+        let no_labels: Vec<String> = Vec::new();
+        let backend = vec!["backend".to_string()];
+
+        let alice = WipCandidate {
+            id: "bd-3",
+            status: "in_progress",
+            assignee: Some("alice"),
+            labels: &no_labels,
+        };
+        let err = check_wip_limits(&storage.reader(), &limits, &alice).unwrap_err();
+        assert!(matches!(
+            err,
+            BeadsError::WipLimitExceeded {
+                current: 2,
+                limit: 2,
+                ..
+            }
+        ));
+
+        let bob = WipCandidate {
+            assignee: Some("bob"),
+            ..alice
+        };
+        check_wip_limits(&storage.reader(), &limits, &bob).unwrap();
+        let bob_backend = WipCandidate {
+            labels: &backend,
+            ..bob
+        };
+        assert!(check_wip_limits(&storage.reader(), &limits, &bob_backend).is_err());
+
+        // An issue already counted does not trip its own limit.
+        let existing = WipCandidate {
+            id: "bd-2",
+            labels: &backend,
+            ..alice
+        };
+        check_wip_limits(&storage.reader(), &limits, &existing).unwrap();
+        info!("test_check_wip_limits: assertions passed");
+    }
+
+    #[test]
+    fn test_check_transition_only_on_status_or_assignee_change() {
+        init_logging();
+        info!("test_check_transition_only_on_status_or_assignee_change: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage
+            .create_issue(&make_issue("bd-1", "alice"), "tester")
+            .unwrap();
+        storage
+            .create_issue(&make_issue("bd-2", "alice"), "tester")
+            .unwrap();
+        let limits = vec![limit(WipScope::Total, 1)];
+        let no_labels: Vec<String> = Vec::new();
+
+        // Already over the limit, but nothing that counts changes
+        let unchanged = WipCandidate {
+            id: "bd-1",
+            status: "in_progress",
+            assignee: Some("alice"),
+            labels: &no_labels,
+        };
+        check_transition(&storage.reader(), &limits, &unchanged).unwrap();
+
+        let reassigned = WipCandidate {
+            assignee: Some("bob"),
+            ..unchanged
+        };
+        assert!(check_transition(&storage.reader(), &limits, &reassigned).is_err());
+
+        let created = WipCandidate {
+            id: "bd-3",
+            ..unchanged
+        };
+        assert!(check_transition(&storage.reader(), &limits, &created).is_err());
+        check_transition(&storage.reader(), &[], &created).unwrap();
+        info!("test_check_transition_only_on_status_or_assignee_change: assertions passed");
+    }
+
+    #[test]
+    fn test_guarded_update_rolls_back_over_limit() {
+        init_logging();
+        info!("test_guarded_update_rolls_back_over_limit: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage
+            .create_issue(&make_issue("bd-1", "alice"), "tester")
+            .unwrap();
+        let open = Issue {
+            status: Status::Open,
+            assignee: None,
+            ..make_issue("bd-2", "bob")
+        };
+        storage.create_issue(&open, "tester").unwrap();
+        let limits = vec![limit(WipScope::Total, 1)];
+        let no_labels: Vec<String> = Vec::new();
+
+        let claim = crate::storage::IssueUpdate {
+            status: Some(Status::InProgress),
+            assignee: Some(Some("bob".to_string())),
+            ..crate::storage::IssueUpdate::default()
+        };
+        let err = storage
+            .update_issue_guarded("bd-2", &claim, "bob", |reader| {
+                check_transition(
+                    reader,
+                    &limits,
+                    &WipCandidate {
+                        id: "bd-2",
+                        status: "in_progress",
+                        assignee: Some("bob"),
+                        labels: &no_labels,
+                    },
+                )
+            })
+            .unwrap_err();
+        assert!(matches!(err, BeadsError::WipLimitExceeded { .. }));
+        let after = storage.get_issue("bd-2").unwrap().unwrap();
+        assert_eq!(after.status, Status::Open);
+        assert_eq!(after.assignee, None);
+
+        check_status_change(&storage.reader(), &limits, "bd-2", &Status::InProgress).unwrap_err();
+        check_status_change(&storage.reader(), &limits, "bd-1", &Status::InProgress).unwrap();
+        info!("test_guarded_update_rolls_back_over_limit: assertions passed");
+    }
+
+    #[test]
+    fn test_concurrent_claims_respect_limit() {
+        init_logging();
+        info!("test_concurrent_claims_respect_limit: starting");
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("beads.db");
+        let mut storage = SqliteStorage::open(&path).unwrap();
+        let ids: Vec<String> = (1..=6).map(|n| format!("bd-{n}")).collect();
+        for id in &ids {
+            let open = Issue {
+                status: Status::Open,
+                assignee: None,
+                ..make_issue(id, "nobody")
+            };
+            storage.create_issue(&open, "tester").unwrap();
+        }
+        drop(storage);
+
+        let handles: Vec<_> = ids
+            .into_iter()
+            .map(|id| {
+                let path = path.clone();
+                std::thread::spawn(move || {
+                    let mut storage =
+                        SqliteStorage::open_with_timeout(&path, Some(10_000)).unwrap();
+                    let limits = vec![limit(WipScope::Total, 2)];
+                    let no_labels: Vec<String> = Vec::new();
+                    let claim = crate::storage::IssueUpdate {
+                        status: Some(Status::InProgress),
+                        ..crate::storage::IssueUpdate::default()
+                    };
+                    storage
+                        .update_issue_guarded(&id, &claim, "agent", |reader| {
+                            check_transition(
+                                reader,
+                                &limits,
+                                &WipCandidate {
+                                    id: &id,
+                                    status: "in_progress",
+                                    assignee: None,
+                                    labels: &no_labels,
+                                },
+                            )
+                        })
+                        .is_ok()
+                })
+            })
+            .collect();
+        let claimed = handles
+            .into_iter()
+            .map(|handle| handle.join().unwrap())
+            .filter(|ok| *ok)
+            .count();
+        assert_eq!(claimed, 2);
+        info!("test_concurrent_claims_respect_limit: assertions passed");
+    }
+
+    #[test]
+    fn test_wip_usage() {
+        init_logging();
+        info!("test_wip_usage: starting");
+        let mut storage = SqliteStorage::open_memory().unwrap();
+        storage
+            .create_issue(&make_issue("bd-1", "alice"), "tester")
+            .unwrap();
+        storage
+            .create_issue(&make_issue("bd-2", "bob"), "tester")
+            .unwrap();
+        storage
+            .create_issue(&make_issue("bd-3", "bob"), "tester")
+            .unwrap();
+        let limits = vec![limit(WipScope::Total, 2), limit(WipScope::PerAssignee, 2)];
+
+        let usage = wip_usage(&storage, &limits).unwrap();
+        assert_eq!(usage.len(), 3);
+        assert_eq!(usage[0].current, 3);
+        assert!(usage[0].exceeded());
+        assert_eq!(usage[1].assignee.as_deref(), Some("alice"));
+        assert_eq!(usage[2].current, 2);
+        assert!(!usage[2].exceeded());
+        info!("test_wip_usage: assertions passed");
+    }
+}
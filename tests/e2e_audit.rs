@@ -3,12 +3,13 @@
 //! Tests cover:
 //! - audit record: Record audit entries to interactions.jsonl
 //! - audit label: Label existing audit entries
+//! - audit verify / export: Hash chain verification and signed manifests
 //! - Error handling: Before init, missing required fields
 //! - Edge cases: Long text, special characters, stdin input
 
 mod common;
 
-use common::cli::{BrWorkspace, extract_json_payload, run_br};
+use common::cli::{BrWorkspace, extract_json_payload, run_br, run_br_with_env};
 use serde_json::Value;
 use std::fs;
 use std::io::Write;
@@ -806,3 +807,94 @@ fn e2e_audit_summary() {
 
     info!("e2e_audit_summary: done");
 }
+
+#[test]
+fn e2e_audit_verify_detects_tampering() {
+    common::init_test_logging();
+    info!("e2e_audit_verify_detects_tampering: start");
+    let workspace = BrWorkspace::new();
+
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+
+    for prompt in ["first prompt", "second prompt"] {
+        let record = run_br(
+            &workspace,
+            ["audit", "record", "--kind", "llm_call", "--prompt", prompt],
+            "record",
+        );
+        assert!(record.status.success(), "record failed: {}", record.stderr);
+    }
+
+    let entries = read_interactions(&workspace);
+    assert_eq!(entries[1]["prev_hash"], entries[0]["hash"]);
+
+    let verify = run_br(&workspace, ["audit", "verify", "--json"], "verify_ok");
+    assert!(verify.status.success(), "verify failed: {}", verify.stderr);
+    let json: Value = serde_json::from_str(&extract_json_payload(&verify.stdout)).unwrap();
+    assert_eq!(json["valid"], true);
+    assert_eq!(json["chained"], 2);
+
+    let path = workspace.root.join(".beads").join("interactions.jsonl");
+    let tampered = fs::read_to_string(&path)
+        .unwrap()
+        .replace("first prompt", "edited prompt");
+    fs::write(&path, tampered).unwrap();
+
+    let verify = run_br(&workspace, ["audit", "verify", "--json"], "verify_tampered");
+    assert!(
+        !verify.status.success(),
+        "verify should fail after tampering"
+    );
+    let json: Value = serde_json::from_str(&extract_json_payload(&verify.stdout)).unwrap();
+    assert_eq!(json["valid"], false);
+    assert_eq!(json["first_break"]["line"], 1);
+    info!("e2e_audit_verify_detects_tampering: done");
+}
+
+#[test]
+fn e2e_audit_export_signed_manifest() {
+    common::init_test_logging();
+    info!("e2e_audit_export_signed_manifest: start");
+    let workspace = BrWorkspace::new();
+
+    let init = run_br(&workspace, ["init"], "init");
+    assert!(init.status.success(), "init failed: {}", init.stderr);
+    let record = run_br(
+        &workspace,
+        ["audit", "record", "--kind", "tool_call"],
+        "record",
+    );
+    assert!(record.status.success(), "record failed: {}", record.stderr);
+
+    let unsigned = run_br(
+        &workspace,
+        ["audit", "export", "-o", "audit.jsonl", "--signed-manifest"],
+        "export_no_key",
+    );
+    assert!(
+        !unsigned.status.success(),
+        "export without a key should fail"
+    );
+
+    let export = run_br_with_env(
+        &workspace,
+        ["audit", "export", "-o", "audit.jsonl", "--signed-manifest"],
+        [("BEADS_AUDIT_SIGNING_KEY", "secret")],
+        "export_signed",
+    );
+    assert!(export.status.success(), "export failed: {}", export.stderr);
+
+    let manifest: Value = serde_json::from_str(
+        &fs::read_to_string(workspace.root.join("audit.jsonl.manifest.json")).unwrap(),
+    )
+    .unwrap();
+    assert_eq!(manifest["entries"], 1);
+    assert_eq!(manifest["algorithm"], "HMAC-SHA256");
+    assert!(manifest["signature"].is_string());
+    assert_eq!(
+        fs::read(workspace.root.join("audit.jsonl")).unwrap(),
+        fs::read(workspace.root.join(".beads").join("interactions.jsonl")).unwrap()
+    );
+    info!("e2e_audit_export_signed_manifest: done");
+}
@@ -86,6 +86,7 @@ bug
     let json: serde_json::Value = serde_json::from_str(&payload).expect("json parse");
     let array = json.as_array().expect("json array");
     assert_eq!(array.len(), 2);
+    assert!(array.iter().all(|issue| issue["action"] == "created"));
     assert!(payload.contains("\"One\""));
     assert!(payload.contains("\"Two\""));
 }